@@ -1,9 +1,14 @@
 export type SubscriptionTier = "free" | "starter" | "professional" | "enterprise";
 
+export type DigestFrequency = "off" | "daily" | "weekly";
+
 export interface NotificationSettings {
   email: boolean;
   push: boolean;
   mentions: boolean;
+  inviteEmails: boolean;
+  reminderEmails: boolean;
+  digestFrequency?: DigestFrequency;
 }
 
 export interface DefaultBoardSettings {
@@ -7,11 +7,19 @@ import type {
   BoardMembersResponse,
   CreateBoardRequest,
   CreateBoardElementRequest,
+  BulkBoardElementsResponse,
   DeleteBoardElementResponse,
   InviteBoardMembersRequest,
+  ReactionListResponse,
+  ReactionTargetType,
+  ToggleReactionRequest,
+  ToggleReactionResponse,
+  VotingSession,
   InviteBoardMembersResponse,
+  RecentlyDeletedElementsResponse,
   RestoreBoardElementResponse,
   TransferBoardOwnershipRequest,
+  AddBoardCoOwnerRequest,
   UpdateBoardRequest,
   UpdateBoardMemberRoleRequest,
   UpdateBoardElementRequest,
@@ -28,6 +36,7 @@ export const createBoard = async (data: CreateBoardRequest): Promise<Board> => {
 type BoardListOptions = {
   organizationId?: string;
   isTemplate?: boolean;
+  tag?: string;
 };
 
 export const getBoardsList = async (
@@ -35,16 +44,30 @@ export const getBoardsList = async (
 ): Promise<Board[]> => {
   const response = await apiClient.get<Board[]>("/api/boards/list", {
     params:
-      options.organizationId || typeof options.isTemplate === "boolean"
+      options.organizationId ||
+      typeof options.isTemplate === "boolean" ||
+      options.tag
         ? {
             organization_id: options.organizationId,
             is_template: options.isTemplate,
+            tag: options.tag,
           }
         : undefined,
   });
   return response.data;
 };
 
+export const listOrganizationTemplates = async (
+  organizationId: string,
+  tag?: string,
+): Promise<Board[]> => {
+  const response = await apiClient.get<Board[]>(
+    `/organizations/${organizationId}/templates`,
+    { params: tag ? { tag } : undefined },
+  );
+  return response.data;
+};
+
 export const getBoardDetail = async (boardId: string): Promise<Board> => {
   const response = await apiClient.get<Board>(`/api/boards/${boardId}`);
   return response.data;
@@ -106,6 +129,17 @@ export const transferBoardOwnership = async (
   return response.data;
 };
 
+export const addBoardCoOwner = async (
+  boardId: string,
+  data: AddBoardCoOwnerRequest,
+): Promise<BoardActionMessage> => {
+  const response = await apiClient.post<BoardActionMessage>(
+    `/api/boards/${boardId}/co-owners`,
+    data,
+  );
+  return response.data;
+};
+
 export const getBoardElements = async (
   boardId: string,
 ): Promise<BoardElement[]> => {
@@ -152,6 +186,39 @@ export const deleteBoardElement = async (
   return response.data;
 };
 
+export const bulkDeleteBoardElements = async (
+  boardId: string,
+  elements: { id: string; expected_version: number }[],
+): Promise<BulkBoardElementsResponse> => {
+  const response = await apiClient.post<BulkBoardElementsResponse>(
+    `/api/boards/${boardId}/elements/bulk-delete`,
+    { elements },
+  );
+  return response.data;
+};
+
+export const listRecentlyDeletedElements = async (
+  boardId: string,
+  withinMinutes?: number,
+): Promise<RecentlyDeletedElementsResponse> => {
+  const response = await apiClient.get<RecentlyDeletedElementsResponse>(
+    `/api/boards/${boardId}/elements/recently-deleted`,
+    { params: { within_minutes: withinMinutes } },
+  );
+  return response.data;
+};
+
+export const bulkRestoreBoardElements = async (
+  boardId: string,
+  elements: { id: string; expected_version: number }[],
+): Promise<BulkBoardElementsResponse> => {
+  const response = await apiClient.post<BulkBoardElementsResponse>(
+    `/api/boards/${boardId}/elements/bulk-restore`,
+    { elements },
+  );
+  return response.data;
+};
+
 export const restoreBoardElement = async (
   boardId: string,
   elementId: string,
@@ -208,3 +275,56 @@ export const removeBoardMember = async (
   );
   return response.data;
 };
+
+export const listBoardReactions = async (
+  boardId: string,
+  target?: { target_type: ReactionTargetType; target_id: string },
+): Promise<ReactionListResponse> => {
+  const response = await apiClient.get<ReactionListResponse>(
+    `/api/boards/${boardId}/reactions`,
+    { params: target },
+  );
+  return response.data;
+};
+
+export const toggleBoardReaction = async (
+  boardId: string,
+  data: ToggleReactionRequest,
+): Promise<ToggleReactionResponse> => {
+  const response = await apiClient.post<ToggleReactionResponse>(
+    `/api/boards/${boardId}/reactions`,
+    data,
+  );
+  return response.data;
+};
+
+export const getBoardVoting = async (boardId: string): Promise<VotingSession> => {
+  const response = await apiClient.get<VotingSession>(`/api/boards/${boardId}/voting`);
+  return response.data;
+};
+
+export const startBoardVoting = async (
+  boardId: string,
+  votesPerUser: number,
+): Promise<VotingSession> => {
+  const response = await apiClient.post<VotingSession>(`/api/boards/${boardId}/voting`, {
+    votes_per_user: votesPerUser,
+  });
+  return response.data;
+};
+
+export const castBoardVote = async (
+  boardId: string,
+  elementId: string,
+): Promise<VotingSession> => {
+  const response = await apiClient.post<VotingSession>(
+    `/api/boards/${boardId}/voting/votes`,
+    { element_id: elementId },
+  );
+  return response.data;
+};
+
+export const endBoardVoting = async (boardId: string): Promise<VotingSession> => {
+  const response = await apiClient.post<VotingSession>(`/api/boards/${boardId}/voting/end`);
+  return response.data;
+};
@@ -64,6 +64,7 @@ const PRESENCE_IDLE_MS = 60_000;
 const PRESENCE_AWAY_MS = 180_000;
 const SYNC_STATUS_THROTTLE_MS = 250;
 const MAX_RECONNECT_ATTEMPTS = 8;
+const WS_SUBPROTOCOL = "board.v1";
 const MIN_CONNECT_INTERVAL_MS = 400;
 const DEBUG_REALTIME_SAMPLE_MS = 5000;
 
@@ -79,7 +80,7 @@ const resolveWsBaseUrl = () => {
 };
 
 const buildBoardWsUrl = (boardId: string, token: string) =>
-  `${resolveWsBaseUrl()}/ws/boards/${boardId}?token=${encodeURIComponent(token)}`;
+  `${resolveWsBaseUrl()}/ws/boards/${boardId}?access_token=${encodeURIComponent(token)}`;
 
 const shouldDebugRealtime = () =>
   typeof window !== "undefined"
@@ -1199,7 +1200,7 @@ export function useBoardRealtime({
         const attempt = reconnectAttemptRef.current + 1;
         logWsConnect(boardId, attempt);
         syncStartAtRef.current = Date.now();
-        const socket = new WebSocket(wsUrl);
+        const socket = new WebSocket(wsUrl, [WS_SUBPROTOCOL]);
         wsRef.current = socket;
         socket.binaryType = "arraybuffer";
 
@@ -27,3 +27,23 @@ export const createBoardComment = async (
   );
   return response.data;
 };
+
+export const pinBoardComment = async (
+  boardId: string,
+  commentId: string,
+): Promise<BoardComment> => {
+  const response = await apiClient.post<BoardComment>(
+    `/api/boards/${boardId}/comments/${commentId}/pin`,
+  );
+  return response.data;
+};
+
+export const unpinBoardComment = async (
+  boardId: string,
+  commentId: string,
+): Promise<BoardComment> => {
+  const response = await apiClient.delete<BoardComment>(
+    `/api/boards/${boardId}/comments/${commentId}/pin`,
+  );
+  return response.data;
+};
@@ -95,12 +95,11 @@ export function BoardShareDialog({ boardId }: BoardShareDialogProps) {
     [t],
   );
 
-  const inviteRoleOptions = useMemo<BoardRole[]>(() => {
-    if (canAssignOwner) {
-      return roleOptions;
-    }
-    return roleOptions.filter((role) => role !== "owner");
-  }, [canAssignOwner, roleOptions]);
+  // Owners are added via ownership transfer or co-owner promotion, never by invite.
+  const inviteRoleOptions = useMemo<BoardRole[]>(
+    () => roleOptions.filter((role) => role !== "owner"),
+    [roleOptions],
+  );
 
   const loadMembers = useCallback(async () => {
     setIsLoading(true);
@@ -126,12 +125,6 @@ export function BoardShareDialog({ boardId }: BoardShareDialogProps) {
     loadMembers().catch(() => undefined);
   }, [isOpen, loadMembers]);
 
-  useEffect(() => {
-    if (!canAssignOwner && inviteRole === "owner") {
-      setInviteRole("viewer");
-    }
-  }, [canAssignOwner, inviteRole]);
-
   const handleAddInvite = () => {
     setInviteStatus(null);
     const nextEmails = parseEmails(inviteInput);
@@ -215,7 +208,7 @@ export function BoardShareDialog({ boardId }: BoardShareDialogProps) {
         });
         return;
       }
-      await inviteBoardMembers(boardId, {
+      const result = await inviteBoardMembers(boardId, {
         emails: targets,
         role: inviteRole,
       });
@@ -223,7 +216,12 @@ export function BoardShareDialog({ boardId }: BoardShareDialogProps) {
       setInviteInput("");
       setInviteStatus({
         tone: "success",
-        message: t("board.inviteSent"),
+        message:
+          result.already_member.length > 0
+            ? t("board.inviteAlreadyMember", {
+                emails: result.already_member.join(", "),
+              })
+            : t("board.inviteSent"),
       });
       await loadMembers();
     } catch (error) {
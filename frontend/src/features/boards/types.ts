@@ -6,6 +6,7 @@ export interface Board {
   username: string;
   description?: string;
   thumbnail_url?: string;
+  tags?: string[] | null;
   is_favorite?: boolean;
   last_accessed_at?: string | null;
   is_public?: boolean;
@@ -13,6 +14,8 @@ export interface Board {
   archived_at?: string | null;
   deleted_at?: string | null;
   canvas_settings?: CanvasSettings;
+  features?: BoardFeatures;
+  element_delete_policy?: ElementDeletePolicy;
   created_at: string;
   updated_at: string;
 }
@@ -24,10 +27,20 @@ export interface CanvasSettings {
   gridSize: number;
   gridEnabled: boolean;
   snapToGrid: boolean;
+  enforceSnapToGrid?: boolean;
+  lastWriteWinsFields?: string[];
   showRulers: boolean;
   defaultZoom: number;
 }
 
+export interface BoardFeatures {
+  comments: boolean;
+  public_sharing: boolean;
+  embeds: boolean;
+}
+
+export type ElementDeletePolicy = "soft" | "hard";
+
 export interface CreateBoardRequest {
   name: string;
   description?: string;
@@ -43,10 +56,18 @@ export interface UpdateBoardRequest {
   name?: string;
   description?: string;
   is_public?: boolean;
+  tags?: string[];
+  features?: Partial<BoardFeatures>;
+  element_delete_policy?: ElementDeletePolicy;
 }
 
 export interface TransferBoardOwnershipRequest {
   new_owner_id: string;
+  keep_current_owner?: boolean;
+}
+
+export interface AddBoardCoOwnerRequest {
+  user_id: string;
 }
 
 export type BoardRole = "owner" | "admin" | "editor" | "commenter" | "viewer";
@@ -86,10 +107,61 @@ export interface BoardComment {
   is_edited: boolean;
   edited_at?: string | null;
   reply_count: number;
+  is_pinned: boolean;
+  pinned_by?: string | null;
+  pinned_at?: string | null;
   created_at: string;
   updated_at: string;
 }
 
+export type ReactionTargetType = "element" | "comment";
+
+export interface ToggleReactionRequest {
+  target_type: ReactionTargetType;
+  target_id: string;
+  emoji: string;
+}
+
+export interface ReactionCount {
+  emoji: string;
+  count: number;
+  reacted: boolean;
+}
+
+export interface ReactionSummary {
+  target_type: ReactionTargetType;
+  target_id: string;
+  reactions: ReactionCount[];
+}
+
+export interface ToggleReactionResponse extends ReactionSummary {
+  added: boolean;
+}
+
+export interface ReactionListResponse {
+  data: ReactionSummary[];
+}
+
+export interface VoteTally {
+  element_id: string;
+  votes: number;
+}
+
+export interface VotingSession {
+  id: string;
+  board_id: string;
+  votes_per_user: number;
+  started_by: string;
+  started_at: string;
+  ended_by?: string | null;
+  ended_at?: string | null;
+  is_active: boolean;
+  total_votes: number;
+  tallies: VoteTally[];
+  my_votes: number;
+  votes_remaining: number;
+}
+
 export interface CommentPagination {
   next_cursor?: string | null;
   has_more: boolean;
@@ -154,6 +226,7 @@ export interface InviteBoardMembersRequest {
 
 export interface InviteBoardMembersResponse {
   invited: string[];
+  already_member: string[];
 }
 
 export interface UpdateBoardMemberRoleRequest {
@@ -182,6 +255,7 @@ export interface CreateBoardElementRequest {
   style?: ElementStyle;
   properties?: Record<string, unknown>;
   metadata?: Record<string, unknown>;
+  force?: boolean;
 }
 
 export interface BoardElementResponse {
@@ -229,6 +303,34 @@ export interface DeleteBoardElementResponse {
   deleted_at: string;
   updated_at: string;
   already_deleted?: boolean | null;
+  hard_deleted?: boolean;
+}
+
+export type BulkElementStatus =
+  | "deleted"
+  | "already_deleted"
+  | "restored"
+  | "not_deleted"
+  | "not_found";
+
+export interface BulkElementResult {
+  id: string;
+  status: BulkElementStatus;
+  version: number | null;
+  deleted_at: string | null;
+  updated_at: string | null;
+}
+
+export interface BulkBoardElementsResponse {
+  data: BulkElementResult[];
+}
+
+export type DeletedBoardElement = BoardElementResponse & {
+  deleted_at: string;
+};
+
+export interface RecentlyDeletedElementsResponse {
+  data: DeletedBoardElement[];
 }
 
 export interface RestoreBoardElementResponse {
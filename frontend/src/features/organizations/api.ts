@@ -7,9 +7,12 @@ import type {
   InviteValidationResponse,
   Organization,
   OrganizationActionMessage,
+  OrganizationBoardDefaults,
+  OrganizationBranding,
   OrganizationEmailInvite,
   OrganizationInvitation,
   OrganizationMember,
+  OrganizationMembersPage,
   OrganizationSummary,
   OrganizationUsage,
   SlugAvailabilityResponse,
@@ -56,10 +59,40 @@ export async function inviteOrganizationMembers(
 export async function listOrganizationMembers(
   organizationId: string,
 ): Promise<OrganizationMember[]> {
-  const response = await apiClient.get<{ data: OrganizationMember[] }>(
-    `/organizations/${organizationId}/members`,
+  const members: OrganizationMember[] = [];
+  let cursor: string | null = null;
+  do {
+    const response: { data: OrganizationMembersPage } =
+      await apiClient.get<OrganizationMembersPage>(
+        `/organizations/${organizationId}/members`,
+        { params: { limit: 200, cursor: cursor ?? undefined } },
+      );
+    members.push(...response.data.data);
+    cursor = response.data.pagination.next_cursor;
+  } while (cursor);
+  return members;
+}
+
+export async function updateOrganizationBranding(
+  organizationId: string,
+  data: Partial<OrganizationBranding>,
+): Promise<OrganizationBranding> {
+  const response = await apiClient.put<OrganizationBranding>(
+    `/organizations/${organizationId}/branding`,
+    data,
   );
-  return response.data.data;
+  return response.data;
+}
+
+export async function updateOrganizationBoardDefaults(
+  organizationId: string,
+  data: OrganizationBoardDefaults,
+): Promise<OrganizationBoardDefaults> {
+  const response = await apiClient.put<OrganizationBoardDefaults>(
+    `/organizations/${organizationId}/board-defaults`,
+    data,
+  );
+  return response.data;
 }
 
 export async function getOrganizationUsage(
@@ -19,6 +19,17 @@ export interface Organization {
   created_at: string;
 }
 
+export interface OrganizationBoardDefaults {
+  default_member_board_role: "admin" | "editor" | "commenter" | "viewer" | null;
+}
+
+export interface OrganizationBranding {
+  logo_url: string | null;
+  email_from_name: string | null;
+  accent_color: string | null;
+  support_email: string | null;
+}
+
 export type OrganizationSummary = Pick<
   Organization,
   "id" | "name" | "slug"
@@ -69,6 +80,15 @@ export interface OrganizationMember {
   updated_at: string;
 }
 
+export interface OrganizationMembersPage {
+  data: OrganizationMember[];
+  pagination: {
+    next_cursor: string | null;
+    has_more: boolean;
+    total: number;
+  };
+}
+
 export interface OrganizationInvitationOrganization {
   id: string;
   name: string;
@@ -166,6 +166,7 @@ const resources = {
       "board.inviteSend": "Send invites",
       "board.inviteSending": "Sending invites...",
       "board.inviteSent": "Invites sent.",
+      "board.inviteAlreadyMember": "Invites sent. Already members: {{emails}}",
       "board.inviteEmpty": "Add at least one email to invite.",
       "board.inviteSendError": "Failed to send invites.",
       "board.inviteErrorInvalid": "Invalid email(s): {{emails}}",
@@ -324,6 +325,12 @@ const resources = {
       "profile.language": "Language",
       "profile.notifications": "Notifications",
       "profile.emailNotifications": "Email notifications",
+      "profile.inviteEmails": "Workspace invite emails",
+      "profile.reminderEmails": "Invite reminder emails",
+      "profile.digestFrequency": "Board activity digest email",
+      "profile.digestOff": "Off",
+      "profile.digestDaily": "Daily",
+      "profile.digestWeekly": "Weekly",
       "profile.pushNotifications": "Push notifications",
       "profile.mentions": "Mentions",
       "profile.boardDefaults": "Board defaults",
@@ -507,6 +514,7 @@ const resources = {
       "board.inviteSend": "Gửi lời mời",
       "board.inviteSending": "Đang gửi lời mời...",
       "board.inviteSent": "Đã gửi lời mời.",
+      "board.inviteAlreadyMember": "Đã gửi lời mời. Đã là thành viên: {{emails}}",
       "board.inviteEmpty": "Hãy thêm ít nhất một email để mời.",
       "board.inviteSendError": "Không thể gửi lời mời.",
       "board.inviteErrorInvalid": "Email không hợp lệ: {{emails}}",
@@ -663,6 +671,12 @@ const resources = {
       "profile.language": "Ngôn ngữ",
       "profile.notifications": "Thông báo",
       "profile.emailNotifications": "Email thông báo",
+      "profile.inviteEmails": "Email lời mời không gian làm việc",
+      "profile.reminderEmails": "Email nhắc lời mời",
+      "profile.digestFrequency": "Email tóm tắt hoạt động bảng",
+      "profile.digestOff": "Tắt",
+      "profile.digestDaily": "Hàng ngày",
+      "profile.digestWeekly": "Hàng tuần",
       "profile.pushNotifications": "Thông báo đẩy",
       "profile.mentions": "Nhắc tên",
       "profile.boardDefaults": "Mặc định bảng",
@@ -15,7 +15,7 @@ import {
 import { Input } from "@/components/ui/Input";
 import { Label } from "@/components/ui/Label";
 import { useAppStore } from "@/store/useAppStore";
-import type { UserPreferences } from "@/features/auth/types";
+import type { DigestFrequency, UserPreferences } from "@/features/auth/types";
 import { useTranslation } from "react-i18next";
 import { getApiErrorMessage } from "@/shared/api/errors";
 
@@ -31,6 +31,9 @@ const DEFAULT_PREFERENCES: UserPreferences = {
     email: true,
     push: true,
     mentions: true,
+    inviteEmails: true,
+    reminderEmails: true,
+    digestFrequency: "off",
   },
   defaultBoardSettings: {
     gridEnabled: true,
@@ -50,6 +53,15 @@ function normalizePreferences(preferences?: UserPreferences): UserPreferences {
       mentions:
         preferences?.notifications?.mentions ??
         DEFAULT_PREFERENCES.notifications.mentions,
+      inviteEmails:
+        preferences?.notifications?.inviteEmails ??
+        DEFAULT_PREFERENCES.notifications.inviteEmails,
+      reminderEmails:
+        preferences?.notifications?.reminderEmails ??
+        DEFAULT_PREFERENCES.notifications.reminderEmails,
+      digestFrequency:
+        preferences?.notifications?.digestFrequency ??
+        DEFAULT_PREFERENCES.notifications.digestFrequency,
     },
     defaultBoardSettings: {
       gridEnabled:
@@ -225,7 +237,9 @@ function Profile() {
     }
   };
 
-  const updateNotification = (key: keyof UserPreferences["notifications"]) => {
+  const updateNotification = (
+    key: Exclude<keyof UserPreferences["notifications"], "digestFrequency">,
+  ) => {
     setPreferences((prev) => {
       const next = prev ?? resolvedPreferences;
       return {
@@ -411,7 +425,9 @@ function Profile() {
                 <div className="text-sm font-medium text-text-primary">
                   {t("profile.notifications")}
                 </div>
-                {(["email", "push", "mentions"] as const).map((key) => (
+                {(
+                  ["email", "inviteEmails", "reminderEmails", "push", "mentions"] as const
+                ).map((key) => (
                   <label
                     key={key}
                     className="flex items-center gap-3 text-sm text-text-secondary"
@@ -423,10 +439,36 @@ function Profile() {
                       onChange={() => updateNotification(key)}
                     />
                     {key === "email" && t("profile.emailNotifications")}
+                    {key === "inviteEmails" && t("profile.inviteEmails")}
+                    {key === "reminderEmails" && t("profile.reminderEmails")}
                     {key === "push" && t("profile.pushNotifications")}
                     {key === "mentions" && t("profile.mentions")}
                   </label>
                 ))}
+                <div className="space-y-2">
+                  <Label htmlFor="digestFrequency">{t("profile.digestFrequency")}</Label>
+                  <select
+                    id="digestFrequency"
+                    className="h-10 w-full rounded-md border border-border bg-bg-base px-3 text-sm text-text-primary focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-yellow-500/50 focus-visible:ring-offset-2 ring-offset-bg-base"
+                    value={resolvedPreferences.notifications.digestFrequency ?? "off"}
+                    onChange={(event) =>
+                      setPreferences((prev) => {
+                        const next = prev ?? resolvedPreferences;
+                        return {
+                          ...next,
+                          notifications: {
+                            ...next.notifications,
+                            digestFrequency: event.target.value as DigestFrequency,
+                          },
+                        };
+                      })
+                    }
+                  >
+                    <option value="off">{t("profile.digestOff")}</option>
+                    <option value="daily">{t("profile.digestDaily")}</option>
+                    <option value="weekly">{t("profile.digestWeekly")}</option>
+                  </select>
+                </div>
               </div>
               <div className="space-y-3">
                 <div className="text-sm font-medium text-text-primary">
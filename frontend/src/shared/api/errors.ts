@@ -5,6 +5,7 @@ type ApiErrorPayload = {
   error?: {
     message?: unknown;
     code?: unknown;
+    fields?: unknown;
   };
 };
 
@@ -65,3 +66,23 @@ export function getApiErrorCode(error: unknown): string | null {
   }
   return null;
 }
+
+/** Per-field messages from a `VALIDATION_ERROR` response, keyed by field. */
+export function getApiFieldErrors(error: unknown): Record<string, string> {
+  const fieldErrors: Record<string, string> = {};
+  if (!axios.isAxiosError(error)) {
+    return fieldErrors;
+  }
+  const payload = error.response?.data as ApiErrorPayload | undefined;
+  const fields = payload?.error?.fields;
+  if (!Array.isArray(fields)) {
+    return fieldErrors;
+  }
+  for (const entry of fields) {
+    const { field, message } = (entry ?? {}) as { field?: unknown; message?: unknown };
+    if (typeof field === "string" && typeof message === "string" && !(field in fieldErrors)) {
+      fieldErrors[field] = message;
+    }
+  }
+  return fieldErrors;
+}
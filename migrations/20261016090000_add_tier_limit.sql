@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS core.tier_limit (
+    tier                core.subscription_tier PRIMARY KEY,
+    max_members         INTEGER NOT NULL CHECK (max_members >= 0),
+    max_boards          INTEGER NOT NULL CHECK (max_boards >= 0),
+    storage_limit_mb    INTEGER NOT NULL CHECK (storage_limit_mb >= 0),
+    updated_by          UUID REFERENCES core.user(id) ON DELETE SET NULL,
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO core.tier_limit (tier, max_members, max_boards, storage_limit_mb)
+VALUES
+    ('free', 3, 5, 100),
+    ('starter', 10, 25, 1024),
+    ('professional', 50, 0, 10240),
+    ('enterprise', 0, 0, 102400)
+ON CONFLICT (tier) DO NOTHING;
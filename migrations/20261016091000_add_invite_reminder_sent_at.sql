@@ -0,0 +1,13 @@
+ALTER TABLE core.organization_member
+    ADD COLUMN IF NOT EXISTS reminder_sent_at TIMESTAMPTZ;
+
+ALTER TABLE core.organization_invite
+    ADD COLUMN IF NOT EXISTS reminder_sent_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_org_member_pending_reminder
+    ON core.organization_member(invited_at)
+    WHERE accepted_at IS NULL AND reminder_sent_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_org_invite_pending_reminder
+    ON core.organization_invite(invited_at)
+    WHERE reminder_sent_at IS NULL;
@@ -0,0 +1,6 @@
+-- GiST index over each element's board and bounding box for viewport
+-- (overlap) queries; btree_gist lets `board_id` sit in the same index.
+CREATE INDEX IF NOT EXISTS idx_element_bounds_gist ON board.element USING gist (
+    board_id,
+    box(point(position_x, position_y), point(position_x + width, position_y + height))
+) WHERE deleted_at IS NULL;
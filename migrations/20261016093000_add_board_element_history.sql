@@ -0,0 +1,62 @@
+CREATE TABLE IF NOT EXISTS board.element_history (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v7(),
+    element_id      UUID NOT NULL,
+    board_id        UUID NOT NULL REFERENCES board.board(id) ON DELETE CASCADE,
+    version         INTEGER NOT NULL,
+    operation       VARCHAR(20) NOT NULL,
+    changed_by      UUID REFERENCES core.user(id) ON DELETE SET NULL,
+    changed_fields  TEXT[] NOT NULL DEFAULT '{}',
+    element_data    JSONB NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_board_element_history_version
+    ON board.element_history(element_id, version);
+CREATE INDEX IF NOT EXISTS idx_board_element_history_board
+    ON board.element_history(board_id, element_id, version DESC);
+
+CREATE OR REPLACE FUNCTION board.record_element_history()
+RETURNS TRIGGER AS $$
+DECLARE
+    _operation VARCHAR(20);
+    _changed TEXT[];
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        _operation := 'create';
+        _changed := ARRAY[]::TEXT[];
+    ELSE
+        IF NEW.version IS NOT DISTINCT FROM OLD.version THEN
+            RETURN NEW;
+        END IF;
+        _operation := CASE
+            WHEN NEW.deleted_at IS NOT NULL AND OLD.deleted_at IS NULL THEN 'delete'
+            WHEN NEW.deleted_at IS NULL AND OLD.deleted_at IS NOT NULL THEN 'restore'
+            ELSE 'update'
+        END;
+        SELECT COALESCE(array_agg(changed.key ORDER BY changed.key), ARRAY[]::TEXT[])
+        INTO _changed
+        FROM jsonb_each(to_jsonb(NEW)) AS changed
+        WHERE changed.key NOT IN ('version', 'updated_at')
+          AND changed.value IS DISTINCT FROM (to_jsonb(OLD) -> changed.key);
+    END IF;
+
+    INSERT INTO board.element_history (
+        element_id, board_id, version, operation, changed_by, changed_fields, element_data
+    ) VALUES (
+        NEW.id,
+        NEW.board_id,
+        NEW.version,
+        _operation,
+        NULLIF(current_setting('app.current_user_id', true), '')::uuid,
+        _changed,
+        to_jsonb(NEW)
+    )
+    ON CONFLICT (element_id, version) DO NOTHING;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS record_element_history_trigger ON board.element;
+CREATE TRIGGER record_element_history_trigger
+    AFTER INSERT OR UPDATE ON board.element
+    FOR EACH ROW EXECUTE FUNCTION board.record_element_history();
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS core.organization_slug_alias (
+    slug                VARCHAR(100) PRIMARY KEY,
+    organization_id     UUID NOT NULL REFERENCES core.organization(id) ON DELETE CASCADE,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_org_slug_alias_org
+    ON core.organization_slug_alias(organization_id);
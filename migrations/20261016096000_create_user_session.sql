@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS core.session (
+    id                  UUID PRIMARY KEY,
+    user_id             UUID NOT NULL REFERENCES core.user(id) ON DELETE CASCADE,
+    device              VARCHAR(255),
+    ip_address          VARCHAR(64),
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at        TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at          TIMESTAMPTZ NOT NULL,
+    revoked_at          TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_user_active
+    ON core.session(user_id, last_seen_at DESC)
+    WHERE revoked_at IS NULL;
@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS board.watcher (
+    board_id            UUID NOT NULL REFERENCES board.board(id) ON DELETE CASCADE,
+    user_id             UUID NOT NULL REFERENCES core.user(id) ON DELETE CASCADE,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_notified_at    TIMESTAMPTZ,
+    PRIMARY KEY (board_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_board_watcher_user
+    ON board.watcher(user_id);
+
+ALTER TABLE collab.notification
+    DROP CONSTRAINT IF EXISTS notification_type_valid;
+
+ALTER TABLE collab.notification
+    ADD CONSTRAINT notification_type_valid CHECK (
+        notification_type IN (
+            'board_invite',
+            'board_mention',
+            'comment_reply',
+            'comment_mention',
+            'element_update',
+            'board_shared',
+            'board_activity'
+        )
+    );
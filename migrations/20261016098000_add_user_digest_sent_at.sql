@@ -0,0 +1,2 @@
+ALTER TABLE core.user
+    ADD COLUMN IF NOT EXISTS digest_sent_at TIMESTAMPTZ;
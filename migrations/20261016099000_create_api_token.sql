@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS core.api_token (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v7(),
+    organization_id     UUID NOT NULL REFERENCES core.organization(id) ON DELETE CASCADE,
+    board_id            UUID REFERENCES board.board(id) ON DELETE CASCADE,
+    created_by          UUID NOT NULL REFERENCES core.user(id) ON DELETE CASCADE,
+    name                VARCHAR(100) NOT NULL,
+    token_hash          VARCHAR(64) NOT NULL UNIQUE,
+    token_prefix        VARCHAR(16) NOT NULL,
+    scopes              TEXT[] NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at        TIMESTAMPTZ,
+    expires_at          TIMESTAMPTZ,
+    revoked_at          TIMESTAMPTZ,
+
+    CONSTRAINT api_token_scopes_valid CHECK (
+        cardinality(scopes) > 0
+        AND scopes <@ ARRAY['read', 'elements_write']::TEXT[]
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_token_organization
+    ON core.api_token(organization_id, created_at DESC);
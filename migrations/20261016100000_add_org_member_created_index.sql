@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS idx_org_member_org_created
+    ON core.organization_member(organization_id, created_at, id);
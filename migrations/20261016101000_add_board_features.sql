@@ -0,0 +1,2 @@
+ALTER TABLE board.board
+    ADD COLUMN IF NOT EXISTS features JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,8 @@
+ALTER TABLE collab.comment
+    ADD COLUMN IF NOT EXISTS is_pinned BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS pinned_by UUID REFERENCES core.user(id),
+    ADD COLUMN IF NOT EXISTS pinned_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_comment_pinned
+    ON collab.comment(board_id, pinned_at DESC)
+    WHERE is_pinned AND deleted_at IS NULL;
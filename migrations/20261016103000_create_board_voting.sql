@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS board.voting_session (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v7(),
+    board_id UUID NOT NULL REFERENCES board.board(id) ON DELETE CASCADE,
+    votes_per_user INTEGER NOT NULL CHECK (votes_per_user > 0),
+    started_by UUID NOT NULL REFERENCES core.user(id),
+    started_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ended_by UUID REFERENCES core.user(id),
+    ended_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_voting_session_active
+    ON board.voting_session(board_id)
+    WHERE ended_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_voting_session_board
+    ON board.voting_session(board_id, started_at DESC);
+
+CREATE TABLE IF NOT EXISTS board.vote (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v7(),
+    session_id UUID NOT NULL REFERENCES board.voting_session(id) ON DELETE CASCADE,
+    element_id UUID NOT NULL REFERENCES board.element(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES core.user(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_vote_session_element ON board.vote(session_id, element_id);
+CREATE INDEX IF NOT EXISTS idx_vote_session_user ON board.vote(session_id, user_id);
@@ -0,0 +1,9 @@
+DO $$
+BEGIN
+    CREATE TYPE board.element_delete_policy AS ENUM ('soft', 'hard');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+ALTER TABLE board.board
+    ADD COLUMN IF NOT EXISTS element_delete_policy board.element_delete_policy NOT NULL DEFAULT 'soft';
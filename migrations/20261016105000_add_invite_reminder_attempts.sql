@@ -0,0 +1,5 @@
+ALTER TABLE core.organization_member
+    ADD COLUMN IF NOT EXISTS reminder_attempts INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE core.organization_invite
+    ADD COLUMN IF NOT EXISTS reminder_attempts INTEGER NOT NULL DEFAULT 0;
@@ -1,37 +1,96 @@
-use axum::{Extension, Json, extract::State};
+use std::net::SocketAddr;
+
+use axum::{
+    Extension, Json,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, header},
+};
+use uuid::Uuid;
 
 use crate::{
     app::state::AppState,
     auth::middleware::AuthUser,
     dto::auth::{
         ChangePasswordRequest, DeleteAccountRequest, LoginRequest, LoginResponse, MessageResponse,
-        RegisterRequest, UpdatePreferencesRequest, UpdateUserRequest, UserProfileResponse,
-        UserReponse, VerifyEmailRequest,
+        RegisterRequest, UpdatePreferencesRequest, UpdateUserRequest, UserLookupRequest,
+        UserLookupResponse, UserProfileResponse, UserReponse, UserSessionsResponse,
+        VerifyEmailRequest,
     },
     dto::organizations::OrganizationInvitationsResponse,
     error::AppError,
-    usecases::auth::UserServices,
+    usecases::auth::{SessionClient, UserServices},
     usecases::organizations::OrganizationService,
 };
 
+const MAX_SESSION_DEVICE_LEN: usize = 255;
+
+/// Describes the signing-in client from its User-Agent and address, using the
+/// first `X-Forwarded-For` hop when present.
+fn session_client(headers: &HeaderMap, peer: SocketAddr) -> SessionClient {
+    let device = headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| value.chars().take(MAX_SESSION_DEVICE_LEN).collect());
+    let forwarded = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .and_then(|value| value.trim().parse::<std::net::IpAddr>().ok());
+    SessionClient {
+        device,
+        ip_address: Some(forwarded.unwrap_or(peer.ip()).to_string()),
+    }
+}
+
 pub async fn register_handle(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(req): Json<RegisterRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
     let jwt_config = state.jwt_config.clone();
-    let response =
-        UserServices::register_user(&state.db, &jwt_config, state.email_service.as_ref(), req)
-            .await?;
+    let client = session_client(&headers, peer);
+    let response = UserServices::register_user(
+        &state.db,
+        &jwt_config,
+        state.email_service.as_ref(),
+        req,
+        &client,
+    )
+    .await?;
     Ok(Json(response))
 }
 pub async fn login_handle(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(req): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
     let jwt_config = state.jwt_config.clone();
-    let response = UserServices::login(&state.db, &jwt_config, req).await?;
+    let client = session_client(&headers, peer);
+    let response = UserServices::login(&state.db, &jwt_config, req, &client).await?;
     Ok(Json(response))
 }
+
+pub async fn list_sessions_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<UserSessionsResponse>, AppError> {
+    let response =
+        UserServices::list_sessions(&state.db, auth_user.user_id, auth_user.session_id).await?;
+    Ok(Json(response))
+}
+
+pub async fn revoke_session_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(session_id): Path<Uuid>,
+) -> Result<axum::http::StatusCode, AppError> {
+    UserServices::revoke_session(&state.db, auth_user.user_id, session_id).await?;
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
 pub async fn get_me_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
@@ -43,6 +102,16 @@ pub async fn get_me_handle(
     Ok(Json(user))
 }
 
+/// Resolves public profiles for a batch of user ids (mentions, collaborator lists).
+pub async fn lookup_users_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UserLookupRequest>,
+) -> Result<Json<UserLookupResponse>, AppError> {
+    let response = UserServices::lookup_users(&state.db, auth_user.user_id, req).await?;
+    Ok(Json(response))
+}
+
 /// Returns profile data for the profile setup wizard.
 pub async fn get_profile_setup_handle(
     State(state): State<AppState>,
@@ -100,6 +169,16 @@ pub async fn change_password_handle(
     Ok(axum::http::StatusCode::NO_CONTENT)
 }
 
+/// Clears a user's failed-login lockout (platform admins only).
+pub async fn unlock_user_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(user_id): Path<Uuid>,
+) -> Result<axum::http::StatusCode, AppError> {
+    UserServices::unlock_account(&state.db, auth_user.user_id, user_id).await?;
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
 pub async fn delete_account_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
@@ -146,3 +225,29 @@ pub async fn list_invitations_handle(
 
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{HeaderMap, HeaderValue, header};
+
+    use super::session_client;
+
+    #[test]
+    fn session_client_prefers_first_forwarded_hop() {
+        let peer = "10.0.0.2:443".parse().expect("addr");
+        let mut headers = HeaderMap::new();
+        headers.insert(header::USER_AGENT, HeaderValue::from_static("Firefox/130"));
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.9, 10.0.0.1"),
+        );
+
+        let client = session_client(&headers, peer);
+        assert_eq!(client.device.as_deref(), Some("Firefox/130"));
+        assert_eq!(client.ip_address.as_deref(), Some("203.0.113.9"));
+
+        let client = session_client(&HeaderMap::new(), peer);
+        assert_eq!(client.device, None);
+        assert_eq!(client.ip_address.as_deref(), Some("10.0.0.2"));
+    }
+}
@@ -2,20 +2,27 @@ use axum::{
     Extension, Json,
     body::Bytes,
     extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
 };
 
 use crate::{
     app::state::AppState,
     auth::middleware::AuthUser,
     dto::boards::{
-        BoardActionMessage, BoardFavoriteResponse, BoardListQuery, BoardMembersResponse,
-        BoardResponse, CreateBoardRequest, InviteBoardMembersRequest, InviteBoardMembersResponse,
+        AddBoardCoOwnerRequest, BoardAccessBatchRequest, BoardAccessBatchResponse,
+        BoardAccessOverviewResponse, BoardActionMessage, BoardCardListResponse, BoardCardsQuery,
+        BoardCrdtDumpResponse, BoardDetailResponse, BoardFavoriteResponse, BoardListQuery,
+        BoardMembersResponse, BoardPermissionsResponse, BoardRenderQuery, BoardResponse,
+        BoardStatsResponse, BoardWatchResponse, CreateBoardRequest, ImportBoardBinaryQuery,
+        ImportBoardQuery, ImportBoardResponse, InviteBoardMembersRequest,
+        InviteBoardMembersResponse, ProjectionRebuildResponse, TemplateListQuery,
         TransferBoardOwnershipRequest, UpdateBoardMemberRoleRequest, UpdateBoardRequest,
     },
     error::AppError,
-    models::boards::{Board, BoardPermissions, BoardRole},
-    realtime::{protocol, room},
-    usecases::boards::{BoardMemberChange, BoardService},
+    models::boards::Board,
+    realtime::resume::ResyncMetrics,
+    usecases::{board_watchers::BoardWatcherService, boards::BoardService},
 };
 
 pub async fn create_board_handle(
@@ -34,28 +41,151 @@ pub async fn get_board_handle(
     Query(query): Query<BoardListQuery>,
 ) -> Result<Json<Vec<BoardResponse>>, AppError> {
     let user_id = auth_user.user_id;
-    let board =
-        BoardService::get_board(&state.db, user_id, query.organization_id, query.is_template)
-            .await?;
+    let board = BoardService::get_board(
+        &state.db,
+        user_id,
+        query.organization_id,
+        query.is_template,
+        query.tag,
+    )
+    .await?;
     Ok(Json(board))
 }
 
+pub async fn list_organization_templates_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<uuid::Uuid>,
+    Query(query): Query<TemplateListQuery>,
+) -> Result<Json<Vec<BoardResponse>>, AppError> {
+    let templates = BoardService::list_organization_templates(
+        &state.db,
+        auth_user.user_id,
+        organization_id,
+        query.tag,
+    )
+    .await?;
+    Ok(Json(templates))
+}
+
+pub async fn list_board_cards_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<BoardCardsQuery>,
+) -> Result<Json<BoardCardListResponse>, AppError> {
+    let cards = BoardService::list_board_cards(&state.db, auth_user.user_id, query).await?;
+    Ok(Json(cards))
+}
+
 pub async fn get_board_detail_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(board_id): Path<uuid::Uuid>,
-) -> Result<Json<Board>, AppError> {
+) -> Result<Json<BoardDetailResponse>, AppError> {
     let board = BoardService::get_board_detail(&state.db, board_id, auth_user.user_id).await?;
     Ok(Json(board))
 }
 
+/// Resolves role and permissions for many boards in one request (dashboard use).
+pub async fn resolve_board_access_batch_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<BoardAccessBatchRequest>,
+) -> Result<Json<BoardAccessBatchResponse>, AppError> {
+    let data =
+        BoardService::resolve_access_for_boards(&state.db, auth_user.user_id, &req.board_ids)
+            .await?;
+    Ok(Json(BoardAccessBatchResponse { data }))
+}
+
+pub async fn download_board_snapshot_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let snapshot =
+        BoardService::export_board_snapshot(&state.db, &state.rooms, board_id, auth_user.user_id)
+            .await?;
+    let disposition = format!("attachment; filename=\"board-{}.bin\"", board_id);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        snapshot,
+    ))
+}
+
+pub async fn get_board_access_summary_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<Json<BoardAccessOverviewResponse>, AppError> {
+    let response = BoardService::access_summary(&state.db, board_id, auth_user.user_id).await?;
+    Ok(Json(response))
+}
+
+pub async fn get_board_permissions_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<Json<BoardPermissionsResponse>, AppError> {
+    let response =
+        BoardService::get_permission_summary(&state.db, board_id, auth_user.user_id).await?;
+    Ok(Json(response))
+}
+
+pub async fn board_stats_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<Json<BoardStatsResponse>, AppError> {
+    let stats = BoardService::board_stats(&state.db, board_id, auth_user.user_id).await?;
+    Ok(Json(stats))
+}
+
+pub async fn render_board_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+    Query(query): Query<BoardRenderQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let svg =
+        BoardService::render_board(&state.db, &state.rooms, board_id, auth_user.user_id, query)
+            .await?;
+    Ok(([(header::CONTENT_TYPE, "image/svg+xml")], svg))
+}
+
+pub async fn import_board_binary_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ImportBoardBinaryQuery>,
+    body: Bytes,
+) -> Result<Json<Board>, AppError> {
+    let board =
+        BoardService::import_board_binary(&state.db, query, auth_user.user_id, &body).await?;
+    Ok(Json(board))
+}
+
+pub async fn import_board_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ImportBoardQuery>,
+    body: Bytes,
+) -> Result<Json<ImportBoardResponse>, AppError> {
+    let response = BoardService::import_board(&state.db, query, auth_user.user_id, &body).await?;
+    Ok(Json(response))
+}
+
 pub async fn update_board_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(board_id): Path<uuid::Uuid>,
     Json(req): Json<UpdateBoardRequest>,
 ) -> Result<Json<Board>, AppError> {
-    let board = BoardService::update_board(&state.db, board_id, auth_user.user_id, req).await?;
+    let board =
+        BoardService::update_board(&state.db, &state.rooms, board_id, auth_user.user_id, req)
+            .await?;
     Ok(Json(board))
 }
 
@@ -88,6 +218,16 @@ pub async fn transfer_board_ownership_handle(
     Ok(Json(response))
 }
 
+pub async fn add_board_co_owner_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+    Json(req): Json<AddBoardCoOwnerRequest>,
+) -> Result<Json<BoardActionMessage>, AppError> {
+    let response = BoardService::add_co_owner(&state.db, board_id, auth_user.user_id, req).await?;
+    Ok(Json(response))
+}
+
 pub async fn delete_board_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
@@ -116,6 +256,34 @@ pub async fn toggle_board_favorite_handle(
     Ok(Json(response))
 }
 
+pub async fn get_board_watch_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<Json<BoardWatchResponse>, AppError> {
+    let response =
+        BoardWatcherService::get_watch_status(&state.db, board_id, auth_user.user_id).await?;
+    Ok(Json(response))
+}
+
+pub async fn watch_board_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<Json<BoardWatchResponse>, AppError> {
+    let response = BoardWatcherService::watch(&state.db, board_id, auth_user.user_id).await?;
+    Ok(Json(response))
+}
+
+pub async fn unwatch_board_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<Json<BoardWatchResponse>, AppError> {
+    let response = BoardWatcherService::unwatch(&state.db, board_id, auth_user.user_id).await?;
+    Ok(Json(response))
+}
+
 pub async fn list_board_members_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
@@ -156,8 +324,7 @@ pub async fn update_board_member_role_handle(
         req,
     )
     .await?;
-    apply_board_member_change(&state, board_id, &result);
-    Ok(Json(result.message))
+    Ok(Json(result))
 }
 
 pub async fn remove_board_member_handle(
@@ -168,56 +335,34 @@ pub async fn remove_board_member_handle(
     let result =
         BoardService::remove_board_member(&state.db, board_id, auth_user.user_id, member_id)
             .await?;
-    apply_board_member_change(&state, board_id, &result);
-    Ok(Json(result.message))
-}
-
-fn apply_board_member_change(state: &AppState, board_id: uuid::Uuid, change: &BoardMemberChange) {
-    let Some(room_ref) = state.rooms.get(&board_id) else {
-        return;
-    };
-    let room = room_ref.value().clone();
-    update_room_permissions(&room, change.member_user_id, change.permissions);
-    broadcast_role_update(
-        &room,
-        change.member_user_id,
-        change.role,
-        change.permissions,
-    );
-}
-
-fn update_room_permissions(
-    room: &room::Room,
-    user_id: uuid::Uuid,
-    permissions: Option<BoardPermissions>,
-) {
-    if let Some(permissions) = permissions {
-        room.edit_permissions.insert(user_id, permissions.can_edit);
-        return;
-    }
-    room.edit_permissions.remove(&user_id);
-}
-
-fn broadcast_role_update(
-    room: &room::Room,
-    user_id: uuid::Uuid,
-    role: Option<BoardRole>,
-    permissions: Option<BoardPermissions>,
-) {
-    let payload = protocol::BoardRoleUpdate {
-        user_id,
-        role,
-        permissions,
-    };
-    let encoded = match serde_json::to_vec(&payload) {
-        Ok(encoded) => encoded,
-        Err(error) => {
-            tracing::warn!("Failed to encode board role update: {}", error);
-            return;
-        }
-    };
-    let mut message = Vec::with_capacity(encoded.len() + 1);
-    message.push(protocol::OP_ROLE_UPDATE);
-    message.extend(encoded);
-    let _ = room.tx.send(Bytes::from(message));
+    Ok(Json(result))
+}
+
+/// Queues a rebuild of every board's element projection (platform admins
+/// only).
+pub async fn rebuild_projections_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<(axum::http::StatusCode, Json<ProjectionRebuildResponse>), AppError> {
+    let response =
+        BoardService::rebuild_projections(&state.db, &state.rooms, auth_user.user_id).await?;
+    Ok((axum::http::StatusCode::ACCEPTED, Json(response)))
+}
+
+/// Returns WebSocket resync counters for this node (platform admins only).
+pub async fn resync_metrics_handle(
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<ResyncMetrics>, AppError> {
+    Ok(Json(BoardService::resync_metrics(auth_user.user_id)?))
+}
+
+/// Dumps a board's raw CRDT element map (platform admins only).
+pub async fn dump_board_crdt_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<Json<BoardCrdtDumpResponse>, AppError> {
+    let response =
+        BoardService::dump_board_crdt(&state.db, &state.rooms, board_id, auth_user.user_id).await?;
+    Ok(Json(response))
 }
@@ -32,7 +32,45 @@ pub async fn create_board_comment_handle(
     Path(board_id): Path<Uuid>,
     Json(req): Json<CreateCommentRequest>,
 ) -> Result<(StatusCode, Json<CommentResponse>), AppError> {
-    let response =
-        CommentService::create_comment(&state.db, board_id, auth_user.user_id, req).await?;
+    let response = CommentService::create_comment(
+        &state.db,
+        state.content_filter.as_ref(),
+        board_id,
+        auth_user.user_id,
+        req,
+    )
+    .await?;
     Ok((StatusCode::CREATED, Json(response)))
 }
+
+pub async fn pin_board_comment_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((board_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<CommentResponse>, AppError> {
+    let response = CommentService::pin_comment(
+        &state.db,
+        &state.rooms,
+        board_id,
+        comment_id,
+        auth_user.user_id,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+pub async fn unpin_board_comment_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((board_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<CommentResponse>, AppError> {
+    let response = CommentService::unpin_comment(
+        &state.db,
+        &state.rooms,
+        board_id,
+        comment_id,
+        auth_user.user_id,
+    )
+    .await?;
+    Ok(Json(response))
+}
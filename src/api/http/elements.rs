@@ -7,25 +7,66 @@ use crate::{
     app::state::AppState,
     auth::middleware::AuthUser,
     dto::elements::{
-        BoardElementResponse, CreateBoardElementRequest, DeleteBoardElementResponse,
-        ExpectedVersionQuery, RestoreBoardElementResponse, UpdateBoardElementRequest,
+        ApplyStyleRequest, ApplyStyleResponse, BoardElementListResponse, BoardElementResponse,
+        BulkBoardElementsResponse, BulkDeleteBoardElementsRequest, BulkRestoreBoardElementsRequest,
+        CompactZIndexResponse, ConvertBoardElementRequest, CreateBoardElementRequest,
+        DeleteBoardElementResponse, ElementDiffQuery, ElementDiffResponse, ElementHistoryQuery,
+        ElementHistoryResponse, ExpectedVersionQuery, ListBoardElementsQuery,
+        PasteBoardElementsRequest, PasteBoardElementsResponse, RecentlyDeletedElementsResponse,
+        RecentlyDeletedQuery, RestoreBoardElementResponse, RevertBoardElementRequest,
+        UpdateBoardElementRequest,
     },
     error::AppError,
     usecases::elements::ElementService,
 };
 
+pub async fn list_board_elements_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+    Query(query): Query<ListBoardElementsQuery>,
+) -> Result<Json<BoardElementListResponse>, AppError> {
+    let response =
+        ElementService::list_elements(&state.db, board_id, auth_user.user_id, query).await?;
+    Ok(Json(response))
+}
+
 pub async fn create_board_element_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(board_id): Path<uuid::Uuid>,
     Json(req): Json<CreateBoardElementRequest>,
 ) -> Result<(axum::http::StatusCode, Json<BoardElementResponse>), AppError> {
-    let element =
-        ElementService::create_element(&state.db, &state.rooms, board_id, auth_user.user_id, req)
-            .await?;
+    let element = ElementService::create_element(
+        &state.db,
+        &state.rooms,
+        state.content_filter.as_ref(),
+        board_id,
+        auth_user.user_id,
+        req,
+    )
+    .await?;
     Ok((axum::http::StatusCode::CREATED, Json(element)))
 }
 
+pub async fn paste_board_elements_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+    Json(req): Json<PasteBoardElementsRequest>,
+) -> Result<(axum::http::StatusCode, Json<PasteBoardElementsResponse>), AppError> {
+    let response = ElementService::paste_elements(
+        &state.db,
+        &state.rooms,
+        state.content_filter.as_ref(),
+        board_id,
+        auth_user.user_id,
+        req,
+    )
+    .await?;
+    Ok((axum::http::StatusCode::CREATED, Json(response)))
+}
+
 pub async fn update_board_element_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
@@ -35,6 +76,7 @@ pub async fn update_board_element_handle(
     let element = ElementService::update_element(
         &state.db,
         &state.rooms,
+        state.content_filter.as_ref(),
         board_id,
         element_id,
         auth_user.user_id,
@@ -62,6 +104,85 @@ pub async fn delete_board_element_handle(
     Ok(Json(response))
 }
 
+pub async fn bulk_delete_board_elements_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+    Json(req): Json<BulkDeleteBoardElementsRequest>,
+) -> Result<Json<BulkBoardElementsResponse>, AppError> {
+    let response = ElementService::bulk_delete(
+        &state.db,
+        &state.rooms,
+        board_id,
+        auth_user.user_id,
+        req.elements,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+pub async fn apply_board_element_style_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+    Json(req): Json<ApplyStyleRequest>,
+) -> Result<Json<ApplyStyleResponse>, AppError> {
+    let response = ElementService::apply_style(
+        &state.db,
+        &state.rooms,
+        board_id,
+        auth_user.user_id,
+        req.source_id,
+        req.target_ids,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+pub async fn compact_board_z_index_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+) -> Result<Json<CompactZIndexResponse>, AppError> {
+    let response =
+        ElementService::compact_z_indices(&state.db, &state.rooms, board_id, auth_user.user_id)
+            .await?;
+    Ok(Json(response))
+}
+
+pub async fn list_recently_deleted_elements_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+    Query(query): Query<RecentlyDeletedQuery>,
+) -> Result<Json<RecentlyDeletedElementsResponse>, AppError> {
+    let response = ElementService::list_recently_deleted(
+        &state.db,
+        board_id,
+        auth_user.user_id,
+        query.within_minutes,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+pub async fn bulk_restore_board_elements_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<uuid::Uuid>,
+    Json(req): Json<BulkRestoreBoardElementsRequest>,
+) -> Result<Json<BulkBoardElementsResponse>, AppError> {
+    let response = ElementService::bulk_restore(
+        &state.db,
+        &state.rooms,
+        board_id,
+        auth_user.user_id,
+        req.elements,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
 pub async fn restore_board_element_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
@@ -79,3 +200,73 @@ pub async fn restore_board_element_handle(
     .await?;
     Ok(Json(response))
 }
+
+pub async fn list_element_history_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((board_id, element_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    Query(query): Query<ElementHistoryQuery>,
+) -> Result<Json<ElementHistoryResponse>, AppError> {
+    let response = ElementService::list_element_history(
+        &state.db,
+        board_id,
+        element_id,
+        auth_user.user_id,
+        query,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+pub async fn diff_element_versions_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((board_id, element_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    Query(query): Query<ElementDiffQuery>,
+) -> Result<Json<ElementDiffResponse>, AppError> {
+    let response = ElementService::diff_element_versions(
+        &state.db,
+        board_id,
+        element_id,
+        auth_user.user_id,
+        query,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+pub async fn revert_board_element_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((board_id, element_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    Json(req): Json<RevertBoardElementRequest>,
+) -> Result<Json<BoardElementResponse>, AppError> {
+    let element = ElementService::revert_element(
+        &state.db,
+        &state.rooms,
+        board_id,
+        element_id,
+        auth_user.user_id,
+        req.target_version,
+    )
+    .await?;
+    Ok(Json(element))
+}
+
+pub async fn convert_board_element_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((board_id, element_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    Json(req): Json<ConvertBoardElementRequest>,
+) -> Result<Json<BoardElementResponse>, AppError> {
+    let element = ElementService::change_element_type(
+        &state.db,
+        &state.rooms,
+        board_id,
+        element_id,
+        auth_user.user_id,
+        req.element_type,
+    )
+    .await?;
+    Ok(Json(element))
+}
@@ -3,4 +3,6 @@ pub(crate) mod boards;
 pub(crate) mod comments;
 pub(crate) mod elements;
 pub(crate) mod organizations;
+pub(crate) mod reactions;
 pub(crate) mod telemetry;
+pub(crate) mod voting;
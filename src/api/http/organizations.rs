@@ -1,7 +1,8 @@
 use axum::{
     Extension, Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
 };
 use uuid::Uuid;
 
@@ -9,11 +10,17 @@ use crate::{
     app::state::AppState,
     auth::middleware::AuthUser,
     dto::organizations::{
-        CreateOrganizationRequest, InviteMembersRequest, InviteMembersResponse,
-        InviteValidationQuery, InviteValidationResponse, OrganizationActionMessage,
-        OrganizationEmailInvitesResponse, OrganizationListResponse, OrganizationMembersResponse,
-        OrganizationResponse, OrganizationUsageResponse, SlugAvailabilityQuery,
-        SlugAvailabilityResponse, UpdateMemberRoleRequest, UpdateOrganizationSubscriptionRequest,
+        ApiTokensResponse, CreateApiTokenRequest, CreateOrganizationRequest,
+        CreatedApiTokenResponse, InviteMembersRequest, InviteMembersResponse,
+        InviteValidationQuery, InviteValidationResponse, ListMembersQuery, MemberSearchQuery,
+        OrganizationActionMessage, OrganizationBoardDefaultsResponse, OrganizationBrandingResponse,
+        OrganizationEmailInvitesResponse, OrganizationListResponse,
+        OrganizationMemberSearchResponse, OrganizationMembersResponse, OrganizationResponse,
+        OrganizationUsageResponse, SlugAvailabilityQuery, SlugAvailabilityResponse,
+        TierLimitResponse, TierLimitsResponse, TransferOrganizationOwnershipRequest,
+        UpdateMemberRoleRequest, UpdateOrganizationBoardDefaultsRequest,
+        UpdateOrganizationBrandingRequest, UpdateOrganizationSlugRequest,
+        UpdateOrganizationSubscriptionRequest, UpdateTierLimitRequest,
     },
     error::AppError,
     usecases::organizations::OrganizationService,
@@ -46,9 +53,87 @@ pub async fn list_members_handle(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(organization_id): Path<Uuid>,
+    Query(query): Query<ListMembersQuery>,
 ) -> Result<Json<OrganizationMembersResponse>, AppError> {
     let response =
-        OrganizationService::list_members(&state.db, organization_id, auth_user.user_id).await?;
+        OrganizationService::list_members(&state.db, organization_id, auth_user.user_id, query)
+            .await?;
+
+    Ok(Json(response))
+}
+
+/// Downloads the member roster as a CSV attachment (owners/admins only).
+pub async fn export_members_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let csv =
+        OrganizationService::export_members_csv(&state.db, organization_id, auth_user.user_id)
+            .await?;
+    let disposition = format!(
+        "attachment; filename=\"organization-{}-members.csv\"",
+        organization_id
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        csv,
+    ))
+}
+
+/// Lists active API tokens for an organization (owners/admins only).
+pub async fn list_api_tokens_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<Json<ApiTokensResponse>, AppError> {
+    let tokens =
+        OrganizationService::list_api_tokens(&state.db, organization_id, auth_user.user_id).await?;
+    Ok(Json(tokens))
+}
+
+/// Creates an API token; the secret is only included in this response.
+pub async fn create_api_token_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(req): Json<CreateApiTokenRequest>,
+) -> Result<(StatusCode, Json<CreatedApiTokenResponse>), AppError> {
+    let token =
+        OrganizationService::create_api_token(&state.db, organization_id, auth_user.user_id, req)
+            .await?;
+    Ok((StatusCode::CREATED, Json(token)))
+}
+
+/// Revokes an organization API token.
+pub async fn revoke_api_token_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((organization_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<OrganizationActionMessage>, AppError> {
+    let message = OrganizationService::revoke_api_token(
+        &state.db,
+        organization_id,
+        auth_user.user_id,
+        token_id,
+    )
+    .await?;
+    Ok(Json(message))
+}
+
+/// Searches organization members for pickers and autocomplete.
+pub async fn search_members_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<Uuid>,
+    Query(query): Query<MemberSearchQuery>,
+) -> Result<Json<OrganizationMemberSearchResponse>, AppError> {
+    let response =
+        OrganizationService::search_members(&state.db, organization_id, auth_user.user_id, query)
+            .await?;
 
     Ok(Json(response))
 }
@@ -71,7 +156,18 @@ pub async fn update_subscription_tier_handle(
     Extension(auth_user): Extension<AuthUser>,
     Path(organization_id): Path<Uuid>,
     Json(req): Json<UpdateOrganizationSubscriptionRequest>,
-) -> Result<Json<OrganizationResponse>, AppError> {
+) -> Result<Response, AppError> {
+    if req.dry_run {
+        let preview = OrganizationService::preview_subscription_tier(
+            &state.db,
+            organization_id,
+            auth_user.user_id,
+            req,
+        )
+        .await?;
+        return Ok(Json(preview).into_response());
+    }
+
     let response = OrganizationService::update_subscription_tier(
         &state.db,
         organization_id,
@@ -80,6 +176,27 @@ pub async fn update_subscription_tier_handle(
     )
     .await?;
 
+    Ok(Json(response).into_response())
+}
+
+/// Lists effective subscription tier limits (platform admins only).
+pub async fn list_tier_limits_handle(
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<TierLimitsResponse>, AppError> {
+    let response = OrganizationService::list_tier_limits(auth_user.user_id).await?;
+
+    Ok(Json(response))
+}
+
+/// Updates a subscription tier's limits (platform admins only).
+pub async fn update_tier_limit_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UpdateTierLimitRequest>,
+) -> Result<Json<TierLimitResponse>, AppError> {
+    let response =
+        OrganizationService::update_tier_limit(&state.db, auth_user.user_id, req).await?;
+
     Ok(Json(response))
 }
 
@@ -141,6 +258,53 @@ pub async fn check_slug_availability_handle(
     Ok(Json(response))
 }
 
+/// Changes an organization's slug (owner only).
+pub async fn update_slug_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(req): Json<UpdateOrganizationSlugRequest>,
+) -> Result<Json<OrganizationResponse>, AppError> {
+    let response =
+        OrganizationService::update_slug(&state.db, organization_id, auth_user.user_id, req)
+            .await?;
+
+    Ok(Json(response))
+}
+
+/// Replaces an organization's logo and email branding (owner or admin).
+pub async fn update_branding_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(req): Json<UpdateOrganizationBrandingRequest>,
+) -> Result<Json<OrganizationBrandingResponse>, AppError> {
+    let response =
+        OrganizationService::update_branding(&state.db, organization_id, auth_user.user_id, req)
+            .await?;
+
+    Ok(Json(response))
+}
+
+/// Sets the member role and visibility defaults for new org boards (owner or
+/// admin).
+pub async fn update_board_defaults_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(req): Json<UpdateOrganizationBoardDefaultsRequest>,
+) -> Result<Json<OrganizationBoardDefaultsResponse>, AppError> {
+    let response = OrganizationService::update_board_defaults(
+        &state.db,
+        organization_id,
+        auth_user.user_id,
+        req,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
 /// Validates a pre-signup invite token.
 pub async fn validate_invite_handle(
     State(state): State<AppState>,
@@ -190,6 +354,20 @@ pub async fn update_member_role_handle(
     Ok(Json(response))
 }
 
+/// Transfers organization ownership to another member.
+pub async fn transfer_ownership_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(organization_id): Path<Uuid>,
+    Json(req): Json<TransferOrganizationOwnershipRequest>,
+) -> Result<Json<OrganizationActionMessage>, AppError> {
+    let response =
+        OrganizationService::transfer_ownership(&state.db, organization_id, auth_user.user_id, req)
+            .await?;
+
+    Ok(Json(response))
+}
+
 /// Removes a member from an organization.
 pub async fn remove_member_handle(
     State(state): State<AppState>,
@@ -0,0 +1,38 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use uuid::Uuid;
+
+use crate::{
+    app::state::AppState,
+    auth::middleware::AuthUser,
+    dto::reactions::{
+        ListReactionsQuery, ReactionListResponse, ToggleReactionRequest, ToggleReactionResponse,
+    },
+    error::AppError,
+    usecases::reactions::ReactionService,
+};
+
+pub async fn list_board_reactions_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<Uuid>,
+    Query(query): Query<ListReactionsQuery>,
+) -> Result<Json<ReactionListResponse>, AppError> {
+    let response =
+        ReactionService::list_reactions(&state.db, board_id, auth_user.user_id, query).await?;
+    Ok(Json(response))
+}
+
+pub async fn toggle_board_reaction_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<Uuid>,
+    Json(req): Json<ToggleReactionRequest>,
+) -> Result<Json<ToggleReactionResponse>, AppError> {
+    let response =
+        ReactionService::toggle_reaction(&state.db, &state.rooms, board_id, auth_user.user_id, req)
+            .await?;
+    Ok(Json(response))
+}
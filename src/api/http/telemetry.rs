@@ -1,21 +1,43 @@
 use axum::{
-    Json,
-    extract::State,
-    http::{HeaderMap, header},
+    Extension, Json,
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::AtomicU64;
 use uuid::Uuid;
 
-use crate::{app::state::AppState, error::AppError};
+use crate::{
+    app::state::AppState,
+    auth::middleware::AuthUser,
+    error::AppError,
+    telemetry::{http::RequestContext, redact_pii, sampling},
+};
 
 const MAX_EVENTS: usize = 50;
 const MAX_MESSAGE_LEN: usize = 2000;
 const MAX_STACK_LEN: usize = 8000;
 const MAX_CONTEXT_LEN: usize = 8000;
+const SAMPLE_TARGET: &str = "client_log";
+const SENSITIVE_CONTEXT_KEYS: &[&str] = &[
+    "password",
+    "token",
+    "access_token",
+    "refresh_token",
+    "authorization",
+    "cookie",
+    "secret",
+];
+
+static SAMPLE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// Events are kept as raw JSON so one malformed entry is dropped on its own
+/// instead of rejecting the whole batch.
 #[derive(Debug, Deserialize)]
 pub(crate) struct ClientLogBatch {
-    events: Vec<ClientLogEvent>,
+    events: Vec<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,49 +56,114 @@ pub(crate) struct ClientLogEvent {
     source: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub(crate) struct ClientLogIngestResponse {
+    accepted: usize,
+    dropped: usize,
+}
+
 pub async fn ingest_client_logs(
-    State(state): State<AppState>,
-    headers: HeaderMap,
+    auth_user: Option<Extension<AuthUser>>,
+    request_context: Option<Extension<RequestContext>>,
     Json(payload): Json<ClientLogBatch>,
-) -> Result<(), AppError> {
+) -> Result<Json<ClientLogIngestResponse>, AppError> {
     if payload.events.is_empty() {
         return Err(AppError::BadRequest("Events payload is empty".to_string()));
     }
     if payload.events.len() > MAX_EVENTS {
-        return Err(AppError::BadRequest("Too many log events".to_string()));
+        return Err(AppError::PayloadTooLarge(format!(
+            "At most {} log events can be sent at once",
+            MAX_EVENTS
+        )));
     }
 
-    let user_id = maybe_extract_user_id(&state, &headers)?;
-
-    for event in payload.events {
-        validate_event(&event)?;
-        emit_client_log(event, user_id.as_ref());
+    let user_id = auth_user.map(|Extension(auth_user)| auth_user.user_id);
+    let request_id = request_context
+        .map(|Extension(context)| context.request_id)
+        .unwrap_or_default();
+    let received = payload.events.len();
+    let mut accepted = 0;
+    for event in payload.events.into_iter().filter_map(parse_event) {
+        accepted += 1;
+        // Errors are always kept; other levels follow `LOG_SAMPLE_RATES`.
+        if event.level != "error" && !sampling::should_sample(SAMPLE_TARGET, &SAMPLE_COUNTER) {
+            continue;
+        }
+        emit_client_log(redact_event(event), user_id.as_ref(), &request_id);
+    }
+    let dropped = received - accepted;
+    if dropped > 0 {
+        tracing::debug!(dropped, "Dropped malformed client log events");
     }
 
-    Ok(())
+    Ok(Json(ClientLogIngestResponse { accepted, dropped }))
 }
 
-fn maybe_extract_user_id(state: &AppState, headers: &HeaderMap) -> Result<Option<Uuid>, AppError> {
-    let token = headers
+/// Attaches the caller's identity when a bearer token is sent, so client log
+/// rate limits apply per user; anonymous callers are limited per IP.
+pub async fn client_log_identity_middleware(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let token = req
+        .headers()
         .get(header::AUTHORIZATION)
         .and_then(|value| value.to_str().ok())
         .and_then(|value| value.strip_prefix("Bearer "))
         .map(str::to_string);
 
-    let Some(token) = token else {
-        return Ok(None);
-    };
+    if let Some(token) = token {
+        let claims = state
+            .jwt_config
+            .verify_token(&token)
+            .map_err(|err| AppError::Unauthorized(format!("Invalid token: {}", err)))?;
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized("Invalid user id".to_string()))?;
+        req.extensions_mut().insert(AuthUser {
+            user_id,
+            email: claims.email,
+            session_id: None,
+        });
+    }
 
-    let jwt_config = state.jwt_config.clone();
+    Ok(next.run(req).await)
+}
 
-    let claims = jwt_config
-        .verify_token(&token)
-        .map_err(|err| AppError::Unauthorized(format!("Invalid token: {}", err)))?;
+fn parse_event(value: serde_json::Value) -> Option<ClientLogEvent> {
+    let event = serde_json::from_value::<ClientLogEvent>(value).ok()?;
+    validate_event(&event).ok()?;
+    Some(event)
+}
 
-    let user_id = Uuid::parse_str(&claims.sub)
-        .map_err(|_| AppError::Unauthorized("Invalid user id".to_string()))?;
+/// Strips emails and credentials from client-supplied text before it reaches
+/// the log pipeline.
+fn redact_event(mut event: ClientLogEvent) -> ClientLogEvent {
+    event.message = redact_pii(&event.message);
+    event.stack = event.stack.as_deref().map(redact_pii);
+    event.url = event.url.as_deref().map(redact_pii);
+    event.route = event.route.as_deref().map(redact_pii);
+    if let Some(context) = event.context.as_mut() {
+        redact_context(context);
+    }
+    event
+}
 
-    Ok(Some(user_id))
+fn redact_context(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(text) => *text = redact_pii(text),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_context),
+        serde_json::Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                if SENSITIVE_CONTEXT_KEYS.contains(&key.to_ascii_lowercase().as_str()) {
+                    *field = serde_json::Value::String("[REDACTED]".to_string());
+                } else {
+                    redact_context(field);
+                }
+            }
+        }
+        _ => {}
+    }
 }
 
 fn validate_event(event: &ClientLogEvent) -> Result<(), AppError> {
@@ -110,7 +197,7 @@ fn validate_event(event: &ClientLogEvent) -> Result<(), AppError> {
     Ok(())
 }
 
-fn emit_client_log(event: ClientLogEvent, user_id: Option<&Uuid>) {
+fn emit_client_log(event: ClientLogEvent, user_id: Option<&Uuid>, request_id: &str) {
     let level = event.level.as_str();
     let user_id = user_id.map(ToString::to_string).unwrap_or_default();
 
@@ -126,6 +213,7 @@ fn emit_client_log(event: ClientLogEvent, user_id: Option<&Uuid>) {
             route = event.route.as_deref().unwrap_or(""),
             source = event.source.as_deref().unwrap_or(""),
             user_id = %user_id,
+            request_id = %request_id,
             timestamp = event.timestamp.as_deref().unwrap_or(""),
             context = ?event.context,
             stack = event.stack.as_deref().unwrap_or(""),
@@ -142,6 +230,7 @@ fn emit_client_log(event: ClientLogEvent, user_id: Option<&Uuid>) {
             route = event.route.as_deref().unwrap_or(""),
             source = event.source.as_deref().unwrap_or(""),
             user_id = %user_id,
+            request_id = %request_id,
             timestamp = event.timestamp.as_deref().unwrap_or(""),
             context = ?event.context,
             stack = event.stack.as_deref().unwrap_or(""),
@@ -158,6 +247,7 @@ fn emit_client_log(event: ClientLogEvent, user_id: Option<&Uuid>) {
             route = event.route.as_deref().unwrap_or(""),
             source = event.source.as_deref().unwrap_or(""),
             user_id = %user_id,
+            request_id = %request_id,
             timestamp = event.timestamp.as_deref().unwrap_or(""),
             context = ?event.context,
             stack = event.stack.as_deref().unwrap_or(""),
@@ -174,6 +264,7 @@ fn emit_client_log(event: ClientLogEvent, user_id: Option<&Uuid>) {
             route = event.route.as_deref().unwrap_or(""),
             source = event.source.as_deref().unwrap_or(""),
             user_id = %user_id,
+            request_id = %request_id,
             timestamp = event.timestamp.as_deref().unwrap_or(""),
             context = ?event.context,
             stack = event.stack.as_deref().unwrap_or(""),
@@ -181,3 +272,50 @@ fn emit_client_log(event: ClientLogEvent, user_id: Option<&Uuid>) {
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{parse_event, redact_event};
+
+    #[test]
+    fn malformed_events_are_dropped_individually() {
+        assert!(parse_event(json!({ "level": "error", "message": "boom" })).is_some());
+        assert!(parse_event(json!({ "level": "fatal", "message": "boom" })).is_none());
+        assert!(parse_event(json!({ "level": "info", "message": "   " })).is_none());
+        assert!(parse_event(json!({ "level": "info" })).is_none());
+        assert!(parse_event(json!("not an object")).is_none());
+        assert!(
+            parse_event(json!({
+                "level": "warn",
+                "message": "slow",
+                "stack": "x".repeat(super::MAX_STACK_LEN + 1),
+            }))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn redaction_covers_message_url_and_context() {
+        let event = parse_event(json!({
+            "level": "error",
+            "message": "Invite failed for ann@acme.io",
+            "url": "https://app/join?token=abc",
+            "context": { "password": "hunter2", "users": ["bo@acme.io"], "count": 2 },
+        }))
+        .unwrap();
+
+        let event = redact_event(event);
+
+        assert_eq!(event.message, "Invite failed for a***@acme.io");
+        assert_eq!(
+            event.url.as_deref(),
+            Some("https://app/join?token=[REDACTED]")
+        );
+        assert_eq!(
+            event.context,
+            Some(json!({ "password": "[REDACTED]", "users": ["b***@acme.io"], "count": 2 }))
+        );
+    }
+}
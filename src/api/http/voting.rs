@@ -0,0 +1,56 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::{
+    app::state::AppState,
+    auth::middleware::AuthUser,
+    dto::voting::{CastVoteRequest, StartVotingRequest, VotingSessionResponse},
+    error::AppError,
+    usecases::voting::VotingService,
+};
+
+pub async fn get_board_voting_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<Uuid>,
+) -> Result<Json<VotingSessionResponse>, AppError> {
+    let response = VotingService::get_voting(&state.db, board_id, auth_user.user_id).await?;
+    Ok(Json(response))
+}
+
+pub async fn start_board_voting_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<Uuid>,
+    Json(req): Json<StartVotingRequest>,
+) -> Result<(StatusCode, Json<VotingSessionResponse>), AppError> {
+    let response =
+        VotingService::start_voting(&state.db, &state.rooms, board_id, auth_user.user_id, req)
+            .await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+pub async fn cast_board_vote_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<Uuid>,
+    Json(req): Json<CastVoteRequest>,
+) -> Result<Json<VotingSessionResponse>, AppError> {
+    let response =
+        VotingService::cast_vote(&state.db, &state.rooms, board_id, auth_user.user_id, req).await?;
+    Ok(Json(response))
+}
+
+pub async fn end_board_voting_handle(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(board_id): Path<Uuid>,
+) -> Result<Json<VotingSessionResponse>, AppError> {
+    let response =
+        VotingService::end_voting(&state.db, &state.rooms, board_id, auth_user.user_id).await?;
+    Ok(Json(response))
+}
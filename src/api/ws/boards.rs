@@ -8,18 +8,18 @@ use axum::{
     Extension,
     body::Bytes,
     extract::{
-        Path, State, WebSocketUpgrade,
-        ws::{Message, WebSocket},
+        Path, Query, State, WebSocketUpgrade,
+        ws::{CloseFrame, Message, WebSocket},
     },
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
 };
 use chrono::Utc;
-use futures::{SinkExt, StreamExt};
+use futures::{SinkExt, StreamExt, stream::SplitSink};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::atomic::Ordering;
-use tokio::sync::watch;
+use tokio::sync::{broadcast::error::RecvError, watch};
 use tracing::Instrument;
 use uuid::Uuid;
 use yrs::{
@@ -32,20 +32,33 @@ use yrs::{
 use crate::{
     app::state::AppState,
     auth::middleware::AuthUser,
+    dto::{boards::BoardCapabilities, elements::CreateBoardElementRequest},
     error::AppError,
     models::{
         boards::BoardPermissions,
         presence::{PresenceStatus, PresenceUser},
     },
-    realtime::{protocol, room, snapshot},
+    realtime::{
+        awareness::{self, AwarenessBroadcast, AwarenessThrottle},
+        element_crdt, outbound, protocol, resume, room,
+        room::Rooms,
+        snapshot,
+    },
     repositories::boards as board_repo,
-    telemetry::{REQUEST_ID_HEADER, TRACE_ID_HEADER, extract_header, extract_or_generate_header},
+    services::content_filter::SharedContentFilter,
+    telemetry::{
+        BusinessEvent, REQUEST_ID_HEADER, TRACE_ID_HEADER, extract_header,
+        extract_or_generate_header, sample_session_events, sampling,
+    },
     usecases::boards::BoardService,
+    usecases::elements::ElementService,
     usecases::presence::PresenceService,
 };
 
 const MAX_CONCURRENT_USERS: i64 = 100;
 const PRESENCE_CLEANUP_INTERVAL_MS: u64 = 60_000;
+const OVERLOAD_CLOSE_TIMEOUT_MS: u64 = 1_000;
+const DEFAULT_QUEUE_TIMEOUT_SECS: u64 = 300;
 
 #[derive(Debug, Deserialize)]
 struct ClientEvent {
@@ -54,12 +67,113 @@ struct ClientEvent {
     payload: Option<serde_json::Value>,
 }
 
+/// Optional query parameters on the WebSocket upgrade request.
+#[derive(Debug, Default, Deserialize)]
+pub struct WsConnectQuery {
+    /// Hex-encoded yrs state vector the client already has; when present the
+    /// initial sync only carries the missing delta.
+    resume_sv: Option<String>,
+    /// Token from a previous session's `board:joined`; resumes from the state
+    /// the server last knew that session had.
+    resume_token: Option<String>,
+}
+
+fn decode_resume_state_vector(value: Option<&str>) -> Option<StateVector> {
+    let value = value?.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let bytes = match hex::decode(value) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!("Ignoring resume state vector with invalid hex: {}", error);
+            return None;
+        }
+    };
+    match StateVector::decode_v1(&bytes) {
+        Ok(state_vector) => Some(state_vector),
+        Err(error) => {
+            tracing::warn!("Ignoring undecodable resume state vector: {}", error);
+            None
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct PresenceUpdatePayload {
     status: String,
     metadata: Option<serde_json::Value>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ElementCreatePayload {
+    request_id: Option<String>,
+    #[serde(flatten)]
+    element: CreateBoardElementRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct PresenterSetPayload {
+    active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewportFollowPayload {
+    center_x: f64,
+    center_y: f64,
+    zoom: f64,
+}
+
+impl ViewportFollowPayload {
+    fn is_valid(&self) -> bool {
+        self.center_x.is_finite()
+            && self.center_y.is_finite()
+            && self.zoom.is_finite()
+            && self.zoom > 0.0
+    }
+}
+
+/// Text events accepted from clients, keyed by the envelope `type`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+enum ClientTextEvent {
+    #[serde(rename = "heartbeat")]
+    Heartbeat,
+    #[serde(rename = "presence:update")]
+    PresenceUpdate(PresenceUpdatePayload),
+    #[serde(rename = "element:create")]
+    ElementCreate(Box<ElementCreatePayload>),
+    #[serde(rename = "presenter:set")]
+    PresenterSet(PresenterSetPayload),
+    #[serde(rename = "viewport:follow")]
+    ViewportFollow(ViewportFollowPayload),
+}
+
+impl ClientTextEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Heartbeat => "heartbeat",
+            Self::PresenceUpdate(_) => "presence:update",
+            Self::ElementCreate(_) => "element:create",
+            Self::PresenterSet(_) => "presenter:set",
+            Self::ViewportFollow(_) => "viewport:follow",
+        }
+    }
+}
+
+fn parse_client_text_event(text: &str) -> Result<ClientTextEvent, String> {
+    let envelope = serde_json::from_str::<ClientEvent>(text)
+        .map_err(|error| format!("malformed event envelope: {}", error))?;
+    serde_json::from_str::<ClientTextEvent>(text).map_err(|error| {
+        format!(
+            "unsupported event `{}` (payload present: {}): {}",
+            envelope.event_type,
+            envelope.payload.is_some(),
+            error
+        )
+    })
+}
+
 fn build_text_message<T: Serialize>(event_type: &str, payload: T) -> Option<Message> {
     let value = json!({ "type": event_type, "payload": payload });
     match serde_json::to_string(&value) {
@@ -92,6 +206,76 @@ fn presence_user_payload(user: &PresenceUser) -> serde_json::Value {
     })
 }
 
+fn presenter_payload(presenter: Option<room::Presenter>) -> serde_json::Value {
+    match presenter {
+        Some(presenter) => json!({
+            "user_id": presenter.user_id,
+            "session_id": presenter.session_id,
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
+fn broadcast_presenter_changed(room: &room::Room, presenter: Option<room::Presenter>) {
+    if let Some(Message::Text(text)) = build_text_message(
+        "presenter:changed",
+        json!({
+            "presenter": presenter_payload(presenter),
+            "timestamp": Utc::now().timestamp_millis(),
+        }),
+    ) {
+        let _ = room.text_tx.send(text.to_string());
+    }
+}
+
+async fn sleep_until_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Broadcasts the current awareness state of `clients` as one coalesced update.
+async fn broadcast_awareness_clients(room: &room::Room, clients: Vec<ClientID>, user_id: Uuid) {
+    if clients.is_empty() {
+        return;
+    }
+    let update = {
+        let awareness = room.awareness.read().await;
+        awareness.update_with_clients(clients)
+    };
+    match update {
+        Ok(update) => {
+            let mut msg = vec![protocol::OP_AWARENESS];
+            msg.extend(update.encode_v1());
+            let _ = room.tx.send(Bytes::from(msg));
+        }
+        Err(error) => {
+            tracing::warn!(
+                "Failed to build coalesced awareness update for user {}: {}",
+                user_id,
+                error
+            );
+        }
+    }
+}
+
+/// Encodes the full document state as a sync step 2 message.
+async fn full_sync_message(room: &room::Room) -> Bytes {
+    sync_message_since(room, &StateVector::default()).await
+}
+
+/// Encodes the document changes missing from `state_vector` as a sync step 2 message.
+async fn sync_message_since(room: &room::Room, state_vector: &StateVector) -> Bytes {
+    let doc_guard = room.doc.lock().await;
+    let txn = doc_guard.transact();
+    let update = txn.encode_state_as_update_v1(state_vector);
+    let mut msg = Vec::with_capacity(update.len() + 1);
+    msg.push(protocol::OP_SYNCSTEP_2);
+    msg.extend(update);
+    Bytes::from(msg)
+}
+
 fn op_name(op_code: u8) -> &'static str {
     match op_code {
         protocol::OP_SYNCSTEP_1 => "syncstep_1",
@@ -103,22 +287,12 @@ fn op_name(op_code: u8) -> &'static str {
     }
 }
 
-fn log_ws_message_sample_rate() -> u64 {
-    std::env::var("WS_MESSAGE_LOG_SAMPLE_RATE")
-        .ok()
-        .and_then(|value| value.parse::<u64>().ok())
-        .filter(|value| *value > 0)
-        .unwrap_or(100)
-}
-
 fn log_ws_message(direction: &str, message: &Message) {
     if !tracing::enabled!(target: "ws_message", tracing::Level::DEBUG) {
         return;
     }
     static LOG_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
-    let sample_rate = log_ws_message_sample_rate();
-    let current = LOG_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-    if current % sample_rate != 0 {
+    if !sampling::should_sample("ws_message", &LOG_COUNTER) {
         return;
     }
     match message {
@@ -173,10 +347,13 @@ fn log_ws_message(direction: &str, message: &Message) {
                 .as_ref()
                 .map(|inner| inner.reason.to_string())
                 .unwrap_or_else(|| "client_close".to_string());
+            let code = frame.as_ref().map(|inner| inner.code);
             tracing::debug!(
                 target: "ws_message",
                 direction = direction,
                 message_type = "close",
+                code = ?code,
+                code_name = code.map(protocol::close_code_name),
                 reason = %reason,
                 "WebSocket close"
             );
@@ -184,6 +361,24 @@ fn log_ws_message(direction: &str, message: &Message) {
     }
 }
 
+fn is_access_revoked_for(message: &[u8], user_id: Uuid) -> bool {
+    let Some((&protocol::OP_ROLE_UPDATE, payload)) = message.split_first() else {
+        return false;
+    };
+    serde_json::from_slice::<protocol::BoardRoleUpdate>(payload)
+        .is_ok_and(|update| update.user_id == user_id && protocol::revokes_access(&update))
+}
+
+/// Longest a session waits in a full board's queue before being turned away.
+fn queue_timeout() -> Duration {
+    let secs = std::env::var("WS_QUEUE_TIMEOUT_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_QUEUE_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+}
+
 fn should_emit_user_left(
     active_session: Result<bool, AppError>,
     board_id: Uuid,
@@ -208,14 +403,37 @@ pub async fn ws_handler(
     headers: HeaderMap,
     Extension(auth_user): Extension<AuthUser>,
     Path(board_id): Path<Uuid>,
+    Query(query): Query<WsConnectQuery>,
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
+    let negotiation = protocol::negotiate_subprotocol(
+        headers
+            .get(header::SEC_WEBSOCKET_PROTOCOL)
+            .and_then(|value| value.to_str().ok()),
+    );
+    let Some(protocol_version) = negotiation.protocol_version() else {
+        tracing::warn!(
+            board_id = %board_id,
+            offered = ?negotiation,
+            "Rejecting WebSocket with unsupported protocol version"
+        );
+        let reason = format!(
+            "Unsupported protocol version; supported: {}",
+            protocol::SUPPORTED_SUBPROTOCOLS.join(", ")
+        );
+        return close_after_upgrade(ws, protocol::CLOSE_UNSUPPORTED_PROTOCOL, reason);
+    };
+    let ws = ws.protocols(protocol::SUPPORTED_SUBPROTOCOLS);
+
     let user_id = auth_user.user_id;
     let permissions = match BoardService::get_access_permissions(&state.db, board_id, user_id).await
     {
         Ok(permissions) => permissions,
+        Err(AppError::Unauthorized(message)) => {
+            return close_after_upgrade(ws, protocol::CLOSE_AUTH_FAILED, message);
+        }
         Err(AppError::Forbidden(message)) => {
-            return (StatusCode::FORBIDDEN, message).into_response();
+            return close_after_upgrade(ws, protocol::CLOSE_PERMISSION_REVOKED, message);
         }
         Err(AppError::NotFound(message)) => {
             return (StatusCode::NOT_FOUND, message).into_response();
@@ -264,38 +482,83 @@ pub async fn ws_handler(
 
     let request_id = extract_or_generate_header(&headers, REQUEST_ID_HEADER);
     let trace_id = extract_header(&headers, TRACE_ID_HEADER).unwrap_or_else(|| request_id.clone());
+    let resume_state_vector = decode_resume_state_vector(query.resume_sv.as_deref());
+    let resume_token = query
+        .resume_token
+        .as_deref()
+        .and_then(|token| Uuid::parse_str(token.trim()).ok());
 
     ws.on_upgrade(move |socket| {
         handle_socket(
             socket,
             state.db.clone(),
             state.redis.clone(),
+            state.rooms.clone(),
+            state.content_filter.clone(),
             board_id,
             board_name,
             user_id,
             permissions,
             room,
+            resume_state_vector,
+            resume_token,
+            protocol_version,
             request_id,
             trace_id,
         )
     })
 }
 
+fn close_message(code: u16, reason: impl Into<String>) -> Message {
+    Message::Close(Some(CloseFrame {
+        code,
+        reason: reason.into().into(),
+    }))
+}
+
+/// Closes a session whose client missed document traffic; closing makes it
+/// reconnect and resync from its state vector.
+async fn close_overloaded(sender: &mut SplitSink<WebSocket, Message>) {
+    let close = close_message(
+        protocol::CLOSE_SERVER_OVERLOAD,
+        "Client too slow; reconnect to resync",
+    );
+    let _ = tokio::time::timeout(
+        Duration::from_millis(OVERLOAD_CLOSE_TIMEOUT_MS),
+        sender.send(close),
+    )
+    .await;
+}
+
+/// Completes the upgrade only to close with `code`, since browsers cannot
+/// read the status of a rejected handshake.
+fn close_after_upgrade(ws: WebSocketUpgrade, code: u16, reason: String) -> Response {
+    ws.on_upgrade(move |mut socket| async move {
+        let _ = socket.send(close_message(code, reason)).await;
+    })
+}
+
 pub async fn handle_socket(
     socket: WebSocket,
     db: sqlx::PgPool,
     redis: Option<redis::Client>,
+    rooms: Rooms,
+    content_filter: SharedContentFilter,
     board_id: Uuid,
     board_name: String,
     user_id: Uuid,
     permissions: BoardPermissions,
     room: Arc<room::Room>,
+    resume_state_vector: Option<StateVector>,
+    resume_token: Option<Uuid>,
+    protocol_version: u32,
     request_id: String,
     trace_id: String,
 ) {
     let can_edit = permissions.can_edit;
     let (sender, mut receiver) = socket.split();
-    let (out_tx, mut out_rx) = tokio::sync::mpsc::unbounded_channel::<Message>();
+    let (out_tx, mut out_rx) = outbound::channel(outbound::outbound_queue_capacity());
+    let outbound_stats = out_tx.stats();
     let (join_tx, join_rx) = watch::channel(false);
     let mut rx = room.tx.subscribe();
     let mut text_rx = room.text_tx.subscribe();
@@ -311,21 +574,46 @@ pub async fn handle_socket(
     );
     tracing::info!(parent: &connection_span, "WebSocket connected");
 
+    let write_stats = outbound_stats.clone();
     let mut write_task = tokio::spawn(
         async move {
             let mut sender = sender;
-            while let Some(msg) = out_rx.recv().await {
+            loop {
+                let msg = tokio::select! {
+                    _ = write_stats.wait_overloaded() => {
+                        close_overloaded(&mut sender).await;
+                        break;
+                    }
+                    msg = out_rx.recv() => match msg {
+                        Some(msg) => msg,
+                        None => break,
+                    },
+                };
                 log_ws_message("outbound", &msg);
-                if sender.send(msg).await.is_err() {
+                let is_close = matches!(msg, Message::Close(_));
+                // A client that stops reading blocks this send; racing it
+                // against the overload signal still lets it be disconnected.
+                let sent = tokio::select! {
+                    sent = sender.send(msg) => sent,
+                    _ = write_stats.wait_overloaded() => {
+                        close_overloaded(&mut sender).await;
+                        break;
+                    }
+                };
+                if sent.is_err() {
                     tracing::warn!("Failed to send websocket message; client disconnected");
                     break;
                 }
+                if is_close {
+                    break;
+                }
             }
         }
         .instrument(connection_span.clone()),
     );
 
     let out_tx_clone = out_tx.clone();
+    let room_send = room.clone();
     let mut send_task = tokio::spawn(
         {
             let join_rx = join_rx.clone();
@@ -334,7 +622,26 @@ pub async fn handle_socket(
                 if !wait_for_join(&mut join_rx).await {
                     return;
                 }
-                while let Ok(msg) = rx.recv().await {
+                loop {
+                    let msg = match rx.recv().await {
+                        Ok(msg) => msg,
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!(
+                                skipped,
+                                "WebSocket receiver lagged behind room broadcast; sending full resync"
+                            );
+                            full_sync_message(&room_send).await
+                        }
+                        Err(RecvError::Closed) => break,
+                    };
+                    if is_access_revoked_for(&msg, user_id) {
+                        tracing::info!("Board access revoked; closing WebSocket");
+                        let _ = out_tx_clone.send(close_message(
+                            protocol::CLOSE_PERMISSION_REVOKED,
+                            "Board access revoked",
+                        ));
+                        break;
+                    }
                     if out_tx_clone.send(Message::Binary(msg)).is_err() {
                         break;
                     }
@@ -353,7 +660,18 @@ pub async fn handle_socket(
                 if !wait_for_join(&mut join_rx).await {
                     return;
                 }
-                while let Ok(msg) = text_rx.recv().await {
+                loop {
+                    let msg = match text_rx.recv().await {
+                        Ok(msg) => msg,
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!(
+                                skipped,
+                                "WebSocket receiver lagged behind room text events"
+                            );
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    };
                     if out_tx_text.send(Message::Text(msg.into())).is_err() {
                         break;
                     }
@@ -415,15 +733,27 @@ pub async fn handle_socket(
             let connection_id = Some(session_id.to_string());
             let mut awareness_clients: HashSet<ClientID> = HashSet::new();
             let mut close_reason: Option<String> = None;
-            let already_active = PresenceService::has_active_session(&db, board_id, user_id)
-                .await
-                .unwrap_or(false);
-            let active_count = PresenceService::count_active_users(&db, board_id)
-                .await
-                .unwrap_or(0);
+            let already_active =
+                PresenceService::has_active_session(&db, redis_clone.as_ref(), board_id, user_id)
+                    .await
+                    .unwrap_or(false);
+            let active_count =
+                PresenceService::count_active_users(&db, redis_clone.as_ref(), board_id)
+                    .await
+                    .unwrap_or(0);
 
             if active_count >= MAX_CONCURRENT_USERS && !already_active {
-                let (notify, position) = room_clone.enqueue_session(session_id, user_id).await;
+                let (notify, mut position_rx) =
+                    room_clone.enqueue_session(session_id, user_id).await;
+                let max_wait = queue_timeout();
+                let queue_deadline = tokio::time::Instant::now() + max_wait;
+                let position = *position_rx.borrow_and_update();
+                tracing::info!(
+                    position,
+                    active_count,
+                    capacity = MAX_CONCURRENT_USERS,
+                    "Board at capacity, session queued"
+                );
                 if let Some(msg) = build_text_message(
                     "board:queued",
                     json!({
@@ -439,6 +769,39 @@ pub async fn handle_socket(
                         _ = notify.notified() => {
                             break;
                         }
+                        _ = tokio::time::sleep_until(queue_deadline) => {
+                            // Already dequeued: the wake-up permit is stored, so join.
+                            if !room_clone.remove_queued_session(session_id).await {
+                                break;
+                            }
+                            tracing::info!("Queued session timed out waiting for board capacity");
+                            if let Some(msg) = build_text_message(
+                                "board:queue_timeout",
+                                json!({
+                                    "board_id": board_id,
+                                    "waited_secs": max_wait.as_secs(),
+                                }),
+                            ) {
+                                let _ = out_tx_recv.send(msg);
+                            }
+                            let _ = out_tx_recv.send(close_message(
+                                protocol::CLOSE_QUEUE_TIMEOUT,
+                                "Board is full, try again later",
+                            ));
+                            return;
+                        }
+                        Ok(()) = position_rx.changed() => {
+                            let position = *position_rx.borrow_and_update();
+                            if let Some(msg) = build_text_message(
+                                "board:queued",
+                                json!({
+                                    "board_id": board_id,
+                                    "position": position,
+                                }),
+                            ) {
+                                let _ = out_tx_recv.send(msg);
+                            }
+                        }
                         message = receiver.next() => {
                             match message {
                                 Some(Ok(Message::Close(_))) | None => {
@@ -471,6 +834,16 @@ pub async fn handle_socket(
                 return;
             }
             tracing::info!("WebSocket presence joined");
+            let joined_at = Instant::now();
+            let log_session_events = sample_session_events(session_id);
+            if log_session_events {
+                BusinessEvent::BoardJoined {
+                    board_id,
+                    user_id,
+                    session_id,
+                }
+                .log();
+            }
 
             {
                 let sessions = room_clone.sessions.write().await;
@@ -480,22 +853,34 @@ pub async fn handle_socket(
             room_clone.edit_permissions.insert(user_id, can_edit);
             let _ = join_tx.send(true);
 
-            let (msg1, msg2) = {
+            let (msg1, synced_state_vector) = {
                 let doc_guard = room_clone.doc.lock().await;
                 let txn = doc_guard.transact();
 
-                let sv = txn.state_vector().encode_v1();
+                let state_vector = txn.state_vector();
                 let mut msg = vec![protocol::OP_SYNCSTEP_1];
-                msg.extend(sv);
-
-                let update = txn.encode_state_as_update_v1(&StateVector::default());
-                let mut msg2 = vec![protocol::OP_SYNCSTEP_2];
-                msg2.extend(update);
-                (msg, msg2)
+                msg.extend(state_vector.encode_v1());
+                (msg, state_vector)
             };
 
             let _ = out_tx_recv.send(Message::Binary(Bytes::from(msg1)));
-            let _ = out_tx_recv.send(Message::Binary(Bytes::from(msg2)));
+            let redeemed_state_vector =
+                resume_token.and_then(|token| room_clone.resume_tokens.redeem(token, user_id));
+            let resume_state_vector = resume_state_vector.or(redeemed_state_vector);
+            let resumed = resume_state_vector.is_some();
+            let initial_sync = match resume_state_vector.as_ref() {
+                Some(state_vector) => sync_message_since(&room_clone, state_vector).await,
+                None => full_sync_message(&room_clone).await,
+            };
+            resume::record_initial_sync(resumed, initial_sync.len());
+            tracing::info!(
+                target: "ws_resync",
+                resumed,
+                resync_bytes = initial_sync.len(),
+                "WebSocket initial sync sent"
+            );
+            let _ = out_tx_recv.send(Message::Binary(initial_sync));
+            let resume_token = room_clone.resume_tokens.issue(user_id, synced_state_vector);
 
             let stale_users =
                 PresenceService::cleanup_stale_sessions(&db, redis_clone.as_ref(), board_id)
@@ -531,11 +916,11 @@ pub async fn handle_socket(
                         .filter(|user| user.status.is_visible())
                         .map(presence_user_payload)
                         .collect::<Vec<_>>(),
-                    "permissions": {
-                        "can_edit": permissions.can_edit,
-                        "can_comment": permissions.can_comment,
-                        "can_share": permissions.can_manage_members || permissions.can_manage_board,
-                    }
+                    "permissions": BoardCapabilities::from_permissions(&permissions),
+                    "presenter": presenter_payload(room_clone.current_presenter().await),
+                    "resumed": resumed,
+                    "resume_token": resume_token,
+                    "protocol_version": protocol_version,
                 }),
             ) {
                 let _ = out_tx_recv.send(msg);
@@ -553,7 +938,21 @@ pub async fn handle_socket(
                 }
             }
 
-            while let Some(Ok(message)) = receiver.next().await {
+            let mut awareness_throttle =
+                AwarenessThrottle::new(awareness::awareness_broadcast_interval());
+            loop {
+                let flush_deadline = awareness_throttle.flush_deadline();
+                let message = tokio::select! {
+                    message = receiver.next() => message,
+                    _ = sleep_until_deadline(flush_deadline), if flush_deadline.is_some() => {
+                        let clients = awareness_throttle.take_pending(Instant::now());
+                        broadcast_awareness_clients(&room_clone, clients, user_id).await;
+                        continue;
+                    }
+                };
+                let Some(Ok(message)) = message else {
+                    break;
+                };
                 *room_clone.last_active.lock().await = Instant::now();
                 match message {
                     Message::Binary(bin) => {
@@ -569,6 +968,7 @@ pub async fn handle_socket(
                                 let txn = doc_guard.transact_mut();
                                 if let Ok(sv) = StateVector::decode_v1(payload) {
                                     let update = txn.encode_state_as_update_v1(&sv);
+                                    room_clone.resume_tokens.record(resume_token, sv);
                                     let mut msg = vec![protocol::OP_UPDATE];
                                     msg.extend(update);
                                     let _ = out_tx_recv.send(Message::Binary(Bytes::from(msg)));
@@ -590,7 +990,8 @@ pub async fn handle_socket(
                                     continue;
                                 }
                                 let doc_guard = room_clone.doc.lock().await;
-                                let mut txn = doc_guard.transact_mut();
+                                let mut txn = doc_guard
+                                    .transact_mut_with(element_crdt::editor_origin(user_id));
                                 if let Ok(update) = Decode::decode_v1(payload) {
                                     txn.apply_update(update).unwrap_or_else(|e| {
                                         tracing::warn!(
@@ -607,9 +1008,22 @@ pub async fn handle_socket(
                                     .pending_update_count
                                     .fetch_add(1, Ordering::Relaxed);
                             }
-                            protocol::OP_AWARENESS => match AwarenessUpdate::decode_v1(payload) {
-                                Ok(update) => {
-                                    awareness_clients.extend(update.clients.keys().copied());
+                            protocol::OP_AWARENESS => {
+                                let update = match AwarenessUpdate::decode_v1(payload) {
+                                    Ok(update) => update,
+                                    Err(e) => {
+                                        tracing::warn!(
+                                            "Failed to decode awareness update from client {}: {}",
+                                            user_id,
+                                            e
+                                        );
+                                        continue;
+                                    }
+                                };
+                                let clients: Vec<ClientID> =
+                                    update.clients.keys().copied().collect();
+                                awareness_clients.extend(clients.iter().copied());
+                                {
                                     let awareness = room_clone.awareness.write().await;
                                     awareness.apply_update(update).unwrap_or_else(|e| {
                                         tracing::warn!(
@@ -619,58 +1033,62 @@ pub async fn handle_socket(
                                         );
                                     });
                                 }
-                                Err(e) => {
-                                    tracing::warn!(
-                                        "Failed to decode awareness update from client {}: {}",
-                                        user_id,
-                                        e
-                                    );
+                                match awareness_throttle.record(Instant::now(), clients) {
+                                    AwarenessBroadcast::Forward => {}
+                                    AwarenessBroadcast::Coalesced(clients) => {
+                                        broadcast_awareness_clients(&room_clone, clients, user_id)
+                                            .await;
+                                        continue;
+                                    }
+                                    AwarenessBroadcast::Deferred => continue,
                                 }
-                            },
+                            }
                             _ => {}
                         }
 
                         let _ = room_clone.tx.send(bin);
                     }
                     Message::Text(text) => {
-                        let Ok(event) = serde_json::from_str::<ClientEvent>(&text) else {
-                            tracing::warn!("Failed to parse websocket text message");
-                            continue;
+                        let event = match parse_client_text_event(&text) {
+                            Ok(event) => event,
+                            Err(error) => {
+                                tracing::warn!(
+                                    target: "ws_message",
+                                    direction = "inbound",
+                                    message_type = "text",
+                                    bytes = text.len(),
+                                    error = %error,
+                                    "Ignoring websocket text event"
+                                );
+                                continue;
+                            }
                         };
                         tracing::info!(
                             target: "ws_message",
                             direction = "inbound",
                             message_type = "text",
-                            event_type = %event.event_type,
+                            event_type = event.name(),
                             bytes = text.len(),
                             "WebSocket text message"
                         );
-                        match event.event_type.as_str() {
-                            "heartbeat" => {
-                                if PresenceService::heartbeat(&db, board_id, session_id)
-                                    .await
-                                    .is_ok()
-                                {
-                                    if let Some(msg) = build_text_message(
-                                        "heartbeat:ack",
-                                        json!({"server_time": Utc::now().timestamp_millis()}),
-                                    ) {
-                                        let _ = out_tx_recv.send(msg);
-                                    }
+                        match event {
+                            ClientTextEvent::Heartbeat => {
+                                room_clone.record_heartbeat(session_id).await;
+                                if let Some(msg) = build_text_message(
+                                    "heartbeat:ack",
+                                    json!({"server_time": Utc::now().timestamp_millis()}),
+                                ) {
+                                    let _ = out_tx_recv.send(msg);
                                 }
                             }
-                            "presence:update" => {
-                                let Some(payload) = event.payload else {
-                                    continue;
-                                };
-                                let Ok(payload) =
-                                    serde_json::from_value::<PresenceUpdatePayload>(payload)
-                                else {
-                                    continue;
-                                };
+                            ClientTextEvent::PresenceUpdate(payload) => {
                                 let Some(status) =
                                     PresenceStatus::normalize_client(payload.status.as_str())
                                 else {
+                                    tracing::warn!(
+                                        status = %payload.status,
+                                        "Ignoring presence update with unknown status"
+                                    );
                                     continue;
                                 };
                                 if PresenceService::update_status(
@@ -682,8 +1100,7 @@ pub async fn handle_socket(
                                 )
                                 .await
                                 .is_ok()
-                                {
-                                    if let Some(Message::Text(text)) = build_text_message(
+                                    && let Some(Message::Text(text)) = build_text_message(
                                         "presence:update",
                                         json!({
                                             "user_id": user_id,
@@ -691,12 +1108,120 @@ pub async fn handle_socket(
                                             "metadata": payload.metadata,
                                             "timestamp": Utc::now().timestamp_millis(),
                                         }),
+                                    )
+                                {
+                                    let _ = room_clone.text_tx.send(text.to_string());
+                                }
+                            }
+                            ClientTextEvent::ElementCreate(payload) => {
+                                let ElementCreatePayload {
+                                    request_id,
+                                    element,
+                                } = *payload;
+                                let can_edit = room_clone
+                                    .edit_permissions
+                                    .get(&user_id)
+                                    .map(|entry| *entry)
+                                    .unwrap_or(false);
+                                let result = if can_edit {
+                                    ElementService::create_element_authorized(
+                                        &db,
+                                        &rooms,
+                                        content_filter.as_ref(),
+                                        board_id,
+                                        user_id,
+                                        element,
+                                    )
+                                    .await
+                                } else {
+                                    Err(AppError::Forbidden(
+                                        "You do not have permission to edit this board".to_string(),
+                                    ))
+                                };
+                                let msg = match result {
+                                    Ok(created) => build_text_message(
+                                        "element:created",
+                                        json!({
+                                            "request_id": request_id,
+                                            "id": created.id,
+                                            "version": created.version,
+                                            "z_index": created.z_index,
+                                        }),
+                                    ),
+                                    Err(error) => {
+                                        tracing::warn!(
+                                            "Rejected element:create from user {} on board {}: {}",
+                                            user_id,
+                                            board_id,
+                                            error
+                                        );
+                                        build_text_message(
+                                            "element:error",
+                                            json!({
+                                                "request_id": request_id,
+                                                "code": error.code(),
+                                                "message": error.client_message(),
+                                            }),
+                                        )
+                                    }
+                                };
+                                if let Some(msg) = msg {
+                                    let _ = out_tx_recv.send(msg);
+                                }
+                            }
+                            ClientTextEvent::PresenterSet(payload) => {
+                                if !permissions.can_manage_board {
+                                    if let Some(msg) = build_text_message(
+                                        "presenter:error",
+                                        json!({
+                                            "code": "FORBIDDEN",
+                                            "message": "Only board managers can present",
+                                        }),
                                     ) {
-                                        let _ = room_clone.text_tx.send(text.to_string());
+                                        let _ = out_tx_recv.send(msg);
                                     }
+                                    continue;
+                                }
+                                let changed = if payload.active {
+                                    let presenter = Some(room::Presenter {
+                                        session_id,
+                                        user_id,
+                                    });
+                                    let previous = room_clone.current_presenter().await;
+                                    room_clone.set_presenter(presenter).await;
+                                    previous != presenter
+                                } else {
+                                    room_clone.clear_presenter_session(session_id).await
+                                };
+                                if changed {
+                                    broadcast_presenter_changed(
+                                        &room_clone,
+                                        room_clone.current_presenter().await,
+                                    );
+                                }
+                            }
+                            ClientTextEvent::ViewportFollow(payload) => {
+                                let is_presenter = room_clone
+                                    .current_presenter()
+                                    .await
+                                    .is_some_and(|presenter| presenter.session_id == session_id);
+                                if !is_presenter || !payload.is_valid() {
+                                    continue;
+                                }
+                                if let Some(Message::Text(text)) = build_text_message(
+                                    "viewport:follow",
+                                    json!({
+                                        "user_id": user_id,
+                                        "session_id": session_id,
+                                        "center_x": payload.center_x,
+                                        "center_y": payload.center_y,
+                                        "zoom": payload.zoom,
+                                        "timestamp": Utc::now().timestamp_millis(),
+                                    }),
+                                ) {
+                                    let _ = room_clone.text_tx.send(text.to_string());
                                 }
                             }
-                            _ => {}
                         }
                     }
                     Message::Close(frame) => {
@@ -741,6 +1266,10 @@ pub async fn handle_socket(
                 }
             }
 
+            if room_clone.clear_presenter_session(session_id).await {
+                broadcast_presenter_changed(&room_clone, None);
+            }
+
             {
                 let sessions = room_clone.sessions.write().await;
                 sessions.remove(&session_id);
@@ -765,6 +1294,8 @@ pub async fn handle_socket(
                     if !pending_updates.is_empty() {
                         snapshot::save_update_logs(board_id, None, pending_updates, db.clone())
                             .await;
+                        // Nothing left to flush; keep the maintenance tick from revisiting the room.
+                        room_clone.pending_update_count.store(0, Ordering::Release);
                     }
                 }
             }
@@ -783,9 +1314,18 @@ pub async fn handle_socket(
                 reason = close_reason.unwrap_or_else(|| "server_shutdown".to_string()),
                 "WebSocket disconnected"
             );
+            if log_session_events {
+                BusinessEvent::BoardLeft {
+                    board_id,
+                    user_id,
+                    duration_secs: joined_at.elapsed().as_secs(),
+                }
+                .log();
+            }
 
             if should_emit_user_left(
-                PresenceService::has_active_session(&db, board_id, user_id).await,
+                PresenceService::has_active_session(&db, redis_clone.as_ref(), board_id, user_id)
+                    .await,
                 board_id,
                 user_id,
             ) {
@@ -816,13 +1356,27 @@ pub async fn handle_socket(
         _ = (&mut cleanup_task) => {},
     }
 
+    let dropped = outbound_stats.dropped();
+    if dropped > 0 {
+        tracing::info!(
+            parent: &connection_span,
+            dropped,
+            overloaded = outbound_stats.is_overloaded(),
+            "WebSocket session dropped outbound messages under backpressure"
+        );
+    }
+
     cleanup_task.abort();
 }
 
 #[cfg(test)]
 mod tests {
-    use super::should_emit_user_left;
+    use super::{
+        ClientTextEvent, ViewportFollowPayload, decode_resume_state_vector, is_access_revoked_for,
+        parse_client_text_event, should_emit_user_left,
+    };
     use crate::error::AppError;
+    use crate::realtime::protocol;
     use uuid::Uuid;
 
     #[test]
@@ -845,4 +1399,108 @@ mod tests {
             user_id
         ));
     }
+
+    #[test]
+    fn parses_known_client_text_events() {
+        assert!(matches!(
+            parse_client_text_event(r#"{"type":"heartbeat"}"#),
+            Ok(ClientTextEvent::Heartbeat)
+        ));
+
+        let event = parse_client_text_event(
+            r#"{"type":"presence:update","payload":{"status":"idle","metadata":null}}"#,
+        );
+        match event {
+            Ok(ClientTextEvent::PresenceUpdate(payload)) => assert_eq!(payload.status, "idle"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_or_malformed_client_text_events() {
+        let unknown = parse_client_text_event(r#"{"type":"presence:updte"}"#).unwrap_err();
+        assert!(unknown.contains("presence:updte"));
+
+        assert!(parse_client_text_event(r#"{"type":"presence:update"}"#).is_err());
+        assert!(parse_client_text_event("not json").is_err());
+    }
+
+    #[test]
+    fn parses_element_create_with_request_id() {
+        let event = parse_client_text_event(
+            r#"{"type":"element:create","payload":{"request_id":"r-1","element_type":"shape","position_x":10,"position_y":20,"width":100,"height":50}}"#,
+        );
+        match event {
+            Ok(ClientTextEvent::ElementCreate(payload)) => {
+                assert_eq!(payload.request_id.as_deref(), Some("r-1"));
+                assert_eq!(payload.element.width, 100.0);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn viewport_follow_requires_finite_positive_zoom() {
+        let valid = ViewportFollowPayload {
+            center_x: 10.0,
+            center_y: -5.0,
+            zoom: 1.5,
+        };
+        let zero_zoom = ViewportFollowPayload {
+            center_x: 0.0,
+            center_y: 0.0,
+            zoom: 0.0,
+        };
+        let infinite = ViewportFollowPayload {
+            center_x: f64::INFINITY,
+            center_y: 0.0,
+            zoom: 1.0,
+        };
+
+        assert!(valid.is_valid());
+        assert!(!zero_zoom.is_valid());
+        assert!(!infinite.is_valid());
+    }
+
+    #[test]
+    fn resume_state_vector_decodes_hex_and_ignores_garbage() {
+        use yrs::{Doc, ReadTxn, Text, Transact, updates::encoder::Encode};
+
+        let doc = Doc::new();
+        let text = doc.get_or_insert_text("t");
+        {
+            let mut txn = doc.transact_mut();
+            text.insert(&mut txn, 0, "hello");
+        }
+        let encoded = hex::encode(doc.transact().state_vector().encode_v1());
+
+        let decoded = decode_resume_state_vector(Some(&encoded)).expect("state vector");
+        assert_eq!(decoded, doc.transact().state_vector());
+        assert!(decode_resume_state_vector(Some("zz")).is_none());
+        assert!(decode_resume_state_vector(Some("")).is_none());
+        assert!(decode_resume_state_vector(None).is_none());
+    }
+
+    #[test]
+    fn detects_role_updates_that_revoke_this_user() {
+        let user_id = Uuid::now_v7();
+        let encode = |update: &protocol::BoardRoleUpdate| {
+            let mut message = vec![protocol::OP_ROLE_UPDATE];
+            message.extend(serde_json::to_vec(update).unwrap());
+            message
+        };
+        let removed = protocol::BoardRoleUpdate {
+            user_id,
+            role: None,
+            permissions: None,
+        };
+        let other = protocol::BoardRoleUpdate {
+            user_id: Uuid::now_v7(),
+            ..removed.clone()
+        };
+
+        assert!(is_access_revoked_for(&encode(&removed), user_id));
+        assert!(!is_access_revoked_for(&encode(&other), user_id));
+        assert!(!is_access_revoked_for(&[protocol::OP_UPDATE, 1], user_id));
+    }
 }
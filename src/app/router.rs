@@ -1,11 +1,12 @@
 use axum::{
     Router,
+    extract::DefaultBodyLimit,
     http::{HeaderName, HeaderValue, Method, header},
     middleware,
     routing::{delete, get, patch, post, put},
 };
 use governor::middleware::NoOpMiddleware;
-use std::{net::IpAddr, sync::Arc};
+use std::{net::IpAddr, sync::Arc, time::Duration};
 use tower_governor::{
     GovernorLayer,
     errors::GovernorError,
@@ -20,7 +21,7 @@ use crate::{
         http::{
             auth as auth_http, boards as boards_http, comments as comments_http,
             elements as elements_http, organizations as organizations_http,
-            telemetry as telemetry_http,
+            reactions as reactions_http, telemetry as telemetry_http, voting as voting_http,
         },
         ws::boards as boards_ws,
     },
@@ -30,24 +31,24 @@ use crate::{
 };
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
-enum InviteRateLimitKey {
+enum UserRateLimitKey {
     User(Uuid),
     Ip(IpAddr),
 }
 
 #[derive(Clone, Copy, Debug, Default)]
-struct InviteKeyExtractor;
+struct UserKeyExtractor;
 
-impl KeyExtractor for InviteKeyExtractor {
-    type Key = InviteRateLimitKey;
+impl KeyExtractor for UserKeyExtractor {
+    type Key = UserRateLimitKey;
 
     fn extract<T>(&self, req: &axum::http::Request<T>) -> Result<Self::Key, GovernorError> {
         if let Some(auth_user) = req.extensions().get::<AuthUser>() {
-            return Ok(InviteRateLimitKey::User(auth_user.user_id));
+            return Ok(UserRateLimitKey::User(auth_user.user_id));
         }
 
         let ip = SmartIpKeyExtractor.extract(req)?;
-        Ok(InviteRateLimitKey::Ip(ip))
+        Ok(UserRateLimitKey::Ip(ip))
     }
 }
 
@@ -56,6 +57,8 @@ pub fn build_router(state: AppState) -> Router {
     let auth_rate_limit = build_auth_rate_limiter();
     let onboarding_rate_limit = build_auth_rate_limiter();
     let invite_rate_limit = build_invite_rate_limiter();
+    let board_creation_rate_limit = build_board_creation_rate_limiter();
+    let client_log_rate_limit = build_client_log_rate_limiter();
 
     let auth_routes = Router::new()
         .route("/auth/register", post(auth_http::register_handle))
@@ -67,10 +70,17 @@ pub fn build_router(state: AppState) -> Router {
         )
         .layer(auth_rate_limit);
 
-    let telemetry_routes = Router::new().route(
-        "/api/telemetry/client",
-        post(telemetry_http::ingest_client_logs),
-    );
+    let telemetry_routes = Router::new()
+        .route(
+            "/api/telemetry/client",
+            post(telemetry_http::ingest_client_logs),
+        )
+        .layer(DefaultBodyLimit::max(client_log_max_body_bytes()))
+        .layer(client_log_rate_limit)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            telemetry_http::client_log_identity_middleware,
+        ));
 
     let onboarding_routes = Router::new()
         .route(
@@ -110,11 +120,21 @@ pub fn build_router(state: AppState) -> Router {
         )
         .route_layer(invite_rate_limit);
 
+    let board_creation_routes = Router::new()
+        .route("/api/boards/", post(boards_http::create_board_handle))
+        .route(
+            "/api/boards/import-binary",
+            post(boards_http::import_board_binary_handle),
+        )
+        .route("/api/boards/import", post(boards_http::import_board_handle))
+        .route_layer(board_creation_rate_limit);
+
     let verified_routes = Router::new()
         .route("/users/me", get(auth_http::get_me_handle))
         .route("/users/me", put(auth_http::update_me_handle))
         .route("/users/me", patch(auth_http::update_me_handle))
         .route("/users/me", delete(auth_http::delete_account_handle))
+        .route("/users/lookup", post(auth_http::lookup_users_handle))
         .route(
             "/users/me/invitations",
             get(auth_http::list_invitations_handle),
@@ -127,6 +147,11 @@ pub fn build_router(state: AppState) -> Router {
             "/users/me/password",
             post(auth_http::change_password_handle),
         )
+        .route("/users/me/sessions", get(auth_http::list_sessions_handle))
+        .route(
+            "/users/me/sessions/{session_id}",
+            delete(auth_http::revoke_session_handle),
+        )
         .route(
             "/organizations",
             get(organizations_http::list_organizations_handle)
@@ -140,6 +165,39 @@ pub fn build_router(state: AppState) -> Router {
             "/organizations/{organization_id}/members",
             get(organizations_http::list_members_handle),
         )
+        .route(
+            "/organizations/{organization_id}/members/search",
+            get(organizations_http::search_members_handle),
+        )
+        .route(
+            "/organizations/{organization_id}/members/export",
+            get(organizations_http::export_members_handle),
+        )
+        .route(
+            "/organizations/{organization_id}/api-tokens",
+            get(organizations_http::list_api_tokens_handle)
+                .post(organizations_http::create_api_token_handle),
+        )
+        .route(
+            "/organizations/{organization_id}/api-tokens/{token_id}",
+            delete(organizations_http::revoke_api_token_handle),
+        )
+        .route(
+            "/organizations/{organization_id}/slug",
+            patch(organizations_http::update_slug_handle),
+        )
+        .route(
+            "/organizations/{organization_id}/branding",
+            put(organizations_http::update_branding_handle),
+        )
+        .route(
+            "/organizations/{organization_id}/board-defaults",
+            put(organizations_http::update_board_defaults_handle),
+        )
+        .route(
+            "/organizations/{organization_id}/templates",
+            get(boards_http::list_organization_templates_handle),
+        )
         .route(
             "/organizations/{organization_id}/usage",
             get(organizations_http::get_usage_handle),
@@ -148,6 +206,27 @@ pub fn build_router(state: AppState) -> Router {
             "/organizations/{organization_id}/subscription",
             patch(organizations_http::update_subscription_tier_handle),
         )
+        .route(
+            "/admin/users/{user_id}/unlock",
+            post(auth_http::unlock_user_handle),
+        )
+        .route(
+            "/admin/boards/{board_id}/crdt-dump",
+            get(boards_http::dump_board_crdt_handle),
+        )
+        .route(
+            "/admin/realtime/resync-metrics",
+            get(boards_http::resync_metrics_handle),
+        )
+        .route(
+            "/admin/projections/rebuild",
+            post(boards_http::rebuild_projections_handle),
+        )
+        .route(
+            "/admin/tier-limits",
+            get(organizations_http::list_tier_limits_handle)
+                .put(organizations_http::update_tier_limit_handle),
+        )
         .route(
             "/organizations/{organization_id}/invites",
             get(organizations_http::list_email_invites_handle),
@@ -161,6 +240,10 @@ pub fn build_router(state: AppState) -> Router {
             patch(organizations_http::update_member_role_handle)
                 .delete(organizations_http::remove_member_handle),
         )
+        .route(
+            "/organizations/{organization_id}/transfer-ownership",
+            post(organizations_http::transfer_ownership_handle),
+        )
         .route(
             "/organizations/{organization_id}/members/{member_id}/accept",
             post(organizations_http::accept_invite_handle),
@@ -169,14 +252,38 @@ pub fn build_router(state: AppState) -> Router {
             "/organizations/{organization_id}/members/{member_id}/decline",
             delete(organizations_http::decline_invite_handle),
         )
-        .route("/api/boards/", post(boards_http::create_board_handle))
         .route("/api/boards/list", get(boards_http::get_board_handle))
+        .route("/api/boards/cards", get(boards_http::list_board_cards_handle))
+        .route(
+            "/api/boards/access",
+            post(boards_http::resolve_board_access_batch_handle),
+        )
         .route(
             "/api/boards/{board_id}",
             get(boards_http::get_board_detail_handle)
                 .patch(boards_http::update_board_handle)
                 .delete(boards_http::delete_board_handle),
         )
+        .route(
+            "/api/boards/{board_id}/snapshot.bin",
+            get(boards_http::download_board_snapshot_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/access",
+            get(boards_http::get_board_access_summary_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/permissions",
+            get(boards_http::get_board_permissions_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/render",
+            get(boards_http::render_board_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/stats",
+            get(boards_http::board_stats_handle),
+        )
         .route(
             "/api/boards/{board_id}/archive",
             post(boards_http::archive_board_handle),
@@ -189,10 +296,20 @@ pub fn build_router(state: AppState) -> Router {
             "/api/boards/{board_id}/transfer-ownership",
             post(boards_http::transfer_board_ownership_handle),
         )
+        .route(
+            "/api/boards/{board_id}/co-owners",
+            post(boards_http::add_board_co_owner_handle),
+        )
         .route(
             "/api/boards/{board_id}/favorite",
             post(boards_http::toggle_board_favorite_handle),
         )
+        .route(
+            "/api/boards/{board_id}/watch",
+            get(boards_http::get_board_watch_handle)
+                .put(boards_http::watch_board_handle)
+                .delete(boards_http::unwatch_board_handle),
+        )
         .route(
             "/api/boards/{board_id}/restore",
             post(boards_http::restore_board_handle),
@@ -206,6 +323,28 @@ pub fn build_router(state: AppState) -> Router {
             get(comments_http::list_board_comments_handle)
                 .post(comments_http::create_board_comment_handle),
         )
+        .route(
+            "/api/boards/{board_id}/comments/{comment_id}/pin",
+            post(comments_http::pin_board_comment_handle)
+                .delete(comments_http::unpin_board_comment_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/reactions",
+            get(reactions_http::list_board_reactions_handle)
+                .post(reactions_http::toggle_board_reaction_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/voting",
+            get(voting_http::get_board_voting_handle).post(voting_http::start_board_voting_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/voting/votes",
+            post(voting_http::cast_board_vote_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/voting/end",
+            post(voting_http::end_board_voting_handle),
+        )
         .route(
             "/api/boards/{board_id}/members/{member_id}",
             patch(boards_http::update_board_member_role_handle)
@@ -213,7 +352,32 @@ pub fn build_router(state: AppState) -> Router {
         )
         .route(
             "/api/boards/{board_id}/elements",
-            post(elements_http::create_board_element_handle),
+            get(elements_http::list_board_elements_handle)
+                .post(elements_http::create_board_element_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/paste",
+            post(elements_http::paste_board_elements_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/bulk-delete",
+            post(elements_http::bulk_delete_board_elements_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/bulk-restore",
+            post(elements_http::bulk_restore_board_elements_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/apply-style",
+            post(elements_http::apply_board_element_style_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/compact-z-index",
+            post(elements_http::compact_board_z_index_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/recently-deleted",
+            get(elements_http::list_recently_deleted_elements_handle),
         )
         .route(
             "/api/boards/{board_id}/elements/{element_id}",
@@ -224,7 +388,24 @@ pub fn build_router(state: AppState) -> Router {
             "/api/boards/{board_id}/elements/{element_id}/restore",
             post(elements_http::restore_board_element_handle),
         )
+        .route(
+            "/api/boards/{board_id}/elements/{element_id}/revert",
+            post(elements_http::revert_board_element_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/{element_id}/convert",
+            post(elements_http::convert_board_element_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/{element_id}/history",
+            get(elements_http::list_element_history_handle),
+        )
+        .route(
+            "/api/boards/{board_id}/elements/{element_id}/history/diff",
+            get(elements_http::diff_element_versions_handle),
+        )
         .merge(invite_routes)
+        .merge(board_creation_routes)
         // Layer order matters: auth must run before verified.
         .layer(middleware::from_fn_with_state(
             state.clone(),
@@ -279,7 +460,7 @@ fn build_auth_rate_limiter() -> GovernorLayer<PeerIpKeyExtractor, NoOpMiddleware
     GovernorLayer { config }
 }
 
-fn build_invite_rate_limiter() -> GovernorLayer<InviteKeyExtractor, NoOpMiddleware> {
+fn build_invite_rate_limiter() -> GovernorLayer<UserKeyExtractor, NoOpMiddleware> {
     let per_second = std::env::var("INVITE_RATE_LIMIT_PER_SECOND")
         .ok()
         .and_then(|value| value.parse::<u32>().ok())
@@ -292,7 +473,7 @@ fn build_invite_rate_limiter() -> GovernorLayer<InviteKeyExtractor, NoOpMiddlewa
         .unwrap_or(5);
     let config = Arc::new(
         GovernorConfigBuilder::default()
-            .key_extractor(InviteKeyExtractor)
+            .key_extractor(UserKeyExtractor)
             .per_second(u64::from(per_second))
             .burst_size(burst_size)
             .finish()
@@ -301,6 +482,56 @@ fn build_invite_rate_limiter() -> GovernorLayer<InviteKeyExtractor, NoOpMiddlewa
     GovernorLayer { config }
 }
 
+/// Per-user board creation budget, refilled evenly over the hour so
+/// create/delete churn is throttled without blocking normal bursts.
+fn build_board_creation_rate_limiter() -> GovernorLayer<UserKeyExtractor, NoOpMiddleware> {
+    let per_hour = std::env::var("BOARD_CREATION_RATE_LIMIT_PER_HOUR")
+        .ok()
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(30);
+    let config = Arc::new(
+        GovernorConfigBuilder::default()
+            .key_extractor(UserKeyExtractor)
+            .period(Duration::from_secs(3600) / per_hour)
+            .burst_size(per_hour)
+            .finish()
+            .expect("board creation rate limiter config"),
+    );
+    GovernorLayer { config }
+}
+
+/// Client log budget keyed by user when a token is sent, otherwise by IP.
+fn build_client_log_rate_limiter() -> GovernorLayer<UserKeyExtractor, NoOpMiddleware> {
+    let per_second = std::env::var("CLIENT_LOG_RATE_LIMIT_PER_SECOND")
+        .ok()
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(1);
+    let burst_size = std::env::var("CLIENT_LOG_RATE_LIMIT_BURST")
+        .ok()
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(10);
+    let config = Arc::new(
+        GovernorConfigBuilder::default()
+            .key_extractor(UserKeyExtractor)
+            .per_second(u64::from(per_second))
+            .burst_size(burst_size)
+            .finish()
+            .expect("client log rate limiter config"),
+    );
+    GovernorLayer { config }
+}
+
+fn client_log_max_body_bytes() -> usize {
+    std::env::var("CLIENT_LOG_MAX_BODY_BYTES")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(256 * 1024)
+}
+
 fn build_cors_layer() -> CorsLayer {
     let mut cors = CorsLayer::new()
         .allow_methods([
@@ -349,24 +580,25 @@ mod tests {
     use uuid::Uuid;
 
     #[test]
-    fn invite_key_extractor_falls_back_to_ip() {
+    fn user_key_extractor_falls_back_to_ip() {
         let request = Request::builder()
             .uri("/")
             .header("x-forwarded-for", "203.0.113.9")
             .body(())
             .expect("request");
-        let extractor = InviteKeyExtractor;
+        let extractor = UserKeyExtractor;
         let key = extractor.extract(&request).expect("key");
         let expected_ip: IpAddr = "203.0.113.9".parse().expect("ip");
-        assert!(matches!(key, InviteRateLimitKey::Ip(ip) if ip == expected_ip));
+        assert!(matches!(key, UserRateLimitKey::Ip(ip) if ip == expected_ip));
     }
 
     #[test]
-    fn invite_key_extractor_uses_auth_user() {
+    fn user_key_extractor_uses_auth_user() {
         let user_id = Uuid::new_v4();
         let auth_user = AuthUser {
             user_id,
             email: "owner@example.com".to_string(),
+            session_id: None,
         };
         let mut request = Request::builder()
             .uri("/")
@@ -374,8 +606,8 @@ mod tests {
             .body(())
             .expect("request");
         request.extensions_mut().insert(auth_user);
-        let extractor = InviteKeyExtractor;
+        let extractor = UserKeyExtractor;
         let key = extractor.extract(&request).expect("key");
-        assert!(matches!(key, InviteRateLimitKey::User(id) if id == user_id));
+        assert!(matches!(key, UserRateLimitKey::User(id) if id == user_id));
     }
 }
@@ -2,7 +2,7 @@ use sqlx::postgres::PgPoolOptions;
 use std::{net::SocketAddr, time::Duration};
 use tokio::net::TcpListener;
 
-use crate::{app, error::AppError, realtime, services, telemetry};
+use crate::{app, error::AppError, realtime, services, telemetry, usecases};
 
 pub async fn run() -> Result<(), AppError> {
     let _ = dotenvy::dotenv();
@@ -23,9 +23,31 @@ pub async fn run() -> Result<(), AppError> {
         .map_err(AppError::Database)?;
 
     let state = app::state::AppState::new(pool);
+    match usecases::organizations::OrganizationService::load_tier_limits(&state.db).await {
+        Ok(loaded) => tracing::info!(loaded, "Subscription tier limits loaded"),
+        Err(error) => tracing::warn!("Using default subscription tier limits: {}", error),
+    }
     realtime::snapshot::spawn_maintenance(state.db.clone(), state.rooms.clone());
-    realtime::projection::spawn_projection(state.db.clone(), state.rooms.clone());
+    realtime::projection::spawn_projection(
+        state.db.clone(),
+        state.rooms.clone(),
+        state.content_filter.clone(),
+    );
+    realtime::access_sync::spawn_access_sync(state.db.clone(), state.rooms.clone());
+    services::access_cache::spawn_invalidation_listener();
+    services::redis_health::spawn_probe(state.redis.clone());
+    realtime::presence::spawn_presence_maintenance(
+        state.db.clone(),
+        state.redis.clone(),
+        state.rooms.clone(),
+    );
     services::maintenance::spawn_board_cleanup(state.db.clone());
+    services::maintenance::spawn_invite_cleanup(state.db.clone());
+    services::maintenance::spawn_element_history_pruning(state.db.clone());
+    services::maintenance::spawn_tier_limit_refresh(state.db.clone());
+    services::maintenance::spawn_z_index_compaction(state.db.clone(), state.rooms.clone());
+    services::maintenance::spawn_invite_reminders(state.db.clone(), state.email_service.clone());
+    services::maintenance::spawn_activity_digests(state.db.clone(), state.email_service.clone());
 
     let app = app::router::build_router(state);
 
@@ -2,7 +2,15 @@ use redis::Client;
 use sqlx::PgPool;
 use std::sync::Arc;
 
-use crate::{auth::jwt::JwtConfig, realtime::room::Rooms, services::email::EmailService};
+use crate::{
+    auth::jwt::JwtConfig,
+    realtime::room::Rooms,
+    services::{
+        access_cache,
+        content_filter::{self, SharedContentFilter},
+        email::EmailService,
+    },
+};
 use tracing::warn;
 
 #[derive(Clone)]
@@ -12,6 +20,7 @@ pub struct AppState {
     pub rooms: Rooms,
     pub redis: Option<Client>,
     pub email_service: Option<EmailService>,
+    pub content_filter: SharedContentFilter,
 }
 
 impl AppState {
@@ -33,6 +42,7 @@ impl AppState {
             },
             Err(_) => None,
         };
+        access_cache::init(redis.clone());
 
         Self {
             db,
@@ -42,6 +52,7 @@ impl AppState {
             rooms: Arc::new(dashmap::DashMap::new()),
             redis,
             email_service,
+            content_filter: content_filter::from_env(),
         }
     }
 }
@@ -0,0 +1,46 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Marks a bearer token as an organization API token rather than a JWT.
+pub const API_TOKEN_PREFIX: &str = "rtb_";
+/// Characters of the secret kept in listings so users can tell tokens apart.
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+pub fn generate_api_token() -> String {
+    format!(
+        "{}{}{}",
+        API_TOKEN_PREFIX,
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+pub fn hash_api_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+pub fn display_prefix(token: &str) -> String {
+    token.chars().take(DISPLAY_PREFIX_LEN).collect()
+}
+
+pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(API_TOKEN_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_tokens_are_prefixed_and_unique() {
+        let first = generate_api_token();
+        let second = generate_api_token();
+        assert!(is_api_token(&first));
+        assert_eq!(first.len(), API_TOKEN_PREFIX.len() + 64);
+        assert_ne!(first, second);
+        assert_ne!(hash_api_token(&first), hash_api_token(&second));
+        assert_eq!(display_prefix(&first).len(), 12);
+    }
+}
@@ -17,6 +17,10 @@ pub struct Claims {
     pub iss: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aud: Option<String>,
+    /// Session id in `core.session`; absent on tokens issued before sessions
+    /// were tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -65,6 +69,7 @@ impl JwtConfig {
         &self,
         user_id: Uuid,
         email: String,
+        session_id: Uuid,
     ) -> Result<String, jsonwebtoken::errors::Error> {
         let now = Utc::now();
         let exp = now + Duration::hours(self.expiration_hours);
@@ -75,6 +80,7 @@ impl JwtConfig {
             iat: now.timestamp(),
             iss: self.issuer.clone(),
             aud: self.audience.clone(),
+            jti: Some(session_id.to_string()),
         };
         encode(
             &Header::new(Algorithm::HS256),
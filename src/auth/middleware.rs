@@ -1,19 +1,30 @@
 use axum::{
     Extension,
     extract::{Request, State},
-    http::header,
+    http::{Method, header},
     middleware::Next,
     response::Response,
 };
 use uuid::Uuid;
 
-use crate::{app::state::AppState, error::AppError, repositories::users as user_repo};
+use crate::{
+    app::state::AppState,
+    auth::api_tokens::{hash_api_token, is_api_token},
+    error::AppError,
+    models::organizations::ApiTokenScope,
+    repositories::{
+        api_tokens as api_token_repo, boards as board_repo, sessions as session_repo,
+        users as user_repo,
+    },
+};
 
 #[derive(Debug, Clone)]
 pub struct AuthUser {
     pub user_id: Uuid,
     #[allow(dead_code)]
     pub email: String,
+    /// `None` for tokens issued before sessions were tracked.
+    pub session_id: Option<Uuid>,
 }
 
 fn extract_token_from_header(req: &Request) -> Option<String> {
@@ -24,12 +35,17 @@ fn extract_token_from_header(req: &Request) -> Option<String> {
         .map(str::to_string)
 }
 
+/// Browsers cannot set headers on WebSocket upgrades, so the token may also
+/// come from `?access_token=` (or the older `?token=`); the header wins.
 fn extract_token_from_header_or_query(req: &Request) -> Option<String> {
     extract_token_from_header(req).or_else(|| {
         let query = req.uri().query().unwrap_or("");
-        let params: std::collections::HashMap<String, String> =
+        let mut params: std::collections::HashMap<String, String> =
             serde_urlencoded::from_str(query).unwrap_or_default();
-        params.get("token").cloned()
+        params
+            .remove("access_token")
+            .or_else(|| params.remove("token"))
+            .filter(|token| !token.is_empty())
     })
 }
 
@@ -46,6 +62,14 @@ where
         "Missing authorization token".to_string(),
     ))?;
 
+    if is_api_token(&token) {
+        let method = req.method().clone();
+        let path = req.uri().path().to_string();
+        let auth_user = authenticate_api_token(&state, &method, &path, &token).await?;
+        req.extensions_mut().insert(auth_user);
+        return Ok(next.run(req).await);
+    }
+
     let jwt_config = state.jwt_config.clone();
 
     let claim = jwt_config
@@ -55,9 +79,24 @@ where
     let user_id = Uuid::parse_str(&claim.sub)
         .map_err(|_| AppError::Unauthorized("Invaliod User id ".to_string()))?;
 
+    let session_id = match claim.jti.as_deref() {
+        Some(jti) => {
+            let session_id = Uuid::parse_str(jti)
+                .map_err(|_| AppError::Unauthorized("Invalid session id".to_string()))?;
+            if !session_repo::touch_active_session(&state.db, session_id, user_id).await? {
+                return Err(AppError::Unauthorized(
+                    "Session has been revoked".to_string(),
+                ));
+            }
+            Some(session_id)
+        }
+        None => None,
+    };
+
     let auth_user = AuthUser {
         user_id,
         email: claim.email,
+        session_id,
     };
 
     req.extensions_mut().insert(auth_user);
@@ -65,6 +104,71 @@ where
     Ok(next.run(req).await)
 }
 
+/// Resolves an organization API token to a synthetic [`AuthUser`] acting as
+/// the token's creator, after checking the request against its scopes,
+/// board restriction and organization.
+async fn authenticate_api_token(
+    state: &AppState,
+    method: &Method,
+    path: &str,
+    token: &str,
+) -> Result<AuthUser, AppError> {
+    let grant = api_token_repo::authenticate_api_token(&state.db, &hash_api_token(token))
+        .await?
+        .ok_or(AppError::Unauthorized(
+            "Invalid or revoked API token".to_string(),
+        ))?;
+    let scopes: Vec<ApiTokenScope> = grant
+        .scopes
+        .iter()
+        .filter_map(|scope| ApiTokenScope::parse(scope))
+        .collect();
+    let board_id = api_token_board_target(method, path, &scopes, grant.board_id).ok_or(
+        AppError::Forbidden("API token is not allowed to perform this request".to_string()),
+    )?;
+    if board_repo::load_board_organization_id(&state.db, board_id).await?
+        != Some(grant.organization_id)
+    {
+        return Err(AppError::Forbidden(
+            "API token is not allowed to access this board".to_string(),
+        ));
+    }
+
+    Ok(AuthUser {
+        user_id: grant.created_by,
+        email: grant.creator_email,
+        session_id: None,
+    })
+}
+
+/// Board targeted by a request an API token may make, or `None` when the
+/// path, scopes or board restriction do not allow it. Tokens only reach
+/// `/api/boards/{board_id}` routes: any scope may read, and
+/// `elements_write` may also change elements.
+fn api_token_board_target(
+    method: &Method,
+    path: &str,
+    scopes: &[ApiTokenScope],
+    restricted_to: Option<Uuid>,
+) -> Option<Uuid> {
+    let rest = path.strip_prefix("/api/boards/")?;
+    let (board_segment, tail) = match rest.split_once('/') {
+        Some((board_segment, tail)) => (board_segment, Some(tail)),
+        None => (rest, None),
+    };
+    let board_id = Uuid::parse_str(board_segment).ok()?;
+    if restricted_to.is_some_and(|restricted| restricted != board_id) {
+        return None;
+    }
+    let allowed = if method == Method::GET || method == Method::HEAD {
+        !scopes.is_empty()
+    } else {
+        scopes.contains(&ApiTokenScope::ElementsWrite)
+            && tail.is_some_and(|tail| tail == "elements" || tail.starts_with("elements/"))
+    };
+    allowed.then_some(board_id)
+}
+
 pub async fn auth_middleware(
     State(state): State<AppState>,
     req: Request,
@@ -135,5 +239,48 @@ mod tests {
             extract_token_from_header_or_query(&req),
             Some("query_token".to_string())
         );
+
+        let req = Request::builder()
+            .uri("/?token=legacy&access_token=access")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            extract_token_from_header_or_query(&req),
+            Some("access".to_string())
+        );
+    }
+
+    #[test]
+    fn api_token_scopes_gate_methods_and_boards() {
+        let board_id = Uuid::now_v7();
+        let elements = format!("/api/boards/{}/elements", board_id);
+        let read = [ApiTokenScope::Read];
+        let write = [ApiTokenScope::ElementsWrite];
+
+        assert_eq!(
+            api_token_board_target(&Method::GET, &elements, &read, None),
+            Some(board_id)
+        );
+        assert_eq!(
+            api_token_board_target(&Method::POST, &elements, &read, None),
+            None
+        );
+        assert_eq!(
+            api_token_board_target(&Method::POST, &elements, &write, Some(board_id)),
+            Some(board_id)
+        );
+        assert_eq!(
+            api_token_board_target(&Method::POST, &elements, &write, Some(Uuid::now_v7())),
+            None
+        );
+        let members = format!("/api/boards/{}/members", board_id);
+        assert_eq!(
+            api_token_board_target(&Method::POST, &members, &write, None),
+            None
+        );
+        assert_eq!(
+            api_token_board_target(&Method::GET, "/users/me", &write, None),
+            None
+        );
     }
 }
@@ -1,3 +1,4 @@
+pub(crate) mod api_tokens;
 pub(crate) mod invite_tokens;
 pub(crate) mod jwt;
 pub(crate) mod middleware;
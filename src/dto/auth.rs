@@ -4,7 +4,8 @@ use std::fmt;
 use uuid::Uuid;
 
 use crate::models::users::{
-    DefaultBoardSettings, NotificationSettings, SubscriptionTier, User, UserPreferences,
+    DefaultBoardSettings, DigestFrequency, NotificationSettings, SubscriptionTier, User,
+    UserPreferences, default_enabled,
 };
 
 #[derive(Deserialize, Clone)]
@@ -126,6 +127,42 @@ pub struct UserResponse {
 
 pub type UserReponse = UserResponse;
 
+#[derive(Debug, Deserialize)]
+pub struct UserLookupRequest {
+    pub user_ids: Vec<Uuid>,
+}
+
+/// Public profile fields safe to expose to collaborators.
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicUserProfile {
+    pub id: Uuid,
+    pub username: Option<String>,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserLookupResponse {
+    pub users: Vec<PublicUserProfile>,
+}
+
+/// A signed-in device; `current` marks the session making the request.
+#[derive(Debug, Serialize)]
+pub struct UserSessionResponse {
+    pub id: Uuid,
+    pub device: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub current: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserSessionsResponse {
+    pub sessions: Vec<UserSessionResponse>,
+}
+
 fn profile_setup_completed(metadata: &serde_json::Value) -> bool {
     metadata
         .get("profile_setup_completed")
@@ -139,6 +176,12 @@ pub struct NotificationSettingsDto {
     pub email: bool,
     pub push: bool,
     pub mentions: bool,
+    #[serde(default = "default_enabled")]
+    pub invite_emails: bool,
+    #[serde(default = "default_enabled")]
+    pub reminder_emails: bool,
+    #[serde(default)]
+    pub digest_frequency: DigestFrequency,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -182,6 +225,9 @@ impl From<NotificationSettings> for NotificationSettingsDto {
             email: settings.email,
             push: settings.push,
             mentions: settings.mentions,
+            invite_emails: settings.invite_emails,
+            reminder_emails: settings.reminder_emails,
+            digest_frequency: settings.digest_frequency,
         }
     }
 }
@@ -212,6 +258,9 @@ impl From<NotificationSettingsDto> for NotificationSettings {
             email: settings.email,
             push: settings.push,
             mentions: settings.mentions,
+            invite_emails: settings.invite_emails,
+            reminder_emails: settings.reminder_emails,
+            digest_frequency: settings.digest_frequency,
         }
     }
 }
@@ -250,6 +299,17 @@ impl From<User> for UserResponse {
     }
 }
 
+impl From<User> for PublicUserProfile {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+            avatar_url: user.avatar_url,
+        }
+    }
+}
+
 impl From<User> for UserProfileResponse {
     fn from(user: User) -> Self {
         Self {
@@ -389,4 +449,48 @@ mod tests {
         assert!(debug_output.contains("user"));
         assert!(debug_output.contains("user@example.com"));
     }
+
+    #[test]
+    fn email_opt_outs_default_on_and_respect_master_switch() {
+        use super::UpdatePreferencesRequest;
+        use crate::models::users::{EmailCategory, UserPreferences};
+
+        let req: UpdatePreferencesRequest = serde_json::from_value(json!({
+            "theme": "dark",
+            "language": "en",
+            "notifications": { "email": true, "push": false, "mentions": true },
+            "defaultBoardSettings": null
+        }))
+        .expect("legacy payload");
+        let mut preferences = UserPreferences::from(req);
+        assert!(
+            preferences
+                .notifications
+                .allows_email(EmailCategory::InviteReminder)
+        );
+        assert!(
+            !preferences
+                .notifications
+                .allows_email(EmailCategory::ActivityDigest)
+        );
+
+        preferences.notifications.invite_emails = false;
+        assert!(
+            !preferences
+                .notifications
+                .allows_email(EmailCategory::Invite)
+        );
+        assert!(
+            preferences
+                .notifications
+                .allows_email(EmailCategory::InviteReminder)
+        );
+
+        preferences.notifications.email = false;
+        assert!(
+            !preferences
+                .notifications
+                .allows_email(EmailCategory::InviteReminder)
+        );
+    }
 }
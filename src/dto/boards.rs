@@ -2,8 +2,13 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::models::boards::{
-    BoardPermissionOverrides, BoardPermissions, BoardRole, CanvasSettings,
+use crate::models::{
+    boards::{
+        Board, BoardFeatures, BoardPermissionOverrides, BoardPermissions, BoardRole,
+        CanvasSettings, ElementDeletePolicy,
+    },
+    elements::ElementType,
+    organizations::OrgRole,
 };
 
 /// Optional filters for listing boards.
@@ -11,6 +16,21 @@ use crate::models::boards::{
 pub struct BoardListQuery {
     pub organization_id: Option<Uuid>,
     pub is_template: Option<bool>,
+    pub tag: Option<String>,
+}
+
+/// Query parameters for `GET /organizations/{organization_id}/templates`.
+#[derive(Debug, Deserialize)]
+pub struct TemplateListQuery {
+    pub tag: Option<String>,
+}
+
+/// Query parameters for the paginated dashboard board cards.
+#[derive(Debug, Deserialize)]
+pub struct BoardCardsQuery {
+    pub organization_id: Option<Uuid>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -23,6 +43,73 @@ pub struct CreateBoardRequest {
     pub is_template: Option<bool>,
     pub template_board_id: Option<Uuid>,
     pub canvas_settings: Option<CanvasSettingsInput>,
+    /// Creates the board even when same-named organization boards exist.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Query parameters for importing a board from a raw yrs update blob.
+#[derive(Debug, Deserialize)]
+pub struct ImportBoardBinaryQuery {
+    pub name: String,
+    pub organization_id: Option<Uuid>,
+    pub description: Option<String>,
+}
+
+/// Formats accepted by `POST /api/boards/import`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardImportFormat {
+    Excalidraw,
+}
+
+/// Query parameters for importing a board exported from another tool.
+#[derive(Debug, Deserialize)]
+pub struct ImportBoardQuery {
+    pub format: BoardImportFormat,
+    pub name: String,
+    pub organization_id: Option<Uuid>,
+    pub description: Option<String>,
+}
+
+/// Source element that could not be mapped to a board element.
+#[derive(Debug, Serialize)]
+pub struct SkippedImportElement {
+    pub id: String,
+    pub element_type: String,
+    pub reason: String,
+}
+
+/// Response payload for a board import with the elements left out.
+#[derive(Debug, Serialize)]
+pub struct ImportBoardResponse {
+    pub board: Board,
+    pub imported: usize,
+    pub skipped: Vec<SkippedImportElement>,
+}
+
+/// Raw CRDT element map returned by `GET /admin/boards/{board_id}/crdt-dump`.
+#[derive(Debug, Serialize)]
+pub struct BoardCrdtDumpResponse {
+    pub board_id: Uuid,
+    /// Whether the dump came from a live room rather than stored updates.
+    pub live_room: bool,
+    pub element_count: usize,
+    pub elements: serde_json::Value,
+}
+
+/// Returned by `POST /admin/projections/rebuild` once the rebuild is queued.
+#[derive(Debug, Serialize)]
+pub struct ProjectionRebuildResponse {
+    pub boards: usize,
+    pub concurrency: usize,
+}
+
+/// Query parameters for `GET /api/boards/{board_id}/render`.
+#[derive(Debug, Deserialize)]
+pub struct BoardRenderQuery {
+    /// Output format; only `svg` is supported (the default).
+    pub format: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -34,6 +121,8 @@ pub struct CanvasSettingsInput {
     pub grid_size: Option<i32>,
     pub grid_enabled: Option<bool>,
     pub snap_to_grid: Option<bool>,
+    pub enforce_snap_to_grid: Option<bool>,
+    pub last_write_wins_fields: Option<Vec<String>>,
     pub show_rulers: Option<bool>,
     pub default_zoom: Option<f64>,
 }
@@ -58,6 +147,12 @@ impl CanvasSettingsInput {
         if let Some(snap_to_grid) = self.snap_to_grid {
             settings.snap_to_grid = snap_to_grid;
         }
+        if let Some(enforce_snap_to_grid) = self.enforce_snap_to_grid {
+            settings.enforce_snap_to_grid = enforce_snap_to_grid;
+        }
+        if let Some(last_write_wins_fields) = &self.last_write_wins_fields {
+            settings.last_write_wins_fields = last_write_wins_fields.clone();
+        }
         if let Some(show_rulers) = self.show_rulers {
             settings.show_rulers = show_rulers;
         }
@@ -77,12 +172,84 @@ pub struct BoardResponse {
     pub username: String,
     pub description: Option<String>,
     pub thumbnail_url: Option<String>,
+    pub tags: Vec<String>,
     pub is_favorite: bool,
     pub last_accessed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Dashboard card for a board, enriched with owner, favorite state and
+/// member count so the list renders without per-board requests.
+#[derive(Debug, Serialize)]
+pub struct BoardCardResponse {
+    pub id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub owner_username: String,
+    pub is_favorite: bool,
+    pub member_count: i64,
+    pub last_activity_at: DateTime<Utc>,
+    pub last_accessed_at: Option<DateTime<Utc>>,
+}
+
+/// Pagination details for dashboard board cards.
+#[derive(Debug, Serialize)]
+pub struct BoardCardPagination {
+    pub next_offset: Option<u32>,
+    pub has_more: bool,
+}
+
+/// Response payload for `GET /api/boards/cards`.
+#[derive(Debug, Serialize)]
+pub struct BoardCardListResponse {
+    pub data: Vec<BoardCardResponse>,
+    pub pagination: BoardCardPagination,
+}
+
+/// Number of live elements of one type.
+#[derive(Debug, Serialize)]
+pub struct ElementTypeCount {
+    pub element_type: ElementType,
+    pub count: i64,
+}
+
+/// Server-computed board statistics.
+#[derive(Debug, Serialize)]
+pub struct BoardStatsResponse {
+    pub board_id: Uuid,
+    pub total_elements: i64,
+    pub elements_by_type: Vec<ElementTypeCount>,
+    pub total_comments: i64,
+    pub unique_contributors: i64,
+    pub created_at: DateTime<Utc>,
+    pub last_edited_at: Option<DateTime<Utc>>,
+}
+
+/// Existing board that shares the requested name.
+#[derive(Debug, Serialize)]
+pub struct DuplicateBoardSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Board detail payload: the board plus lightweight content stats.
+#[derive(Debug, Serialize)]
+pub struct BoardDetailResponse {
+    #[serde(flatten)]
+    pub board: Board,
+    /// Number of active (non-deleted) elements.
+    pub element_count: i64,
+    /// Sequence of the latest CRDT snapshot, if one was taken.
+    pub snapshot_seq: Option<i64>,
+    /// Size in bytes of the latest CRDT snapshot.
+    pub snapshot_bytes: Option<i32>,
+    pub last_snapshot_at: Option<DateTime<Utc>>,
+}
+
 /// Board member user payload.
 #[derive(Debug, Serialize)]
 pub struct BoardMemberUser {
@@ -110,6 +277,29 @@ pub struct BoardMembersResponse {
     pub data: Vec<BoardMemberResponse>,
 }
 
+/// Org owner/admin who reaches a board without an explicit membership.
+#[derive(Debug, Serialize)]
+pub struct ImplicitBoardAccess {
+    pub user: BoardMemberUser,
+    pub org_role: OrgRole,
+    pub role: BoardRole,
+}
+
+/// Everyone who can reach a board, grouped by access path.
+///
+/// `org_admin_role` and `public_role` are the roles granted through those
+/// paths, or `null` when the path does not apply.
+#[derive(Debug, Serialize)]
+pub struct BoardAccessOverviewResponse {
+    pub board_id: Uuid,
+    pub members: Vec<BoardMemberResponse>,
+    pub org_admin_access: bool,
+    pub org_admin_role: Option<BoardRole>,
+    pub org_admins: Vec<ImplicitBoardAccess>,
+    pub is_public: bool,
+    pub public_role: Option<BoardRole>,
+}
+
 /// Request payload for inviting board members.
 #[derive(Debug, Deserialize)]
 pub struct InviteBoardMembersRequest {
@@ -123,17 +313,56 @@ pub struct UpdateBoardRequest {
     pub name: Option<String>,
     pub description: Option<String>,
     pub is_public: Option<bool>,
+    /// Replaces the board's tags; normalized to trimmed, lowercase, unique values.
+    pub tags: Option<Vec<String>>,
+    pub features: Option<BoardFeaturesInput>,
+    pub element_delete_policy: Option<ElementDeletePolicy>,
+}
+
+/// Partial update of a board's feature switches; omitted keys are unchanged.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct BoardFeaturesInput {
+    pub comments: Option<bool>,
+    pub public_sharing: Option<bool>,
+    pub embeds: Option<bool>,
+}
+
+impl BoardFeaturesInput {
+    pub fn apply_to(&self, mut features: BoardFeatures) -> BoardFeatures {
+        if let Some(comments) = self.comments {
+            features.comments = comments;
+        }
+        if let Some(public_sharing) = self.public_sharing {
+            features.public_sharing = public_sharing;
+        }
+        if let Some(embeds) = self.embeds {
+            features.embeds = embeds;
+        }
+        features
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct TransferBoardOwnershipRequest {
     pub new_owner_id: Uuid,
+    /// Keeps the existing owners instead of demoting them to admin, making
+    /// the new owner a co-owner.
+    #[serde(default)]
+    pub keep_current_owner: bool,
+}
+
+/// Request payload for `POST /api/boards/{board_id}/co-owners`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AddBoardCoOwnerRequest {
+    pub user_id: Uuid,
 }
 
 /// Response payload for invite results.
 #[derive(Debug, Serialize)]
 pub struct InviteBoardMembersResponse {
     pub invited: Vec<String>,
+    /// Invitees skipped because they are already board members.
+    pub already_member: Vec<String>,
 }
 
 /// Request payload for updating a board member role.
@@ -149,7 +378,77 @@ pub struct BoardActionMessage {
     pub message: String,
 }
 
+/// Request payload for resolving access to many boards at once.
+#[derive(Debug, Deserialize)]
+pub struct BoardAccessBatchRequest {
+    pub board_ids: Vec<Uuid>,
+}
+
+/// Resolved role and permissions for a single board.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct BoardAccessSummary {
+    pub role: BoardRole,
+    pub permissions: BoardPermissions,
+}
+
+/// UI capabilities derived from board permissions; same shape as the
+/// `permissions` object of the `board:joined` WebSocket event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BoardCapabilities {
+    pub can_edit: bool,
+    pub can_comment: bool,
+    pub can_share: bool,
+}
+
+impl BoardCapabilities {
+    pub fn from_permissions(permissions: &BoardPermissions) -> Self {
+        Self {
+            can_edit: permissions.can_edit,
+            can_comment: permissions.can_comment,
+            can_share: permissions.can_manage_members || permissions.can_manage_board,
+        }
+    }
+}
+
+/// The caller's resolved access for a single board.
+#[derive(Debug, Serialize)]
+pub struct BoardPermissionsResponse {
+    pub board_id: Uuid,
+    pub role: BoardRole,
+    pub permissions: BoardPermissions,
+    pub capabilities: BoardCapabilities,
+}
+
+/// Access keyed by board id; inaccessible boards are omitted.
+#[derive(Debug, Serialize)]
+pub struct BoardAccessBatchResponse {
+    pub data: std::collections::HashMap<Uuid, BoardAccessSummary>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct BoardFavoriteResponse {
     pub is_favorite: bool,
 }
+
+#[derive(Debug, Serialize)]
+pub struct BoardWatchResponse {
+    pub is_watching: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoardCapabilities;
+    use crate::models::boards::{BoardPermissions, BoardRole};
+
+    #[test]
+    fn capabilities_treat_member_or_board_management_as_sharing() {
+        let viewer = BoardCapabilities::from_permissions(&BoardRole::Viewer.permissions());
+        assert!(!viewer.can_edit && !viewer.can_comment && !viewer.can_share);
+
+        let members_only = BoardPermissions {
+            can_manage_members: true,
+            ..BoardRole::Viewer.permissions()
+        };
+        assert!(BoardCapabilities::from_permissions(&members_only).can_share);
+    }
+}
@@ -50,6 +50,9 @@ pub struct CommentResponse {
     pub is_edited: bool,
     pub edited_at: Option<DateTime<Utc>>,
     pub reply_count: i32,
+    pub is_pinned: bool,
+    pub pinned_by: Option<Uuid>,
+    pub pinned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -1,11 +1,15 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::models::elements::ElementType;
+use crate::{dto::auth::PublicUserProfile, models::elements::ElementType};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateBoardElementRequest {
+    /// Client-generated UUIDv7 so optimistic elements keep their id; must be
+    /// unused, otherwise the request fails with a conflict.
     pub id: Option<Uuid>,
     pub element_type: ElementType,
     pub position_x: f64,
@@ -18,11 +22,18 @@ pub struct CreateBoardElementRequest {
     pub style: Option<serde_json::Value>,
     pub properties: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
+    /// Skips the check that a connector's bound elements exist, for
+    /// intentionally free-floating connectors.
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateBoardElementRequest {
     pub expected_version: i32,
+    /// When the client made the edit; stale writes to the board's
+    /// last-write-wins fields are skipped.
+    pub client_updated_at: Option<DateTime<Utc>>,
     pub position_x: Option<f64>,
     pub position_y: Option<f64>,
     pub width: Option<f64>,
@@ -38,6 +49,37 @@ pub struct ExpectedVersionQuery {
     pub expected_version: i32,
 }
 
+/// Query parameters for listing board elements.
+///
+/// The four bounds are optional but must be given together; elements whose
+/// box intersects the region are returned. `include_creator` adds each
+/// creator's public profile to the response.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListBoardElementsQuery {
+    pub min_x: Option<f64>,
+    pub min_y: Option<f64>,
+    pub max_x: Option<f64>,
+    pub max_y: Option<f64>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    #[serde(default)]
+    pub include_creator: bool,
+}
+
+/// Pagination details for element listings.
+#[derive(Debug, Serialize)]
+pub struct ElementListPagination {
+    pub next_offset: Option<u32>,
+    pub has_more: bool,
+}
+
+/// Response payload for element listings.
+#[derive(Debug, Serialize)]
+pub struct BoardElementListResponse {
+    pub data: Vec<BoardElementResponse>,
+    pub pagination: ElementListPagination,
+}
+
 #[derive(Debug, Serialize)]
 pub struct BoardElementResponse {
     pub id: Uuid,
@@ -58,6 +100,9 @@ pub struct BoardElementResponse {
     pub metadata: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Present only when requested with `include_creator`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<PublicUserProfile>,
 }
 
 #[derive(Debug, Serialize)]
@@ -67,6 +112,9 @@ pub struct DeleteBoardElementResponse {
     pub deleted_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub already_deleted: Option<bool>,
+    /// Set when the board's delete policy removed the element permanently.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hard_deleted: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -76,3 +124,168 @@ pub struct RestoreBoardElementResponse {
     pub deleted_at: Option<DateTime<Utc>>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// Request payload for reverting an element to a recorded version.
+#[derive(Debug, Deserialize)]
+pub struct RevertBoardElementRequest {
+    pub target_version: i32,
+}
+
+/// Request payload for converting an element to another type in place.
+#[derive(Debug, Deserialize)]
+pub struct ConvertBoardElementRequest {
+    pub element_type: ElementType,
+}
+
+/// Request payload for pasting elements copied from another board.
+#[derive(Debug, Deserialize)]
+pub struct PasteBoardElementsRequest {
+    pub source_board_id: Uuid,
+    pub element_ids: Vec<Uuid>,
+}
+
+/// Request payload for copying one element's style onto others.
+#[derive(Debug, Deserialize)]
+pub struct ApplyStyleRequest {
+    pub source_id: Uuid,
+    pub target_ids: Vec<Uuid>,
+}
+
+/// Elements restyled by an apply-style request; `not_found` lists targets
+/// that are missing or deleted.
+#[derive(Debug, Serialize)]
+pub struct ApplyStyleResponse {
+    pub data: Vec<BoardElementResponse>,
+    pub not_found: Vec<Uuid>,
+}
+
+/// Request payload for deleting several elements at once.
+#[derive(Debug, Deserialize)]
+pub struct BulkDeleteBoardElementsRequest {
+    pub elements: Vec<BulkElementTarget>,
+}
+
+/// Request payload for restoring several soft-deleted elements at once.
+#[derive(Debug, Deserialize)]
+pub struct BulkRestoreBoardElementsRequest {
+    pub elements: Vec<BulkElementTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkElementTarget {
+    pub id: Uuid,
+    pub expected_version: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkElementStatus {
+    Deleted,
+    AlreadyDeleted,
+    Restored,
+    NotDeleted,
+    VersionConflict,
+    NotFound,
+}
+
+/// Per-element outcome of a bulk delete or restore; timestamps are omitted
+/// for elements that were not found.
+#[derive(Debug, Serialize)]
+pub struct BulkElementResult {
+    pub id: Uuid,
+    pub status: BulkElementStatus,
+    pub version: Option<i32>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkBoardElementsResponse {
+    pub data: Vec<BulkElementResult>,
+}
+
+/// Query parameters for listing recently deleted elements.
+#[derive(Debug, Default, Deserialize)]
+pub struct RecentlyDeletedQuery {
+    /// Look-back window in minutes; defaults to 30, at most one day.
+    pub within_minutes: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeletedBoardElementResponse {
+    #[serde(flatten)]
+    pub element: BoardElementResponse,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecentlyDeletedElementsResponse {
+    pub data: Vec<DeletedBoardElementResponse>,
+}
+
+/// Response payload for a paste; `id_map` maps source ids to the new ids.
+#[derive(Debug, Serialize)]
+pub struct PasteBoardElementsResponse {
+    pub data: Vec<BoardElementResponse>,
+    pub id_map: HashMap<Uuid, Uuid>,
+}
+
+/// Query parameters for an element's version history.
+#[derive(Debug, Default, Deserialize)]
+pub struct ElementHistoryQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// One recorded version of an element.
+#[derive(Debug, Serialize)]
+pub struct ElementHistoryEntry {
+    pub version: i32,
+    pub operation: String,
+    pub changed_by: Option<Uuid>,
+    pub changed_fields: Vec<String>,
+    pub element: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response payload for element history listings, newest version first.
+#[derive(Debug, Serialize)]
+pub struct ElementHistoryResponse {
+    pub data: Vec<ElementHistoryEntry>,
+    pub pagination: ElementListPagination,
+}
+
+/// Query parameters selecting the two versions to compare.
+#[derive(Debug, Deserialize)]
+pub struct ElementDiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+/// A single field that differs between two element versions.
+///
+/// `field` uses dotted paths for keys inside `style`, `properties` and
+/// `metadata` (e.g. `style.fill`); a missing side is `null`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ElementFieldChange {
+    pub field: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+/// Response payload for an element version diff.
+#[derive(Debug, Serialize)]
+pub struct ElementDiffResponse {
+    pub element_id: Uuid,
+    pub from_version: i32,
+    pub to_version: i32,
+    pub changes: Vec<ElementFieldChange>,
+}
+
+/// Response payload for an on-demand z-index compaction.
+#[derive(Debug, Serialize)]
+pub struct CompactZIndexResponse {
+    pub board_id: Uuid,
+    /// Elements whose `z_index` was rewritten.
+    pub compacted: usize,
+}
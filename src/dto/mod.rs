@@ -3,3 +3,5 @@ pub(crate) mod boards;
 pub(crate) mod comments;
 pub(crate) mod elements;
 pub(crate) mod organizations;
+pub(crate) mod reactions;
+pub(crate) mod voting;
@@ -2,7 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::models::organizations::{OrgRole, Organization};
+use crate::models::boards::BoardRole;
+use crate::models::organizations::{ApiTokenScope, OrgRole, Organization};
 use crate::models::users::SubscriptionTier;
 
 /// Request payload for creating an organization.
@@ -80,10 +81,58 @@ pub struct OrganizationMemberResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Invitation state filter for the organization member listing.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberStatusFilter {
+    Accepted,
+    Pending,
+}
+
+/// Query parameters for the organization member listing.
+#[derive(Debug, Deserialize)]
+pub struct ListMembersQuery {
+    pub role: Option<OrgRole>,
+    pub status: Option<MemberStatusFilter>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+/// Pagination details for the organization member listing.
+#[derive(Debug, Serialize)]
+pub struct MemberListPagination {
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+    pub total: i64,
+}
+
 /// Response payload for organization members.
 #[derive(Debug, Serialize)]
 pub struct OrganizationMembersResponse {
     pub data: Vec<OrganizationMemberResponse>,
+    pub pagination: MemberListPagination,
+}
+
+/// Query parameters for organization member search.
+#[derive(Debug, Deserialize)]
+pub struct MemberSearchQuery {
+    pub q: String,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// Pagination details for member search results.
+#[derive(Debug, Serialize)]
+pub struct MemberSearchPagination {
+    pub next_offset: Option<u32>,
+    pub has_more: bool,
+}
+
+/// Response payload for organization member search.
+#[derive(Debug, Serialize)]
+pub struct OrganizationMemberSearchResponse {
+    pub data: Vec<OrganizationMemberResponse>,
+    pub pagination: MemberSearchPagination,
 }
 
 /// Organization info included in invitation responses.
@@ -146,10 +195,108 @@ pub struct UpdateMemberRoleRequest {
     pub role: OrgRole,
 }
 
+/// Request payload for transferring organization ownership.
+///
+/// `confirmation` must equal the organization slug.
+#[derive(Debug, Deserialize)]
+pub struct TransferOrganizationOwnershipRequest {
+    pub new_owner_member_id: Uuid,
+    pub confirmation: String,
+}
+
+/// Request payload for changing an organization's slug.
+#[derive(Debug, Deserialize)]
+pub struct UpdateOrganizationSlugRequest {
+    pub slug: String,
+}
+
+/// Request payload replacing an organization's email branding; omitted or
+/// empty fields are cleared.
+#[derive(Debug, Deserialize)]
+pub struct UpdateOrganizationBrandingRequest {
+    pub logo_url: Option<String>,
+    pub email_from_name: Option<String>,
+    pub accent_color: Option<String>,
+    pub support_email: Option<String>,
+}
+
+/// Response payload for an organization's email branding.
+#[derive(Debug, Serialize)]
+pub struct OrganizationBrandingResponse {
+    pub logo_url: Option<String>,
+    pub email_from_name: Option<String>,
+    pub accent_color: Option<String>,
+    pub support_email: Option<String>,
+}
+
+/// Request payload for the defaults applied to new org boards. A `null`
+/// role adds only the board creator; a `null` visibility falls back to
+/// `DEFAULT_ORG_BOARD_PUBLIC`.
+#[derive(Debug, Deserialize)]
+pub struct UpdateOrganizationBoardDefaultsRequest {
+    pub default_member_board_role: Option<BoardRole>,
+    #[serde(default)]
+    pub default_board_public: Option<bool>,
+}
+
+/// Response payload for an organization's board defaults.
+#[derive(Debug, Serialize)]
+pub struct OrganizationBoardDefaultsResponse {
+    pub default_member_board_role: Option<BoardRole>,
+    pub default_board_public: Option<bool>,
+}
+
 /// Request payload for updating organization subscription tier.
 #[derive(Debug, Deserialize)]
 pub struct UpdateOrganizationSubscriptionRequest {
     pub subscription_tier: SubscriptionTier,
+    /// Validates the change and returns a preview without applying it.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Resource whose usage exceeds the limit of a requested tier.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriptionLimitViolation {
+    pub resource: String,
+    pub usage: i64,
+    pub current_limit: i32,
+    pub new_limit: i32,
+}
+
+/// Preview of a subscription tier change returned by `dry_run`.
+#[derive(Debug, Serialize)]
+pub struct SubscriptionChangePreview {
+    pub subscription_tier: SubscriptionTier,
+    pub allowed: bool,
+    pub max_members: i32,
+    pub max_boards: i32,
+    pub storage_limit_mb: i32,
+    pub violations: Vec<SubscriptionLimitViolation>,
+}
+
+/// Request payload for updating a subscription tier's limits (0 = unlimited).
+#[derive(Debug, Deserialize)]
+pub struct UpdateTierLimitRequest {
+    pub tier: SubscriptionTier,
+    pub max_members: i32,
+    pub max_boards: i32,
+    pub storage_limit_mb: i32,
+}
+
+/// Limits applied to a subscription tier.
+#[derive(Debug, Serialize)]
+pub struct TierLimitResponse {
+    pub tier: SubscriptionTier,
+    pub max_members: i32,
+    pub max_boards: i32,
+    pub storage_limit_mb: i32,
+}
+
+/// Response payload for listing tier limits.
+#[derive(Debug, Serialize)]
+pub struct TierLimitsResponse {
+    pub data: Vec<TierLimitResponse>,
 }
 
 /// Response payload for simple action messages.
@@ -205,3 +352,61 @@ impl From<Organization> for OrganizationResponse {
         }
     }
 }
+
+impl From<Organization> for OrganizationBrandingResponse {
+    fn from(organization: Organization) -> Self {
+        let branding = organization.settings.branding;
+        Self {
+            logo_url: organization.logo_url,
+            email_from_name: branding.email_from_name,
+            accent_color: branding.accent_color,
+            support_email: branding.support_email,
+        }
+    }
+}
+
+impl From<Organization> for OrganizationBoardDefaultsResponse {
+    fn from(organization: Organization) -> Self {
+        Self {
+            default_member_board_role: organization.settings.default_member_board_role,
+            default_board_public: organization.settings.default_board_public,
+        }
+    }
+}
+
+/// Request payload for creating an organization API token.
+#[derive(Debug, Deserialize)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scopes: Vec<ApiTokenScope>,
+    /// Restricts the token to one board of the organization.
+    pub board_id: Option<Uuid>,
+    pub expires_in_days: Option<u32>,
+}
+
+/// Organization API token without its secret.
+#[derive(Debug, Serialize)]
+pub struct ApiTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub board_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Newly created token; `token` is only returned once.
+#[derive(Debug, Serialize)]
+pub struct CreatedApiTokenResponse {
+    #[serde(flatten)]
+    pub api_token: ApiTokenResponse,
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiTokensResponse {
+    pub data: Vec<ApiTokenResponse>,
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::comments::ReactionTargetType;
+
+#[derive(Debug, Deserialize)]
+pub struct ToggleReactionRequest {
+    pub target_type: ReactionTargetType,
+    pub target_id: Uuid,
+    pub emoji: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListReactionsQuery {
+    pub target_type: Option<ReactionTargetType>,
+    pub target_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReactionCountResponse {
+    pub emoji: String,
+    pub count: i64,
+    /// Whether the requesting user reacted with this emoji.
+    pub reacted: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactionSummaryResponse {
+    pub target_type: ReactionTargetType,
+    pub target_id: Uuid,
+    pub reactions: Vec<ReactionCountResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToggleReactionResponse {
+    /// `true` when the reaction was added, `false` when it was removed.
+    pub added: bool,
+    #[serde(flatten)]
+    pub summary: ReactionSummaryResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactionListResponse {
+    pub data: Vec<ReactionSummaryResponse>,
+}
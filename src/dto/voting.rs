@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct StartVotingRequest {
+    pub votes_per_user: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CastVoteRequest {
+    pub element_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VoteTallyResponse {
+    pub element_id: Uuid,
+    pub votes: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VotingSessionResponse {
+    pub id: Uuid,
+    pub board_id: Uuid,
+    pub votes_per_user: i32,
+    pub started_by: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_by: Option<Uuid>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub is_active: bool,
+    pub total_votes: i64,
+    pub tallies: Vec<VoteTallyResponse>,
+    /// Votes the requesting user has cast in this session.
+    pub my_votes: i64,
+    /// Votes the requesting user can still cast; 0 once the session ended.
+    pub votes_remaining: i64,
+}
@@ -27,7 +27,9 @@ pub enum AppError {
 
     // Validation errors
     BadRequest(String),
+    BadRequestWithPayload(String, serde_json::Value),
     ValidationError(String),
+    FieldValidation(Vec<FieldError>),
 
     // WebSocket errors
     WebSocketError(String),
@@ -38,10 +40,58 @@ pub enum AppError {
     // Subscription limits
     LimitExceeded(String),
 
+    // Request size limits
+    PayloadTooLarge(String),
+
     // Internal errors
     Internal(String),
 }
 
+/// A validation failure tied to one request field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Collects field validation failures so a form can show all of them at once.
+#[derive(Debug, Default)]
+pub struct FieldErrors(Vec<FieldError>);
+
+impl FieldErrors {
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.0.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Records a `ValidationError`/`BadRequest` against `field` and yields
+    /// `None`; any other error is returned as-is.
+    pub fn check<T>(
+        &mut self,
+        field: &str,
+        result: Result<T, AppError>,
+    ) -> Result<Option<T>, AppError> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(AppError::ValidationError(message) | AppError::BadRequest(message)) => {
+                self.add(field, message);
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn into_result(self) -> Result<(), AppError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::FieldValidation(self.0))
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct ErrorResponse {
     success: bool,
@@ -54,6 +104,8 @@ struct ErrorResponse {
 struct ErrorDetail {
     code: String,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fields: Option<Vec<FieldError>>,
 }
 
 impl fmt::Display for AppError {
@@ -70,10 +122,22 @@ impl fmt::Display for AppError {
             AppError::BoardArchived(msg) => write!(f, "Board archived: {}", msg),
             AppError::BoardDeleted(msg) => write!(f, "Board deleted: {}", msg),
             AppError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
+            AppError::BadRequestWithPayload(msg, _) => write!(f, "Bad request: {}", msg),
             AppError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
+            AppError::FieldValidation(errors) => {
+                write!(f, "Validation error: ")?;
+                for (index, error) in errors.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}: {}", error.field, error.message)?;
+                }
+                Ok(())
+            }
             AppError::WebSocketError(msg) => write!(f, "WebSocket error: {}", msg),
             AppError::ExternalService(msg) => write!(f, "External service error: {}", msg),
             AppError::LimitExceeded(msg) => write!(f, "Limit exceeded: {}", msg),
+            AppError::PayloadTooLarge(msg) => write!(f, "Payload too large: {}", msg),
             AppError::Internal(msg) => write!(f, "Internal error: {}", msg),
         }
     }
@@ -81,77 +145,111 @@ impl fmt::Display for AppError {
 
 impl std::error::Error for AppError {}
 
+impl AppError {
+    /// Machine-readable error code shared by HTTP and WebSocket responses.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Database(_) => "DATABASE_ERROR",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::InvalidCredentials(_) => "INVALID_CREDENTIALS",
+            AppError::EmailNotVerified(_) => "EMAIL_NOT_VERIFIED",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Conflict(_) | AppError::ConflictWithPayload(_, _) => "CONFLICT",
+            AppError::BoardArchived(_) => "BOARD_ARCHIVED",
+            AppError::BoardDeleted(_) => "BOARD_DELETED",
+            AppError::BadRequest(_) | AppError::BadRequestWithPayload(_, _) => "BAD_REQUEST",
+            AppError::ValidationError(_) | AppError::FieldValidation(_) => "VALIDATION_ERROR",
+            AppError::WebSocketError(_) => "WEBSOCKET_ERROR",
+            AppError::ExternalService(_) => "EXTERNAL_SERVICE_ERROR",
+            AppError::LimitExceeded(_) => "LIMIT_EXCEEDED",
+            AppError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            AppError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// Message safe to return to clients; server-side details are hidden.
+    pub fn client_message(&self) -> String {
+        match self {
+            AppError::Database(_) => "database error".to_string(),
+            AppError::ExternalService(_) => "Error service".to_string(),
+            AppError::Internal(_) => "Server Error".to_string(),
+            AppError::FieldValidation(errors) => errors
+                .first()
+                .map(|error| error.message.clone())
+                .unwrap_or_else(|| "Validation failed".to_string()),
+            AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::InvalidCredentials(msg)
+            | AppError::EmailNotVerified(msg)
+            | AppError::NotFound(msg)
+            | AppError::Conflict(msg)
+            | AppError::ConflictWithPayload(msg, _)
+            | AppError::BoardArchived(msg)
+            | AppError::BoardDeleted(msg)
+            | AppError::BadRequest(msg)
+            | AppError::BadRequestWithPayload(msg, _)
+            | AppError::ValidationError(msg)
+            | AppError::WebSocketError(msg)
+            | AppError::LimitExceeded(msg)
+            | AppError::PayloadTooLarge(msg) => msg.clone(),
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::Database(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::Unauthorized(_)
+            | AppError::InvalidCredentials(_)
+            | AppError::EmailNotVerified(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) | AppError::ConflictWithPayload(_, _) => StatusCode::CONFLICT,
+            AppError::BoardArchived(_) | AppError::BoardDeleted(_) => StatusCode::GONE,
+            AppError::BadRequest(_)
+            | AppError::BadRequestWithPayload(_, _)
+            | AppError::WebSocketError(_) => StatusCode::BAD_REQUEST,
+            AppError::ValidationError(_) | AppError::FieldValidation(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            AppError::ExternalService(_) => StatusCode::BAD_GATEWAY,
+            AppError::LimitExceeded(_) => StatusCode::PAYMENT_REQUIRED,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, code, message) = match &self {
-            AppError::Database(e) => {
-                tracing::error!("Database error: {:?}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "DATABASE_ERROR",
-                    "database error".to_string(),
-                )
-            }
-            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED", msg.clone()),
-            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, "FORBIDDEN", msg.clone()),
-            AppError::InvalidCredentials(msg) => {
-                (StatusCode::UNAUTHORIZED, "INVALID_CREDENTIALS", msg.clone())
-            }
-            AppError::EmailNotVerified(msg) => {
-                (StatusCode::UNAUTHORIZED, "EMAIL_NOT_VERIFIED", msg.clone())
-            }
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "NOT_FOUND", msg.clone()),
-            AppError::Conflict(msg) => (StatusCode::CONFLICT, "CONFLICT", msg.clone()),
-            AppError::ConflictWithPayload(msg, _) => {
-                (StatusCode::CONFLICT, "CONFLICT", msg.clone())
-            }
-            AppError::BoardArchived(msg) => (StatusCode::GONE, "BOARD_ARCHIVED", msg.clone()),
-            AppError::BoardDeleted(msg) => (StatusCode::GONE, "BOARD_DELETED", msg.clone()),
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "BAD_REQUEST", msg.clone()),
-            AppError::ValidationError(msg) => (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                "VALIDATION_ERROR",
-                msg.clone(),
-            ),
-            AppError::WebSocketError(msg) => {
-                (StatusCode::BAD_REQUEST, "WEBSOCKET_ERROR", msg.clone())
-            }
-            AppError::ExternalService(msg) => {
-                tracing::error!("External service error: {}", msg);
-                (
-                    StatusCode::BAD_GATEWAY,
-                    "EXTERNAL_SERVICE_ERROR",
-                    "Error service".to_string(),
-                )
-            }
-            AppError::LimitExceeded(msg) => {
-                (StatusCode::PAYMENT_REQUIRED, "LIMIT_EXCEEDED", msg.clone())
-            }
-            AppError::Internal(msg) => {
-                tracing::error!("Internal error: {}", msg);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "INTERNAL_ERROR",
-                    "Server Error".to_string(),
-                )
-            }
-        };
+        match &self {
+            AppError::Database(e) => tracing::error!("Database error: {:?}", e),
+            AppError::ExternalService(msg) => tracing::error!("External service error: {}", msg),
+            AppError::Internal(msg) => tracing::error!("Internal error: {}", msg),
+            _ => {}
+        }
 
         let data = match &self {
-            AppError::ConflictWithPayload(_, payload) => Some(payload.clone()),
+            AppError::ConflictWithPayload(_, payload)
+            | AppError::BadRequestWithPayload(_, payload) => Some(payload.clone()),
+            _ => None,
+        };
+
+        let fields = match &self {
+            AppError::FieldValidation(errors) => Some(errors.clone()),
             _ => None,
         };
 
         let body = ErrorResponse {
             success: false,
             error: ErrorDetail {
-                code: code.to_string(),
-                message,
+                code: self.code().to_string(),
+                message: self.client_message(),
+                fields,
             },
             data,
         };
 
-        (status, Json(body)).into_response()
+        (self.status_code(), Json(body)).into_response()
     }
 }
 
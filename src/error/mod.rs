@@ -1,3 +1,3 @@
 mod app_error;
 
-pub use app_error::AppError;
+pub use app_error::{AppError, FieldErrors};
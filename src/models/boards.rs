@@ -110,7 +110,9 @@ pub struct BoardPermissionOverrides {
 
 #[cfg(test)]
 mod tests {
-    use super::{BoardPermissionOverrides, BoardPermissions, BoardRole};
+    use super::{
+        BoardFeature, BoardFeatures, BoardPermissionOverrides, BoardPermissions, BoardRole,
+    };
 
     #[test]
     fn board_permissions_from_role_defaults() {
@@ -155,6 +157,19 @@ mod tests {
         assert!(result.can_manage_members);
         assert!(!result.can_manage_board);
     }
+
+    #[test]
+    fn board_features_default_missing_keys_to_enabled() {
+        let features: BoardFeatures = serde_json::from_str(r#"{"embeds":false}"#).unwrap();
+
+        assert!(features.is_enabled(BoardFeature::Comments));
+        assert!(features.is_enabled(BoardFeature::PublicSharing));
+        assert!(!features.is_enabled(BoardFeature::Embeds));
+        assert_eq!(
+            serde_json::from_str::<BoardFeatures>("{}").unwrap(),
+            BoardFeatures::default()
+        );
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -166,6 +181,15 @@ pub struct CanvasSettings {
     pub grid_size: i32,
     pub grid_enabled: bool,
     pub snap_to_grid: bool,
+    /// Server-side counterpart of `snap_to_grid`: element positions written
+    /// through the element API are rounded to `grid_size` multiples.
+    #[serde(default)]
+    pub enforce_snap_to_grid: bool,
+    /// Element fields (`rotation`, `style`, `properties.votes`, ...) that
+    /// REST updates resolve by last-write-wins on `updated_at` instead of
+    /// merging. Empty keeps pure CRDT merge.
+    #[serde(default)]
+    pub last_write_wins_fields: Vec<String>,
     pub show_rulers: bool,
     pub default_zoom: f64,
 }
@@ -179,11 +203,72 @@ impl Default for CanvasSettings {
             grid_size: 20,
             grid_enabled: true,
             snap_to_grid: true,
+            enforce_snap_to_grid: false,
+            last_write_wins_fields: Vec::new(),
             show_rulers: true,
             default_zoom: 1.0,
         }
     }
 }
+
+/// Optional board features that can be switched off per board. Keys missing
+/// from the stored JSON are enabled.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct BoardFeatures {
+    pub comments: bool,
+    pub public_sharing: bool,
+    pub embeds: bool,
+}
+
+impl Default for BoardFeatures {
+    fn default() -> Self {
+        Self {
+            comments: true,
+            public_sharing: true,
+            embeds: true,
+        }
+    }
+}
+
+/// Whether element deletes on a board are recoverable (`soft`) or remove the
+/// element permanently (`hard`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "board.element_delete_policy", rename_all = "lowercase")]
+pub enum ElementDeletePolicy {
+    #[default]
+    Soft,
+    Hard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardFeature {
+    Comments,
+    PublicSharing,
+    Embeds,
+}
+
+impl BoardFeatures {
+    pub fn is_enabled(&self, feature: BoardFeature) -> bool {
+        match feature {
+            BoardFeature::Comments => self.comments,
+            BoardFeature::PublicSharing => self.public_sharing,
+            BoardFeature::Embeds => self.embeds,
+        }
+    }
+}
+
+impl BoardFeature {
+    pub fn label(self) -> &'static str {
+        match self {
+            BoardFeature::Comments => "comments",
+            BoardFeature::PublicSharing => "public sharing",
+            BoardFeature::Embeds => "embeds",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Viewport {
@@ -217,6 +302,11 @@ pub struct Board {
     #[sqlx(json)]
     pub viewport: Option<Viewport>,
 
+    #[sqlx(json)]
+    pub features: BoardFeatures,
+
+    pub element_delete_policy: ElementDeletePolicy,
+
     pub version: i32,
 
     // Statistics
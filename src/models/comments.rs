@@ -13,6 +13,14 @@ pub enum CommentStatus {
     Archived,
 }
 
+/// What a collab.reaction row is attached to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ReactionTargetType {
+    Element,
+    Comment,
+}
+
 /// Comment model mapped to collab.comment.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Comment {
@@ -32,6 +40,9 @@ pub struct Comment {
     pub is_edited: bool,
     pub edited_at: Option<DateTime<Utc>>,
     pub reply_count: i32,
+    pub is_pinned: bool,
+    pub pinned_by: Option<Uuid>,
+    pub pinned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
 use uuid::Uuid;
 
-use crate::models::users::SubscriptionTier;
+use crate::models::{boards::BoardRole, users::SubscriptionTier};
 
 /// Organization member role mapping for core.org_role.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
@@ -16,6 +16,33 @@ pub enum OrgRole {
     Guest,
 }
 
+/// Permission granted to an organization API token.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenScope {
+    /// Read boards and their elements.
+    Read,
+    /// Create, update and delete elements; implies `Read`.
+    ElementsWrite,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiTokenScope::Read => "read",
+            ApiTokenScope::ElementsWrite => "elements_write",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "read" => Some(ApiTokenScope::Read),
+            "elements_write" => Some(ApiTokenScope::ElementsWrite),
+            _ => None,
+        }
+    }
+}
+
 /// Organization settings stored as JSON.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -24,6 +51,32 @@ pub struct OrganizationSettings {
     pub default_board_permission: String,
     pub sso_enabled: bool,
     pub domain_restriction: Option<String>,
+    /// Visibility applied to new org boards that don't set `is_public`.
+    #[serde(default)]
+    pub default_board_public: Option<bool>,
+    /// Grants org owners/admins full permissions on every org board.
+    #[serde(default)]
+    pub admins_manage_all_boards: bool,
+    /// Role every accepted non-guest member receives on new org boards;
+    /// `None` adds only the creator.
+    #[serde(default)]
+    pub default_member_board_role: Option<BoardRole>,
+    /// Branding applied to emails sent on behalf of the organization.
+    #[serde(default)]
+    pub branding: OrganizationBranding,
+}
+
+/// Email branding stored under `settings.branding`; the logo is the
+/// organization's `logo_url`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationBranding {
+    /// Sender display name used instead of `SMTP_FROM_NAME`.
+    pub email_from_name: Option<String>,
+    /// Hex color such as `#1a73e8` used for buttons and accents.
+    pub accent_color: Option<String>,
+    /// Address shown to recipients and used as `Reply-To`.
+    pub support_email: Option<String>,
 }
 
 /// Organization model mapped to core.organization.
@@ -4,7 +4,7 @@ use sqlx::prelude::FromRow;
 use uuid::Uuid;
 
 // enum for subcription
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, Default, PartialEq, Eq, Hash)]
 #[sqlx(type_name = "core.subscription_tier", rename_all = "lowercase")]
 pub enum SubscriptionTier {
     #[default]
@@ -21,11 +21,76 @@ pub struct UserPreferences {
     pub notifications: NotificationSettings,
     pub default_board_settings: Option<DefaultBoardSettings>,
 }
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct NotificationSettings {
+    /// Master switch for non-transactional email.
     pub email: bool,
     pub push: bool,
     pub mentions: bool,
+    #[serde(default = "default_enabled")]
+    pub invite_emails: bool,
+    #[serde(default = "default_enabled")]
+    pub reminder_emails: bool,
+    #[serde(default)]
+    pub digest_frequency: DigestFrequency,
+}
+
+/// How often the board activity digest email is sent; off unless opted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    pub fn period(self) -> Option<chrono::Duration> {
+        match self {
+            DigestFrequency::Off => None,
+            DigestFrequency::Daily => Some(chrono::Duration::days(1)),
+            DigestFrequency::Weekly => Some(chrono::Duration::weeks(1)),
+        }
+    }
+}
+
+/// Non-transactional email a user can opt out of. Verification and other
+/// account emails are always sent and have no category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailCategory {
+    Invite,
+    InviteReminder,
+    ActivityDigest,
+}
+
+impl NotificationSettings {
+    pub fn allows_email(&self, category: EmailCategory) -> bool {
+        self.email
+            && match category {
+                EmailCategory::Invite => self.invite_emails,
+                EmailCategory::InviteReminder => self.reminder_emails,
+                EmailCategory::ActivityDigest => self.digest_frequency != DigestFrequency::Off,
+            }
+    }
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            email: true,
+            push: true,
+            mentions: true,
+            invite_emails: true,
+            reminder_emails: true,
+            digest_frequency: DigestFrequency::Off,
+        }
+    }
+}
+
+pub(crate) fn default_enabled() -> bool {
+    true
 }
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -52,6 +117,11 @@ pub struct User {
     pub is_active: bool,
     pub last_active_at: Option<DateTime<Utc>>,
 
+    #[serde(skip)]
+    pub failed_login_count: i32,
+    #[serde(skip)]
+    pub locked_until: Option<DateTime<Utc>>,
+
     pub subscription_tier: SubscriptionTier,
     pub subscription_expires_at: Option<DateTime<Utc>>,
 
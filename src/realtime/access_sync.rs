@@ -0,0 +1,121 @@
+use axum::body::Bytes;
+use sqlx::PgPool;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    realtime::{
+        protocol::{self, BoardRoleUpdate},
+        room::{Room, Rooms},
+    },
+    services::access_cache::{self, AccessInvalidation},
+    usecases::boards::BoardService,
+};
+
+/// Applies access invalidations to this node's open rooms: edit permissions
+/// are updated and an `OP_ROLE_UPDATE` is broadcast, which closes sessions
+/// whose access was revoked.
+pub fn spawn_access_sync(db: PgPool, rooms: Rooms) {
+    let mut invalidations = access_cache::subscribe();
+    tokio::spawn(async move {
+        loop {
+            match invalidations.recv().await {
+                Ok(invalidation) => apply_invalidation(&db, &rooms, invalidation).await,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Board access sync lagged behind invalidations");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+async fn apply_invalidation(db: &PgPool, rooms: &Rooms, invalidation: AccessInvalidation) {
+    match invalidation {
+        AccessInvalidation::Member { board_id, update } => {
+            if let Some(room) = rooms.get(&board_id).map(|entry| entry.clone()) {
+                apply_role_update(&room, &update);
+            }
+        }
+        AccessInvalidation::Board { board_id } => {
+            let Some(room) = rooms.get(&board_id).map(|entry| entry.clone()) else {
+                return;
+            };
+            for user_id in connected_users(&room) {
+                refresh_user_access(db, &room, user_id).await;
+            }
+        }
+        AccessInvalidation::User { user_id } => {
+            let joined: Vec<_> = rooms
+                .iter()
+                .filter(|entry| entry.edit_permissions.contains_key(&user_id))
+                .map(|entry| entry.value().clone())
+                .collect();
+            for room in joined {
+                refresh_user_access(db, &room, user_id).await;
+            }
+        }
+    }
+}
+
+fn connected_users(room: &Room) -> Vec<Uuid> {
+    room.edit_permissions
+        .iter()
+        .map(|entry| *entry.key())
+        .collect()
+}
+
+/// Re-resolves a connected user's access and pushes the result to the room.
+async fn refresh_user_access(db: &PgPool, room: &Room, user_id: Uuid) {
+    let update = match BoardService::get_access(db, room.board_id, user_id).await {
+        Ok((role, permissions)) => BoardRoleUpdate {
+            user_id,
+            role: Some(role),
+            permissions: Some(permissions),
+        },
+        Err(
+            AppError::Forbidden(_)
+            | AppError::NotFound(_)
+            | AppError::BoardArchived(_)
+            | AppError::BoardDeleted(_),
+        ) => BoardRoleUpdate {
+            user_id,
+            role: None,
+            permissions: None,
+        },
+        Err(error) => {
+            tracing::warn!(
+                "Failed to refresh access for user {} on board {}: {}",
+                user_id,
+                room.board_id,
+                error
+            );
+            return;
+        }
+    };
+    apply_role_update(room, &update);
+}
+
+fn apply_role_update(room: &Room, update: &BoardRoleUpdate) {
+    match update.permissions {
+        Some(permissions) if permissions.can_view => {
+            room.edit_permissions
+                .insert(update.user_id, permissions.can_edit);
+        }
+        _ => {
+            room.edit_permissions.remove(&update.user_id);
+        }
+    }
+    let encoded = match serde_json::to_vec(update) {
+        Ok(encoded) => encoded,
+        Err(error) => {
+            tracing::warn!("Failed to encode board role update: {}", error);
+            return;
+        }
+    };
+    let mut message = Vec::with_capacity(encoded.len() + 1);
+    message.push(protocol::OP_ROLE_UPDATE);
+    message.extend(encoded);
+    let _ = room.tx.send(Bytes::from(message));
+}
@@ -0,0 +1,144 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use yrs::block::ClientID;
+
+const DEFAULT_AWARENESS_BROADCASTS_PER_SEC: u64 = 20;
+
+pub fn awareness_broadcast_interval() -> Duration {
+    let per_sec = std::env::var("AWARENESS_BROADCASTS_PER_SEC")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_AWARENESS_BROADCASTS_PER_SEC);
+    Duration::from_millis(1_000 / per_sec.min(1_000))
+}
+
+/// Outcome of recording an inbound awareness update.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AwarenessBroadcast {
+    /// Forward the inbound message as-is.
+    Forward,
+    /// Broadcast the current state of these clients, coalescing earlier
+    /// throttled updates with the inbound one.
+    Coalesced(Vec<ClientID>),
+    /// Hold the update until the throttle window elapses.
+    Deferred,
+}
+
+/// Per-session throttle that coalesces awareness broadcasts to a fixed rate.
+///
+/// The room awareness state is always updated; only the fan-out is delayed.
+pub struct AwarenessThrottle {
+    interval: Duration,
+    last_sent: Option<Instant>,
+    pending: HashSet<ClientID>,
+}
+
+impl AwarenessThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_sent: None,
+            pending: HashSet::new(),
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        now: Instant,
+        clients: impl IntoIterator<Item = ClientID>,
+    ) -> AwarenessBroadcast {
+        let ready = self
+            .last_sent
+            .is_none_or(|last_sent| now.duration_since(last_sent) >= self.interval);
+        if !ready {
+            self.pending.extend(clients);
+            return AwarenessBroadcast::Deferred;
+        }
+
+        self.last_sent = Some(now);
+        if self.pending.is_empty() {
+            return AwarenessBroadcast::Forward;
+        }
+        self.pending.extend(clients);
+        AwarenessBroadcast::Coalesced(self.pending.drain().collect())
+    }
+
+    /// When the deferred updates may be flushed, if any are pending.
+    pub fn flush_deadline(&self) -> Option<Instant> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some(
+            self.last_sent
+                .map(|last_sent| last_sent + self.interval)
+                .unwrap_or_else(Instant::now),
+        )
+    }
+
+    pub fn take_pending(&mut self, now: Instant) -> Vec<ClientID> {
+        if self.pending.is_empty() {
+            return Vec::new();
+        }
+        self.last_sent = Some(now);
+        self.pending.drain().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AwarenessBroadcast, AwarenessThrottle};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn forwards_first_update_and_defers_bursts() {
+        let mut throttle = AwarenessThrottle::new(Duration::from_millis(50));
+        let start = Instant::now();
+
+        assert_eq!(throttle.record(start, [1]), AwarenessBroadcast::Forward);
+        assert_eq!(
+            throttle.record(start + Duration::from_millis(10), [1]),
+            AwarenessBroadcast::Deferred
+        );
+        assert_eq!(
+            throttle.flush_deadline(),
+            Some(start + Duration::from_millis(50))
+        );
+    }
+
+    #[test]
+    fn coalesces_pending_clients_once_window_elapses() {
+        let mut throttle = AwarenessThrottle::new(Duration::from_millis(50));
+        let start = Instant::now();
+        throttle.record(start, [1]);
+        throttle.record(start + Duration::from_millis(10), [1]);
+
+        match throttle.record(start + Duration::from_millis(60), [2]) {
+            AwarenessBroadcast::Coalesced(mut clients) => {
+                clients.sort();
+                assert_eq!(clients, vec![1, 2]);
+            }
+            other => panic!("unexpected broadcast: {:?}", other),
+        }
+        assert!(throttle.flush_deadline().is_none());
+    }
+
+    #[test]
+    fn take_pending_drains_and_restarts_window() {
+        let mut throttle = AwarenessThrottle::new(Duration::from_millis(50));
+        let start = Instant::now();
+        throttle.record(start, [1]);
+        throttle.record(start + Duration::from_millis(5), [3]);
+
+        let flushed_at = start + Duration::from_millis(50);
+        assert_eq!(throttle.take_pending(flushed_at), vec![3]);
+        assert!(throttle.take_pending(flushed_at).is_empty());
+        assert_eq!(
+            throttle.record(flushed_at + Duration::from_millis(10), [1]),
+            AwarenessBroadcast::Deferred
+        );
+    }
+}
@@ -1,12 +1,18 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{Arc, PoisonError},
+};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 use yrs::encoding::serde::{from_any, to_any};
-use yrs::types::ToJson;
+use yrs::types::{Event, PathSegment, ToJson};
 use yrs::{
-    Any, Array, ArrayRef, Doc, Map, MapRef, Out, ReadTxn, Text, TextRef, Transact, TransactionMut,
-    WriteTxn,
+    Any, Array, ArrayRef, DeepObservable, Doc, Map, MapRef, Origin, Out, ReadTxn, Subscription,
+    Text, TextRef, Transact, TransactionMut, WriteTxn,
 };
 
 use crate::{
@@ -33,6 +39,8 @@ const FIELD_PROPERTIES: &str = "properties";
 const FIELD_METADATA: &str = "metadata";
 const FIELD_DELETED_AT: &str = "deleted_at";
 const FIELD_VERSION: &str = "version";
+/// Per-field write times for last-write-wins fields, keyed by field path.
+const FIELD_LWW_UPDATED_AT: &str = "field_updated_at";
 const TEXT_KEYS: [&str; 3] = ["content", "title", "name"];
 
 #[derive(Debug, Clone)]
@@ -81,6 +89,36 @@ pub struct ElementMaterialized {
     pub version: Option<i32>,
 }
 
+/// In-place type conversion with the style and property patches it needs.
+#[derive(Debug)]
+pub struct ElementTypeChange {
+    pub element_type: ElementType,
+    pub style_patch: Value,
+    pub properties_patch: Value,
+}
+
+/// Write time and board conflict settings for [`apply_update`].
+#[derive(Debug, Clone, Copy)]
+pub struct UpdateContext<'a> {
+    pub updated_at: DateTime<Utc>,
+    pub last_write_wins_fields: &'a [String],
+}
+
+/// Outcome of [`compact_z_indices`]; `update` is empty when no element moved.
+#[derive(Debug)]
+pub struct ZIndexCompaction {
+    pub changed: usize,
+    pub update: Vec<u8>,
+}
+
+/// Outcome of [`apply_style`]; `elements` follows the order of the target
+/// ids, with `None` for targets that are missing or deleted.
+#[derive(Debug)]
+pub struct StyleApplied {
+    pub elements: Vec<Option<ElementMaterialized>>,
+    pub update: Vec<u8>,
+}
+
 #[derive(Debug)]
 pub struct AppliedElement {
     pub element: ElementMaterialized,
@@ -169,11 +207,19 @@ pub fn apply_missing_fields(
     Ok(Some(AppliedElement { element, update }))
 }
 
+/// Applies a REST patch to an element.
+///
+/// Fields listed in `context.last_write_wins_fields` are left untouched when
+/// the request's `client_updated_at` is older than the last accepted write to
+/// that same field; everything else merges as usual. Each accepted write to
+/// such a field records its time under `field_updated_at`. Client clocks are
+/// clamped to `context.updated_at`, so a clock running ahead cannot lock a
+/// field against every later write.
 pub fn apply_update(
     doc: &Doc,
     element_id: Uuid,
     req: &UpdateBoardElementRequest,
-    updated_at: DateTime<Utc>,
+    context: UpdateContext<'_>,
 ) -> Result<Option<AppliedElement>, AppError> {
     let mut txn = doc.transact_mut();
     let elements = txn.get_or_insert_map(ELEMENTS_MAP);
@@ -185,31 +231,96 @@ pub fn apply_update(
         return Ok(None);
     }
 
-    if let Some(value) = req.position_x {
-        set_number(&mut txn, &map, FIELD_POSITION_X, value);
-    }
-    if let Some(value) = req.position_y {
-        set_number(&mut txn, &map, FIELD_POSITION_Y, value);
-    }
-    if let Some(value) = req.width {
-        set_number(&mut txn, &map, FIELD_WIDTH, value);
+    let field_stamps = match map.get(&txn, FIELD_LWW_UPDATED_AT) {
+        Some(Out::YMap(stamps)) => Some(stamps),
+        _ => None,
+    };
+    let client_updated_at = req
+        .client_updated_at
+        .map(|client_updated_at| client_updated_at.min(context.updated_at));
+    let stale_fields: Vec<String> = match (client_updated_at, field_stamps) {
+        (Some(client_updated_at), Some(stamps)) => context
+            .last_write_wins_fields
+            .iter()
+            .filter(|field| {
+                get_datetime(&txn, &stamps, field).is_some_and(|stored| client_updated_at < stored)
+            })
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    };
+    let stale_fields = stale_fields.as_slice();
+    let scalars = [
+        (FIELD_POSITION_X, req.position_x),
+        (FIELD_POSITION_Y, req.position_y),
+        (FIELD_WIDTH, req.width),
+        (FIELD_HEIGHT, req.height),
+        (FIELD_ROTATION, req.rotation),
+    ];
+    for (field, value) in scalars {
+        if let Some(value) = value
+            && !stale_fields.iter().any(|stale| stale == field)
+        {
+            set_number(&mut txn, &map, field, value);
+        }
     }
-    if let Some(value) = req.height {
-        set_number(&mut txn, &map, FIELD_HEIGHT, value);
+    if let Some(style) = fresh_patch(FIELD_STYLE, req.style.as_ref(), stale_fields) {
+        apply_object_patch(&mut txn, &map, FIELD_STYLE, &style);
     }
-    if let Some(value) = req.rotation {
-        set_number(&mut txn, &map, FIELD_ROTATION, value);
+    if let Some(properties) = fresh_patch(FIELD_PROPERTIES, req.properties.as_ref(), stale_fields) {
+        apply_properties_patch(&mut txn, &map, FIELD_PROPERTIES, &properties);
     }
-    if let Some(style) = req.style.as_ref() {
-        apply_object_patch(&mut txn, &map, FIELD_STYLE, style);
+    if let Some(metadata) = fresh_patch(FIELD_METADATA, req.metadata.as_ref(), stale_fields) {
+        apply_object_patch(&mut txn, &map, FIELD_METADATA, &metadata);
     }
-    if let Some(properties) = req.properties.as_ref() {
-        apply_properties_patch(&mut txn, &map, FIELD_PROPERTIES, properties);
+    let written: Vec<&String> = context
+        .last_write_wins_fields
+        .iter()
+        .filter(|field| !stale_fields.contains(field) && request_writes_field(req, field))
+        .collect();
+    if !written.is_empty() {
+        let written_at = client_updated_at.unwrap_or(context.updated_at);
+        let stamps: MapRef = map.get_or_init(&mut txn, FIELD_LWW_UPDATED_AT);
+        for field in written {
+            set_datetime(&mut txn, &stamps, field, written_at);
+        }
     }
-    if let Some(metadata) = req.metadata.as_ref() {
-        apply_object_patch(&mut txn, &map, FIELD_METADATA, metadata);
+
+    bump_version(&mut txn, &map);
+    set_datetime(&mut txn, &map, FIELD_UPDATED_AT, context.updated_at);
+
+    let update = txn.encode_update_v1();
+    let element = materialize_from_map(&txn, &map, &key)
+        .ok_or_else(|| AppError::Internal("Failed to materialize element".to_string()))?;
+    Ok(Some(AppliedElement { element, update }))
+}
+
+/// Switches an element to `change.element_type`, applying the style and
+/// property patches in the same transaction.
+pub fn apply_type_change(
+    doc: &Doc,
+    element_id: Uuid,
+    change: &ElementTypeChange,
+    updated_at: DateTime<Utc>,
+) -> Result<Option<AppliedElement>, AppError> {
+    let mut txn = doc.transact_mut();
+    let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+    let key = element_id.to_string();
+    let Some(map) = get_existing_element_map(&mut txn, &elements, &key) else {
+        return Ok(None);
+    };
+    if map.get(&txn, FIELD_DELETED_AT).is_some() {
+        return Ok(None);
     }
 
+    set_string(
+        &mut txn,
+        &map,
+        FIELD_ELEMENT_TYPE,
+        element_type_to_client(change.element_type),
+    );
+    apply_object_patch(&mut txn, &map, FIELD_STYLE, &change.style_patch);
+    apply_properties_patch(&mut txn, &map, FIELD_PROPERTIES, &change.properties_patch);
     bump_version(&mut txn, &map);
     set_datetime(&mut txn, &map, FIELD_UPDATED_AT, updated_at);
 
@@ -242,6 +353,79 @@ pub fn apply_deleted(
     Ok(Some(AppliedElement { element, update }))
 }
 
+/// Overwrites property values of a live element without bumping its version,
+/// for server-side corrections of edits clients already made. Returns `None`
+/// when the element is missing or deleted.
+pub fn correct_properties(
+    doc: &Doc,
+    element_id: Uuid,
+    patch: &Value,
+) -> Result<Option<AppliedElement>, AppError> {
+    let mut txn = doc.transact_mut();
+    let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+    let key = element_id.to_string();
+    let Some(map) = get_existing_element_map(&mut txn, &elements, &key) else {
+        return Ok(None);
+    };
+    if map.get(&txn, FIELD_DELETED_AT).is_some() {
+        return Ok(None);
+    }
+
+    apply_properties_patch(&mut txn, &map, FIELD_PROPERTIES, patch);
+
+    let update = txn.encode_update_v1();
+    let element = materialize_from_map(&txn, &map, &key)
+        .ok_or_else(|| AppError::Internal("Failed to materialize element".to_string()))?;
+    Ok(Some(AppliedElement { element, update }))
+}
+
+/// Removes an element's entry from the `elements` map, returning the element
+/// as it was before removal along with the encoded update.
+pub fn remove_element(doc: &Doc, element_id: Uuid) -> Option<AppliedElement> {
+    let mut txn = doc.transact_mut();
+    let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+    let key = element_id.to_string();
+    let value = elements.get(&txn, &key)?;
+    let element = materialize_from_out(&txn, &key, value)?;
+    elements.remove(&mut txn, &key);
+
+    let update = txn.encode_update_v1();
+    Some(AppliedElement { element, update })
+}
+
+/// Last editor of each element changed since it was last projected; `None`
+/// when the change came from a transaction without an [`editor_origin`].
+pub type ElementEditors = Arc<std::sync::Mutex<HashMap<Uuid, Option<Uuid>>>>;
+
+/// Transaction origin naming the user whose update is being applied.
+pub fn editor_origin(user_id: Uuid) -> Origin {
+    Origin::from(user_id.as_bytes().as_slice())
+}
+
+/// Records in `editors` every element a transaction on `doc` changes,
+/// credited to the user in the transaction's [`editor_origin`].
+pub fn observe_element_editors(doc: &Doc, editors: ElementEditors) -> Subscription {
+    let elements = doc.transact_mut().get_or_insert_map(ELEMENTS_MAP);
+    elements.observe_deep(move |txn, events| {
+        let editor = txn
+            .origin()
+            .and_then(|origin| Uuid::from_slice(origin.as_ref()).ok());
+        let mut editors = editors.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut record = |key: &str| {
+            if let Ok(element_id) = Uuid::parse_str(key) {
+                editors.insert(element_id, editor);
+            }
+        };
+        for event in events.iter() {
+            match (event.path().front(), event) {
+                (Some(PathSegment::Key(key)), _) => record(key),
+                (None, Event::Map(event)) => event.keys(txn).keys().for_each(|key| record(key)),
+                _ => {}
+            }
+        }
+    })
+}
+
 pub fn materialize_elements(doc: &Doc) -> Vec<ElementMaterialized> {
     let txn = doc.transact();
     let Some(map) = txn.get_map(ELEMENTS_MAP) else {
@@ -257,14 +441,29 @@ pub fn materialize_elements(doc: &Doc) -> Vec<ElementMaterialized> {
     elements
 }
 
+/// Raw contents of the `elements` map as JSON, keyed by element id.
+///
+/// Entries are returned as stored, including internal fields and entries
+/// that [`materialize_elements`] would skip.
+pub fn dump_elements(doc: &Doc) -> Value {
+    let txn = doc.transact();
+    let Some(map) = txn.get_map(ELEMENTS_MAP) else {
+        return Value::Object(Default::default());
+    };
+    from_any::<Value>(&map.to_json(&txn)).unwrap_or_else(|error| {
+        tracing::warn!("Failed to convert elements map to JSON: {}", error);
+        Value::Null
+    })
+}
+
 pub fn max_z_index(doc: &Doc, layer_id: Option<Uuid>) -> i32 {
     let txn = doc.transact();
     let Some(map) = txn.get_map(ELEMENTS_MAP) else {
         return 0;
     };
     let mut max = 0;
-    for (_key, value) in map.iter(&txn) {
-        let Some(element) = materialize_from_out(&txn, "", value) else {
+    for (key, value) in map.iter(&txn) {
+        let Some(element) = materialize_from_out(&txn, key, value) else {
             continue;
         };
         if element.deleted_at.is_some() {
@@ -278,6 +477,102 @@ pub fn max_z_index(doc: &Doc, layer_id: Option<Uuid>) -> i32 {
     max
 }
 
+/// Merges `style` onto each live target element in one transaction.
+pub fn apply_style(
+    doc: &Doc,
+    element_ids: &[Uuid],
+    style: &Value,
+    updated_at: DateTime<Utc>,
+) -> Result<StyleApplied, AppError> {
+    let mut txn = doc.transact_mut();
+    let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+    let mut applied = Vec::with_capacity(element_ids.len());
+    for element_id in element_ids {
+        let key = element_id.to_string();
+        let Some(map) = get_existing_element_map(&mut txn, &elements, &key) else {
+            applied.push(None);
+            continue;
+        };
+        if map.get(&txn, FIELD_DELETED_AT).is_some() {
+            applied.push(None);
+            continue;
+        }
+        apply_object_patch(&mut txn, &map, FIELD_STYLE, style);
+        bump_version(&mut txn, &map);
+        set_datetime(&mut txn, &map, FIELD_UPDATED_AT, updated_at);
+        let element = materialize_from_map(&txn, &map, &key)
+            .ok_or_else(|| AppError::Internal("Failed to materialize element".to_string()))?;
+        applied.push(Some(element));
+    }
+
+    let update = if applied.iter().any(Option::is_some) {
+        txn.encode_update_v1()
+    } else {
+        Vec::new()
+    };
+    Ok(StyleApplied {
+        elements: applied,
+        update,
+    })
+}
+
+/// Rewrites live elements' `z_index` to a dense `0..n` sequence per layer in
+/// one transaction, keeping their stacking order.
+pub fn compact_z_indices(
+    doc: &Doc,
+    updated_at: DateTime<Utc>,
+) -> Result<ZIndexCompaction, AppError> {
+    let changes = dense_z_indices(&materialize_elements(doc));
+    if changes.is_empty() {
+        return Ok(ZIndexCompaction {
+            changed: 0,
+            update: Vec::new(),
+        });
+    }
+
+    let mut txn = doc.transact_mut();
+    let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+    let mut changed = 0;
+    for (element_id, z_index) in changes {
+        let Some(map) = get_existing_element_map(&mut txn, &elements, &element_id.to_string())
+        else {
+            continue;
+        };
+        set_number(&mut txn, &map, FIELD_Z_INDEX, f64::from(z_index));
+        bump_version(&mut txn, &map);
+        set_datetime(&mut txn, &map, FIELD_UPDATED_AT, updated_at);
+        changed += 1;
+    }
+
+    Ok(ZIndexCompaction {
+        changed,
+        update: txn.encode_update_v1(),
+    })
+}
+
+/// New `z_index` for each live element whose dense position differs from its
+/// current value. Ties keep creation order, then id order.
+fn dense_z_indices(elements: &[ElementMaterialized]) -> Vec<(Uuid, i32)> {
+    let mut layers: HashMap<Option<Uuid>, Vec<&ElementMaterialized>> = HashMap::new();
+    for element in elements
+        .iter()
+        .filter(|element| element.deleted_at.is_none())
+    {
+        layers.entry(element.layer_id).or_default().push(element);
+    }
+    let mut changes = Vec::new();
+    for layer in layers.values_mut() {
+        layer.sort_by_key(|element| (element.z_index, element.created_at, element.id));
+        for (position, element) in layer.iter().enumerate() {
+            let z_index = position as i32;
+            if element.z_index != z_index {
+                changes.push((element.id, z_index));
+            }
+        }
+    }
+    changes
+}
+
 pub fn materialize_element(doc: &Doc, element_id: Uuid) -> Option<ElementMaterialized> {
     let txn = doc.transact();
     let map = txn.get_map(ELEMENTS_MAP)?;
@@ -315,16 +610,16 @@ fn materialize_from_out<T: ReadTxn>(
 ) -> Option<ElementMaterialized> {
     match value {
         Out::YMap(map) => materialize_from_map(txn, &map, element_id),
-        Out::Any(any) => materialize_from_any(any),
+        Out::Any(any) => materialize_from_any(any, element_id),
         _ => None,
     }
 }
 
-fn materialize_from_any(any: Any) -> Option<ElementMaterialized> {
+fn materialize_from_any(any: Any, element_id: &str) -> Option<ElementMaterialized> {
     let Ok(json) = from_any::<Value>(&any) else {
         return None;
     };
-    materialize_from_json(&json)
+    materialize_from_json(&json, element_id)
 }
 
 fn materialize_from_map<T: ReadTxn>(
@@ -336,18 +631,18 @@ fn materialize_from_map<T: ReadTxn>(
     let Ok(json) = from_any::<Value>(&any) else {
         return None;
     };
-    let mut element = materialize_from_json(&json)?;
-    if element.id == Uuid::nil() && !element_id.is_empty() {
-        if let Ok(parsed) = Uuid::parse_str(element_id) {
-            element.id = parsed;
-        }
-    }
-    Some(element)
+    materialize_from_json(&json, element_id)
 }
 
-fn materialize_from_json(json: &Value) -> Option<ElementMaterialized> {
+/// Builds an element from its JSON form.
+///
+/// A missing or nil `id` is backfilled from `element_id`, the key the element
+/// is stored under; older documents did not always write the id field.
+fn materialize_from_json(json: &Value, element_id: &str) -> Option<ElementMaterialized> {
     let object = json.as_object()?;
-    let id = parse_uuid(object.get(FIELD_ID))?;
+    let id = parse_uuid(object.get(FIELD_ID))
+        .filter(|id| !id.is_nil())
+        .or_else(|| Uuid::parse_str(element_id).ok())?;
     let board_id = parse_uuid(object.get(FIELD_BOARD_ID))?;
     let element_type = parse_element_type(object.get(FIELD_ELEMENT_TYPE))?;
     let position_x = parse_number(object.get(FIELD_POSITION_X))?;
@@ -392,6 +687,58 @@ fn materialize_from_json(json: &Value) -> Option<ElementMaterialized> {
     })
 }
 
+/// `patch` without the keys listed in `stale_fields` as `field.key`, or
+/// `None` when the whole field is stale.
+/// Whether `req` writes `field`, either a top-level field or a
+/// `style`/`properties`/`metadata` key given as `name.key`.
+fn request_writes_field(req: &UpdateBoardElementRequest, field: &str) -> bool {
+    let (name, key) = match field.split_once('.') {
+        Some((name, key)) => (name, Some(key)),
+        None => (field, None),
+    };
+    let patch = match name {
+        FIELD_POSITION_X => return key.is_none() && req.position_x.is_some(),
+        FIELD_POSITION_Y => return key.is_none() && req.position_y.is_some(),
+        FIELD_WIDTH => return key.is_none() && req.width.is_some(),
+        FIELD_HEIGHT => return key.is_none() && req.height.is_some(),
+        FIELD_ROTATION => return key.is_none() && req.rotation.is_some(),
+        FIELD_STYLE => req.style.as_ref(),
+        FIELD_PROPERTIES => req.properties.as_ref(),
+        FIELD_METADATA => req.metadata.as_ref(),
+        _ => return false,
+    };
+    match (patch, key) {
+        (Some(patch), Some(key)) => patch.get(key).is_some(),
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn fresh_patch<'a>(
+    field: &str,
+    patch: Option<&'a Value>,
+    stale_fields: &[String],
+) -> Option<Cow<'a, Value>> {
+    let patch = patch?;
+    if stale_fields.iter().any(|stale| stale == field) {
+        return None;
+    }
+    let stale_keys: Vec<&str> = stale_fields
+        .iter()
+        .filter_map(|stale| stale.strip_prefix(field)?.strip_prefix('.'))
+        .collect();
+    match patch.as_object() {
+        Some(object) if !stale_keys.is_empty() => Some(Cow::Owned(Value::Object(
+            object
+                .iter()
+                .filter(|(key, _)| !stale_keys.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        ))),
+        _ => Some(Cow::Borrowed(patch)),
+    }
+}
+
 fn apply_object_patch(txn: &mut TransactionMut, map: &MapRef, key: &str, value: &Value) {
     if key.is_empty() {
         if let Some(object) = value.as_object() {
@@ -515,6 +862,15 @@ fn set_uuid_opt(txn: &mut TransactionMut, map: &MapRef, key: &str, value: Option
     }
 }
 
+fn get_datetime<T: ReadTxn>(txn: &T, map: &MapRef, key: &str) -> Option<DateTime<Utc>> {
+    match map.get(txn, key) {
+        Some(Out::Any(Any::String(value))) => DateTime::parse_from_rfc3339(&value)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
 fn set_datetime(txn: &mut TransactionMut, map: &MapRef, key: &str, value: DateTime<Utc>) {
     map.insert(txn, key.to_string(), value.to_rfc3339());
 }
@@ -658,3 +1014,531 @@ fn element_type_to_client(element_type: ElementType) -> &'static str {
         ElementType::Component => "Component",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use uuid::Uuid;
+    use yrs::{Any, Doc, Map, ReadTxn, Transact, WriteTxn, updates::decoder::Decode};
+
+    use super::{
+        ELEMENTS_MAP, ElementEditors, ElementSnapshot, UpdateContext, apply_snapshot, apply_style,
+        apply_update, compact_z_indices, dump_elements, editor_origin, materialize_element,
+        materialize_elements, observe_element_editors, remove_element,
+    };
+    use crate::{dto::elements::UpdateBoardElementRequest, models::elements::ElementType};
+
+    fn legacy_element(id: Option<&str>) -> Any {
+        let mut element = json!({
+            "board_id": Uuid::now_v7().to_string(),
+            "element_type": "Shape",
+            "position_x": 1.0,
+            "position_y": 2.0,
+            "width": 3.0,
+            "height": 4.0,
+        });
+        if let Some(id) = id {
+            element["id"] = json!(id);
+        }
+        Any::from_json(&element.to_string()).expect("valid json")
+    }
+
+    #[test]
+    fn any_stored_elements_backfill_nil_or_missing_ids_from_key() {
+        let doc = Doc::new();
+        let nil_key = Uuid::now_v7();
+        let missing_key = Uuid::now_v7();
+        {
+            let mut txn = doc.transact_mut();
+            let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+            elements.insert(
+                &mut txn,
+                nil_key.to_string(),
+                legacy_element(Some(&Uuid::nil().to_string())),
+            );
+            elements.insert(&mut txn, missing_key.to_string(), legacy_element(None));
+        }
+
+        let mut ids: Vec<Uuid> = materialize_elements(&doc)
+            .into_iter()
+            .map(|element| element.id)
+            .collect();
+        ids.sort();
+        let mut expected = vec![nil_key, missing_key];
+        expected.sort();
+
+        assert_eq!(ids, expected);
+        assert_eq!(
+            materialize_element(&doc, nil_key).map(|element| element.id),
+            Some(nil_key)
+        );
+    }
+
+    #[test]
+    fn dump_elements_keeps_entries_materialize_skips() {
+        let doc = Doc::new();
+        assert_eq!(dump_elements(&doc), json!({}));
+
+        let key = Uuid::now_v7().to_string();
+        {
+            let mut txn = doc.transact_mut();
+            let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+            let broken = Any::from_json(&json!({ "version": 3 }).to_string()).expect("valid json");
+            elements.insert(&mut txn, key.clone(), broken);
+        }
+
+        assert!(materialize_elements(&doc).is_empty());
+        assert_eq!(dump_elements(&doc)[&key]["version"], json!(3.0));
+    }
+
+    #[test]
+    fn stale_writes_skip_only_last_write_wins_fields() {
+        let doc = Doc::new();
+        let stored_at = Utc::now();
+        let element_id = Uuid::now_v7();
+        apply_snapshot(
+            &doc,
+            &ElementSnapshot {
+                id: element_id,
+                board_id: Uuid::now_v7(),
+                layer_id: None,
+                parent_id: None,
+                created_by: Uuid::now_v7(),
+                element_type: ElementType::StickyNote,
+                position_x: 0.0,
+                position_y: 0.0,
+                width: 10.0,
+                height: 10.0,
+                rotation: 0.0,
+                z_index: 1,
+                style: json!({}),
+                properties: json!({ "votes": 1 }),
+                metadata: json!({}),
+                created_at: stored_at,
+                updated_at: stored_at,
+                deleted_at: None,
+                version: 1,
+            },
+        )
+        .unwrap();
+        let lww_fields = ["properties.votes".to_string(), "rotation".to_string()];
+        let write = |client_updated_at, value: f64, label| UpdateBoardElementRequest {
+            expected_version: 1,
+            client_updated_at: Some(client_updated_at),
+            position_x: Some(value),
+            position_y: None,
+            width: None,
+            height: None,
+            rotation: Some(value),
+            style: None,
+            properties: Some(json!({ "votes": value, "label": label })),
+            metadata: None,
+        };
+        let context = UpdateContext {
+            updated_at: stored_at + Duration::seconds(10),
+            last_write_wins_fields: &lww_fields,
+        };
+
+        let fresh = apply_update(
+            &doc,
+            element_id,
+            &write(stored_at + Duration::seconds(5), 45.0, "first"),
+            context,
+        )
+        .unwrap()
+        .unwrap()
+        .element;
+
+        assert_eq!(fresh.rotation, 45.0);
+        assert_eq!(fresh.properties["votes"], json!(45.0));
+
+        let stale = apply_update(
+            &doc,
+            element_id,
+            &write(stored_at + Duration::seconds(2), 7.0, "late"),
+            context,
+        )
+        .unwrap()
+        .unwrap()
+        .element;
+
+        assert_eq!(stale.position_x, 7.0);
+        assert_eq!(stale.rotation, 45.0);
+        assert_eq!(stale.properties, json!({ "votes": 45.0, "label": "late" }));
+    }
+
+    #[test]
+    fn last_write_wins_clamps_client_clocks_to_server_time() {
+        let doc = Doc::new();
+        let stored_at = Utc::now();
+        let element_id = Uuid::now_v7();
+        apply_snapshot(
+            &doc,
+            &ElementSnapshot {
+                id: element_id,
+                board_id: Uuid::now_v7(),
+                layer_id: None,
+                parent_id: None,
+                created_by: Uuid::now_v7(),
+                element_type: ElementType::StickyNote,
+                position_x: 0.0,
+                position_y: 0.0,
+                width: 10.0,
+                height: 10.0,
+                rotation: 0.0,
+                z_index: 1,
+                style: json!({}),
+                properties: json!({}),
+                metadata: json!({}),
+                created_at: stored_at,
+                updated_at: stored_at,
+                deleted_at: None,
+                version: 1,
+            },
+        )
+        .unwrap();
+        let lww_fields = ["rotation".to_string()];
+        let request = |client_updated_at, rotation| UpdateBoardElementRequest {
+            expected_version: 1,
+            client_updated_at: Some(client_updated_at),
+            position_x: None,
+            position_y: None,
+            width: None,
+            height: None,
+            rotation: Some(rotation),
+            style: None,
+            properties: None,
+            metadata: None,
+        };
+        let first = UpdateContext {
+            updated_at: stored_at + Duration::seconds(1),
+            last_write_wins_fields: &lww_fields,
+        };
+        let second = UpdateContext {
+            updated_at: stored_at + Duration::seconds(2),
+            last_write_wins_fields: &lww_fields,
+        };
+
+        // A client clock a day ahead is stamped with the server's time instead.
+        apply_update(
+            &doc,
+            element_id,
+            &request(stored_at + Duration::days(1), 30.0),
+            first,
+        )
+        .unwrap();
+        let element = apply_update(
+            &doc,
+            element_id,
+            &request(stored_at + Duration::seconds(2), 60.0),
+            second,
+        )
+        .unwrap()
+        .unwrap()
+        .element;
+
+        assert_eq!(element.rotation, 60.0);
+    }
+
+    #[test]
+    fn last_write_wins_compares_each_field_separately() {
+        let doc = Doc::new();
+        let stored_at = Utc::now();
+        let element_id = Uuid::now_v7();
+        apply_snapshot(
+            &doc,
+            &ElementSnapshot {
+                id: element_id,
+                board_id: Uuid::now_v7(),
+                layer_id: None,
+                parent_id: None,
+                created_by: Uuid::now_v7(),
+                element_type: ElementType::StickyNote,
+                position_x: 0.0,
+                position_y: 0.0,
+                width: 10.0,
+                height: 10.0,
+                rotation: 0.0,
+                z_index: 1,
+                style: json!({}),
+                properties: json!({ "votes": 1 }),
+                metadata: json!({}),
+                created_at: stored_at,
+                updated_at: stored_at,
+                deleted_at: None,
+                version: 1,
+            },
+        )
+        .unwrap();
+        let lww_fields = ["properties.votes".to_string(), "rotation".to_string()];
+        let request = |client_updated_at, rotation, properties| UpdateBoardElementRequest {
+            expected_version: 1,
+            client_updated_at: Some(client_updated_at),
+            position_x: None,
+            position_y: None,
+            width: None,
+            height: None,
+            rotation,
+            style: None,
+            properties,
+            metadata: None,
+        };
+        let context = UpdateContext {
+            updated_at: stored_at + Duration::seconds(10),
+            last_write_wins_fields: &lww_fields,
+        };
+
+        apply_update(
+            &doc,
+            element_id,
+            &request(stored_at + Duration::seconds(10), Some(90.0), None),
+            context,
+        )
+        .unwrap();
+        // Older than the rotation write, but votes has not been written since.
+        let element = apply_update(
+            &doc,
+            element_id,
+            &request(
+                stored_at + Duration::seconds(5),
+                None,
+                Some(json!({ "votes": 3 })),
+            ),
+            context,
+        )
+        .unwrap()
+        .unwrap()
+        .element;
+
+        assert_eq!(element.rotation, 90.0);
+        assert_eq!(element.properties["votes"], json!(3.0));
+    }
+
+    #[test]
+    fn compaction_densifies_z_indices_per_layer_preserving_order() {
+        let doc = Doc::new();
+        let board_id = Uuid::now_v7();
+        let layer_id = Uuid::now_v7();
+        let created_at = Utc::now();
+        let insert = |layer_id, z_index, deleted: bool| {
+            let id = Uuid::now_v7();
+            apply_snapshot(
+                &doc,
+                &ElementSnapshot {
+                    id,
+                    board_id,
+                    layer_id,
+                    parent_id: None,
+                    created_by: Uuid::now_v7(),
+                    element_type: ElementType::Shape,
+                    position_x: 0.0,
+                    position_y: 0.0,
+                    width: 10.0,
+                    height: 10.0,
+                    rotation: 0.0,
+                    z_index,
+                    style: json!({}),
+                    properties: json!({}),
+                    metadata: json!({}),
+                    created_at,
+                    updated_at: created_at,
+                    deleted_at: deleted.then_some(created_at),
+                    version: 1,
+                },
+            )
+            .unwrap();
+            id
+        };
+        let top = insert(None, 9_000, false);
+        let bottom = insert(None, 40, false);
+        let middle = insert(None, 500, false);
+        let deleted = insert(None, 7_000, true);
+        let layered = insert(Some(layer_id), 3, false);
+
+        let compaction = compact_z_indices(&doc, created_at).unwrap();
+
+        assert_eq!(compaction.changed, 4);
+        let z_index = |id| materialize_element(&doc, id).unwrap().z_index;
+        assert_eq!((z_index(bottom), z_index(middle), z_index(top)), (0, 1, 2));
+        assert_eq!(z_index(layered), 0);
+        assert_eq!(z_index(deleted), 7_000);
+        assert_eq!(materialize_element(&doc, top).unwrap().version, Some(2));
+        assert!(
+            compact_z_indices(&doc, created_at)
+                .unwrap()
+                .update
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn remove_element_drops_the_map_entry() {
+        let doc = Doc::new();
+        let id = Uuid::now_v7();
+        {
+            let mut txn = doc.transact_mut();
+            let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+            elements.insert(&mut txn, id.to_string(), legacy_element(None));
+        }
+
+        let removed = remove_element(&doc, id).unwrap();
+
+        assert_eq!(removed.element.id, id);
+        assert!(!removed.update.is_empty());
+        assert!(materialize_element(&doc, id).is_none());
+        assert!(remove_element(&doc, id).is_none());
+    }
+
+    #[test]
+    fn apply_style_merges_onto_live_targets_only() {
+        let doc = Doc::new();
+        let created_at = Utc::now();
+        let insert = |style: serde_json::Value, deleted: bool| {
+            let id = Uuid::now_v7();
+            apply_snapshot(
+                &doc,
+                &ElementSnapshot {
+                    id,
+                    board_id: Uuid::now_v7(),
+                    layer_id: None,
+                    parent_id: None,
+                    created_by: Uuid::now_v7(),
+                    element_type: ElementType::Shape,
+                    position_x: 0.0,
+                    position_y: 0.0,
+                    width: 10.0,
+                    height: 10.0,
+                    rotation: 0.0,
+                    z_index: 0,
+                    style,
+                    properties: json!({}),
+                    metadata: json!({}),
+                    created_at,
+                    updated_at: created_at,
+                    deleted_at: deleted.then_some(created_at),
+                    version: 1,
+                },
+            )
+            .unwrap();
+            id
+        };
+        let target = insert(json!({ "fill": "#fff", "opacity": 0.5 }), false);
+        let deleted = insert(json!({ "fill": "#fff" }), true);
+        let missing = Uuid::now_v7();
+
+        let applied = apply_style(
+            &doc,
+            &[target, deleted, missing],
+            &json!({ "fill": "#f00", "stroke": "#000" }),
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert!(!applied.update.is_empty());
+        assert!(applied.elements[1].is_none() && applied.elements[2].is_none());
+        let styled = applied.elements[0].as_ref().unwrap();
+        assert_eq!(styled.version, Some(2));
+        assert_eq!(
+            styled.style,
+            json!({ "fill": "#f00", "opacity": 0.5, "stroke": "#000" })
+        );
+        assert_eq!(
+            materialize_element(&doc, deleted).unwrap().style,
+            json!({ "fill": "#fff" })
+        );
+    }
+
+    #[test]
+    fn removed_elements_leave_no_content_in_encoded_state() {
+        let doc = Doc::new();
+        let now = Utc::now();
+        let element_id = Uuid::now_v7();
+        apply_snapshot(
+            &doc,
+            &ElementSnapshot {
+                id: element_id,
+                board_id: Uuid::now_v7(),
+                layer_id: None,
+                parent_id: None,
+                created_by: Uuid::now_v7(),
+                element_type: ElementType::Text,
+                position_x: 0.0,
+                position_y: 0.0,
+                width: 10.0,
+                height: 10.0,
+                rotation: 0.0,
+                z_index: 1,
+                style: json!({}),
+                properties: json!({ "text": "secret-hard-delete-marker" }),
+                metadata: json!({}),
+                created_at: now,
+                updated_at: now,
+                deleted_at: None,
+                version: 1,
+            },
+        )
+        .unwrap();
+
+        assert!(remove_element(&doc, element_id).is_some());
+
+        let state = doc
+            .transact()
+            .encode_state_as_update_v1(&yrs::StateVector::default());
+        let marker = b"secret-hard-delete-marker";
+        assert!(!state.windows(marker.len()).any(|window| window == marker));
+    }
+
+    #[test]
+    fn element_editors_follow_the_transaction_origin() {
+        let doc = Doc::new();
+        let editors = ElementEditors::default();
+        let _subscription = observe_element_editors(&doc, editors.clone());
+        let edited = Uuid::now_v7();
+        let seeded = Uuid::now_v7();
+        let user_id = Uuid::now_v7();
+        {
+            let mut txn = doc.transact_mut();
+            let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+            elements.insert(&mut txn, seeded.to_string(), legacy_element(None));
+            elements.insert(&mut txn, edited.to_string(), legacy_element(None));
+        }
+        *editors.lock().unwrap() = Default::default();
+        let remote = Doc::new();
+        let state = doc
+            .transact()
+            .encode_state_as_update_v1(&yrs::StateVector::default());
+        let mut txn = remote.transact_mut();
+        txn.apply_update(yrs::Update::decode_v1(&state).unwrap())
+            .unwrap();
+        let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+        elements.insert(&mut txn, edited.to_string(), legacy_element(None));
+        drop(txn);
+        let update = remote
+            .transact()
+            .encode_state_as_update_v1(&doc.transact().state_vector());
+
+        doc.transact_mut_with(editor_origin(user_id))
+            .apply_update(yrs::Update::decode_v1(&update).unwrap())
+            .unwrap();
+
+        let editors = editors.lock().unwrap();
+        assert_eq!(editors.get(&edited), Some(&Some(user_id)));
+        assert!(!editors.contains_key(&seeded));
+    }
+
+    #[test]
+    fn element_editors_record_no_user_without_an_origin() {
+        let doc = Doc::new();
+        let editors = ElementEditors::default();
+        let _subscription = observe_element_editors(&doc, editors.clone());
+        let element_id = Uuid::now_v7();
+        {
+            let mut txn = doc.transact_mut();
+            let elements = txn.get_or_insert_map(ELEMENTS_MAP);
+            elements.insert(&mut txn, element_id.to_string(), legacy_element(None));
+        }
+
+        assert_eq!(editors.lock().unwrap().get(&element_id), Some(&None));
+    }
+}
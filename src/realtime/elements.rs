@@ -5,13 +5,16 @@ use axum::body::Bytes;
 use sqlx::PgPool;
 use tokio::sync::Mutex;
 use uuid::Uuid;
-use yrs::Doc;
+use yrs::{Doc, merge_updates_v1};
 
 use crate::{
     dto::elements::UpdateBoardElementRequest,
     error::AppError,
     realtime::{
-        element_crdt::{self, AppliedElement, ElementMaterialized, ElementSnapshot},
+        element_crdt::{
+            self, AppliedElement, ElementMaterialized, ElementSnapshot, ElementTypeChange,
+            StyleApplied, UpdateContext, ZIndexCompaction,
+        },
         projection, protocol,
         room::Rooms,
         snapshot,
@@ -24,6 +27,15 @@ pub struct DeletedApplied {
     pub was_deleted: bool,
 }
 
+/// Outcome for one element of a batch delete or restore; elements already in
+/// the requested state, or whose version differs from the expected one, are
+/// left untouched.
+pub struct BatchDeleted {
+    pub element: ElementMaterialized,
+    pub was_deleted: bool,
+    pub version_conflict: bool,
+}
+
 pub async fn apply_element_snapshot(
     rooms: &Rooms,
     db: &PgPool,
@@ -50,7 +62,7 @@ pub async fn apply_element_snapshot(
 
     if let Some(applied) = applied {
         persist_update(db, board_id, actor_id, &applied.update).await?;
-        projection::project_doc(db, board_id, doc).await?;
+        projection::project_doc(db, board_id, doc, Some(actor_id)).await?;
         Ok(applied)
     } else {
         Err(AppError::Internal(
@@ -59,6 +71,55 @@ pub async fn apply_element_snapshot(
     }
 }
 
+/// Applies several new elements in one doc lock and broadcasts them as a
+/// single merged update.
+pub async fn apply_element_snapshots(
+    rooms: &Rooms,
+    db: &PgPool,
+    actor_id: Uuid,
+    board_id: Uuid,
+    snapshots: &[ElementSnapshot],
+) -> Result<Vec<ElementMaterialized>, AppError> {
+    if let Some(room_entry) = rooms.get(&board_id) {
+        let room = room_entry.clone();
+        drop(room_entry);
+
+        let (elements, update) = {
+            let doc_guard = room.doc.lock().await;
+            apply_snapshots_merged(&doc_guard, snapshots)?
+        };
+        broadcast_update(&room, update).await;
+        return Ok(elements);
+    }
+
+    let (doc, (elements, update)) =
+        apply_with_loaded_doc(db, board_id, |doc| apply_snapshots_merged(doc, snapshots)).await?;
+
+    persist_update(db, board_id, actor_id, &update).await?;
+    projection::project_doc(db, board_id, doc, Some(actor_id)).await?;
+    Ok(elements)
+}
+
+fn apply_snapshots_merged(
+    doc: &Doc,
+    snapshots: &[ElementSnapshot],
+) -> Result<(Vec<ElementMaterialized>, Vec<u8>), AppError> {
+    let mut elements = Vec::with_capacity(snapshots.len());
+    let mut updates = Vec::with_capacity(snapshots.len());
+    for snapshot in snapshots {
+        let applied = element_crdt::apply_snapshot(doc, snapshot)?;
+        elements.push(applied.element);
+        updates.push(applied.update);
+    }
+    if updates.is_empty() {
+        return Ok((elements, Vec::new()));
+    }
+    let refs: Vec<&[u8]> = updates.iter().map(Vec::as_slice).collect();
+    let merged = merge_updates_v1(&refs)
+        .map_err(|error| AppError::Internal(format!("Failed to merge updates: {}", error)))?;
+    Ok((elements, merged))
+}
+
 pub async fn apply_element_update(
     rooms: &Rooms,
     db: &PgPool,
@@ -66,7 +127,7 @@ pub async fn apply_element_update(
     board_id: Uuid,
     element_id: Uuid,
     req: &UpdateBoardElementRequest,
-    updated_at: chrono::DateTime<chrono::Utc>,
+    context: UpdateContext<'_>,
 ) -> Result<Option<AppliedElement>, AppError> {
     if let Some(room_entry) = rooms.get(&board_id) {
         let room = room_entry.clone();
@@ -74,7 +135,7 @@ pub async fn apply_element_update(
 
         let applied = {
             let doc_guard = room.doc.lock().await;
-            element_crdt::apply_update(&doc_guard, element_id, req, updated_at)?
+            element_crdt::apply_update(&doc_guard, element_id, req, context)?
         };
         if let Some(applied) = applied.as_ref() {
             broadcast_update(&room, applied.update.clone()).await;
@@ -83,13 +144,47 @@ pub async fn apply_element_update(
     }
 
     let (doc, applied) = apply_with_loaded_doc(db, board_id, |doc| {
-        element_crdt::apply_update(doc, element_id, req, updated_at)
+        element_crdt::apply_update(doc, element_id, req, context)
     })
     .await?;
 
     if let Some(applied) = applied.as_ref() {
         persist_update(db, board_id, actor_id, &applied.update).await?;
-        projection::project_doc(db, board_id, doc).await?;
+        projection::project_doc(db, board_id, doc, Some(actor_id)).await?;
+    }
+
+    Ok(applied)
+}
+
+pub async fn apply_element_type_change(
+    rooms: &Rooms,
+    db: &PgPool,
+    actor_id: Uuid,
+    board_id: Uuid,
+    element_id: Uuid,
+    change: &ElementTypeChange,
+    updated_at: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<AppliedElement>, AppError> {
+    let apply = |doc: &Doc| element_crdt::apply_type_change(doc, element_id, change, updated_at);
+    if let Some(room_entry) = rooms.get(&board_id) {
+        let room = room_entry.clone();
+        drop(room_entry);
+
+        let applied = {
+            let doc_guard = room.doc.lock().await;
+            apply(&doc_guard)?
+        };
+        if let Some(applied) = applied.as_ref() {
+            broadcast_update(&room, applied.update.clone()).await;
+        }
+        return Ok(applied);
+    }
+
+    let (doc, applied) = apply_with_loaded_doc(db, board_id, apply).await?;
+
+    if let Some(applied) = applied.as_ref() {
+        persist_update(db, board_id, actor_id, &applied.update).await?;
+        projection::project_doc(db, board_id, doc, Some(actor_id)).await?;
     }
 
     Ok(applied)
@@ -139,12 +234,249 @@ pub async fn apply_element_deleted(
 
     if let Some(result) = result.as_ref() {
         persist_update(db, board_id, actor_id, &result.applied.update).await?;
-        projection::project_doc(db, board_id, doc).await?;
+        projection::project_doc(db, board_id, doc, Some(actor_id)).await?;
     }
 
     Ok(result)
 }
 
+/// Permanently removes several elements from the board doc in one doc lock,
+/// then compacts the board's CRDT history so the removed content is not kept
+/// in older snapshots or update logs. Results follow the order of `targets`,
+/// as in [`apply_elements_deleted`]; removed elements are reported with
+/// `deleted_at` set to `removed_at`. The projected rows are left for the
+/// caller to delete.
+pub async fn apply_elements_removed(
+    rooms: &Rooms,
+    db: &PgPool,
+    actor_id: Uuid,
+    board_id: Uuid,
+    targets: &[(Uuid, i32)],
+    removed_at: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<Option<BatchDeleted>>, AppError> {
+    if let Some(room_entry) = rooms.get(&board_id) {
+        let room = room_entry.clone();
+        drop(room_entry);
+        return remove_from_room(db, &room, targets, removed_at).await;
+    }
+
+    let (_, (results, update)) = apply_with_loaded_doc(db, board_id, |doc| {
+        apply_removed_merged(doc, targets, removed_at)
+    })
+    .await?;
+
+    if !update.is_empty() {
+        persist_update(db, board_id, actor_id, &update).await?;
+        snapshot::compact_history(db, board_id).await?;
+    }
+    Ok(results)
+}
+
+/// [`apply_elements_removed`] against an open room.
+pub(crate) async fn remove_from_room(
+    db: &PgPool,
+    room: &Arc<crate::realtime::room::Room>,
+    targets: &[(Uuid, i32)],
+    removed_at: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<Option<BatchDeleted>>, AppError> {
+    let (results, update) = {
+        let doc_guard = room.doc.lock().await;
+        apply_removed_merged(&doc_guard, targets, removed_at)?
+    };
+    if !update.is_empty() {
+        broadcast_update(room, update).await;
+        snapshot::compact_room_history(db, room).await?;
+    }
+    Ok(results)
+}
+
+/// Soft-deletes (or, with `deleted_at: None`, restores) several elements in
+/// one doc lock and broadcasts them as a single merged update. `targets` pairs
+/// each element id with its expected version. Results follow the order of
+/// `targets`; `None` marks an element missing from the board.
+pub async fn apply_elements_deleted(
+    rooms: &Rooms,
+    db: &PgPool,
+    actor_id: Uuid,
+    board_id: Uuid,
+    targets: &[(Uuid, i32)],
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<Option<BatchDeleted>>, AppError> {
+    if let Some(room_entry) = rooms.get(&board_id) {
+        let room = room_entry.clone();
+        drop(room_entry);
+
+        let (results, update) = {
+            let doc_guard = room.doc.lock().await;
+            apply_deleted_merged(&doc_guard, targets, deleted_at, updated_at)?
+        };
+        broadcast_update(&room, update).await;
+        return Ok(results);
+    }
+
+    let (doc, (results, update)) = apply_with_loaded_doc(db, board_id, |doc| {
+        apply_deleted_merged(doc, targets, deleted_at, updated_at)
+    })
+    .await?;
+
+    if !update.is_empty() {
+        persist_update(db, board_id, actor_id, &update).await?;
+        projection::project_doc(db, board_id, doc, Some(actor_id)).await?;
+    }
+    Ok(results)
+}
+
+fn apply_deleted_merged(
+    doc: &Doc,
+    targets: &[(Uuid, i32)],
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+) -> Result<(Vec<Option<BatchDeleted>>, Vec<u8>), AppError> {
+    let mut results = Vec::with_capacity(targets.len());
+    let mut updates = Vec::new();
+    for &(element_id, expected_version) in targets {
+        let Some(existing) = element_crdt::materialize_element(doc, element_id) else {
+            results.push(None);
+            continue;
+        };
+        let was_deleted = existing.deleted_at.is_some();
+        let already_applied = was_deleted == deleted_at.is_some();
+        if already_applied || existing.version != Some(expected_version) {
+            results.push(Some(BatchDeleted {
+                element: existing,
+                was_deleted,
+                version_conflict: !already_applied,
+            }));
+            continue;
+        }
+        let applied = element_crdt::apply_deleted(doc, element_id, deleted_at, updated_at)?;
+        results.push(applied.map(|applied| {
+            updates.push(applied.update);
+            BatchDeleted {
+                element: applied.element,
+                was_deleted,
+                version_conflict: false,
+            }
+        }));
+    }
+    Ok((results, merge_batch_updates(&updates)?))
+}
+
+fn apply_removed_merged(
+    doc: &Doc,
+    targets: &[(Uuid, i32)],
+    removed_at: chrono::DateTime<chrono::Utc>,
+) -> Result<(Vec<Option<BatchDeleted>>, Vec<u8>), AppError> {
+    let mut results = Vec::with_capacity(targets.len());
+    let mut updates = Vec::new();
+    for &(element_id, expected_version) in targets {
+        let Some(existing) = element_crdt::materialize_element(doc, element_id) else {
+            results.push(None);
+            continue;
+        };
+        let was_deleted = existing.deleted_at.is_some();
+        if existing.version != Some(expected_version) {
+            results.push(Some(BatchDeleted {
+                element: existing,
+                was_deleted,
+                version_conflict: true,
+            }));
+            continue;
+        }
+        let removed = element_crdt::remove_element(doc, element_id);
+        results.push(removed.map(|removed| {
+            updates.push(removed.update);
+            let mut element = removed.element;
+            element.deleted_at = Some(removed_at);
+            element.updated_at = Some(removed_at);
+            BatchDeleted {
+                element,
+                was_deleted,
+                version_conflict: false,
+            }
+        }));
+    }
+    Ok((results, merge_batch_updates(&updates)?))
+}
+
+fn merge_batch_updates(updates: &[Vec<u8>]) -> Result<Vec<u8>, AppError> {
+    if updates.is_empty() {
+        return Ok(Vec::new());
+    }
+    let refs: Vec<&[u8]> = updates.iter().map(Vec::as_slice).collect();
+    merge_updates_v1(&refs)
+        .map_err(|error| AppError::Internal(format!("Failed to merge updates: {}", error)))
+}
+
+/// Merges `style` onto several elements and broadcasts the change as one
+/// update. Results follow the order of `element_ids`.
+pub async fn apply_style_to_elements(
+    rooms: &Rooms,
+    db: &PgPool,
+    actor_id: Uuid,
+    board_id: Uuid,
+    element_ids: &[Uuid],
+    style: &serde_json::Value,
+) -> Result<Vec<Option<ElementMaterialized>>, AppError> {
+    let updated_at = chrono::Utc::now();
+    if let Some(room_entry) = rooms.get(&board_id) {
+        let room = room_entry.clone();
+        drop(room_entry);
+
+        let StyleApplied { elements, update } = {
+            let doc_guard = room.doc.lock().await;
+            element_crdt::apply_style(&doc_guard, element_ids, style, updated_at)?
+        };
+        broadcast_update(&room, update).await;
+        return Ok(elements);
+    }
+
+    let (doc, StyleApplied { elements, update }) = apply_with_loaded_doc(db, board_id, |doc| {
+        element_crdt::apply_style(doc, element_ids, style, updated_at)
+    })
+    .await?;
+
+    if !update.is_empty() {
+        persist_update(db, board_id, actor_id, &update).await?;
+        projection::project_doc(db, board_id, doc, Some(actor_id)).await?;
+    }
+    Ok(elements)
+}
+
+/// Compacts z-indices in the board doc; see [`element_crdt::compact_z_indices`].
+/// Maintenance runs pass no `actor_id`. Returns how many elements moved.
+pub async fn apply_z_index_compaction(
+    rooms: &Rooms,
+    db: &PgPool,
+    actor_id: Option<Uuid>,
+    board_id: Uuid,
+) -> Result<usize, AppError> {
+    let updated_at = chrono::Utc::now();
+    if let Some(room_entry) = rooms.get(&board_id) {
+        let room = room_entry.clone();
+        drop(room_entry);
+
+        let ZIndexCompaction { changed, update } = {
+            let doc_guard = room.doc.lock().await;
+            element_crdt::compact_z_indices(&doc_guard, updated_at)?
+        };
+        broadcast_update(&room, update).await;
+        return Ok(changed);
+    }
+
+    let (doc, ZIndexCompaction { changed, update }) = apply_with_loaded_doc(db, board_id, |doc| {
+        element_crdt::compact_z_indices(doc, updated_at)
+    })
+    .await?;
+
+    if !update.is_empty() {
+        realtime_repo::insert_update_log(db, board_id, actor_id, update).await?;
+        projection::project_doc(db, board_id, doc, actor_id).await?;
+    }
+    Ok(changed)
+}
+
 pub async fn next_z_index(
     rooms: &Rooms,
     db: &PgPool,
@@ -191,6 +523,25 @@ pub async fn load_element_materialized(
     Ok(element)
 }
 
+/// Materializes every element of a board, including deleted ones.
+pub async fn load_elements_materialized(
+    rooms: &Rooms,
+    db: &PgPool,
+    board_id: Uuid,
+) -> Result<Vec<ElementMaterialized>, AppError> {
+    if let Some(room_entry) = rooms.get(&board_id) {
+        let room = room_entry.clone();
+        drop(room_entry);
+
+        let doc_guard = room.doc.lock().await;
+        return Ok(element_crdt::materialize_elements(&doc_guard));
+    }
+
+    let doc = load_doc(db, board_id).await?;
+    let doc_guard = doc.lock().await;
+    Ok(element_crdt::materialize_elements(&doc_guard))
+}
+
 async fn apply_with_loaded_doc<T, F>(
     db: &PgPool,
     board_id: Uuid,
@@ -229,7 +580,7 @@ async fn persist_update(
     realtime_repo::insert_update_log(db, board_id, Some(actor_id), update.to_vec()).await
 }
 
-async fn broadcast_update(room: &Arc<crate::realtime::room::Room>, update: Vec<u8>) {
+pub(crate) async fn broadcast_update(room: &Arc<crate::realtime::room::Room>, update: Vec<u8>) {
     if update.is_empty() {
         return;
     }
@@ -245,3 +596,103 @@ async fn broadcast_update(room: &Arc<crate::realtime::room::Room>, update: Vec<u
     message.extend(update);
     let _ = room.tx.send(Bytes::from(message));
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+    use yrs::Doc;
+
+    use super::{apply_deleted_merged, apply_removed_merged};
+    use crate::{
+        models::elements::ElementType,
+        realtime::element_crdt::{self, ElementSnapshot},
+    };
+
+    fn insert_element(doc: &Doc) -> Uuid {
+        let now = Utc::now();
+        let id = Uuid::now_v7();
+        element_crdt::apply_snapshot(
+            doc,
+            &ElementSnapshot {
+                id,
+                board_id: Uuid::now_v7(),
+                layer_id: None,
+                parent_id: None,
+                created_by: Uuid::now_v7(),
+                element_type: ElementType::Shape,
+                position_x: 0.0,
+                position_y: 0.0,
+                width: 10.0,
+                height: 10.0,
+                rotation: 0.0,
+                z_index: 1,
+                style: json!({}),
+                properties: json!({}),
+                metadata: json!({}),
+                created_at: now,
+                updated_at: now,
+                deleted_at: None,
+                version: 1,
+            },
+        )
+        .unwrap();
+        id
+    }
+
+    #[test]
+    fn batch_delete_skips_elements_with_a_stale_expected_version() {
+        let doc = Doc::new();
+        let current = insert_element(&doc);
+        let stale = insert_element(&doc);
+        let now = Utc::now();
+
+        let (results, update) =
+            apply_deleted_merged(&doc, &[(current, 1), (stale, 2)], Some(now), now).unwrap();
+
+        assert!(!update.is_empty());
+        let deleted = results[0].as_ref().unwrap();
+        assert!(!deleted.version_conflict && deleted.element.deleted_at.is_some());
+        let conflict = results[1].as_ref().unwrap();
+        assert!(conflict.version_conflict && conflict.element.deleted_at.is_none());
+        assert_eq!(conflict.element.version, Some(1));
+    }
+
+    #[test]
+    fn batch_restore_skips_elements_with_a_stale_expected_version() {
+        let doc = Doc::new();
+        let current = insert_element(&doc);
+        let stale = insert_element(&doc);
+        let now = Utc::now();
+        apply_deleted_merged(&doc, &[(current, 1), (stale, 1)], Some(now), now).unwrap();
+
+        let (results, _) =
+            apply_deleted_merged(&doc, &[(current, 2), (stale, 1)], None, now).unwrap();
+
+        let restored = results[0].as_ref().unwrap();
+        assert!(!restored.version_conflict && restored.element.deleted_at.is_none());
+        let conflict = results[1].as_ref().unwrap();
+        assert!(conflict.version_conflict && conflict.element.deleted_at.is_some());
+    }
+
+    #[test]
+    fn batch_remove_drops_only_elements_at_the_expected_version() {
+        let doc = Doc::new();
+        let current = insert_element(&doc);
+        let stale = insert_element(&doc);
+        let missing = Uuid::now_v7();
+        let now = Utc::now();
+
+        let (results, update) =
+            apply_removed_merged(&doc, &[(current, 1), (stale, 2), (missing, 1)], now).unwrap();
+
+        assert!(!update.is_empty());
+        let removed = results[0].as_ref().unwrap();
+        assert!(!removed.version_conflict && removed.element.deleted_at == Some(now));
+        assert!(results[1].as_ref().unwrap().version_conflict);
+        assert!(results[2].is_none());
+        assert!(element_crdt::materialize_element(&doc, current).is_none());
+        assert!(element_crdt::materialize_element(&doc, stale).is_some());
+    }
+}
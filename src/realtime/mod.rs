@@ -1,6 +1,12 @@
+pub(crate) mod access_sync;
+pub(crate) mod awareness;
 pub(crate) mod element_crdt;
 pub(crate) mod elements;
+pub(crate) mod outbound;
+pub(crate) mod presence;
 pub(crate) mod projection;
 pub(crate) mod protocol;
+pub(crate) mod render;
+pub(crate) mod resume;
 pub(crate) mod room;
 pub(crate) mod snapshot;
@@ -0,0 +1,332 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+};
+
+use axum::extract::ws::Message;
+use serde::Deserialize;
+use tokio::sync::Notify;
+
+use crate::realtime::protocol;
+
+const DEFAULT_OUTBOUND_QUEUE_CAPACITY: usize = 256;
+
+pub fn outbound_queue_capacity() -> usize {
+    std::env::var("WS_OUTBOUND_QUEUE_CAPACITY")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_OUTBOUND_QUEUE_CAPACITY)
+}
+
+/// How an outbound message is treated when the session queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboundPriority {
+    /// Ephemeral state (cursors, awareness, viewport) that the next update supersedes.
+    Droppable,
+    /// Document or membership traffic; losing it leaves the client out of sync.
+    Essential,
+}
+
+#[derive(Deserialize)]
+struct TextEventType {
+    #[serde(rename = "type")]
+    event_type: String,
+}
+
+pub fn classify(message: &Message) -> OutboundPriority {
+    match message {
+        Message::Binary(bin) if bin.first() == Some(&protocol::OP_AWARENESS) => {
+            OutboundPriority::Droppable
+        }
+        Message::Text(text) => match serde_json::from_str::<TextEventType>(text.as_str()) {
+            Ok(event)
+                if matches!(
+                    event.event_type.as_str(),
+                    "presence:update" | "viewport:follow"
+                ) =>
+            {
+                OutboundPriority::Droppable
+            }
+            _ => OutboundPriority::Essential,
+        },
+        _ => OutboundPriority::Essential,
+    }
+}
+
+/// The session's write task has stopped; nothing more can be queued.
+#[derive(Debug)]
+pub struct OutboundClosed;
+
+/// Per-session backpressure counters shared by every producer of the queue.
+#[derive(Debug, Default)]
+pub struct OutboundStats {
+    dropped: AtomicU64,
+    overloaded: AtomicBool,
+    overload_notify: Notify,
+}
+
+impl OutboundStats {
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn is_overloaded(&self) -> bool {
+        self.overloaded.load(Ordering::Acquire)
+    }
+
+    /// Resolves once an essential message could not be queued.
+    pub async fn wait_overloaded(&self) {
+        loop {
+            let notified = self.overload_notify.notified();
+            if self.is_overloaded() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    fn record_drop(&self) -> u64 {
+        self.dropped.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn mark_overloaded(&self) {
+        if !self.overloaded.swap(true, Ordering::AcqRel) {
+            self.overload_notify.notify_waiters();
+        }
+    }
+}
+
+/// Bounded queue shared by a session's senders and its write task.
+struct OutboundQueue {
+    messages: Mutex<VecDeque<(OutboundPriority, Message)>>,
+    capacity: usize,
+    senders: AtomicUsize,
+    receiver_closed: AtomicBool,
+    notify: Notify,
+}
+
+/// Bounded sender for a single WebSocket session.
+///
+/// Producers never wait on a slow client. When the queue is full a droppable
+/// message is discarded; an essential message evicts the oldest queued
+/// droppable message instead, and only if none is queued is the session
+/// marked overloaded so the write task can disconnect it (the client resyncs
+/// on reconnect).
+pub struct OutboundSender {
+    queue: Arc<OutboundQueue>,
+    stats: Arc<OutboundStats>,
+}
+
+impl OutboundSender {
+    pub fn send(&self, message: Message) -> Result<(), OutboundClosed> {
+        if self.queue.receiver_closed.load(Ordering::Acquire) {
+            return Err(OutboundClosed);
+        }
+        let priority = classify(&message);
+        let mut messages = self
+            .queue
+            .messages
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if messages.len() < self.queue.capacity {
+            messages.push_back((priority, message));
+            drop(messages);
+            self.queue.notify.notify_one();
+            return Ok(());
+        }
+
+        let dropped = self.stats.record_drop();
+        match priority {
+            OutboundPriority::Droppable => {
+                if dropped.is_power_of_two() {
+                    tracing::debug!(dropped, "Outbound queue full; dropped ephemeral message");
+                }
+            }
+            OutboundPriority::Essential => {
+                let evicted = messages
+                    .iter()
+                    .position(|(queued, _)| *queued == OutboundPriority::Droppable);
+                match evicted {
+                    Some(index) => {
+                        messages.remove(index);
+                        messages.push_back((priority, message));
+                        drop(messages);
+                        self.queue.notify.notify_one();
+                        if dropped.is_power_of_two() {
+                            tracing::debug!(
+                                dropped,
+                                "Outbound queue full; evicted queued ephemeral message"
+                            );
+                        }
+                    }
+                    None => {
+                        if !self.stats.is_overloaded() {
+                            tracing::warn!(
+                                dropped,
+                                "Outbound queue full on essential message; disconnecting slow client"
+                            );
+                        }
+                        self.stats.mark_overloaded();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn stats(&self) -> Arc<OutboundStats> {
+        self.stats.clone()
+    }
+}
+
+impl Clone for OutboundSender {
+    fn clone(&self) -> Self {
+        self.queue.senders.fetch_add(1, Ordering::AcqRel);
+        Self {
+            queue: self.queue.clone(),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+impl Drop for OutboundSender {
+    fn drop(&mut self) {
+        if self.queue.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.queue.notify.notify_one();
+        }
+    }
+}
+
+/// Receiving half drained by the session's write task.
+pub struct OutboundReceiver {
+    queue: Arc<OutboundQueue>,
+}
+
+impl OutboundReceiver {
+    /// Waits for the next message; `None` once every sender is dropped and
+    /// the queue is drained. Cancel safe.
+    pub async fn recv(&mut self) -> Option<Message> {
+        loop {
+            {
+                let mut messages = self
+                    .queue
+                    .messages
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                if let Some((_, message)) = messages.pop_front() {
+                    return Some(message);
+                }
+                if self.queue.senders.load(Ordering::Acquire) == 0 {
+                    return None;
+                }
+            }
+            self.queue.notify.notified().await;
+        }
+    }
+}
+
+impl Drop for OutboundReceiver {
+    fn drop(&mut self) {
+        self.queue.receiver_closed.store(true, Ordering::Release);
+    }
+}
+
+pub fn channel(capacity: usize) -> (OutboundSender, OutboundReceiver) {
+    let queue = Arc::new(OutboundQueue {
+        messages: Mutex::new(VecDeque::new()),
+        capacity: capacity.max(1),
+        senders: AtomicUsize::new(1),
+        receiver_closed: AtomicBool::new(false),
+        notify: Notify::new(),
+    });
+    (
+        OutboundSender {
+            queue: queue.clone(),
+            stats: Arc::new(OutboundStats::default()),
+        },
+        OutboundReceiver { queue },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Bytes;
+    use serde_json::json;
+
+    use super::{OutboundPriority, channel, classify};
+    use crate::realtime::protocol;
+
+    fn text(event_type: &str) -> axum::extract::ws::Message {
+        axum::extract::ws::Message::Text(
+            json!({ "type": event_type, "payload": {} })
+                .to_string()
+                .into(),
+        )
+    }
+
+    #[test]
+    fn classifies_ephemeral_messages_as_droppable() {
+        let awareness =
+            axum::extract::ws::Message::Binary(Bytes::from(vec![protocol::OP_AWARENESS, 1]));
+        let update = axum::extract::ws::Message::Binary(Bytes::from(vec![protocol::OP_UPDATE, 1]));
+
+        assert_eq!(classify(&awareness), OutboundPriority::Droppable);
+        assert_eq!(
+            classify(&text("presence:update")),
+            OutboundPriority::Droppable
+        );
+        assert_eq!(classify(&update), OutboundPriority::Essential);
+        assert_eq!(classify(&text("user:joined")), OutboundPriority::Essential);
+    }
+
+    #[test]
+    fn full_queue_drops_ephemeral_and_flags_essential_overflow() {
+        let (tx, _rx) = channel(1);
+        let stats = tx.stats();
+
+        tx.send(text("user:joined")).unwrap();
+        tx.send(text("presence:update")).unwrap();
+        assert_eq!(stats.dropped(), 1);
+        assert!(!stats.is_overloaded());
+
+        tx.send(text("board:renamed")).unwrap();
+        assert_eq!(stats.dropped(), 2);
+        assert!(stats.is_overloaded());
+    }
+
+    #[tokio::test]
+    async fn essential_message_evicts_oldest_queued_ephemeral_message() {
+        let (tx, mut rx) = channel(3);
+        let stats = tx.stats();
+
+        tx.send(text("presence:update")).unwrap();
+        tx.send(text("user:joined")).unwrap();
+        tx.send(text("viewport:follow")).unwrap();
+        tx.send(text("board:renamed")).unwrap();
+
+        assert_eq!(stats.dropped(), 1);
+        assert!(!stats.is_overloaded());
+        let mut received = Vec::new();
+        drop(tx);
+        while let Some(axum::extract::ws::Message::Text(message)) = rx.recv().await {
+            let value: serde_json::Value = serde_json::from_str(message.as_str()).unwrap();
+            received.push(value["type"].as_str().unwrap().to_string());
+        }
+        assert_eq!(
+            received,
+            ["user:joined", "viewport:follow", "board:renamed"]
+        );
+    }
+
+    #[test]
+    fn send_fails_once_receiver_is_dropped() {
+        let (tx, rx) = channel(1);
+        drop(rx);
+
+        assert!(tx.send(text("user:joined")).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use std::{sync::Arc, time::Duration};
+
+use sqlx::PgPool;
+
+use crate::{
+    realtime::room::{Room, Rooms},
+    usecases::presence::PresenceService,
+};
+
+const DEFAULT_HEARTBEAT_FLUSH_INTERVAL_MS: u64 = 5_000;
+const DEFAULT_RECONCILE_INTERVAL_SECS: u64 = 120;
+
+pub fn spawn_presence_maintenance(db: PgPool, redis: Option<redis::Client>, rooms: Rooms) {
+    tokio::spawn(async move {
+        let mut flush_interval = tokio::time::interval(Duration::from_millis(read_env_u64(
+            "PRESENCE_HEARTBEAT_FLUSH_INTERVAL_MS",
+            DEFAULT_HEARTBEAT_FLUSH_INTERVAL_MS,
+        )));
+        let mut reconcile_interval = tokio::time::interval(Duration::from_secs(read_env_u64(
+            "PRESENCE_RECONCILE_INTERVAL_SECS",
+            DEFAULT_RECONCILE_INTERVAL_SECS,
+        )));
+        loop {
+            tokio::select! {
+                _ = flush_interval.tick() => {
+                    flush_heartbeats(&db, redis.as_ref(), &rooms).await;
+                }
+                _ = reconcile_interval.tick() => {
+                    if redis.is_some() {
+                        reconcile_presence(&db, redis.as_ref(), &rooms).await;
+                    }
+                }
+            }
+        }
+    });
+}
+
+async fn flush_heartbeats(db: &PgPool, redis: Option<&redis::Client>, rooms: &Rooms) {
+    let rooms_snapshot: Vec<Arc<Room>> = rooms.iter().map(|entry| entry.value().clone()).collect();
+    let mut flushed = 0u64;
+    for room in rooms_snapshot {
+        let session_ids = room.take_pending_heartbeats().await;
+        if session_ids.is_empty() {
+            continue;
+        }
+        match PresenceService::flush_heartbeats(db, redis, room.board_id, &session_ids).await {
+            Ok(updated) => flushed += updated,
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to flush {} presence heartbeats for board {}: {}",
+                    session_ids.len(),
+                    room.board_id,
+                    error
+                );
+            }
+        }
+    }
+    if flushed > 0 {
+        tracing::debug!(flushed, "Presence heartbeat flush completed");
+    }
+}
+
+async fn reconcile_presence(db: &PgPool, redis: Option<&redis::Client>, rooms: &Rooms) {
+    let rooms_snapshot: Vec<Arc<Room>> = rooms.iter().map(|entry| entry.value().clone()).collect();
+    for room in rooms_snapshot {
+        if let Err(error) = PresenceService::reconcile(db, redis, room.board_id).await {
+            tracing::warn!(
+                "Failed to reconcile presence for board {}: {}",
+                room.board_id,
+                error
+            );
+        }
+    }
+}
+
+fn read_env_u64(key: &str, default: u64) -> u64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(default)
+}
@@ -1,10 +1,12 @@
 use std::{
-    collections::HashMap,
-    sync::{Arc, atomic::Ordering},
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, PoisonError, atomic::Ordering},
     time::{Duration, Instant},
 };
 
 use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde_json::{Map, Value};
 use sqlx::PgPool;
 use tokio::sync::Mutex;
 use uuid::Uuid;
@@ -12,20 +14,137 @@ use yrs::Doc;
 
 use crate::{
     error::AppError,
-    models::elements::ElementType,
-    realtime::{element_crdt, room::Room, room::Rooms},
+    models::{boards::ElementDeletePolicy, elements::ElementType},
+    realtime::{element_crdt, elements as realtime_elements, room::Room, room::Rooms, snapshot},
     repositories::boards as board_repo,
     repositories::elements as element_repo,
+    services::{
+        content_filter::{ContentFilter, SharedContentFilter, moderate_element_properties},
+        embed_policy::embed_url_correction,
+    },
     telemetry::BusinessEvent,
+    usecases::board_watchers::{BoardActivity, BoardWatcherService},
 };
 
+const DEFAULT_COALESCE_MAX_DELAY_MS: u64 = 10_000;
+const REBUILD_PROGRESS_EVERY: usize = 100;
+
+fn coalesce_max_delay() -> Duration {
+    let millis = std::env::var("PROJECTION_COALESCE_MAX_DELAY_MS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_COALESCE_MAX_DELAY_MS);
+    Duration::from_millis(millis)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ElementFingerprint {
+    version: Option<i32>,
+    updated_at: Option<DateTime<Utc>>,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl ElementFingerprint {
+    fn of(element: &element_crdt::ElementMaterialized) -> Self {
+        Self {
+            version: element.version,
+            updated_at: element.updated_at,
+            deleted_at: element.deleted_at,
+        }
+    }
+}
+
+/// Holds back projection writes for elements that are still changing.
+///
+/// An element whose state changed since the previous projection tick (e.g.
+/// mid-drag) is deferred until a tick sees it unchanged, so a burst of
+/// updates lands as one row write. Elements are never deferred longer than
+/// `max_delay`. Only the relational projection is affected; the CRDT update
+/// log is written independently.
+///
+/// Fingerprints are only kept while an element is being tracked: an entry is
+/// dropped once the element is projected in a settled state or disappears
+/// from the document.
+pub struct ProjectionCoalescer {
+    max_delay: Duration,
+    seen: HashMap<Uuid, ElementFingerprint>,
+    deferred_since: HashMap<Uuid, Instant>,
+}
+
+impl Default for ProjectionCoalescer {
+    fn default() -> Self {
+        Self::new(coalesce_max_delay())
+    }
+}
+
+impl ProjectionCoalescer {
+    pub fn new(max_delay: Duration) -> Self {
+        Self {
+            max_delay,
+            seen: HashMap::new(),
+            deferred_since: HashMap::new(),
+        }
+    }
+
+    /// Splits `elements` into those ready to project and a count of deferred ones.
+    fn partition(
+        &mut self,
+        elements: Vec<element_crdt::ElementMaterialized>,
+        now: Instant,
+    ) -> (Vec<element_crdt::ElementMaterialized>, usize) {
+        let present: HashSet<Uuid> = elements.iter().map(|element| element.id).collect();
+        self.seen.retain(|id, _| present.contains(id));
+        self.deferred_since.retain(|id, _| present.contains(id));
+
+        let mut ready = Vec::with_capacity(elements.len());
+        let mut deferred = 0usize;
+        for element in elements {
+            let fingerprint = ElementFingerprint::of(&element);
+            match self.seen.insert(element.id, fingerprint) {
+                Some(previous) if previous == fingerprint => {
+                    // Settled: project it and stop tracking.
+                    self.seen.remove(&element.id);
+                    self.deferred_since.remove(&element.id);
+                }
+                Some(_) if !self.max_delay.is_zero() => {
+                    let since = *self.deferred_since.entry(element.id).or_insert(now);
+                    if now.duration_since(since) < self.max_delay {
+                        deferred += 1;
+                        continue;
+                    }
+                    self.deferred_since.remove(&element.id);
+                }
+                _ => {}
+            }
+            ready.push(element);
+        }
+        (ready, deferred)
+    }
+}
+
+/// `Rebuild` rewrites every element row, even ones whose version already
+/// matches, and skips watcher activity notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProjectionMode {
+    Incremental,
+    Rebuild,
+}
+
+/// Totals reported by [`rebuild_projections`].
+#[derive(Debug, Default)]
+pub struct ProjectionRebuildSummary {
+    pub boards: usize,
+    pub failed: usize,
+    pub elements: usize,
+}
+
 struct ProjectionFallback {
     created_by: Uuid,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
 
-pub fn spawn_projection(db: PgPool, rooms: Rooms) {
+pub fn spawn_projection(db: PgPool, rooms: Rooms, content_filter: SharedContentFilter) {
     tokio::spawn(async move {
         const PROJECTION_INTERVAL_SECS: u64 = 2;
         let mut interval = tokio::time::interval(Duration::from_secs(PROJECTION_INTERVAL_SECS));
@@ -37,7 +156,7 @@ pub fn spawn_projection(db: PgPool, rooms: Rooms) {
             let rooms_snapshot: Vec<Arc<Room>> =
                 rooms.iter().map(|entry| entry.value().clone()).collect();
             for room in rooms_snapshot {
-                match project_room(&db, &room).await {
+                match project_room(&db, &room, content_filter.as_ref()).await {
                     Ok(true) => projected += 1,
                     Ok(false) => skipped += 1,
                     Err(error) => {
@@ -65,15 +184,109 @@ pub async fn project_doc(
     db: &PgPool,
     board_id: Uuid,
     doc: Arc<Mutex<Doc>>,
+    actor_id: Option<Uuid>,
 ) -> Result<(), AppError> {
     let elements = {
         let doc_guard = doc.lock().await;
         element_crdt::materialize_elements(&doc_guard)
     };
-    project_elements(db, board_id, elements).await
+    project_elements(
+        db,
+        board_id,
+        elements,
+        ProjectionMode::Incremental,
+        &ProjectionActors::single(actor_id),
+    )
+    .await
 }
 
-async fn project_room(db: &PgPool, room: &Arc<Room>) -> Result<bool, AppError> {
+/// Re-derives `board.element` rows for each board from its CRDT state,
+/// `concurrency` boards at a time, logging progress as it goes.
+pub async fn rebuild_projections(
+    db: &PgPool,
+    rooms: &Rooms,
+    board_ids: Vec<Uuid>,
+    concurrency: usize,
+) -> ProjectionRebuildSummary {
+    let started = Instant::now();
+    let total = board_ids.len();
+    tracing::info!(total, concurrency, "Projection rebuild started");
+    let mut summary = ProjectionRebuildSummary::default();
+    let mut results = futures::stream::iter(board_ids)
+        .map(|board_id| async move { (board_id, rebuild_board(db, rooms, board_id).await) })
+        .buffer_unordered(concurrency.max(1));
+    while let Some((board_id, result)) = results.next().await {
+        match result {
+            Ok(elements) => {
+                summary.boards += 1;
+                summary.elements += elements;
+            }
+            Err(error) => {
+                summary.failed += 1;
+                tracing::error!(
+                    "Failed to rebuild projection for board {}: {}",
+                    board_id,
+                    error
+                );
+            }
+        }
+        let done = summary.boards + summary.failed;
+        if done % REBUILD_PROGRESS_EVERY == 0 && done < total {
+            tracing::info!(
+                done,
+                total,
+                failed = summary.failed,
+                "Projection rebuild progress"
+            );
+        }
+    }
+    tracing::info!(
+        boards = summary.boards,
+        failed = summary.failed,
+        elements = summary.elements,
+        duration_ms = started.elapsed().as_millis(),
+        "Projection rebuild completed"
+    );
+    summary
+}
+
+/// Rewrites one board's element rows, reading the live room doc when the
+/// board is open and the stored CRDT state otherwise.
+async fn rebuild_board(db: &PgPool, rooms: &Rooms, board_id: Uuid) -> Result<usize, AppError> {
+    let live_room = rooms.get(&board_id).map(|entry| entry.clone());
+    let elements = match live_room {
+        Some(room) => {
+            let doc_guard = room.doc.lock().await;
+            element_crdt::materialize_elements(&doc_guard)
+        }
+        None => {
+            let doc = Arc::new(Mutex::new(Doc::new()));
+            snapshot::load_board_state(db, doc.clone(), board_id)
+                .await
+                .map_err(|error| {
+                    AppError::Internal(format!("Failed to load board state: {}", error))
+                })?;
+            let doc_guard = doc.lock().await;
+            element_crdt::materialize_elements(&doc_guard)
+        }
+    };
+    let count = elements.len();
+    project_elements(
+        db,
+        board_id,
+        elements,
+        ProjectionMode::Rebuild,
+        &ProjectionActors::single(None),
+    )
+    .await?;
+    Ok(count)
+}
+
+async fn project_room(
+    db: &PgPool,
+    room: &Arc<Room>,
+    content_filter: &dyn ContentFilter,
+) -> Result<bool, AppError> {
     let projection_seq = room.projection_seq.load(Ordering::Acquire);
     let projected_seq = room.projected_seq.load(Ordering::Relaxed);
     if projection_seq == projected_seq {
@@ -83,15 +296,186 @@ async fn project_room(db: &PgPool, room: &Arc<Room>) -> Result<bool, AppError> {
         let doc_guard = room.doc.lock().await;
         element_crdt::materialize_elements(&doc_guard)
     };
-    project_elements(db, room.board_id, elements).await?;
+    let (elements, deferred) = room
+        .projection_coalescer
+        .lock()
+        .await
+        .partition(elements, Instant::now());
+    let elements = moderate_elements(room, content_filter, elements).await;
+    let elements = remove_hard_deleted(db, room, elements).await?;
+    let actors = take_element_editors(room, &elements);
+    project_elements(
+        db,
+        room.board_id,
+        elements,
+        ProjectionMode::Incremental,
+        &actors,
+    )
+    .await?;
+    if deferred > 0 {
+        // Leave the room dirty so the next tick revisits deferred elements.
+        tracing::debug!(
+            board_id = %room.board_id,
+            deferred,
+            "Deferred projection of elements still being changed"
+        );
+        return Ok(true);
+    }
     room.projected_seq.store(projection_seq, Ordering::Release);
     Ok(true)
 }
 
+/// Credits each element about to be projected to its last editor, clearing
+/// those entries so deferred elements keep theirs for a later tick.
+fn take_element_editors(
+    room: &Room,
+    elements: &[element_crdt::ElementMaterialized],
+) -> ProjectionActors {
+    let mut editors = room
+        .element_editors
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let per_element = elements
+        .iter()
+        .filter_map(|element| {
+            let editor = editors.remove(&element.id).flatten()?;
+            Some((element.id, editor))
+        })
+        .collect();
+    ProjectionActors {
+        default: None,
+        per_element,
+    }
+}
+
+/// Applies the content filter and embed allowlist to elements that reached
+/// the document through realtime updates, correcting the document and
+/// broadcasting the fix.
+async fn moderate_elements(
+    room: &Arc<Room>,
+    content_filter: &dyn ContentFilter,
+    elements: Vec<element_crdt::ElementMaterialized>,
+) -> Vec<element_crdt::ElementMaterialized> {
+    let mut updates = Vec::new();
+    let elements = {
+        let doc_guard = room.doc.lock().await;
+        elements
+            .into_iter()
+            .map(|element| {
+                if element.deleted_at.is_some() {
+                    return element;
+                }
+                let Some(patch) = moderation_patch(content_filter, &element) else {
+                    return element;
+                };
+                match element_crdt::correct_properties(&doc_guard, element.id, &patch) {
+                    Ok(Some(applied)) => {
+                        updates.push(applied.update);
+                        applied.element
+                    }
+                    Ok(None) => element,
+                    Err(error) => {
+                        tracing::warn!(
+                            board_id = %room.board_id,
+                            element_id = %element.id,
+                            "Failed to apply content filter correction: {}",
+                            error
+                        );
+                        element
+                    }
+                }
+            })
+            .collect()
+    };
+    for update in updates {
+        realtime_elements::broadcast_update(room, update).await;
+    }
+    elements
+}
+
+/// On boards with the hard element delete policy, removes elements that
+/// realtime updates marked as deleted from the document and deletes their
+/// rows, returning the elements left to project.
+async fn remove_hard_deleted(
+    db: &PgPool,
+    room: &Arc<Room>,
+    elements: Vec<element_crdt::ElementMaterialized>,
+) -> Result<Vec<element_crdt::ElementMaterialized>, AppError> {
+    if elements.iter().all(|element| element.deleted_at.is_none()) {
+        return Ok(elements);
+    }
+    let board = board_repo::find_board_by_id_including_deleted(db, room.board_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Board not found".to_string()))?;
+    if board.element_delete_policy != ElementDeletePolicy::Hard {
+        return Ok(elements);
+    }
+
+    let (deleted, kept): (Vec<_>, Vec<_>) = elements
+        .into_iter()
+        .partition(|element| element.deleted_at.is_some());
+    let targets: Vec<(Uuid, i32)> = deleted
+        .iter()
+        .filter_map(|element| Some((element.id, element.version?)))
+        .collect();
+    let results = realtime_elements::remove_from_room(db, room, &targets, Utc::now()).await?;
+    let removed_ids: Vec<Uuid> = results
+        .iter()
+        .flatten()
+        .filter(|result| !result.version_conflict)
+        .map(|result| result.element.id)
+        .collect();
+    if !removed_ids.is_empty() {
+        let mut tx = db.begin().await?;
+        element_repo::hard_delete_elements(&mut tx, room.board_id, &removed_ids).await?;
+        tx.commit().await?;
+    }
+    Ok(kept)
+}
+
+/// Merged property corrections an element needs, if any.
+fn moderation_patch(
+    content_filter: &dyn ContentFilter,
+    element: &element_crdt::ElementMaterialized,
+) -> Option<Value> {
+    let patches = [
+        moderate_element_properties(content_filter, &element.properties),
+        embed_url_correction(element.element_type, &element.properties),
+    ];
+    let mut merged = Map::new();
+    for patch in patches.into_iter().flatten() {
+        if let Value::Object(fields) = patch {
+            merged.extend(fields);
+        }
+    }
+    (!merged.is_empty()).then_some(Value::Object(merged))
+}
+
+/// Users credited in `board.element_history` for projected writes.
+struct ProjectionActors {
+    default: Option<Uuid>,
+    per_element: HashMap<Uuid, Uuid>,
+}
+
+impl ProjectionActors {
+    fn single(actor_id: Option<Uuid>) -> Self {
+        Self {
+            default: actor_id,
+            per_element: HashMap::new(),
+        }
+    }
+
+    fn actor_for(&self, element_id: Uuid) -> Option<Uuid> {
+        self.per_element.get(&element_id).copied().or(self.default)
+    }
+}
+
 async fn project_elements(
     db: &PgPool,
     board_id: Uuid,
     elements: Vec<element_crdt::ElementMaterialized>,
+    mode: ProjectionMode,
+    actors: &ProjectionActors,
 ) -> Result<(), AppError> {
     let mut elements = elements;
     elements.sort_by_key(|element| element.id.as_u128());
@@ -101,7 +485,7 @@ async fn project_elements(
     let mut attempt = 0;
     loop {
         attempt += 1;
-        match project_elements_once(db, board_id, &elements, element_count).await {
+        match project_elements_once(db, board_id, &elements, element_count, mode, actors).await {
             Ok(()) => return Ok(()),
             Err(error) if is_deadlock_error(&error) && attempt < MAX_RETRIES => {
                 let backoff = Duration::from_millis(50 * attempt as u64);
@@ -122,6 +506,8 @@ async fn project_elements_once(
     board_id: Uuid,
     elements: &[element_crdt::ElementMaterialized],
     element_count: usize,
+    mode: ProjectionMode,
+    actors: &ProjectionActors,
 ) -> Result<(), AppError> {
     let board = board_repo::find_board_by_id_including_deleted(db, board_id)
         .await?
@@ -144,20 +530,25 @@ async fn project_elements_once(
         defaults.into_iter().map(|row| (row.id, row)).collect();
     let mut upserts = Vec::new();
     let mut skipped = 0usize;
+    let mut added_by = Vec::new();
+    let incremental = mode == ProjectionMode::Incremental;
     for element in elements {
         let defaults = defaults_map.get(&element.id);
-        if let Some(defaults) = defaults {
-            if element.version == Some(defaults.version)
-                && element.updated_at == Some(defaults.updated_at)
-                && element.deleted_at == defaults.deleted_at
-            {
-                skipped += 1;
-                continue;
-            }
+        if incremental && defaults.is_none() && element.deleted_at.is_none() {
+            added_by.push(element.created_by);
+        }
+        if incremental
+            && let Some(defaults) = defaults
+            && element.version == Some(defaults.version)
+            && element.updated_at == Some(defaults.updated_at)
+            && element.deleted_at == defaults.deleted_at
+        {
+            skipped += 1;
+            continue;
         }
         match to_projected_params(board_id, element.clone(), defaults, &fallback) {
             Ok(params) => {
-                if should_write_projection(defaults, &params) {
+                if !incremental || should_write_projection(defaults, &params) {
                     upserts.push(params);
                 }
             }
@@ -166,7 +557,19 @@ async fn project_elements_once(
             }
         }
     }
-    element_repo::upsert_projected_elements_batch(&mut tx, &upserts).await?;
+    // Writes each actor's rows separately so `board.element_history` credits
+    // the right user.
+    let mut upserts_by_actor: BTreeMap<Option<Uuid>, Vec<_>> = BTreeMap::new();
+    for params in upserts {
+        upserts_by_actor
+            .entry(actors.actor_for(params.id))
+            .or_default()
+            .push(params);
+    }
+    for (actor_id, upserts) in upserts_by_actor {
+        element_repo::set_actor_id(&mut tx, actor_id).await?;
+        element_repo::upsert_projected_elements_batch(&mut tx, &upserts).await?;
+    }
     tx.commit().await?;
     if skipped > 0 {
         tracing::debug!(
@@ -180,9 +583,29 @@ async fn project_elements_once(
         elements_synced: element_count,
     }
     .log();
+    if !added_by.is_empty() {
+        BoardWatcherService::notify_activity(
+            db,
+            board_id,
+            single_actor(&added_by),
+            BoardActivity::ElementsAdded {
+                count: added_by.len(),
+            },
+        )
+        .await;
+    }
     Ok(())
 }
 
+/// The creator shared by every added element, if there is exactly one.
+fn single_actor(added_by: &[Option<Uuid>]) -> Option<Uuid> {
+    let first = added_by.first().copied().flatten()?;
+    added_by
+        .iter()
+        .all(|actor| *actor == Some(first))
+        .then_some(first)
+}
+
 fn to_projected_params(
     board_id: Uuid,
     element: element_crdt::ElementMaterialized,
@@ -316,3 +739,116 @@ fn is_deadlock_error(error: &AppError) -> bool {
     };
     matches!(db_error.code().as_deref(), Some("40P01"))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::{ProjectionCoalescer, single_actor};
+    use crate::{models::elements::ElementType, realtime::element_crdt::ElementMaterialized};
+
+    fn element(id: Uuid, version: i32) -> ElementMaterialized {
+        ElementMaterialized {
+            id,
+            board_id: Uuid::nil(),
+            layer_id: None,
+            parent_id: None,
+            created_by: None,
+            element_type: ElementType::Shape,
+            position_x: version as f64,
+            position_y: 0.0,
+            width: 10.0,
+            height: 10.0,
+            rotation: 0.0,
+            z_index: 0,
+            style: json!({}),
+            properties: json!({}),
+            metadata: json!({}),
+            created_at: None,
+            updated_at: Some(Utc::now()),
+            deleted_at: None,
+            version: Some(version),
+        }
+    }
+
+    #[test]
+    fn defers_changing_elements_until_they_settle() {
+        let mut coalescer = ProjectionCoalescer::new(Duration::from_secs(10));
+        let id = Uuid::now_v7();
+        let start = Instant::now();
+
+        let first = element(id, 1);
+        let (ready, deferred) = coalescer.partition(vec![first.clone()], start);
+        assert_eq!((ready.len(), deferred), (1, 0));
+
+        let moved = element(id, 2);
+        let (ready, deferred) =
+            coalescer.partition(vec![moved.clone()], start + Duration::from_secs(2));
+        assert_eq!((ready.len(), deferred), (0, 1));
+
+        let (ready, deferred) = coalescer.partition(vec![moved], start + Duration::from_secs(4));
+        assert_eq!((ready.len(), deferred), (1, 0));
+        assert_eq!(ready[0].version, Some(2));
+    }
+
+    #[test]
+    fn writes_continuously_changing_elements_after_max_delay() {
+        let mut coalescer = ProjectionCoalescer::new(Duration::from_secs(5));
+        let id = Uuid::now_v7();
+        let start = Instant::now();
+        coalescer.partition(vec![element(id, 1)], start);
+
+        let (_, deferred) =
+            coalescer.partition(vec![element(id, 2)], start + Duration::from_secs(2));
+        assert_eq!(deferred, 1);
+        let (ready, deferred) =
+            coalescer.partition(vec![element(id, 3)], start + Duration::from_secs(8));
+        assert_eq!((ready.len(), deferred), (1, 0));
+    }
+
+    #[test]
+    fn zero_max_delay_disables_coalescing() {
+        let mut coalescer = ProjectionCoalescer::new(Duration::ZERO);
+        let id = Uuid::now_v7();
+        let start = Instant::now();
+        coalescer.partition(vec![element(id, 1)], start);
+
+        let (ready, deferred) = coalescer.partition(vec![element(id, 2)], start);
+        assert_eq!((ready.len(), deferred), (1, 0));
+    }
+
+    #[test]
+    fn forgets_settled_and_removed_elements() {
+        let mut coalescer = ProjectionCoalescer::new(Duration::from_secs(10));
+        let settled = Uuid::now_v7();
+        let removed = Uuid::now_v7();
+        let start = Instant::now();
+        coalescer.partition(vec![element(settled, 1), element(removed, 1)], start);
+        let moved = element(settled, 2);
+        coalescer.partition(
+            vec![moved.clone(), element(removed, 2)],
+            start + Duration::from_secs(1),
+        );
+
+        let (ready, deferred) = coalescer.partition(vec![moved], start + Duration::from_secs(2));
+
+        assert_eq!((ready.len(), deferred), (1, 0));
+        assert!(coalescer.seen.is_empty());
+        assert!(coalescer.deferred_since.is_empty());
+    }
+
+    #[test]
+    fn single_actor_requires_one_known_creator() {
+        let alice = Uuid::now_v7();
+        let bob = Uuid::now_v7();
+
+        assert_eq!(single_actor(&[Some(alice), Some(alice)]), Some(alice));
+        assert_eq!(single_actor(&[Some(alice), Some(bob)]), None);
+        assert_eq!(single_actor(&[Some(alice), None]), None);
+        assert_eq!(single_actor(&[None]), None);
+    }
+}
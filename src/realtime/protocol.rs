@@ -15,3 +15,145 @@ pub struct BoardRoleUpdate {
     pub role: Option<BoardRole>,
     pub permissions: Option<BoardPermissions>,
 }
+
+/// Version of the binary opcode set and text event schema.
+pub const PROTOCOL_VERSION: u32 = 1;
+/// `Sec-WebSocket-Protocol` token for [`PROTOCOL_VERSION`].
+pub const SUBPROTOCOL_V1: &str = "board.v1";
+/// Subprotocols the server accepts, newest first.
+pub const SUPPORTED_SUBPROTOCOLS: [&str; 1] = [SUBPROTOCOL_V1];
+// Close codes sent when the server ends a board session, so clients can tell
+// whether to retry, re-authenticate, or give up.
+/// Authentication failed or expired; re-authenticate before reconnecting.
+pub const CLOSE_AUTH_FAILED: u16 = 4001;
+/// Board access was denied or revoked; do not retry.
+pub const CLOSE_PERMISSION_REVOKED: u16 = 4003;
+/// The client sent messages faster than allowed; back off before retrying.
+pub const CLOSE_RATE_LIMITED: u16 = 4008;
+/// The session waited too long in the board queue; retry later.
+pub const CLOSE_QUEUE_TIMEOUT: u16 = 4009;
+/// The client only offered unsupported protocol versions.
+pub const CLOSE_UNSUPPORTED_PROTOCOL: u16 = 4010;
+/// The server is overloaded (or the client fell behind); reconnect to resync.
+pub const CLOSE_SERVER_OVERLOAD: u16 = 1013;
+
+/// Readable name for a close code, used in logs.
+pub fn close_code_name(code: u16) -> &'static str {
+    match code {
+        1000 => "normal",
+        1001 => "going_away",
+        CLOSE_AUTH_FAILED => "auth_failed",
+        CLOSE_PERMISSION_REVOKED => "permission_revoked",
+        CLOSE_RATE_LIMITED => "rate_limited",
+        CLOSE_QUEUE_TIMEOUT => "queue_timeout",
+        CLOSE_UNSUPPORTED_PROTOCOL => "unsupported_protocol",
+        CLOSE_SERVER_OVERLOAD => "server_overload",
+        _ => "other",
+    }
+}
+
+/// Whether a role update revokes the affected user's access to the board.
+pub fn revokes_access(update: &BoardRoleUpdate) -> bool {
+    update
+        .permissions
+        .is_none_or(|permissions| !permissions.can_view)
+}
+
+/// Result of matching the client's offered subprotocols.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SubprotocolNegotiation {
+    /// A supported `board.*` protocol was offered.
+    Selected(&'static str),
+    /// No `board.*` protocol was offered; treated as version 1 for clients
+    /// that predate negotiation.
+    Legacy,
+    /// Only unknown `board.*` versions were offered.
+    Unsupported(Vec<String>),
+}
+
+impl SubprotocolNegotiation {
+    pub fn protocol_version(&self) -> Option<u32> {
+        match self {
+            Self::Selected(_) | Self::Legacy => Some(PROTOCOL_VERSION),
+            Self::Unsupported(_) => None,
+        }
+    }
+}
+
+/// Picks the first supported protocol from a `Sec-WebSocket-Protocol` header.
+pub fn negotiate_subprotocol(header: Option<&str>) -> SubprotocolNegotiation {
+    let offered: Vec<&str> = header
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|value| value.starts_with("board."))
+        .collect();
+    if offered.is_empty() {
+        return SubprotocolNegotiation::Legacy;
+    }
+    for candidate in &offered {
+        if let Some(supported) = SUPPORTED_SUBPROTOCOLS
+            .iter()
+            .find(|supported| supported.eq_ignore_ascii_case(candidate))
+        {
+            return SubprotocolNegotiation::Selected(supported);
+        }
+    }
+    SubprotocolNegotiation::Unsupported(offered.into_iter().map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{
+        BoardRoleUpdate, CLOSE_QUEUE_TIMEOUT, SUBPROTOCOL_V1, SubprotocolNegotiation,
+        close_code_name, negotiate_subprotocol, revokes_access,
+    };
+    use crate::models::boards::{BoardPermissions, BoardRole};
+
+    #[test]
+    fn negotiates_supported_legacy_and_unknown_versions() {
+        assert_eq!(
+            negotiate_subprotocol(Some("board.v2, board.v1")),
+            SubprotocolNegotiation::Selected(SUBPROTOCOL_V1)
+        );
+        assert_eq!(negotiate_subprotocol(None), SubprotocolNegotiation::Legacy);
+        assert_eq!(
+            negotiate_subprotocol(Some("graphql-ws")),
+            SubprotocolNegotiation::Legacy
+        );
+        assert_eq!(
+            negotiate_subprotocol(Some("board.v9")),
+            SubprotocolNegotiation::Unsupported(vec!["board.v9".to_string()])
+        );
+    }
+
+    #[test]
+    fn role_update_revokes_access_without_view_permission() {
+        let user_id = Uuid::now_v7();
+        let removed = BoardRoleUpdate {
+            user_id,
+            role: None,
+            permissions: None,
+        };
+        let viewer = BoardRoleUpdate {
+            user_id,
+            role: Some(BoardRole::Viewer),
+            permissions: Some(BoardRole::Viewer.permissions()),
+        };
+        let hidden = BoardRoleUpdate {
+            user_id,
+            role: Some(BoardRole::Viewer),
+            permissions: Some(BoardPermissions {
+                can_view: false,
+                ..BoardRole::Viewer.permissions()
+            }),
+        };
+
+        assert!(revokes_access(&removed));
+        assert!(!revokes_access(&viewer));
+        assert!(revokes_access(&hidden));
+        assert_eq!(close_code_name(CLOSE_QUEUE_TIMEOUT), "queue_timeout");
+    }
+}
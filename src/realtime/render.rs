@@ -0,0 +1,374 @@
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use crate::{models::elements::ElementType, realtime::element_crdt::ElementMaterialized};
+
+const CANVAS_PADDING: f64 = 20.0;
+const EMPTY_CANVAS_SIZE: f64 = 100.0;
+const DEFAULT_FONT_SIZE: f64 = 16.0;
+const DEFAULT_TEXT_COLOR: &str = "#1F2937";
+const DEFAULT_STICKY_FILL: &str = "#FEF08A";
+const STICKY_TEXT_INSET: f64 = 12.0;
+
+/// Renders live elements as a standalone SVG document.
+///
+/// Shapes, text, sticky notes and frames are drawn; other element types are
+/// shown as dashed placeholders of the same size. Deleted elements are skipped
+/// and paint order follows `z_index`.
+pub fn render_svg(elements: &[ElementMaterialized]) -> String {
+    let mut live: Vec<&ElementMaterialized> = elements
+        .iter()
+        .filter(|element| element.deleted_at.is_none() && has_finite_geometry(element))
+        .collect();
+    live.sort_by_key(|element| element.z_index);
+
+    let (min_x, min_y, width, height) = canvas_bounds(&live);
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+        fmt_num(min_x),
+        fmt_num(min_y),
+        fmt_num(width),
+        fmt_num(height),
+        fmt_num(width),
+        fmt_num(height),
+    );
+    for element in live {
+        render_element(&mut svg, element);
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn has_finite_geometry(element: &ElementMaterialized) -> bool {
+    [
+        element.position_x,
+        element.position_y,
+        element.width,
+        element.height,
+        element.rotation,
+    ]
+    .iter()
+    .all(|value| value.is_finite())
+}
+
+fn canvas_bounds(elements: &[&ElementMaterialized]) -> (f64, f64, f64, f64) {
+    if elements.is_empty() {
+        return (0.0, 0.0, EMPTY_CANVAS_SIZE, EMPTY_CANVAS_SIZE);
+    }
+    let mut min_x = f64::MAX;
+    let mut min_y = f64::MAX;
+    let mut max_x = f64::MIN;
+    let mut max_y = f64::MIN;
+    for element in elements {
+        let (x, y, w, h) = normalized_rect(element);
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x + w);
+        max_y = max_y.max(y + h);
+    }
+    (
+        min_x - CANVAS_PADDING,
+        min_y - CANVAS_PADDING,
+        (max_x - min_x) + CANVAS_PADDING * 2.0,
+        (max_y - min_y) + CANVAS_PADDING * 2.0,
+    )
+}
+
+fn normalized_rect(element: &ElementMaterialized) -> (f64, f64, f64, f64) {
+    let (x, w) = if element.width < 0.0 {
+        (element.position_x + element.width, -element.width)
+    } else {
+        (element.position_x, element.width)
+    };
+    let (y, h) = if element.height < 0.0 {
+        (element.position_y + element.height, -element.height)
+    } else {
+        (element.position_y, element.height)
+    };
+    (x, y, w, h)
+}
+
+fn render_element(svg: &mut String, element: &ElementMaterialized) {
+    let (x, y, w, h) = normalized_rect(element);
+    let transform = if element.rotation != 0.0 {
+        format!(
+            r#" transform="rotate({} {} {})""#,
+            fmt_num(element.rotation),
+            fmt_num(x + w / 2.0),
+            fmt_num(y + h / 2.0)
+        )
+    } else {
+        String::new()
+    };
+    let opacity = style_number(&element.style, "opacity")
+        .filter(|value| (0.0..1.0).contains(value))
+        .map(|value| format!(r#" opacity="{}""#, fmt_num(value)))
+        .unwrap_or_default();
+    let _ = write!(
+        svg,
+        r#"<g data-element-id="{}"{}{}>"#,
+        element.id, transform, opacity
+    );
+
+    match element.element_type {
+        ElementType::Shape => render_shape(svg, element, x, y, w, h),
+        ElementType::Text => {
+            let color = style_str(&element.style, "textColor").unwrap_or(DEFAULT_TEXT_COLOR);
+            render_text(svg, element, x, y, color);
+        }
+        ElementType::StickyNote => {
+            let fill = style_str(&element.style, "fill").unwrap_or(DEFAULT_STICKY_FILL);
+            let _ = write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                fmt_num(x),
+                fmt_num(y),
+                fmt_num(w),
+                fmt_num(h),
+                escape_xml(fill)
+            );
+            let color = style_str(&element.style, "textColor").unwrap_or(DEFAULT_TEXT_COLOR);
+            render_text(
+                svg,
+                element,
+                x + STICKY_TEXT_INSET,
+                y + STICKY_TEXT_INSET,
+                color,
+            );
+        }
+        ElementType::Frame => {
+            let stroke = style_str(&element.style, "stroke").unwrap_or("#9CA3AF");
+            let _ = write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}"/>"#,
+                fmt_num(x),
+                fmt_num(y),
+                fmt_num(w),
+                fmt_num(h),
+                escape_xml(stroke)
+            );
+            if let Some(title) = element.properties.get("title").and_then(Value::as_str) {
+                let _ = write!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="12" fill="{}">{}</text>"#,
+                    fmt_num(x),
+                    fmt_num(y - 4.0),
+                    DEFAULT_TEXT_COLOR,
+                    escape_xml(title)
+                );
+            }
+        }
+        _ => {
+            let _ = write!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#9CA3AF" stroke-dasharray="4 4"/>"##,
+                fmt_num(x),
+                fmt_num(y),
+                fmt_num(w),
+                fmt_num(h)
+            );
+        }
+    }
+    svg.push_str("</g>");
+}
+
+fn render_shape(svg: &mut String, element: &ElementMaterialized, x: f64, y: f64, w: f64, h: f64) {
+    let paint = format!(
+        r#"fill="{}" stroke="{}" stroke-width="{}""#,
+        escape_xml(style_str(&element.style, "fill").unwrap_or("#FFFFFF")),
+        escape_xml(style_str(&element.style, "stroke").unwrap_or("#000000")),
+        fmt_num(style_number(&element.style, "strokeWidth").unwrap_or(1.0))
+    );
+    let shape_type = element
+        .properties
+        .get("shapeType")
+        .and_then(Value::as_str)
+        .unwrap_or("rectangle");
+    let _ = match shape_type {
+        "circle" => write!(
+            svg,
+            r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}" {}/>"#,
+            fmt_num(x + w / 2.0),
+            fmt_num(y + h / 2.0),
+            fmt_num(w / 2.0),
+            fmt_num(h / 2.0),
+            paint
+        ),
+        "triangle" => write!(
+            svg,
+            r#"<polygon points="{},{} {},{} {},{}" {}/>"#,
+            fmt_num(x + w / 2.0),
+            fmt_num(y),
+            fmt_num(x + w),
+            fmt_num(y + h),
+            fmt_num(x),
+            fmt_num(y + h),
+            paint
+        ),
+        _ => {
+            let radius = style_number(&element.style, "cornerRadius")
+                .filter(|value| *value > 0.0)
+                .map(|value| format!(r#" rx="{}""#, fmt_num(value)))
+                .unwrap_or_default();
+            write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}"{} {}/>"#,
+                fmt_num(x),
+                fmt_num(y),
+                fmt_num(w),
+                fmt_num(h),
+                radius,
+                paint
+            )
+        }
+    };
+}
+
+fn render_text(svg: &mut String, element: &ElementMaterialized, x: f64, y: f64, color: &str) {
+    let content = element
+        .properties
+        .get("content")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if content.is_empty() {
+        return;
+    }
+    let font_size = style_number(&element.style, "fontSize")
+        .filter(|value| *value > 0.0)
+        .unwrap_or(DEFAULT_FONT_SIZE);
+    let _ = write!(
+        svg,
+        r#"<text x="{}" y="{}" font-size="{}" fill="{}">"#,
+        fmt_num(x),
+        fmt_num(y),
+        fmt_num(font_size),
+        escape_xml(color)
+    );
+    for line in content.lines() {
+        let _ = write!(
+            svg,
+            r#"<tspan x="{}" dy="{}">{}</tspan>"#,
+            fmt_num(x),
+            fmt_num(font_size * 1.2),
+            escape_xml(line)
+        );
+    }
+    svg.push_str("</text>");
+}
+
+fn style_str<'a>(style: &'a Value, key: &str) -> Option<&'a str> {
+    style
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn style_number(style: &Value, key: &str) -> Option<f64> {
+    style
+        .get(key)
+        .and_then(Value::as_f64)
+        .filter(|value| value.is_finite())
+}
+
+fn fmt_num(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string()
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::render_svg;
+    use crate::{models::elements::ElementType, realtime::element_crdt::ElementMaterialized};
+
+    fn element(element_type: ElementType, properties: serde_json::Value) -> ElementMaterialized {
+        ElementMaterialized {
+            id: Uuid::now_v7(),
+            board_id: Uuid::nil(),
+            layer_id: None,
+            parent_id: None,
+            created_by: None,
+            element_type,
+            position_x: 10.0,
+            position_y: 20.0,
+            width: 100.0,
+            height: 50.0,
+            rotation: 0.0,
+            z_index: 0,
+            style: json!({ "fill": "#ff0000", "stroke": "#000000", "strokeWidth": 2 }),
+            properties,
+            metadata: json!({}),
+            created_at: Some(Utc::now()),
+            updated_at: None,
+            deleted_at: None,
+            version: Some(1),
+        }
+    }
+
+    #[test]
+    fn renders_shapes_and_fits_view_box_to_elements() {
+        let svg = render_svg(&[element(
+            ElementType::Shape,
+            json!({ "shapeType": "circle" }),
+        )]);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"viewBox="-10 0 140 90""#));
+        assert!(svg.contains(r##"<ellipse cx="60" cy="45" rx="50" ry="25" fill="#ff0000""##));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn escapes_text_and_skips_deleted_elements() {
+        let mut deleted = element(ElementType::Shape, json!({}));
+        deleted.deleted_at = Some(Utc::now());
+        let note = element(
+            ElementType::StickyNote,
+            json!({ "content": "<b>R&D</b>\nnext" }),
+        );
+
+        let svg = render_svg(&[deleted.clone(), note]);
+
+        assert!(!svg.contains(&deleted.id.to_string()));
+        assert!(svg.contains("&lt;b&gt;R&amp;D&lt;/b&gt;"));
+        assert_eq!(svg.matches("<tspan").count(), 2);
+    }
+
+    #[test]
+    fn orders_by_z_index() {
+        let mut top = element(ElementType::Shape, json!({}));
+        top.z_index = 5;
+        let bottom = element(ElementType::Text, json!({ "content": "under" }));
+
+        let svg = render_svg(&[top.clone(), bottom.clone()]);
+
+        let top_at = svg.find(&top.id.to_string()).unwrap();
+        let bottom_at = svg.find(&bottom.id.to_string()).unwrap();
+        assert!(bottom_at < top_at);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use serde::Serialize;
+use uuid::Uuid;
+use yrs::StateVector;
+
+const RESUME_TOKEN_TTL: Duration = Duration::from_secs(120);
+
+static FULL_SYNCS: AtomicU64 = AtomicU64::new(0);
+static FULL_SYNC_BYTES: AtomicU64 = AtomicU64::new(0);
+static RESUMED_SYNCS: AtomicU64 = AtomicU64::new(0);
+static RESUMED_SYNC_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Latest document state a session's client is known to hold.
+struct ResumeCheckpoint {
+    user_id: Uuid,
+    state_vector: StateVector,
+    expires_at: Instant,
+}
+
+/// One-time reconnect tokens issued per room session.
+///
+/// A token remembers the state vector the server last knew the client had
+/// (after the initial sync or the client's latest sync step 1), so a client
+/// reconnecting with it only receives the updates it missed.
+#[derive(Default)]
+pub struct ResumeTokens {
+    checkpoints: DashMap<Uuid, ResumeCheckpoint>,
+}
+
+impl ResumeTokens {
+    /// Issues a token for `user_id`, dropping checkpoints that have expired.
+    pub fn issue(&self, user_id: Uuid, state_vector: StateVector) -> Uuid {
+        let now = Instant::now();
+        self.checkpoints
+            .retain(|_, checkpoint| checkpoint.expires_at > now);
+        let token = Uuid::new_v4();
+        self.checkpoints.insert(
+            token,
+            ResumeCheckpoint {
+                user_id,
+                state_vector,
+                expires_at: now + RESUME_TOKEN_TTL,
+            },
+        );
+        token
+    }
+
+    /// Moves a token's checkpoint forward and restarts its expiry.
+    pub fn record(&self, token: Uuid, state_vector: StateVector) {
+        if let Some(mut checkpoint) = self.checkpoints.get_mut(&token) {
+            checkpoint.state_vector = state_vector;
+            checkpoint.expires_at = Instant::now() + RESUME_TOKEN_TTL;
+        }
+    }
+
+    /// Consumes `token` if it belongs to `user_id` and has not expired.
+    pub fn redeem(&self, token: Uuid, user_id: Uuid) -> Option<StateVector> {
+        let (_, checkpoint) = self
+            .checkpoints
+            .remove_if(&token, |_, checkpoint| checkpoint.user_id == user_id)?;
+        (checkpoint.expires_at > Instant::now()).then_some(checkpoint.state_vector)
+    }
+}
+
+/// Cumulative initial-sync counters for this process.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ResyncMetrics {
+    pub full_syncs: u64,
+    pub full_sync_bytes: u64,
+    pub resumed_syncs: u64,
+    pub resumed_sync_bytes: u64,
+}
+
+pub fn record_initial_sync(resumed: bool, bytes: usize) {
+    let (count, total) = if resumed {
+        (&RESUMED_SYNCS, &RESUMED_SYNC_BYTES)
+    } else {
+        (&FULL_SYNCS, &FULL_SYNC_BYTES)
+    };
+    count.fetch_add(1, Ordering::Relaxed);
+    total.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+pub fn resync_metrics() -> ResyncMetrics {
+    ResyncMetrics {
+        full_syncs: FULL_SYNCS.load(Ordering::Relaxed),
+        full_sync_bytes: FULL_SYNC_BYTES.load(Ordering::Relaxed),
+        resumed_syncs: RESUMED_SYNCS.load(Ordering::Relaxed),
+        resumed_sync_bytes: RESUMED_SYNC_BYTES.load(Ordering::Relaxed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use uuid::Uuid;
+    use yrs::StateVector;
+
+    use super::ResumeTokens;
+
+    #[test]
+    fn tokens_redeem_once_and_only_for_their_user() {
+        let tokens = ResumeTokens::default();
+        let user_id = Uuid::new_v4();
+        let token = tokens.issue(user_id, StateVector::default());
+
+        assert!(tokens.redeem(token, Uuid::new_v4()).is_none());
+        assert!(tokens.redeem(token, user_id).is_some());
+        assert!(tokens.redeem(token, user_id).is_none());
+        assert!(tokens.redeem(Uuid::new_v4(), user_id).is_none());
+    }
+
+    #[test]
+    fn expired_tokens_are_not_redeemable() {
+        let tokens = ResumeTokens::default();
+        let user_id = Uuid::new_v4();
+        let token = tokens.issue(user_id, StateVector::default());
+        tokens.checkpoints.get_mut(&token).unwrap().expires_at = Instant::now();
+
+        assert!(tokens.redeem(token, user_id).is_none());
+    }
+}
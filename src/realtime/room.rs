@@ -2,20 +2,43 @@ use axum::body::Bytes;
 use dashmap::{DashMap, DashSet, Entry};
 use sqlx::PgPool;
 use std::{
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     sync::{Arc, atomic::AtomicU64},
     time::Instant,
 };
-use tokio::sync::{Mutex, Notify, RwLock, broadcast};
+use tokio::sync::{Mutex, Notify, RwLock, broadcast, watch};
 use uuid::Uuid;
-use yrs::{Doc, sync::Awareness};
+use yrs::{Doc, Subscription, sync::Awareness};
 
-use crate::realtime::snapshot;
+use crate::realtime::{
+    element_crdt::{self, ElementEditors},
+    projection::ProjectionCoalescer,
+    resume::ResumeTokens,
+    snapshot,
+};
+
+const DEFAULT_BROADCAST_CAPACITY: usize = 100;
+
+fn broadcast_capacity() -> usize {
+    std::env::var("ROOM_BROADCAST_CAPACITY")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_BROADCAST_CAPACITY)
+}
 
 pub struct QueuedSession {
     pub session_id: Uuid,
     pub user_id: Uuid,
     pub notify: Arc<Notify>,
+    /// 1-based position in the queue, republished whenever the queue drains.
+    position: watch::Sender<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Presenter {
+    pub session_id: Uuid,
+    pub user_id: Uuid,
 }
 
 pub struct Room {
@@ -28,20 +51,34 @@ pub struct Room {
     pub awareness: Arc<RwLock<Awareness>>,
     pub edit_permissions: Arc<DashMap<Uuid, bool>>,
     pub pending_updates: Arc<Mutex<Vec<Vec<u8>>>>,
+    pub pending_heartbeats: Mutex<HashSet<Uuid>>,
+    pub presenter: Mutex<Option<Presenter>>,
     pub last_active: Mutex<Instant>,
     pub last_save: Mutex<Instant>,
     pub pending_update_count: AtomicU64,
     pub projection_seq: AtomicU64,
     pub projected_seq: AtomicU64,
+    pub projection_coalescer: Mutex<ProjectionCoalescer>,
+    /// Who last changed each element, credited for projected writes.
+    pub element_editors: ElementEditors,
+    _element_editors_subscription: Subscription,
+    pub resume_tokens: ResumeTokens,
 }
 
 impl Room {
     pub fn new(board_id: Uuid) -> Self {
-        let (tx, _rx) = broadcast::channel(100);
-        let (text_tx, _text_rx) = broadcast::channel(100);
-        let doc = Arc::new(Mutex::new(Doc::new()));
+        let capacity = broadcast_capacity();
+        let (tx, _rx) = broadcast::channel(capacity);
+        let (text_tx, _text_rx) = broadcast::channel(capacity);
+        let doc = Doc::new();
+        let element_editors = ElementEditors::default();
+        let element_editors_subscription =
+            element_crdt::observe_element_editors(&doc, element_editors.clone());
+        let doc = Arc::new(Mutex::new(doc));
         let awareness = Arc::new(RwLock::new(Awareness::new(Doc::new())));
         let pending_updates = Arc::new(Mutex::new(Vec::new()));
+        let pending_heartbeats = Mutex::new(HashSet::new());
+        let presenter = Mutex::new(None);
         let last_save = Mutex::new(Instant::now());
         let sessions = Arc::new(RwLock::new(DashSet::new()));
         let edit_permissions = Arc::new(DashMap::new());
@@ -50,6 +87,7 @@ impl Room {
         let pending_update_count = AtomicU64::new(0);
         let projection_seq = AtomicU64::new(0);
         let projected_seq = AtomicU64::new(0);
+        let projection_coalescer = Mutex::new(ProjectionCoalescer::default());
         Self {
             doc,
             tx,
@@ -60,35 +98,110 @@ impl Room {
             awareness,
             edit_permissions,
             pending_updates,
+            pending_heartbeats,
+            presenter,
             last_active,
             last_save,
             pending_update_count,
             projection_seq,
             projected_seq,
+            projection_coalescer,
+            element_editors,
+            _element_editors_subscription: element_editors_subscription,
+            resume_tokens: ResumeTokens::default(),
         }
     }
 
-    pub async fn enqueue_session(&self, session_id: Uuid, user_id: Uuid) -> (Arc<Notify>, usize) {
+    /// Queues a session waiting for room capacity, returning its wake-up
+    /// handle and a receiver of its current queue position.
+    pub async fn enqueue_session(
+        &self,
+        session_id: Uuid,
+        user_id: Uuid,
+    ) -> (Arc<Notify>, watch::Receiver<usize>) {
         let notify = Arc::new(Notify::new());
         let mut queue = self.queue.lock().await;
+        let (position, position_rx) = watch::channel(queue.len() + 1);
         queue.push_back(QueuedSession {
             session_id,
             user_id,
             notify: notify.clone(),
+            position,
         });
-        (notify, queue.len())
+        (notify, position_rx)
     }
 
     pub async fn remove_queued_session(&self, session_id: Uuid) -> bool {
         let mut queue = self.queue.lock().await;
         let before = queue.len();
         queue.retain(|entry| entry.session_id != session_id);
-        before != queue.len()
+        let removed = before != queue.len();
+        if removed {
+            Self::broadcast_queue_positions(&queue);
+        }
+        removed
+    }
+
+    pub async fn record_heartbeat(&self, session_id: Uuid) {
+        self.pending_heartbeats.lock().await.insert(session_id);
+    }
+
+    pub async fn take_pending_heartbeats(&self) -> Vec<Uuid> {
+        let mut pending = self.pending_heartbeats.lock().await;
+        pending.drain().collect()
+    }
+
+    pub async fn current_presenter(&self) -> Option<Presenter> {
+        *self.presenter.lock().await
+    }
+
+    pub async fn set_presenter(&self, presenter: Option<Presenter>) {
+        *self.presenter.lock().await = presenter;
+    }
+
+    /// Clears the presenter when it belongs to `session_id`; returns true if cleared.
+    pub async fn clear_presenter_session(&self, session_id: Uuid) -> bool {
+        let mut presenter = self.presenter.lock().await;
+        if presenter.is_some_and(|current| current.session_id == session_id) {
+            *presenter = None;
+            return true;
+        }
+        false
+    }
+
+    /// Sends a `{ type, payload }` text event to every session in the room.
+    pub fn broadcast_event(&self, event_type: &str, payload: serde_json::Value) {
+        let value = serde_json::json!({ "type": event_type, "payload": payload });
+        match serde_json::to_string(&value) {
+            Ok(text) => {
+                let _ = self.text_tx.send(text);
+            }
+            Err(error) => {
+                tracing::warn!("Failed to serialize ws event {}: {}", event_type, error);
+            }
+        }
     }
 
     pub async fn pop_next_queued(&self) -> Option<QueuedSession> {
         let mut queue = self.queue.lock().await;
-        queue.pop_front()
+        let next = queue.pop_front();
+        if next.is_some() {
+            Self::broadcast_queue_positions(&queue);
+        }
+        next
+    }
+
+    /// Pushes each still-queued session its new position; unchanged positions
+    /// are not re-sent.
+    fn broadcast_queue_positions(queue: &VecDeque<QueuedSession>) {
+        for (index, entry) in queue.iter().enumerate() {
+            let position = index + 1;
+            entry.position.send_if_modified(|current| {
+                let changed = *current != position;
+                *current = position;
+                changed
+            });
+        }
     }
 }
 
@@ -116,3 +229,71 @@ pub async fn get_or_load_room(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Presenter, Room};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn heartbeats_are_deduplicated_and_drained() {
+        let room = Room::new(Uuid::now_v7());
+        let session_id = Uuid::now_v7();
+
+        room.record_heartbeat(session_id).await;
+        room.record_heartbeat(session_id).await;
+
+        assert_eq!(room.take_pending_heartbeats().await, vec![session_id]);
+        assert!(room.take_pending_heartbeats().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn broadcast_event_wraps_payload_with_type() {
+        let room = Room::new(Uuid::now_v7());
+        let mut text_rx = room.text_tx.subscribe();
+
+        room.broadcast_event("board:metadata", serde_json::json!({ "name": "Roadmap" }));
+
+        let text = text_rx.recv().await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["type"], "board:metadata");
+        assert_eq!(value["payload"]["name"], "Roadmap");
+    }
+
+    #[tokio::test]
+    async fn queued_sessions_receive_updated_positions_as_queue_drains() {
+        let room = Room::new(Uuid::now_v7());
+        let first = Uuid::now_v7();
+        let second = Uuid::now_v7();
+        let third = Uuid::now_v7();
+        let (_, first_rx) = room.enqueue_session(first, Uuid::now_v7()).await;
+        let (_, second_rx) = room.enqueue_session(second, Uuid::now_v7()).await;
+        let (_, mut third_rx) = room.enqueue_session(third, Uuid::now_v7()).await;
+        assert_eq!(*first_rx.borrow(), 1);
+        assert_eq!(*third_rx.borrow(), 3);
+
+        room.remove_queued_session(second).await;
+        assert!(third_rx.has_changed().unwrap());
+        assert_eq!(*third_rx.borrow_and_update(), 2);
+
+        let popped = room.pop_next_queued().await.unwrap();
+        assert_eq!(popped.session_id, first);
+        assert_eq!(*third_rx.borrow_and_update(), 1);
+        assert!(second_rx.has_changed().is_err());
+    }
+
+    #[tokio::test]
+    async fn presenter_is_cleared_only_by_owning_session() {
+        let room = Room::new(Uuid::now_v7());
+        let presenter = Presenter {
+            session_id: Uuid::now_v7(),
+            user_id: Uuid::now_v7(),
+        };
+        room.set_presenter(Some(presenter)).await;
+
+        assert!(!room.clear_presenter_session(Uuid::now_v7()).await);
+        assert_eq!(room.current_presenter().await, Some(presenter));
+        assert!(room.clear_presenter_session(presenter.session_id).await);
+        assert_eq!(room.current_presenter().await, None);
+    }
+}
@@ -16,93 +16,146 @@ use crate::{
     repositories::elements as element_repo,
     repositories::realtime as realtime_repo,
     telemetry::BusinessEvent,
+    usecases::boards::env_flag,
 };
 
+const DEFAULT_SNAPSHOT_INTERVAL_SECS: u64 = 60;
+const SNAPSHOT_MIN_UPDATES: i64 = 200;
+const CLEANUP_INTERVAL_SECS: u64 = 300;
+const SNAPSHOT_MAX_CONCURRENCY: usize = 4;
+
+/// Spawns the background maintenance loops enabled for this node.
+///
+/// `ENABLE_SNAPSHOT_MAINTENANCE` controls update-log flushing and snapshotting,
+/// `ENABLE_ROOM_CLEANUP` controls idle room eviction. Both default to enabled
+/// so single-node deployments keep working; in a web/worker split only the
+/// worker should leave them on.
 pub fn spawn_maintenance(db: PgPool, rooms: Rooms) {
-    tokio::spawn(async move {
-        const SNAPSHOT_INTERVAL_SECS: u64 = 60;
-        const SNAPSHOT_MIN_UPDATES: i64 = 200;
-        const CLEANUP_INTERVAL_SECS: u64 = 300;
-        const SNAPSHOT_MAX_CONCURRENCY: usize = 4;
-
-        let mut snapshot_interval =
-            tokio::time::interval(std::time::Duration::from_secs(SNAPSHOT_INTERVAL_SECS));
-        let mut cleanup_interval =
-            tokio::time::interval(std::time::Duration::from_secs(CLEANUP_INTERVAL_SECS));
-        let semaphore = Arc::new(Semaphore::new(SNAPSHOT_MAX_CONCURRENCY));
-
-        loop {
-            tokio::select! {
-                _ = snapshot_interval.tick() => {
-                    let tick_started = Instant::now();
-                    let rooms_snapshot: Vec<Arc<Room>> = rooms.iter().map(|entry| entry.value().clone()).collect();
-                    let rooms_total = rooms_snapshot.len();
-                    let mut skipped = 0usize;
-                    let mut tasks = Vec::new();
-                    for room in rooms_snapshot {
-                        let has_pending = room.pending_update_count.load(Ordering::Acquire) > 0;
-                        if !has_pending {
-                            skipped += 1;
-                            continue;
-                        }
-                        let permit = semaphore.clone().acquire_owned().await.expect("semaphore closed");
-                        let db = db.clone();
-                        let room = room.clone();
-                        tasks.push(tokio::spawn(async move {
-                            let _permit = permit;
-                            let pending_updates = {
-                                let mut pending = room.pending_updates.lock().await;
-                                if pending.is_empty() {
-                                    Vec::new()
-                                } else {
-                                    pending.drain(..).collect()
-                                }
-                            };
-
-                            if !pending_updates.is_empty() {
-                                save_update_logs(room.board_id, None, pending_updates, db.clone()).await;
-                                let mut last_save = room.last_save.lock().await;
-                                *last_save = Instant::now();
-                                room.pending_update_count.store(0, Ordering::Release);
-                            }
-
-                            if let Err(e) = maybe_create_snapshot(&db, room.board_id, room.doc.clone(), SNAPSHOT_MIN_UPDATES).await {
-                                tracing::error!("Failed to create snapshot for board {}: {}", room.board_id, e);
-                            }
-                        }));
-                    }
-                    let processed = tasks.len();
-                    if processed > 0 {
-                        for task in tasks {
-                            if let Err(error) = task.await {
-                                tracing::error!("Snapshot maintenance task failed: {}", error);
-                            }
-                        }
-                    }
-                    tracing::debug!(
-                        rooms_total,
-                        processed,
-                        skipped,
-                        duration_ms = tick_started.elapsed().as_millis(),
-                        "Snapshot maintenance tick completed"
+    let snapshot_enabled = env_flag("ENABLE_SNAPSHOT_MAINTENANCE", true);
+    let cleanup_enabled = env_flag("ENABLE_ROOM_CLEANUP", true);
+    tracing::info!(
+        snapshot_enabled,
+        cleanup_enabled,
+        "Configured background maintenance"
+    );
+
+    if snapshot_enabled {
+        tokio::spawn(run_snapshot_maintenance(db, rooms.clone()));
+    }
+    if cleanup_enabled {
+        tokio::spawn(run_room_cleanup(rooms));
+    }
+}
+
+/// How often pending room updates are flushed to the update log.
+fn snapshot_interval_secs() -> u64 {
+    std::env::var("SNAPSHOT_MAINTENANCE_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL_SECS)
+}
+
+async fn run_snapshot_maintenance(db: PgPool, rooms: Rooms) {
+    let mut snapshot_interval =
+        tokio::time::interval(std::time::Duration::from_secs(snapshot_interval_secs()));
+    let semaphore = Arc::new(Semaphore::new(SNAPSHOT_MAX_CONCURRENCY));
+
+    loop {
+        snapshot_interval.tick().await;
+        let tick_started = Instant::now();
+        let rooms_snapshot: Vec<Arc<Room>> =
+            rooms.iter().map(|entry| entry.value().clone()).collect();
+        let rooms_total = rooms_snapshot.len();
+        let mut skipped = 0usize;
+        let mut tasks = Vec::new();
+        for room in rooms_snapshot {
+            let has_pending = room.pending_update_count.load(Ordering::Acquire) > 0;
+            if !has_pending {
+                skipped += 1;
+                continue;
+            }
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("semaphore closed");
+            let db = db.clone();
+            let room = room.clone();
+            tasks.push(tokio::spawn(async move {
+                let _permit = permit;
+                flush_pending_updates(&db, &room).await;
+
+                if let Err(e) = maybe_create_snapshot(
+                    &db,
+                    room.board_id,
+                    room.doc.clone(),
+                    SNAPSHOT_MIN_UPDATES,
+                )
+                .await
+                {
+                    tracing::error!(
+                        "Failed to create snapshot for board {}: {}",
+                        room.board_id,
+                        e
                     );
                 }
-                _ = cleanup_interval.tick() => {
-                    let mut room_to_remove = Vec::new();
-                    for room in rooms.iter() {
-                        let last_active = room.value().last_active.lock().await;
-                        if last_active.elapsed().as_secs() >= CLEANUP_INTERVAL_SECS {
-                            room_to_remove.push(*room.key());
-                        }
-                    }
-                    for board_id in room_to_remove {
-                        rooms.remove(&board_id);
-                        tracing::info!("Removed inactive room for board {}", board_id);
-                    }
+            }));
+        }
+        let processed = tasks.len();
+        if processed > 0 {
+            for task in tasks {
+                if let Err(error) = task.await {
+                    tracing::error!("Snapshot maintenance task failed: {}", error);
                 }
             }
         }
-    });
+        tracing::debug!(
+            rooms_total,
+            processed,
+            skipped,
+            duration_ms = tick_started.elapsed().as_millis(),
+            "Snapshot maintenance tick completed"
+        );
+    }
+}
+
+async fn run_room_cleanup(rooms: Rooms) {
+    let mut cleanup_interval =
+        tokio::time::interval(std::time::Duration::from_secs(CLEANUP_INTERVAL_SECS));
+
+    loop {
+        cleanup_interval.tick().await;
+        let mut room_to_remove = Vec::new();
+        for room in rooms.iter() {
+            let last_active = room.value().last_active.lock().await;
+            if last_active.elapsed().as_secs() >= CLEANUP_INTERVAL_SECS {
+                room_to_remove.push(*room.key());
+            }
+        }
+        for board_id in room_to_remove {
+            rooms.remove(&board_id);
+            tracing::info!("Removed inactive room for board {}", board_id);
+        }
+    }
+}
+
+async fn flush_pending_updates(db: &PgPool, room: &Arc<Room>) {
+    let pending_updates = {
+        let mut pending = room.pending_updates.lock().await;
+        if pending.is_empty() {
+            Vec::new()
+        } else {
+            pending.drain(..).collect()
+        }
+    };
+
+    if !pending_updates.is_empty() {
+        save_update_logs(room.board_id, None, pending_updates, db.clone()).await;
+        let mut last_save = room.last_save.lock().await;
+        *last_save = Instant::now();
+        room.pending_update_count.store(0, Ordering::Release);
+    }
 }
 
 pub async fn save_update_logs(
@@ -130,6 +183,9 @@ pub async fn load_board_state(
     let started_at = Instant::now();
     tracing::info!("load_board_state start for board {}", board_id);
     let mut start_seq: i64 = 0;
+    // A board created from a template or import has a seed snapshot at seq 0.
+    // Update seqs are global, so every update is replayed on top of it; early
+    // updates that already carry the seeded state merge idempotently.
     if let Some((seq, state_bin)) = realtime_repo::latest_snapshot(pool, board_id).await? {
         tracing::info!(
             "load_board_state snapshot found for board {} at seq {} ({} bytes)",
@@ -137,10 +193,19 @@ pub async fn load_board_state(
             seq,
             state_bin.len()
         );
-        let doc_guard = doc.lock().await;
-        let mut txn = doc_guard.transact_mut();
-        let update = yrs::Update::decode_v1(&state_bin)?;
-        let _ = txn.apply_update(update);
+        {
+            let doc_guard = doc.lock().await;
+            // Replaying only the updates after a snapshot that failed to
+            // apply would serve a partial board, so refuse to load instead.
+            apply_state_bin(&doc_guard, &state_bin).inspect_err(|error| {
+                tracing::error!(
+                    "load_board_state failed to apply snapshot seq {} for board {}: {}",
+                    seq,
+                    board_id,
+                    error
+                );
+            })?;
+        }
         start_seq = seq;
         tracing::info!(
             "LOADED SNAPSHOT FOR BOARD {} AT SEQ {}",
@@ -281,12 +346,49 @@ pub async fn build_state_update(pool: &PgPool, board_id: Uuid) -> Result<Vec<u8>
     let doc = Arc::new(Mutex::new(Doc::new()));
     load_board_state(pool, doc.clone(), board_id)
         .await
-        .map_err(|error| {
-            AppError::Internal(format!("Failed to build template state: {}", error))
-        })?;
+        .map_err(|error| AppError::Internal(format!("Failed to build board state: {}", error)))?;
+    Ok(encode_doc_state(&doc).await)
+}
+
+/// Replaces a closed board's snapshots and update log with one snapshot of its
+/// current state, so content removed from the doc is no longer stored.
+pub async fn compact_history(pool: &PgPool, board_id: Uuid) -> Result<(), AppError> {
+    // Read the seq first: the state loaded afterwards covers every update up
+    // to it, and later updates are kept to replay over the snapshot.
+    let snapshot_seq = realtime_repo::latest_update_seq(pool, board_id).await?;
+    let state_bin = build_state_update(pool, board_id).await?;
+    replace_history(pool, board_id, snapshot_seq, state_bin).await
+}
+
+/// [`compact_history`] for an open room, flushing its pending updates first
+/// and snapshotting the live doc.
+pub async fn compact_room_history(pool: &PgPool, room: &Arc<Room>) -> Result<(), AppError> {
+    flush_pending_updates(pool, room).await;
+    let snapshot_seq = realtime_repo::latest_update_seq(pool, room.board_id).await?;
+    let state_bin = encode_doc_state(&room.doc).await;
+    replace_history(pool, room.board_id, snapshot_seq, state_bin).await
+}
+
+async fn replace_history(
+    pool: &PgPool,
+    board_id: Uuid,
+    snapshot_seq: i64,
+    state_bin: Vec<u8>,
+) -> Result<(), AppError> {
+    let deleted = realtime_repo::replace_snapshots(pool, board_id, snapshot_seq, state_bin).await?;
+    tracing::info!(
+        "Compacted CRDT history for board {} at seq {}, deleted {} updates",
+        board_id,
+        snapshot_seq,
+        deleted
+    );
+    Ok(())
+}
+
+pub async fn encode_doc_state(doc: &Arc<Mutex<Doc>>) -> Vec<u8> {
     let doc_guard = doc.lock().await;
     let txn = doc_guard.transact();
-    Ok(txn.encode_state_as_update_v1(&StateVector::default()))
+    txn.encode_state_as_update_v1(&StateVector::default())
 }
 
 async fn hydrate_missing_fields_from_db(
@@ -415,6 +517,13 @@ pub fn build_state_update_from_elements(elements: &[BoardElement]) -> Result<Vec
     Ok(txn.encode_state_as_update_v1(&StateVector::default()))
 }
 
+fn apply_state_bin(doc: &Doc, state_bin: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    let update = yrs::Update::decode_v1(state_bin)?;
+    let mut txn = doc.transact_mut();
+    txn.apply_update(update)?;
+    Ok(())
+}
+
 pub async fn maybe_create_snapshot(
     pool: &PgPool,
     board_id: Uuid,
@@ -466,3 +575,150 @@ async fn create_snapshot_with_seq(
     );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+    use yrs::{Doc, ReadTxn, StateVector, Transact};
+
+    use super::{apply_state_bin, build_state_update_from_elements};
+    use crate::{
+        dto::elements::UpdateBoardElementRequest,
+        models::elements::{BoardElement, ElementType},
+        realtime::{
+            element_crdt::{self, UpdateContext},
+            room::Room,
+        },
+    };
+
+    fn cloned_element(board_id: Uuid) -> BoardElement {
+        let now = Utc::now();
+        BoardElement {
+            id: Uuid::now_v7(),
+            board_id,
+            layer_id: None,
+            parent_id: None,
+            created_by: Uuid::now_v7(),
+            element_type: ElementType::StickyNote,
+            position_x: 0.0,
+            position_y: 0.0,
+            width: 120.0,
+            height: 80.0,
+            rotation: 0.0,
+            z_index: 0,
+            style: json!({}),
+            properties: json!({ "content": "from template" }),
+            version: 1,
+            metadata: json!({}),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    fn move_request(position_x: f64) -> UpdateBoardElementRequest {
+        UpdateBoardElementRequest {
+            expected_version: 1,
+            client_updated_at: None,
+            position_x: Some(position_x),
+            position_y: None,
+            width: None,
+            height: None,
+            rotation: None,
+            style: None,
+            properties: None,
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn clone_then_join_room_syncs_seed_and_reloads_with_edits() {
+        let board_id = Uuid::now_v7();
+        let element = cloned_element(board_id);
+        let seed = build_state_update_from_elements(std::slice::from_ref(&element)).unwrap();
+
+        // The room loads the seq-0 seed written by the clone.
+        let room = Room::new(board_id);
+        apply_state_bin(&*room.doc.lock().await, &seed).unwrap();
+
+        // A fresh client joins and receives sync step 2 for its empty state.
+        let client = Doc::new();
+        let initial_sync = room
+            .doc
+            .lock()
+            .await
+            .transact()
+            .encode_state_as_update_v1(&client.transact().state_vector());
+        apply_state_bin(&client, &initial_sync).unwrap();
+        assert_eq!(element_crdt::materialize_elements(&client).len(), 1);
+
+        // Its first edit reaches the room and the update log.
+        let edit = element_crdt::apply_update(
+            &client,
+            element.id,
+            &move_request(40.0),
+            UpdateContext {
+                updated_at: Utc::now(),
+                last_write_wins_fields: &[],
+            },
+        )
+        .unwrap()
+        .unwrap()
+        .update;
+        apply_state_bin(&*room.doc.lock().await, &edit).unwrap();
+
+        let reloaded = Doc::new();
+        for state_bin in [&seed, &edit] {
+            apply_state_bin(&reloaded, state_bin).unwrap();
+        }
+        let elements = element_crdt::materialize_elements(&reloaded);
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].position_x, 40.0);
+        assert_eq!(
+            elements[0].version,
+            element_crdt::materialize_element(&*room.doc.lock().await, element.id)
+                .unwrap()
+                .version
+        );
+    }
+
+    #[test]
+    fn clone_then_join_replays_early_updates_over_seed_snapshot() {
+        let board_id = Uuid::now_v7();
+        let element = cloned_element(board_id);
+        let seed = build_state_update_from_elements(std::slice::from_ref(&element)).unwrap();
+
+        // A client joins straight after creation and edits the cloned element;
+        // its first logged update is the full state it synced from the seed.
+        let client = Doc::new();
+        apply_state_bin(&client, &seed).unwrap();
+        let joined = client
+            .transact()
+            .encode_state_as_update_v1(&StateVector::default());
+        let edit = element_crdt::apply_update(
+            &client,
+            element.id,
+            &move_request(40.0),
+            UpdateContext {
+                updated_at: Utc::now(),
+                last_write_wins_fields: &[],
+            },
+        )
+        .unwrap()
+        .unwrap()
+        .update;
+
+        let loaded = Doc::new();
+        for state_bin in [&seed, &joined, &edit] {
+            apply_state_bin(&loaded, state_bin).unwrap();
+        }
+
+        let elements = element_crdt::materialize_elements(&loaded);
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].id, element.id);
+        assert_eq!(elements[0].position_x, 40.0);
+        assert_eq!(elements[0].version, Some(2));
+    }
+}
@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, prelude::FromRow};
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// Minimum gap between `last_used_at` writes for one token.
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ApiTokenRow {
+    pub id: Uuid,
+    pub board_id: Option<Uuid>,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Active token resolved from its secret, with the creator it acts as.
+#[derive(Debug, Clone, FromRow)]
+pub struct ApiTokenGrantRow {
+    pub organization_id: Uuid,
+    pub board_id: Option<Uuid>,
+    pub scopes: Vec<String>,
+    pub created_by: Uuid,
+    pub creator_email: String,
+}
+
+pub struct NewApiToken<'a> {
+    pub organization_id: Uuid,
+    pub board_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub name: &'a str,
+    pub token_hash: &'a str,
+    pub token_prefix: &'a str,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub async fn insert_api_token(
+    pool: &PgPool,
+    token: NewApiToken<'_>,
+) -> Result<ApiTokenRow, AppError> {
+    let row = crate::log_query_fetch_one!(
+        "api_tokens.insert_api_token",
+        sqlx::query_as::<_, ApiTokenRow>(
+            r#"
+                INSERT INTO core.api_token (
+                    organization_id, board_id, created_by, name, token_hash,
+                    token_prefix, scopes, expires_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING id, board_id, name, token_prefix, scopes, created_by,
+                    created_at, last_used_at, expires_at
+            "#,
+        )
+        .bind(token.organization_id)
+        .bind(token.board_id)
+        .bind(token.created_by)
+        .bind(token.name)
+        .bind(token.token_hash)
+        .bind(token.token_prefix)
+        .bind(token.scopes)
+        .bind(token.expires_at)
+        .fetch_one(pool)
+    )?;
+    Ok(row)
+}
+
+pub async fn list_active_api_tokens(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<Vec<ApiTokenRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "api_tokens.list_active_api_tokens",
+        sqlx::query_as::<_, ApiTokenRow>(
+            r#"
+                SELECT id, board_id, name, token_prefix, scopes, created_by,
+                    created_at, last_used_at, expires_at
+                FROM core.api_token
+                WHERE organization_id = $1
+                AND revoked_at IS NULL
+                AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+                ORDER BY created_at DESC
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(pool)
+    )?;
+    Ok(rows)
+}
+
+/// Revokes a token. Returns false when it does not exist in the organization
+/// or was already revoked.
+pub async fn revoke_api_token(
+    pool: &PgPool,
+    organization_id: Uuid,
+    token_id: Uuid,
+) -> Result<bool, AppError> {
+    let result = crate::log_query_execute!(
+        "api_tokens.revoke_api_token",
+        sqlx::query(
+            r#"
+                UPDATE core.api_token
+                SET revoked_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND organization_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(token_id)
+        .bind(organization_id)
+        .execute(pool)
+    )?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Resolves an unexpired, unrevoked token whose creator is still an active
+/// organization member, refreshing `last_used_at` at most once per minute.
+pub async fn authenticate_api_token(
+    pool: &PgPool,
+    token_hash: &str,
+) -> Result<Option<ApiTokenGrantRow>, AppError> {
+    let grant = crate::log_query_fetch_optional!(
+        "api_tokens.authenticate_api_token",
+        sqlx::query_as::<_, ApiTokenGrantRow>(
+            r#"
+                WITH active AS (
+                    SELECT
+                        t.id,
+                        t.organization_id,
+                        t.board_id,
+                        t.scopes,
+                        t.created_by,
+                        t.last_used_at,
+                        u.email AS creator_email
+                    FROM core.api_token t
+                    JOIN core.user u ON u.id = t.created_by
+                    JOIN core.organization_member om
+                        ON om.organization_id = t.organization_id
+                        AND om.user_id = t.created_by
+                        AND om.accepted_at IS NOT NULL
+                    WHERE t.token_hash = $1
+                    AND t.revoked_at IS NULL
+                    AND (t.expires_at IS NULL OR t.expires_at > CURRENT_TIMESTAMP)
+                    AND u.deleted_at IS NULL
+                ),
+                touched AS (
+                    UPDATE core.api_token
+                    SET last_used_at = CURRENT_TIMESTAMP
+                    WHERE id IN (
+                        SELECT id FROM active
+                        WHERE last_used_at IS NULL
+                        OR last_used_at < CURRENT_TIMESTAMP - make_interval(secs => $2)
+                    )
+                )
+                SELECT organization_id, board_id, scopes, created_by, creator_email
+                FROM active
+            "#,
+        )
+        .bind(token_hash)
+        .bind(LAST_USED_RESOLUTION_SECS as f64)
+        .fetch_optional(pool)
+    )?;
+    Ok(grant)
+}
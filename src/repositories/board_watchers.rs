@@ -0,0 +1,127 @@
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+pub async fn insert_watcher(pool: &PgPool, board_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "board_watchers.insert_watcher",
+        sqlx::query(
+            r#"
+                INSERT INTO board.watcher (board_id, user_id)
+                VALUES ($1, $2)
+                ON CONFLICT (board_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(board_id)
+        .bind(user_id)
+        .execute(pool)
+    )?;
+    Ok(())
+}
+
+pub async fn delete_watcher(pool: &PgPool, board_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "board_watchers.delete_watcher",
+        sqlx::query("DELETE FROM board.watcher WHERE board_id = $1 AND user_id = $2")
+            .bind(board_id)
+            .bind(user_id)
+            .execute(pool)
+    )?;
+    Ok(())
+}
+
+pub async fn is_watching(pool: &PgPool, board_id: Uuid, user_id: Uuid) -> Result<bool, AppError> {
+    let watching = crate::log_query_fetch_one!(
+        "board_watchers.is_watching",
+        sqlx::query_scalar::<_, bool>(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM board.watcher WHERE board_id = $1 AND user_id = $2
+                )
+            "#,
+        )
+        .bind(board_id)
+        .bind(user_id)
+        .fetch_one(pool)
+    )?;
+    Ok(watching)
+}
+
+/// Claims watchers that have not been notified within `debounce_secs` by
+/// stamping `last_notified_at`, returning their ids. The actor is skipped.
+pub async fn claim_due_watchers(
+    pool: &PgPool,
+    board_id: Uuid,
+    actor_id: Option<Uuid>,
+    debounce_secs: i64,
+) -> Result<Vec<Uuid>, AppError> {
+    let user_ids = crate::log_query_fetch_all!(
+        "board_watchers.claim_due_watchers",
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+                UPDATE board.watcher
+                SET last_notified_at = CURRENT_TIMESTAMP
+                WHERE board_id = $1
+                AND ($2::uuid IS NULL OR user_id <> $2)
+                AND (
+                    last_notified_at IS NULL
+                    OR last_notified_at < CURRENT_TIMESTAMP - make_interval(secs => $3)
+                )
+                RETURNING user_id
+            "#,
+        )
+        .bind(board_id)
+        .bind(actor_id)
+        .bind(debounce_secs as f64)
+        .fetch_all(pool)
+    )?;
+    Ok(user_ids)
+}
+
+pub(crate) struct CreateBoardActivityNotifications {
+    pub user_ids: Vec<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub board_id: Uuid,
+    pub title: String,
+    pub body: String,
+    pub data: Value,
+}
+
+pub async fn create_activity_notifications(
+    pool: &PgPool,
+    params: CreateBoardActivityNotifications,
+) -> Result<u64, AppError> {
+    if params.user_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let rows = crate::log_query_execute!(
+        "board_watchers.create_activity_notifications",
+        sqlx::query(
+            r#"
+            INSERT INTO collab.notification (
+                user_id,
+                actor_id,
+                board_id,
+                notification_type,
+                title,
+                body,
+                data
+            )
+            SELECT target_id, $2, $3, 'board_activity', $4, $5, $6
+            FROM UNNEST($1::uuid[]) AS target_id
+            "#,
+        )
+        .bind(params.user_ids)
+        .bind(params.actor_id)
+        .bind(params.board_id)
+        .bind(params.title)
+        .bind(params.body)
+        .bind(sqlx::types::Json(params.data))
+        .execute(pool)
+    )?;
+
+    Ok(rows.rows_affected())
+}
@@ -3,10 +3,14 @@ use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::{
-    dto::boards::BoardResponse,
+    dto::boards::{BoardCardResponse, BoardResponse},
     error::AppError,
     models::{
-        boards::{Board, BoardPermissionOverrides, BoardRole, CanvasSettings},
+        boards::{
+            Board, BoardFeatures, BoardPermissionOverrides, BoardRole, CanvasSettings,
+            ElementDeletePolicy,
+        },
+        elements::ElementType,
         organizations::OrgRole,
     },
 };
@@ -22,6 +26,17 @@ pub(crate) struct CreateBoardParams {
     pub canvas_settings: CanvasSettings,
 }
 
+/// Board metadata changes; `None` fields are left unchanged.
+#[derive(Debug)]
+pub(crate) struct UpdateBoardMetadataParams {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub is_public: Option<bool>,
+    pub tags: Option<Vec<String>>,
+    pub features: Option<BoardFeatures>,
+    pub element_delete_policy: Option<ElementDeletePolicy>,
+}
+
 #[derive(Debug, sqlx::FromRow)]
 struct BoardResponseRow {
     pub id: Uuid,
@@ -31,12 +46,39 @@ struct BoardResponseRow {
     pub username: String,
     pub description: Option<String>,
     pub thumbnail_url: Option<String>,
+    pub tags: Vec<String>,
     pub is_favorite: bool,
     pub last_accessed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, sqlx::FromRow)]
+struct BoardCardRow {
+    pub id: Uuid,
+    pub organization_id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub owner_username: String,
+    pub is_favorite: bool,
+    pub member_count: i64,
+    pub last_activity_at: DateTime<Utc>,
+    pub last_accessed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct ElementTypeCountRow {
+    pub element_type: ElementType,
+    pub count: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct BoardCollaborationTotalsRow {
+    pub comment_count: i64,
+    pub contributor_count: i64,
+}
+
 #[derive(Debug, sqlx::FromRow)]
 pub(crate) struct BoardMemberRow {
     pub member_id: Uuid,
@@ -60,11 +102,27 @@ pub(crate) struct BoardMemberRecord {
     pub custom_permissions: Option<BoardPermissionOverrides>,
 }
 
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct BoardMemberAccessRow {
+    pub board_id: Uuid,
+    #[sqlx(flatten)]
+    pub member: BoardMemberRecord,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct BoardContentStats {
+    pub element_count: i64,
+    pub snapshot_seq: Option<i64>,
+    pub snapshot_bytes: Option<i32>,
+    pub last_snapshot_at: Option<DateTime<Utc>>,
+}
+
 pub async fn list_boards_for_user(
     pool: &PgPool,
     user_id: Uuid,
     organization_id: Option<Uuid>,
     is_template: Option<bool>,
+    tag: Option<&str>,
 ) -> Result<Vec<BoardResponse>, AppError> {
     let rows = crate::log_query_fetch_all!(
         "boards.list_for_user",
@@ -77,6 +135,7 @@ pub async fn list_boards_for_user(
                 b.name,
                 b.description,
                 b.thumbnail_url,
+                COALESCE(b.tags, '{}') AS tags,
                 b.created_at,
                 b.updated_at,
                 COALESCE(bm.is_favorite, false) AS is_favorite,
@@ -120,6 +179,7 @@ pub async fn list_boards_for_user(
             AND b.archived_at IS NULL
             AND ($2 IS NULL OR b.organization_id = $2)
             AND ($3 IS NULL OR b.is_template = $3)
+            AND ($4::text IS NULL OR b.tags @> ARRAY[$4::text])
             AND (
                 (bm.user_id IS NOT NULL AND (b.organization_id IS NULL OR om.user_id IS NOT NULL))
                 OR om.role IN ('owner', 'admin')
@@ -130,6 +190,7 @@ pub async fn list_boards_for_user(
         .bind(user_id)
         .bind(organization_id)
         .bind(is_template)
+        .bind(tag)
         .fetch_all(pool)
     )?;
 
@@ -143,6 +204,7 @@ pub async fn list_boards_for_user(
             username: row.username,
             description: row.description,
             thumbnail_url: row.thumbnail_url,
+            tags: row.tags,
             is_favorite: row.is_favorite,
             last_accessed_at: row.last_accessed_at,
             created_at: row.created_at,
@@ -151,6 +213,170 @@ pub async fn list_boards_for_user(
         .collect())
 }
 
+/// Counts live elements on a board grouped by type.
+pub async fn count_elements_by_type(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<Vec<ElementTypeCountRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "boards.count_elements_by_type",
+        sqlx::query_as::<_, ElementTypeCountRow>(
+            r#"
+                SELECT element_type, COUNT(*) AS count
+                FROM board.element
+                WHERE board_id = $1
+                AND deleted_at IS NULL
+                GROUP BY element_type
+                ORDER BY count DESC, element_type
+            "#,
+        )
+        .bind(board_id)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Counts live comments and the distinct users who created or edited
+/// elements or commented on a board.
+pub async fn get_collaboration_totals(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<BoardCollaborationTotalsRow, AppError> {
+    let row = crate::log_query_fetch_one!(
+        "boards.get_collaboration_totals",
+        sqlx::query_as::<_, BoardCollaborationTotalsRow>(
+            r#"
+                SELECT
+                    (
+                        SELECT COUNT(*)
+                        FROM collab.comment
+                        WHERE board_id = $1 AND deleted_at IS NULL
+                    ) AS comment_count,
+                    (
+                        SELECT COUNT(DISTINCT actor_id)
+                        FROM (
+                            SELECT created_by AS actor_id
+                            FROM board.element
+                            WHERE board_id = $1
+                            UNION
+                            SELECT changed_by
+                            FROM board.element_history
+                            WHERE board_id = $1
+                            UNION
+                            SELECT created_by
+                            FROM collab.comment
+                            WHERE board_id = $1 AND deleted_at IS NULL
+                        ) actors
+                    ) AS contributor_count
+            "#,
+        )
+        .bind(board_id)
+        .fetch_one(pool)
+    )?;
+
+    Ok(row)
+}
+
+/// Lists one page of non-template boards visible to the user with the owner
+/// username, favorite flag and member count resolved in the same query.
+pub async fn list_board_cards(
+    pool: &PgPool,
+    user_id: Uuid,
+    organization_id: Option<Uuid>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<BoardCardResponse>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "boards.list_cards",
+        sqlx::query_as::<_, BoardCardRow>(
+            r#"
+            SELECT
+                b.id,
+                b.organization_id,
+                b.name,
+                b.description,
+                b.thumbnail_url,
+                COALESCE(owner.username, creator_in_scope.username, '') AS owner_username,
+                COALESCE(bm.is_favorite, false) AS is_favorite,
+                member_count.count AS member_count,
+                COALESCE(b.last_edited_at, b.updated_at) AS last_activity_at,
+                bm.last_accessed_at
+            FROM board.board b
+            JOIN core.user creator ON b.created_by = creator.id
+            LEFT JOIN LATERAL (
+                SELECT creator.username
+                WHERE b.organization_id IS NULL
+                OR EXISTS (
+                    SELECT 1
+                    FROM core.organization_member om_creator
+                    WHERE om_creator.organization_id = b.organization_id
+                    AND om_creator.user_id = creator.id
+                )
+            ) creator_in_scope ON TRUE
+            LEFT JOIN LATERAL (
+                SELECT u.username
+                FROM board.board_member bm_owner
+                JOIN core.user u ON u.id = bm_owner.user_id
+                LEFT JOIN core.organization_member om_owner
+                    ON om_owner.organization_id = b.organization_id
+                    AND om_owner.user_id = bm_owner.user_id
+                    AND om_owner.accepted_at IS NOT NULL
+                WHERE bm_owner.board_id = b.id
+                AND bm_owner.role = 'owner'
+                AND u.deleted_at IS NULL
+                AND (b.organization_id IS NULL OR om_owner.user_id IS NOT NULL)
+                ORDER BY bm_owner.created_at ASC
+                LIMIT 1
+            ) owner ON TRUE
+            CROSS JOIN LATERAL (
+                SELECT COUNT(*) AS count
+                FROM board.board_member bm_count
+                WHERE bm_count.board_id = b.id
+            ) member_count
+            LEFT JOIN board.board_member bm
+                ON bm.board_id = b.id
+                AND bm.user_id = $1
+            LEFT JOIN core.organization_member om
+                ON om.organization_id = b.organization_id
+                AND om.user_id = $1
+                AND om.accepted_at IS NOT NULL
+            WHERE b.deleted_at IS NULL
+            AND b.archived_at IS NULL
+            AND b.is_template = FALSE
+            AND ($2 IS NULL OR b.organization_id = $2)
+            AND (
+                (bm.user_id IS NOT NULL AND (b.organization_id IS NULL OR om.user_id IS NOT NULL))
+                OR om.role IN ('owner', 'admin')
+            )
+            ORDER BY COALESCE(b.last_edited_at, b.updated_at) DESC, b.id
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(organization_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| BoardCardResponse {
+            id: row.id,
+            organization_id: row.organization_id,
+            name: row.name,
+            description: row.description,
+            thumbnail_url: row.thumbnail_url,
+            owner_username: row.owner_username,
+            is_favorite: row.is_favorite,
+            member_count: row.member_count,
+            last_activity_at: row.last_activity_at,
+            last_accessed_at: row.last_accessed_at,
+        })
+        .collect())
+}
+
 pub async fn find_board_by_id(pool: &PgPool, board_id: Uuid) -> Result<Option<Board>, AppError> {
     let board = crate::log_query_fetch_optional!(
         "boards.find_by_id",
@@ -170,6 +396,60 @@ pub async fn find_board_by_id(pool: &PgPool, board_id: Uuid) -> Result<Option<Bo
     Ok(board)
 }
 
+/// Loads active (not archived or deleted) boards by id.
+pub async fn find_active_boards_by_ids(
+    pool: &PgPool,
+    board_ids: &[Uuid],
+) -> Result<Vec<Board>, AppError> {
+    let boards = crate::log_query_fetch_all!(
+        "boards.find_active_by_ids",
+        sqlx::query_as::<_, Board>(
+            r#"
+                SELECT *
+                FROM board.board
+                WHERE id = ANY($1)
+                AND deleted_at IS NULL
+                AND archived_at IS NULL
+            "#,
+        )
+        .bind(board_ids)
+        .fetch_all(pool)
+    )?;
+
+    Ok(boards)
+}
+
+/// Lists active boards in an organization whose name matches
+/// case-insensitively, most recently updated first.
+pub async fn find_active_org_boards_by_name(
+    pool: &PgPool,
+    organization_id: Uuid,
+    name: &str,
+    limit: i64,
+) -> Result<Vec<Board>, AppError> {
+    let boards = crate::log_query_fetch_all!(
+        "boards.find_active_org_boards_by_name",
+        sqlx::query_as::<_, Board>(
+            r#"
+                SELECT *
+                FROM board.board
+                WHERE organization_id = $1
+                AND lower(name) = lower($2)
+                AND deleted_at IS NULL
+                AND archived_at IS NULL
+                ORDER BY updated_at DESC
+                LIMIT $3
+            "#,
+        )
+        .bind(organization_id)
+        .bind(name)
+        .bind(limit)
+        .fetch_all(pool)
+    )?;
+
+    Ok(boards)
+}
+
 pub async fn find_board_by_id_including_deleted(
     pool: &PgPool,
     board_id: Uuid,
@@ -249,6 +529,39 @@ pub async fn add_owner_member(
     Ok(())
 }
 
+/// Adds every accepted non-guest member of the organization, other than
+/// `creator_id`, to the board with `role`.
+pub async fn add_org_members_to_board(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+    organization_id: Uuid,
+    creator_id: Uuid,
+    role: BoardRole,
+) -> Result<u64, AppError> {
+    let result = crate::log_query_execute!(
+        "boards.add_org_members",
+        sqlx::query(
+            r#"
+                INSERT INTO board.board_member (board_id, user_id, role, invited_by)
+                SELECT $1, om.user_id, $4, $3
+                FROM core.organization_member om
+                WHERE om.organization_id = $2
+                AND om.accepted_at IS NOT NULL
+                AND om.role <> 'guest'
+                AND om.user_id <> $3
+                ON CONFLICT (board_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(board_id)
+        .bind(organization_id)
+        .bind(creator_id)
+        .bind(role)
+        .execute(&mut **tx)
+    )?;
+
+    Ok(result.rows_affected())
+}
+
 /// Ensures the user is an owner for the board (upsert).
 pub async fn ensure_board_owner(
     tx: &mut Transaction<'_, Postgres>,
@@ -310,6 +623,34 @@ pub async fn get_board_member_access(
     Ok(member)
 }
 
+/// Loads the user's board memberships for a set of boards.
+pub async fn list_board_member_access_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    board_ids: &[Uuid],
+) -> Result<Vec<BoardMemberAccessRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "boards.list_member_access_for_user",
+        sqlx::query_as::<_, BoardMemberAccessRow>(
+            r#"
+                SELECT
+                    board_id,
+                    user_id,
+                    role,
+                    COALESCE(custom_permissions, '{}'::jsonb) AS custom_permissions
+                FROM board.board_member
+                WHERE user_id = $1
+                AND board_id = ANY($2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(board_ids)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
 /// Loads the organization id for a board, returning `Ok(None)` for personal boards.
 pub async fn load_board_organization_id(
     pool: &PgPool,
@@ -420,6 +761,42 @@ pub async fn get_board_member_by_user_id(
     Ok(member)
 }
 
+/// Counts active elements and reads the latest CRDT snapshot metadata.
+pub async fn get_board_content_stats(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<BoardContentStats, AppError> {
+    let stats = crate::log_query_fetch_one!(
+        "boards.get_board_content_stats",
+        sqlx::query_as::<_, BoardContentStats>(
+            r#"
+                SELECT
+                    (
+                        SELECT COUNT(*)
+                        FROM board.element
+                        WHERE board_id = $1
+                          AND deleted_at IS NULL
+                    ) AS element_count,
+                    snapshot.snapshot_seq,
+                    snapshot.state_bytes AS snapshot_bytes,
+                    snapshot.created_at AS last_snapshot_at
+                FROM (SELECT 1) AS board_row
+                LEFT JOIN LATERAL (
+                    SELECT snapshot_seq, state_bytes, created_at
+                    FROM crdt.board_snapshot
+                    WHERE board_id = $1
+                    ORDER BY snapshot_seq DESC
+                    LIMIT 1
+                ) AS snapshot ON TRUE
+            "#,
+        )
+        .bind(board_id)
+        .fetch_one(pool)
+    )?;
+
+    Ok(stats)
+}
+
 pub async fn touch_board_last_accessed(
     pool: &PgPool,
     board_id: Uuid,
@@ -477,9 +854,7 @@ pub async fn toggle_board_favorite(
 pub async fn update_board_metadata(
     tx: &mut Transaction<'_, Postgres>,
     board_id: Uuid,
-    name: Option<String>,
-    description: Option<String>,
-    is_public: Option<bool>,
+    params: UpdateBoardMetadataParams,
 ) -> Result<Board, AppError> {
     let board = crate::log_query_fetch_one!(
         "boards.update_metadata",
@@ -490,6 +865,9 @@ pub async fn update_board_metadata(
                     name = COALESCE($2, name),
                     description = COALESCE($3, description),
                     is_public = COALESCE($4, is_public),
+                    tags = COALESCE($5, tags),
+                    features = COALESCE($6, features),
+                    element_delete_policy = COALESCE($7, element_delete_policy),
                     updated_at = CURRENT_TIMESTAMP
                 WHERE id = $1
                 AND deleted_at IS NULL
@@ -497,9 +875,12 @@ pub async fn update_board_metadata(
             "#,
         )
         .bind(board_id)
-        .bind(name)
-        .bind(description)
-        .bind(is_public)
+        .bind(params.name)
+        .bind(params.description)
+        .bind(params.is_public)
+        .bind(params.tags)
+        .bind(params.features.map(sqlx::types::Json))
+        .bind(params.element_delete_policy)
         .fetch_one(&mut **tx)
     )?;
 
@@ -550,24 +931,67 @@ pub async fn restore_board(
     Ok(())
 }
 
+/// Rows removed by [`purge_deleted_boards`], per table.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PurgedBoardCounts {
+    pub boards: u64,
+    pub elements: u64,
+    pub snapshots: u64,
+    pub updates: u64,
+}
+
+/// Permanently deletes boards trashed more than `retention_days` ago.
+///
+/// Elements and CRDT snapshots/update logs go with the boards through their
+/// `ON DELETE CASCADE` foreign keys; they are counted first for reporting.
 pub async fn purge_deleted_boards(
     tx: &mut Transaction<'_, Postgres>,
     retention_days: i64,
-) -> Result<u64, AppError> {
-    let result = crate::log_query_execute!(
-        "boards.purge_deleted",
-        sqlx::query(
+) -> Result<PurgedBoardCounts, AppError> {
+    let board_ids = crate::log_query_fetch_all!(
+        "boards.purge_deleted_select",
+        sqlx::query_scalar::<_, Uuid>(
             r#"
-                DELETE FROM board.board
+                SELECT id
+                FROM board.board
                 WHERE deleted_at IS NOT NULL
                 AND deleted_at <= (CURRENT_TIMESTAMP - ($1 * INTERVAL '1 day'))
+                FOR UPDATE
             "#,
         )
         .bind(retention_days)
-        .execute(&mut **tx)
+        .fetch_all(&mut **tx)
     )?;
+    if board_ids.is_empty() {
+        return Ok(PurgedBoardCounts::default());
+    }
 
-    Ok(result.rows_affected())
+    let (elements, snapshots, updates) = crate::log_query_fetch_one!(
+        "boards.purge_deleted_counts",
+        sqlx::query_as::<_, (i64, i64, i64)>(
+            r#"
+                SELECT
+                    (SELECT COUNT(*) FROM board.element WHERE board_id = ANY($1)),
+                    (SELECT COUNT(*) FROM crdt.board_snapshot WHERE board_id = ANY($1)),
+                    (SELECT COUNT(*) FROM crdt.board_update WHERE board_id = ANY($1))
+            "#,
+        )
+        .bind(&board_ids)
+        .fetch_one(&mut **tx)
+    )?;
+    let boards = crate::log_query_execute!(
+        "boards.purge_deleted",
+        sqlx::query("DELETE FROM board.board WHERE id = ANY($1)")
+            .bind(&board_ids)
+            .execute(&mut **tx)
+    )?;
+
+    Ok(PurgedBoardCounts {
+        boards: boards.rows_affected(),
+        elements: elements as u64,
+        snapshots: snapshots as u64,
+        updates: updates as u64,
+    })
 }
 
 pub async fn set_board_archived(
@@ -761,6 +1185,17 @@ pub async fn remove_board_memberships_by_organization(
     Ok(())
 }
 
+/// Ids of every board that has not been purged, oldest first.
+pub async fn list_all_board_ids(pool: &PgPool) -> Result<Vec<Uuid>, AppError> {
+    let board_ids = crate::log_query_fetch_all!(
+        "boards.list_all_ids",
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM board.board ORDER BY created_at")
+            .fetch_all(pool)
+    )?;
+
+    Ok(board_ids)
+}
+
 pub async fn count_board_owners(pool: &PgPool, board_id: Uuid) -> Result<i64, AppError> {
     let count = crate::log_query_fetch_one!(
         "boards.count_owners",
@@ -837,3 +1272,83 @@ fn map_board_member_unique_violation(err: sqlx::Error) -> AppError {
         _ => err.into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    use super::purge_deleted_boards;
+
+    async fn count_rows(pool: &mut sqlx::PgConnection, table: &str, board_id: Uuid) -> i64 {
+        sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM {} WHERE board_id = $1",
+            table
+        ))
+        .bind(board_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated database in DATABASE_URL"]
+    async fn purge_leaves_no_orphaned_rows() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO core.user (email, display_name) VALUES ($1, 'Purge test') RETURNING id",
+        )
+        .bind(format!("purge-{}@example.com", Uuid::now_v7()))
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        let board_id: Uuid = sqlx::query_scalar(
+            r#"
+                INSERT INTO board.board (created_by, name, deleted_at)
+                VALUES ($1, 'Purged', CURRENT_TIMESTAMP - INTERVAL '31 days')
+                RETURNING id
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO board.element (board_id, created_by, element_type) VALUES ($1, $2, 'shape')",
+        )
+        .bind(board_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO crdt.board_snapshot (board_id, snapshot_seq, state_bin) VALUES ($1, 1, '\\x00')",
+        )
+        .bind(board_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+                INSERT INTO crdt.board_update (board_id, update_bin, created_at)
+                VALUES ($1, '\x00', '2025-12-15')
+            "#,
+        )
+        .bind(board_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+        let purged = purge_deleted_boards(&mut tx, 30).await.unwrap();
+
+        assert!(purged.boards >= 1 && purged.elements >= 1);
+        assert!(purged.snapshots >= 1 && purged.updates >= 1);
+        for table in ["board.element", "crdt.board_snapshot", "crdt.board_update"] {
+            assert_eq!(count_rows(&mut tx, table, board_id).await, 0, "{}", table);
+        }
+        tx.rollback().await.unwrap();
+    }
+}
@@ -41,6 +41,9 @@ pub(crate) struct CommentRow {
     pub is_edited: bool,
     pub edited_at: Option<DateTime<Utc>>,
     pub reply_count: i32,
+    pub is_pinned: bool,
+    pub pinned_by: Option<Uuid>,
+    pub pinned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub author_username: Option<String>,
@@ -88,6 +91,9 @@ pub async fn create_comment(
                 inserted.is_edited,
                 inserted.edited_at,
                 inserted.reply_count,
+                inserted.is_pinned,
+                inserted.pinned_by,
+                inserted.pinned_at,
                 inserted.created_at,
                 inserted.updated_at,
                 u.username AS author_username,
@@ -144,6 +150,9 @@ pub async fn list_comments(
                 c.is_edited,
                 c.edited_at,
                 c.reply_count,
+                c.is_pinned,
+                c.pinned_by,
+                c.pinned_at,
                 c.created_at,
                 c.updated_at,
                 u.username AS author_username,
@@ -156,6 +165,7 @@ pub async fn list_comments(
             AND ($2::uuid IS NULL OR c.element_id = $2)
             AND ($3::uuid IS NULL OR c.parent_id = $3)
             AND ($4::collab.comment_status IS NULL OR c.status = $4)
+            AND NOT c.is_pinned
             AND (
                 $5::timestamptz IS NULL
                 OR (c.created_at, c.id) < ($5::timestamptz, $6::uuid)
@@ -177,6 +187,195 @@ pub async fn list_comments(
     Ok(rows)
 }
 
+/// Pinned comments matching the list filters, most recently pinned first.
+pub async fn list_pinned_comments(
+    pool: &PgPool,
+    board_id: Uuid,
+    element_id: Option<Uuid>,
+    parent_id: Option<Uuid>,
+    status: Option<CommentStatus>,
+) -> Result<Vec<CommentRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "comments.list_pinned_comments",
+        sqlx::query_as::<_, CommentRow>(
+            r#"
+            SELECT
+                c.id,
+                c.board_id,
+                c.element_id,
+                c.parent_id,
+                c.created_by,
+                c.position_x,
+                c.position_y,
+                c.content,
+                c.content_html,
+                c.mentions,
+                c.status,
+                c.resolved_by,
+                c.resolved_at,
+                c.is_edited,
+                c.edited_at,
+                c.reply_count,
+                c.is_pinned,
+                c.pinned_by,
+                c.pinned_at,
+                c.created_at,
+                c.updated_at,
+                u.username AS author_username,
+                COALESCE(u.display_name, 'Deleted user') AS author_display_name,
+                u.avatar_url AS author_avatar_url
+            FROM collab.comment c
+            LEFT JOIN core.user u ON u.id = c.created_by
+            WHERE c.board_id = $1
+            AND c.deleted_at IS NULL
+            AND c.is_pinned
+            AND ($2::uuid IS NULL OR c.element_id = $2)
+            AND ($3::uuid IS NULL OR c.parent_id = $3)
+            AND ($4::collab.comment_status IS NULL OR c.status = $4)
+            ORDER BY c.pinned_at DESC, c.id DESC
+            "#,
+        )
+        .bind(board_id)
+        .bind(element_id)
+        .bind(parent_id)
+        .bind(status)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Where a comment sits, used to scope the per-thread pin limit.
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct CommentThreadRow {
+    pub element_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+    pub is_pinned: bool,
+}
+
+/// Serializes pin changes on a board so the per-thread limit holds under
+/// concurrent requests.
+pub async fn lock_comment_pins(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "comments.lock_comment_pins",
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1::text, 0))")
+            .bind(format!("comment_pins:{}", board_id))
+            .execute(&mut **tx)
+    )?;
+    Ok(())
+}
+
+pub async fn find_comment_thread(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+    comment_id: Uuid,
+) -> Result<Option<CommentThreadRow>, AppError> {
+    let row = crate::log_query_fetch_optional!(
+        "comments.find_comment_thread",
+        sqlx::query_as::<_, CommentThreadRow>(
+            r#"
+            SELECT element_id, parent_id, is_pinned
+            FROM collab.comment
+            WHERE id = $1
+            AND board_id = $2
+            AND deleted_at IS NULL
+            "#,
+        )
+        .bind(comment_id)
+        .bind(board_id)
+        .fetch_optional(&mut **tx)
+    )?;
+
+    Ok(row)
+}
+
+/// Counts pinned comments sharing the same element and parent.
+pub async fn count_pinned_in_thread(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+    element_id: Option<Uuid>,
+    parent_id: Option<Uuid>,
+) -> Result<i64, AppError> {
+    let count = crate::log_query_fetch_one!(
+        "comments.count_pinned_in_thread",
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM collab.comment
+            WHERE board_id = $1
+            AND element_id IS NOT DISTINCT FROM $2
+            AND parent_id IS NOT DISTINCT FROM $3
+            AND is_pinned
+            AND deleted_at IS NULL
+            "#,
+        )
+        .bind(board_id)
+        .bind(element_id)
+        .bind(parent_id)
+        .fetch_one(&mut **tx)
+    )?;
+
+    Ok(count)
+}
+
+/// Pins the comment for `pinned_by`, or unpins it when `None`.
+pub async fn set_comment_pinned(
+    tx: &mut Transaction<'_, Postgres>,
+    comment_id: Uuid,
+    pinned_by: Option<Uuid>,
+) -> Result<CommentRow, AppError> {
+    let row = crate::log_query_fetch_one!(
+        "comments.set_comment_pinned",
+        sqlx::query_as::<_, CommentRow>(
+            r#"
+            WITH updated AS (
+                UPDATE collab.comment
+                SET is_pinned = $2::uuid IS NOT NULL,
+                    pinned_by = $2,
+                    pinned_at = CASE WHEN $2::uuid IS NULL THEN NULL ELSE now() END
+                WHERE id = $1
+                RETURNING *
+            )
+            SELECT
+                updated.id,
+                updated.board_id,
+                updated.element_id,
+                updated.parent_id,
+                updated.created_by,
+                updated.position_x,
+                updated.position_y,
+                updated.content,
+                updated.content_html,
+                updated.mentions,
+                updated.status,
+                updated.resolved_by,
+                updated.resolved_at,
+                updated.is_edited,
+                updated.edited_at,
+                updated.reply_count,
+                updated.is_pinned,
+                updated.pinned_by,
+                updated.pinned_at,
+                updated.created_at,
+                updated.updated_at,
+                u.username AS author_username,
+                COALESCE(u.display_name, 'Deleted user') AS author_display_name,
+                u.avatar_url AS author_avatar_url
+            FROM updated
+            LEFT JOIN core.user u ON u.id = updated.created_by
+            "#,
+        )
+        .bind(comment_id)
+        .bind(pinned_by)
+        .fetch_one(&mut **tx)
+    )?;
+
+    Ok(row)
+}
+
 pub async fn filter_mentions(
     pool: &PgPool,
     board_id: Uuid,
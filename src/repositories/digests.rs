@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, prelude::FromRow, types::Json};
+use uuid::Uuid;
+
+use crate::{error::AppError, models::users::UserPreferences};
+
+#[derive(Debug, FromRow)]
+pub struct DigestRecipientRow {
+    pub id: Uuid,
+    pub email: String,
+    pub preferences: Json<UserPreferences>,
+    pub digest_sent_at: Option<DateTime<Utc>>,
+}
+
+/// Per-board, per-actor counts of elements and comments created in a window.
+#[derive(Debug, Clone, FromRow)]
+pub struct BoardActivityRow {
+    pub board_id: Uuid,
+    pub board_name: String,
+    pub actor_name: Option<String>,
+    pub elements: i64,
+    pub comments: i64,
+}
+
+/// Lists verified users who opted into a digest and whose period has elapsed.
+pub async fn list_users_due_for_digest(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<DigestRecipientRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "digests.list_users_due_for_digest",
+        sqlx::query_as::<_, DigestRecipientRow>(
+            r#"
+                SELECT id, email, preferences, digest_sent_at
+                FROM core.user
+                WHERE deleted_at IS NULL
+                AND is_active = true
+                AND email_verified_at IS NOT NULL
+                AND COALESCE((preferences->'notifications'->>'email')::boolean, true)
+                AND preferences->'notifications'->>'digestFrequency' IN ('daily', 'weekly')
+                AND (
+                    digest_sent_at IS NULL
+                    OR digest_sent_at <= CURRENT_TIMESTAMP - CASE
+                        preferences->'notifications'->>'digestFrequency'
+                        WHEN 'weekly' THEN INTERVAL '7 days'
+                        ELSE INTERVAL '1 day'
+                    END
+                )
+                ORDER BY digest_sent_at NULLS FIRST
+                LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(pool)
+    )?;
+    Ok(rows)
+}
+
+/// Stamps `digest_sent_at` unless another worker already did since
+/// `previous`. Returns whether this caller claimed the digest.
+pub async fn claim_digest(
+    pool: &PgPool,
+    user_id: Uuid,
+    previous: Option<DateTime<Utc>>,
+) -> Result<bool, AppError> {
+    let result = crate::log_query_execute!(
+        "digests.claim_digest",
+        sqlx::query(
+            r#"
+                UPDATE core.user
+                SET digest_sent_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND digest_sent_at IS NOT DISTINCT FROM $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(previous)
+        .execute(pool)
+    )?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Restores the `digest_sent_at` that [`claim_digest`] replaced when the
+/// digest could not be sent, so the user is picked up again on the next run.
+pub async fn release_digest_claim(
+    pool: &PgPool,
+    user_id: Uuid,
+    previous: Option<DateTime<Utc>>,
+) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "digests.release_digest_claim",
+        sqlx::query("UPDATE core.user SET digest_sent_at = $2 WHERE id = $1")
+            .bind(user_id)
+            .bind(previous)
+            .execute(pool)
+    )?;
+    Ok(())
+}
+
+/// Activity by other users since `since` on boards the user is a member of
+/// or watches.
+pub async fn list_board_activity_since(
+    pool: &PgPool,
+    user_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<Vec<BoardActivityRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "digests.list_board_activity_since",
+        sqlx::query_as::<_, BoardActivityRow>(
+            r#"
+                WITH followed AS (
+                    SELECT board_id FROM board.board_member WHERE user_id = $1
+                    UNION
+                    SELECT board_id FROM board.watcher WHERE user_id = $1
+                ),
+                activity AS (
+                    SELECT e.board_id, e.created_by AS actor_id, 1 AS elements, 0 AS comments
+                    FROM board.element e
+                    JOIN followed f ON f.board_id = e.board_id
+                    WHERE e.created_at >= $2 AND e.deleted_at IS NULL
+                    UNION ALL
+                    SELECT c.board_id, c.created_by AS actor_id, 0 AS elements, 1 AS comments
+                    FROM collab.comment c
+                    JOIN followed f ON f.board_id = c.board_id
+                    WHERE c.created_at >= $2 AND c.deleted_at IS NULL
+                )
+                SELECT
+                    a.board_id,
+                    b.name AS board_name,
+                    a.actor_id,
+                    u.display_name AS actor_name,
+                    SUM(a.elements)::bigint AS elements,
+                    SUM(a.comments)::bigint AS comments
+                FROM activity a
+                JOIN board.board b ON b.id = a.board_id
+                LEFT JOIN core.user u ON u.id = a.actor_id
+                WHERE b.deleted_at IS NULL
+                AND b.archived_at IS NULL
+                AND a.actor_id IS NOT NULL
+                AND a.actor_id <> $1
+                GROUP BY a.board_id, b.name, a.actor_id, u.display_name
+            "#,
+        )
+        .bind(user_id)
+        .bind(since)
+        .fetch_all(pool)
+    )?;
+    Ok(rows)
+}
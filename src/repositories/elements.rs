@@ -1,3 +1,5 @@
+use std::sync::OnceLock;
+
 use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
@@ -74,6 +76,17 @@ pub struct ElementVersionRow {
     pub version: i32,
 }
 
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ElementHistoryRow {
+    pub version: i32,
+    pub operation: String,
+    pub changed_by: Option<Uuid>,
+    pub changed_fields: Vec<String>,
+    #[sqlx(json)]
+    pub element_data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
 pub async fn find_element_by_id(
     pool: &PgPool,
     board_id: Uuid,
@@ -98,6 +111,26 @@ pub async fn find_element_by_id(
     Ok(element)
 }
 
+/// Whether any board, including deleted elements, already uses `element_id`.
+pub async fn element_id_exists(pool: &PgPool, element_id: Uuid) -> Result<bool, AppError> {
+    let exists = crate::log_query_fetch_one!(
+        "elements.id_exists",
+        sqlx::query_scalar::<_, bool>(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM board.element
+                    WHERE id = $1
+                )
+            "#,
+        )
+        .bind(element_id)
+        .fetch_one(pool)
+    )?;
+
+    Ok(exists)
+}
+
 pub async fn find_element_by_id_including_deleted(
     pool: &PgPool,
     board_id: Uuid,
@@ -152,6 +185,32 @@ pub async fn soft_delete_element(
     Ok(element)
 }
 
+/// Deletes element rows together with their recorded history.
+pub async fn hard_delete_elements(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+    element_ids: &[Uuid],
+) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "elements.hard_delete_history",
+        sqlx::query(
+            "DELETE FROM board.element_history WHERE element_id = ANY($1) AND board_id = $2",
+        )
+        .bind(element_ids)
+        .bind(board_id)
+        .execute(&mut **tx)
+    )?;
+    crate::log_query_execute!(
+        "elements.hard_delete",
+        sqlx::query("DELETE FROM board.element WHERE id = ANY($1) AND board_id = $2")
+            .bind(element_ids)
+            .bind(board_id)
+            .execute(&mut **tx)
+    )?;
+
+    Ok(())
+}
+
 pub async fn restore_element(
     tx: &mut Transaction<'_, Postgres>,
     board_id: Uuid,
@@ -278,14 +337,15 @@ pub async fn lock_board_elements(
     Ok(())
 }
 
+/// Sets the user credited in `board.element_history`; `None` credits nobody.
 pub async fn set_actor_id(
     tx: &mut Transaction<'_, Postgres>,
-    user_id: Uuid,
+    user_id: Option<Uuid>,
 ) -> Result<(), AppError> {
     crate::log_query_execute!(
         "elements.set_actor_id",
         sqlx::query("SELECT set_config('app.current_user_id', $1, true)")
-            .bind(user_id.to_string())
+            .bind(user_id.map(|id| id.to_string()).unwrap_or_default())
             .execute(&mut **tx)
     )?;
 
@@ -622,6 +682,134 @@ pub async fn list_elements_by_board(
     Ok(elements)
 }
 
+/// Axis-aligned region used to filter elements by position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementBounds {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+/// Lists live elements in paint order, optionally limited to those whose box
+/// intersects `bounds`.
+pub async fn list_elements_in_region(
+    pool: &PgPool,
+    board_id: Uuid,
+    bounds: Option<ElementBounds>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<BoardElement>, AppError> {
+    let elements = crate::log_query_fetch_all!(
+        "elements.list_elements_in_region",
+        sqlx::query_as::<_, BoardElement>(
+            r#"
+                SELECT *
+                FROM board.element
+                WHERE board_id = $1
+                  AND deleted_at IS NULL
+                  AND ($2::float8 IS NULL OR position_x + width >= $2)
+                  AND ($3::float8 IS NULL OR position_y + height >= $3)
+                  AND ($4::float8 IS NULL OR position_x <= $4)
+                  AND ($5::float8 IS NULL OR position_y <= $5)
+                ORDER BY z_index ASC, created_at ASC, id ASC
+                LIMIT $6 OFFSET $7
+            "#,
+        )
+        .bind(board_id)
+        .bind(bounds.map(|bounds| bounds.min_x))
+        .bind(bounds.map(|bounds| bounds.min_y))
+        .bind(bounds.map(|bounds| bounds.max_x))
+        .bind(bounds.map(|bounds| bounds.max_y))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+    )?;
+
+    Ok(elements)
+}
+
+const ELEMENT_BOUNDS_GIST_INDEX: &str = "idx_element_bounds_gist";
+
+static BOUNDS_GIST_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Lists live elements intersecting `bounds`, using the bounding-box GiST
+/// index when the database has it and a range scan otherwise.
+pub async fn list_elements_in_bounds(
+    pool: &PgPool,
+    board_id: Uuid,
+    bounds: ElementBounds,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<BoardElement>, AppError> {
+    if !bounds_gist_available(pool).await {
+        return list_elements_in_region(pool, board_id, Some(bounds), limit, offset).await;
+    }
+    let elements = crate::log_query_fetch_all!(
+        "elements.list_elements_in_bounds",
+        sqlx::query_as::<_, BoardElement>(
+            r#"
+                SELECT *
+                FROM board.element
+                WHERE board_id = $1
+                  AND deleted_at IS NULL
+                  AND box(point(position_x, position_y), point(position_x + width, position_y + height))
+                      && box(point($2, $3), point($4, $5))
+                ORDER BY z_index ASC, created_at ASC, id ASC
+                LIMIT $6 OFFSET $7
+            "#,
+        )
+        .bind(board_id)
+        .bind(bounds.min_x)
+        .bind(bounds.min_y)
+        .bind(bounds.max_x)
+        .bind(bounds.max_y)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+    )?;
+
+    Ok(elements)
+}
+
+/// Checks once per process whether the bounding-box GiST index exists.
+async fn bounds_gist_available(pool: &PgPool) -> bool {
+    if let Some(available) = BOUNDS_GIST_AVAILABLE.get() {
+        return *available;
+    }
+    let result = crate::log_query_fetch_one!(
+        "elements.bounds_gist_available",
+        sqlx::query_scalar::<_, bool>(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1
+                    FROM pg_indexes
+                    WHERE schemaname = 'board'
+                      AND tablename = 'element'
+                      AND indexname = $1
+                )
+            "#,
+        )
+        .bind(ELEMENT_BOUNDS_GIST_INDEX)
+        .fetch_one(pool)
+    );
+    match result {
+        Ok(available) => {
+            if !available {
+                tracing::info!(
+                    index = ELEMENT_BOUNDS_GIST_INDEX,
+                    "Element bounds GiST index missing; viewport queries use range scans"
+                );
+            }
+            *BOUNDS_GIST_AVAILABLE.get_or_init(|| available)
+        }
+        Err(error) => {
+            tracing::warn!("Failed to detect element bounds index: {}", error);
+            false
+        }
+    }
+}
+
 pub async fn list_elements_by_board_including_deleted(
     pool: &PgPool,
     board_id: Uuid,
@@ -643,6 +831,62 @@ pub async fn list_elements_by_board_including_deleted(
     Ok(elements)
 }
 
+/// Live boards whose highest live element `z_index` exceeds `threshold`.
+pub async fn list_boards_with_z_index_above(
+    pool: &PgPool,
+    threshold: i32,
+    limit: i64,
+) -> Result<Vec<Uuid>, AppError> {
+    let board_ids = crate::log_query_fetch_all!(
+        "elements.list_boards_with_z_index_above",
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT e.board_id
+            FROM board.element e
+            JOIN board.board b ON b.id = e.board_id
+            WHERE e.deleted_at IS NULL
+            AND b.deleted_at IS NULL
+            GROUP BY e.board_id
+            HAVING MAX(e.z_index) > $1
+            LIMIT $2
+            "#,
+        )
+        .bind(threshold)
+        .bind(limit)
+        .fetch_all(pool)
+    )?;
+
+    Ok(board_ids)
+}
+
+/// Elements soft-deleted at or after `since`, most recently deleted first.
+pub async fn list_recently_deleted_elements(
+    pool: &PgPool,
+    board_id: Uuid,
+    since: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<BoardElement>, AppError> {
+    let elements = crate::log_query_fetch_all!(
+        "elements.list_recently_deleted_elements",
+        sqlx::query_as::<_, BoardElement>(
+            r#"
+                SELECT *
+                FROM board.element
+                WHERE board_id = $1
+                  AND deleted_at >= $2
+                ORDER BY deleted_at DESC, z_index DESC
+                LIMIT $3
+            "#,
+        )
+        .bind(board_id)
+        .bind(since)
+        .bind(limit)
+        .fetch_all(pool)
+    )?;
+
+    Ok(elements)
+}
+
 pub async fn list_projection_defaults(
     pool: &PgPool,
     board_id: Uuid,
@@ -682,3 +926,100 @@ pub async fn list_projection_defaults_tx(
 
     Ok(rows)
 }
+
+pub async fn list_element_history(
+    pool: &PgPool,
+    board_id: Uuid,
+    element_id: Uuid,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ElementHistoryRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "elements.list_element_history",
+        sqlx::query_as::<_, ElementHistoryRow>(
+            r#"
+                SELECT
+                    version,
+                    operation,
+                    changed_by,
+                    changed_fields,
+                    element_data,
+                    created_at
+                FROM board.element_history
+                WHERE board_id = $1
+                  AND element_id = $2
+                ORDER BY version DESC
+                LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(board_id)
+        .bind(element_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Deletes all but the newest `keep_versions` history rows of each element.
+pub async fn prune_element_history(pool: &PgPool, keep_versions: i64) -> Result<u64, AppError> {
+    let rows = crate::log_query_execute!(
+        "elements.prune_element_history",
+        sqlx::query(
+            r#"
+                DELETE FROM board.element_history AS history
+                USING (
+                    SELECT id
+                    FROM (
+                        SELECT
+                            id,
+                            row_number() OVER (
+                                PARTITION BY element_id
+                                ORDER BY version DESC
+                            ) AS position
+                        FROM board.element_history
+                    ) AS ranked
+                    WHERE ranked.position > $1
+                ) AS stale
+                WHERE history.id = stale.id
+            "#,
+        )
+        .bind(keep_versions)
+        .execute(pool)
+    )?;
+
+    Ok(rows.rows_affected())
+}
+
+pub async fn find_element_history_version(
+    pool: &PgPool,
+    board_id: Uuid,
+    element_id: Uuid,
+    version: i32,
+) -> Result<Option<ElementHistoryRow>, AppError> {
+    let row = crate::log_query_fetch_optional!(
+        "elements.find_element_history_version",
+        sqlx::query_as::<_, ElementHistoryRow>(
+            r#"
+                SELECT
+                    version,
+                    operation,
+                    changed_by,
+                    changed_fields,
+                    element_data,
+                    created_at
+                FROM board.element_history
+                WHERE board_id = $1
+                  AND element_id = $2
+                  AND version = $3
+            "#,
+        )
+        .bind(board_id)
+        .bind(element_id)
+        .bind(version)
+        .fetch_optional(pool)
+    )?;
+
+    Ok(row)
+}
@@ -1,8 +1,15 @@
+pub(crate) mod api_tokens;
+pub(crate) mod board_watchers;
 pub(crate) mod boards;
 pub(crate) mod comments;
+pub(crate) mod digests;
 pub(crate) mod elements;
 pub(crate) mod notifications;
 pub(crate) mod organizations;
 pub(crate) mod presence;
+pub(crate) mod reactions;
 pub(crate) mod realtime;
+pub(crate) mod sessions;
+pub(crate) mod tier_limits;
 pub(crate) mod users;
+pub(crate) mod voting;
@@ -5,7 +5,7 @@ use crate::{
     dto::organizations::CreateOrganizationRequest,
     error::AppError,
     models::{
-        organizations::{OrgRole, Organization},
+        organizations::{OrgRole, Organization, OrganizationBranding},
         users::SubscriptionTier,
     },
 };
@@ -32,6 +32,35 @@ pub(crate) struct OrganizationMemberRow {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct OrganizationMemberExportRow {
+    pub email: String,
+    pub display_name: String,
+    pub role: OrgRole,
+    pub accepted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct OrganizationMemberAccessRow {
+    pub organization_id: Uuid,
+    #[sqlx(flatten)]
+    pub member: OrganizationMemberRecord,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct PendingMemberInviteRow {
+    pub member_id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct PendingEmailInviteReminderRow {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub email: String,
+}
+
 #[derive(Debug, sqlx::FromRow)]
 pub(crate) struct OrganizationMemberRecord {
     pub user_id: Uuid,
@@ -155,11 +184,58 @@ pub async fn get_member_by_user_id(
     Ok(member)
 }
 
-/// Lists members of an organization with user info.
+/// Returns the user's membership records for a set of organizations.
+pub async fn list_member_records_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    organization_ids: &[Uuid],
+) -> Result<Vec<OrganizationMemberAccessRow>, AppError> {
+    if organization_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rows = crate::log_query_fetch_all!(
+        "organizations.list_member_records_for_user",
+        sqlx::query_as::<_, OrganizationMemberAccessRow>(
+            r#"
+                SELECT organization_id, user_id, role, accepted_at
+                FROM core.organization_member
+                WHERE user_id = $1
+                AND organization_id = ANY($2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(organization_ids)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Keyset position in the member listing, ordered by `(created_at, id)`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OrganizationMemberCursor {
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub id: Uuid,
+}
+
+/// Optional filters for the member listing; `accepted` selects accepted
+/// (`true`) or pending (`false`) members.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OrganizationMemberFilter {
+    pub role: Option<OrgRole>,
+    pub accepted: Option<bool>,
+}
+
+/// Lists one page of organization members with user info, oldest first.
 pub async fn list_members(
     pool: &PgPool,
     organization_id: Uuid,
+    filter: OrganizationMemberFilter,
+    cursor: Option<OrganizationMemberCursor>,
+    limit: i64,
 ) -> Result<Vec<OrganizationMemberRow>, AppError> {
+    let cursor_created_at = cursor.map(|value| value.created_at);
+    let cursor_id = cursor.map(|value| value.id);
     let rows = crate::log_query_fetch_all!(
         "organizations.list_members",
         sqlx::query_as::<_, OrganizationMemberRow>(
@@ -179,6 +255,106 @@ pub async fn list_members(
                 JOIN core.user u ON u.id = om.user_id
                 WHERE om.organization_id = $1
                 AND u.deleted_at IS NULL
+                AND ($2::core.org_role IS NULL OR om.role = $2)
+                AND ($3::boolean IS NULL OR (om.accepted_at IS NOT NULL) = $3)
+                AND (
+                    $4::timestamptz IS NULL
+                    OR (om.created_at, om.id) > ($4::timestamptz, $5::uuid)
+                )
+                ORDER BY om.created_at ASC, om.id ASC
+                LIMIT $6
+            "#,
+        )
+        .bind(organization_id)
+        .bind(filter.role)
+        .bind(filter.accepted)
+        .bind(cursor_created_at)
+        .bind(cursor_id)
+        .bind(limit)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Counts members shown in the member listing for `filter`.
+pub async fn count_listed_members(
+    pool: &PgPool,
+    organization_id: Uuid,
+    filter: OrganizationMemberFilter,
+) -> Result<i64, AppError> {
+    let total = crate::log_query_fetch_one!(
+        "organizations.count_listed_members",
+        sqlx::query_scalar::<_, i64>(
+            r#"
+                SELECT COUNT(*)
+                FROM core.organization_member om
+                JOIN core.user u ON u.id = om.user_id
+                WHERE om.organization_id = $1
+                AND u.deleted_at IS NULL
+                AND ($2::core.org_role IS NULL OR om.role = $2)
+                AND ($3::boolean IS NULL OR (om.accepted_at IS NOT NULL) = $3)
+            "#,
+        )
+        .bind(organization_id)
+        .bind(filter.role)
+        .bind(filter.accepted)
+        .fetch_one(pool)
+    )?;
+
+    Ok(total)
+}
+
+/// Lists accepted owners and admins with user info.
+pub async fn list_accepted_managers(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<Vec<OrganizationMemberRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "organizations.list_accepted_managers",
+        sqlx::query_as::<_, OrganizationMemberRow>(
+            r#"
+                SELECT
+                    om.id AS member_id,
+                    u.id AS user_id,
+                    u.username,
+                    u.display_name,
+                    u.avatar_url,
+                    om.role,
+                    om.invited_at,
+                    om.accepted_at,
+                    om.created_at,
+                    om.updated_at
+                FROM core.organization_member om
+                JOIN core.user u ON u.id = om.user_id
+                WHERE om.organization_id = $1
+                AND om.role IN ('owner', 'admin')
+                AND om.accepted_at IS NOT NULL
+                AND u.deleted_at IS NULL
+                ORDER BY om.created_at ASC
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Lists members with their email for roster export, pending invites included.
+pub async fn list_members_for_export(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<Vec<OrganizationMemberExportRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "organizations.list_members_for_export",
+        sqlx::query_as::<_, OrganizationMemberExportRow>(
+            r#"
+                SELECT u.email, u.display_name, om.role, om.accepted_at
+                FROM core.organization_member om
+                JOIN core.user u ON u.id = om.user_id
+                WHERE om.organization_id = $1
+                AND u.deleted_at IS NULL
                 ORDER BY om.created_at ASC
             "#,
         )
@@ -189,6 +365,54 @@ pub async fn list_members(
     Ok(rows)
 }
 
+/// Searches organization members by username, display name, or email.
+///
+/// `pattern` is an already-escaped ILIKE pattern.
+pub async fn search_members(
+    pool: &PgPool,
+    organization_id: Uuid,
+    pattern: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<OrganizationMemberRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "organizations.search_members",
+        sqlx::query_as::<_, OrganizationMemberRow>(
+            r#"
+                SELECT
+                    om.id AS member_id,
+                    u.id AS user_id,
+                    u.username,
+                    u.display_name,
+                    u.avatar_url,
+                    om.role,
+                    om.invited_at,
+                    om.accepted_at,
+                    om.created_at,
+                    om.updated_at
+                FROM core.organization_member om
+                JOIN core.user u ON u.id = om.user_id
+                WHERE om.organization_id = $1
+                AND u.deleted_at IS NULL
+                AND (
+                    u.username ILIKE $2 ESCAPE '\'
+                    OR u.display_name ILIKE $2 ESCAPE '\'
+                    OR u.email ILIKE $2 ESCAPE '\'
+                )
+                ORDER BY u.display_name ASC, om.id ASC
+                LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(organization_id)
+        .bind(pattern)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
 /// Lists pre-signup invites for an organization.
 pub async fn list_email_invites(
     pool: &PgPool,
@@ -520,31 +744,33 @@ pub async fn accept_member_invitation(
     Ok(())
 }
 
-/// Updates all owners to a new role, excluding the target member.
+/// Updates all owners to a new role, excluding the target member, and
+/// returns the user ids of the demoted owners.
 pub async fn demote_other_owners(
     tx: &mut Transaction<'_, Postgres>,
     organization_id: Uuid,
     target_member_id: Uuid,
     new_role: OrgRole,
-) -> Result<(), AppError> {
-    crate::log_query_execute!(
+) -> Result<Vec<Uuid>, AppError> {
+    let user_ids = crate::log_query_fetch_all!(
         "organizations.demote_other_owners",
-        sqlx::query(
+        sqlx::query_scalar::<_, Uuid>(
             r#"
                 UPDATE core.organization_member
                 SET role = $3, updated_at = NOW()
                 WHERE organization_id = $1
                 AND role = 'owner'
                 AND id <> $2
+                RETURNING user_id
             "#,
         )
         .bind(organization_id)
         .bind(target_member_id)
         .bind(new_role)
-        .execute(&mut **tx)
+        .fetch_all(&mut **tx)
     )?;
 
-    Ok(())
+    Ok(user_ids)
 }
 
 /// Removes a member from an organization.
@@ -683,6 +909,173 @@ pub async fn resend_invite(
     Ok(())
 }
 
+/// Claims up to `limit` pending member invites sent before `cutoff` that have
+/// not had a reminder, stamping `reminder_sent_at` so no other worker picks
+/// them up. Invites that already failed `max_attempts` times are skipped, and
+/// earlier failures sort behind fresh invites.
+pub async fn claim_members_due_for_reminder(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    max_attempts: i32,
+    limit: i64,
+) -> Result<Vec<PendingMemberInviteRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "organizations.claim_members_due_for_reminder",
+        sqlx::query_as::<_, PendingMemberInviteRow>(
+            r#"
+                UPDATE core.organization_member
+                SET reminder_sent_at = NOW(), updated_at = NOW()
+                WHERE id IN (
+                    SELECT om.id
+                    FROM core.organization_member om
+                    JOIN core.organization o ON o.id = om.organization_id
+                    JOIN core.user u ON u.id = om.user_id
+                    WHERE om.accepted_at IS NULL
+                    AND om.reminder_sent_at IS NULL
+                    AND om.reminder_attempts < $2
+                    AND om.invited_at <= $1
+                    AND o.deleted_at IS NULL
+                    AND u.deleted_at IS NULL
+                    ORDER BY om.reminder_attempts ASC, om.invited_at ASC
+                    LIMIT $3
+                    FOR UPDATE OF om SKIP LOCKED
+                )
+                RETURNING id AS member_id, organization_id, user_id
+            "#,
+        )
+        .bind(cutoff)
+        .bind(max_attempts)
+        .bind(limit)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Claims up to `limit` unexpired email invites sent before `cutoff` that
+/// have not had a reminder; see [`claim_members_due_for_reminder`].
+pub async fn claim_email_invites_due_for_reminder(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    max_attempts: i32,
+    limit: i64,
+) -> Result<Vec<PendingEmailInviteReminderRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "organizations.claim_email_invites_due_for_reminder",
+        sqlx::query_as::<_, PendingEmailInviteReminderRow>(
+            r#"
+                UPDATE core.organization_invite
+                SET reminder_sent_at = NOW(), updated_at = NOW()
+                WHERE id IN (
+                    SELECT oi.id
+                    FROM core.organization_invite oi
+                    JOIN core.organization o ON o.id = oi.organization_id
+                    WHERE oi.reminder_sent_at IS NULL
+                    AND oi.reminder_attempts < $2
+                    AND oi.invited_at <= $1
+                    AND (oi.invite_expires_at IS NULL OR oi.invite_expires_at > NOW())
+                    AND o.deleted_at IS NULL
+                    ORDER BY oi.reminder_attempts ASC, oi.invited_at ASC
+                    LIMIT $3
+                    FOR UPDATE OF oi SKIP LOCKED
+                )
+                RETURNING id, organization_id, email
+            "#,
+        )
+        .bind(cutoff)
+        .bind(max_attempts)
+        .bind(limit)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Clears the claim taken by [`claim_members_due_for_reminder`] after a failed
+/// send and counts the attempt, so the invite is retried on a later run.
+pub async fn release_member_reminder_claim(pool: &PgPool, member_id: Uuid) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "organizations.release_member_reminder_claim",
+        sqlx::query(
+            r#"
+                UPDATE core.organization_member
+                SET reminder_sent_at = NULL, reminder_attempts = reminder_attempts + 1
+                WHERE id = $1
+            "#,
+        )
+        .bind(member_id)
+        .execute(pool)
+    )?;
+
+    Ok(())
+}
+
+/// Clears the claim taken by [`claim_email_invites_due_for_reminder`] after a
+/// failed send and counts the attempt.
+pub async fn release_email_invite_reminder_claim(
+    pool: &PgPool,
+    invite_id: Uuid,
+) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "organizations.release_email_invite_reminder_claim",
+        sqlx::query(
+            r#"
+                UPDATE core.organization_invite
+                SET reminder_sent_at = NULL, reminder_attempts = reminder_attempts + 1
+                WHERE id = $1
+            "#,
+        )
+        .bind(invite_id)
+        .execute(pool)
+    )?;
+
+    Ok(())
+}
+
+/// Deletes pre-signup invites whose expiry has passed.
+pub async fn delete_expired_email_invites(pool: &PgPool) -> Result<u64, AppError> {
+    let result = crate::log_query_execute!(
+        "organizations.delete_expired_email_invites",
+        sqlx::query(
+            r#"
+                DELETE FROM core.organization_invite
+                WHERE invite_expires_at IS NOT NULL
+                AND invite_expires_at < NOW()
+            "#,
+        )
+        .execute(pool)
+    )?;
+
+    Ok(result.rows_affected())
+}
+
+/// Lists pending member invites sent before `cutoff`.
+pub async fn list_stale_member_invites(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    limit: i64,
+) -> Result<Vec<PendingMemberInviteRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "organizations.list_stale_member_invites",
+        sqlx::query_as::<_, PendingMemberInviteRow>(
+            r#"
+                SELECT id AS member_id, organization_id, user_id
+                FROM core.organization_member
+                WHERE accepted_at IS NULL
+                AND invited_at IS NOT NULL
+                AND invited_at < $1
+                ORDER BY invited_at ASC
+                LIMIT $2
+            "#,
+        )
+        .bind(cutoff)
+        .bind(limit)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
 /// Lists organizations that the user belongs to.
 pub async fn list_organizations_by_user(
     pool: &PgPool,
@@ -719,6 +1112,10 @@ pub async fn organization_slug_exists(pool: &PgPool, slug: &str) -> Result<bool,
                     FROM core.organization
                     WHERE slug = $1
                     AND deleted_at IS NULL
+                ) OR EXISTS(
+                    SELECT 1
+                    FROM core.organization_slug_alias
+                    WHERE slug = $1
                 )
             "#,
         )
@@ -729,6 +1126,147 @@ pub async fn organization_slug_exists(pool: &PgPool, slug: &str) -> Result<bool,
     Ok(exists)
 }
 
+/// Returns the organization currently holding `slug`, as its live slug or as
+/// an alias kept from a rename.
+pub async fn find_slug_holder(
+    tx: &mut Transaction<'_, Postgres>,
+    slug: &str,
+) -> Result<Option<Uuid>, AppError> {
+    let holder = crate::log_query_fetch_optional!(
+        "organizations.find_slug_holder",
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+                SELECT id
+                FROM core.organization
+                WHERE slug = $1
+                AND deleted_at IS NULL
+                UNION ALL
+                SELECT organization_id
+                FROM core.organization_slug_alias
+                WHERE slug = $1
+                LIMIT 1
+            "#,
+        )
+        .bind(slug)
+        .fetch_optional(&mut **tx)
+    )?;
+
+    Ok(holder)
+}
+
+/// Changes an organization's slug, keeping the previous one as an alias.
+pub async fn update_organization_slug(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    previous_slug: &str,
+    slug: &str,
+) -> Result<Organization, AppError> {
+    crate::log_query_execute!(
+        "organizations.delete_slug_alias",
+        sqlx::query(
+            r#"
+                DELETE FROM core.organization_slug_alias
+                WHERE slug = $1
+                AND organization_id = $2
+            "#,
+        )
+        .bind(slug)
+        .bind(organization_id)
+        .execute(&mut **tx)
+    )?;
+
+    let organization = crate::log_query_fetch_one!(
+        "organizations.update_slug",
+        sqlx::query_as(
+            r#"
+                UPDATE core.organization
+                SET slug = $2, updated_at = NOW()
+                WHERE id = $1
+                AND deleted_at IS NULL
+                RETURNING *
+            "#,
+        )
+        .bind(organization_id)
+        .bind(slug)
+        .fetch_one(&mut **tx)
+    )
+    .map_err(map_unique_violation)?;
+
+    crate::log_query_execute!(
+        "organizations.insert_slug_alias",
+        sqlx::query(
+            r#"
+                INSERT INTO core.organization_slug_alias (slug, organization_id)
+                VALUES ($1, $2)
+                ON CONFLICT (slug) DO NOTHING
+            "#,
+        )
+        .bind(previous_slug)
+        .bind(organization_id)
+        .execute(&mut **tx)
+    )?;
+
+    Ok(organization)
+}
+
+/// Replaces the organization logo and `settings.branding`.
+pub async fn update_organization_branding(
+    pool: &PgPool,
+    organization_id: Uuid,
+    logo_url: Option<&str>,
+    branding: &OrganizationBranding,
+) -> Result<Organization, AppError> {
+    let branding = serde_json::to_value(branding)
+        .map_err(|error| AppError::Internal(format!("Failed to encode branding: {}", error)))?;
+    let organization = crate::log_query_fetch_optional!(
+        "organizations.update_branding",
+        sqlx::query_as::<_, Organization>(
+            r#"
+                UPDATE core.organization
+                SET logo_url = $2,
+                    settings = jsonb_set(COALESCE(settings, '{}'::jsonb), '{branding}', $3),
+                    updated_at = NOW()
+                WHERE id = $1
+                AND deleted_at IS NULL
+                RETURNING *
+            "#,
+        )
+        .bind(organization_id)
+        .bind(logo_url)
+        .bind(branding)
+        .fetch_optional(pool)
+    )?;
+
+    organization.ok_or(AppError::NotFound("Organization not found".to_string()))
+}
+
+/// Merges `defaults` into `settings`, replacing the board-default keys it
+/// carries.
+pub async fn update_board_defaults(
+    pool: &PgPool,
+    organization_id: Uuid,
+    defaults: &serde_json::Value,
+) -> Result<Organization, AppError> {
+    let organization = crate::log_query_fetch_optional!(
+        "organizations.update_board_defaults",
+        sqlx::query_as::<_, Organization>(
+            r#"
+                UPDATE core.organization
+                SET settings = COALESCE(settings, '{}'::jsonb) || $2,
+                    updated_at = NOW()
+                WHERE id = $1
+                AND deleted_at IS NULL
+                RETURNING *
+            "#,
+        )
+        .bind(organization_id)
+        .bind(defaults)
+        .fetch_optional(pool)
+    )?;
+
+    organization.ok_or(AppError::NotFound("Organization not found".to_string()))
+}
+
 /// Inserts a new organization row and returns the full organization model.
 pub async fn create_organization(
     tx: &mut Transaction<'_, Postgres>,
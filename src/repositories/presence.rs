@@ -139,28 +139,68 @@ pub async fn update_presence_status(
     Ok(())
 }
 
-pub async fn update_heartbeat(
+/// A connected session's presence, keyed by session for re-seeding Redis.
+#[derive(sqlx::FromRow)]
+pub struct PresenceSessionRow {
+    pub session_id: Uuid,
+    #[sqlx(flatten)]
+    pub user: PresenceUser,
+}
+
+pub async fn list_session_presence(
     pool: &PgPool,
     board_id: Uuid,
-    session_id: Uuid,
-) -> Result<(), AppError> {
-    crate::log_query_execute!(
-        "presence.update_heartbeat",
+    session_ids: &[Uuid],
+) -> Result<Vec<PresenceSessionRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "presence.list_session_presence",
+        sqlx::query_as::<_, PresenceSessionRow>(
+            r#"
+                SELECT
+                    p.session_id,
+                    p.user_id,
+                    u.display_name,
+                    u.avatar_url,
+                    p.status,
+                    p.connected_at,
+                    p.last_heartbeat_at
+                FROM collab.presence p
+                JOIN core.user u ON u.id = p.user_id
+                WHERE p.board_id = $1
+                  AND p.session_id = ANY($2)
+                  AND p.disconnected_at IS NULL
+            "#,
+        )
+        .bind(board_id)
+        .bind(session_ids)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+pub async fn update_heartbeats(
+    pool: &PgPool,
+    board_id: Uuid,
+    session_ids: &[Uuid],
+) -> Result<u64, AppError> {
+    let result = crate::log_query_execute!(
+        "presence.update_heartbeats",
         sqlx::query(
             r#"
                 UPDATE collab.presence
                 SET last_heartbeat_at = CURRENT_TIMESTAMP
                 WHERE board_id = $1
-                  AND session_id = $2
+                  AND session_id = ANY($2)
                   AND disconnected_at IS NULL
             "#,
         )
         .bind(board_id)
-        .bind(session_id)
+        .bind(session_ids)
         .execute(pool)
     )?;
 
-    Ok(())
+    Ok(result.rows_affected())
 }
 
 pub async fn mark_disconnected(
@@ -189,6 +229,32 @@ pub async fn mark_disconnected(
     Ok(())
 }
 
+pub async fn mark_sessions_disconnected(
+    pool: &PgPool,
+    board_id: Uuid,
+    session_ids: &[Uuid],
+) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "presence.mark_sessions_disconnected",
+        sqlx::query(
+            r#"
+                UPDATE collab.presence
+                SET status = $3,
+                    disconnected_at = CURRENT_TIMESTAMP
+                WHERE board_id = $1
+                  AND session_id = ANY($2)
+                  AND disconnected_at IS NULL
+            "#,
+        )
+        .bind(board_id)
+        .bind(session_ids)
+        .bind(PresenceStatus::Offline)
+        .execute(pool)
+    )?;
+
+    Ok(())
+}
+
 pub async fn cleanup_stale_presence(
     pool: &PgPool,
     board_id: Uuid,
@@ -0,0 +1,154 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{error::AppError, models::comments::ReactionTargetType};
+
+/// Reaction count for one emoji on one target.
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct ReactionCountRow {
+    pub element_id: Option<Uuid>,
+    pub comment_id: Option<Uuid>,
+    pub emoji: String,
+    pub count: i64,
+    pub reacted: bool,
+}
+
+fn target_columns(
+    target_type: ReactionTargetType,
+    target_id: Uuid,
+) -> (Option<Uuid>, Option<Uuid>) {
+    match target_type {
+        ReactionTargetType::Element => (Some(target_id), None),
+        ReactionTargetType::Comment => (None, Some(target_id)),
+    }
+}
+
+/// Whether the live element or comment belongs to the board.
+pub async fn target_exists(
+    pool: &PgPool,
+    board_id: Uuid,
+    target_type: ReactionTargetType,
+    target_id: Uuid,
+) -> Result<bool, AppError> {
+    let (element_id, comment_id) = target_columns(target_type, target_id);
+    let exists = crate::log_query_fetch_one!(
+        "reactions.target_exists",
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM board.element
+                WHERE id = $2
+                AND board_id = $1
+                AND deleted_at IS NULL
+            )
+            OR EXISTS (
+                SELECT 1
+                FROM collab.comment
+                WHERE id = $3
+                AND board_id = $1
+                AND deleted_at IS NULL
+            )
+            "#,
+        )
+        .bind(board_id)
+        .bind(element_id)
+        .bind(comment_id)
+        .fetch_one(pool)
+    )?;
+
+    Ok(exists)
+}
+
+/// Removes the user's reaction when present, otherwise adds it. Returns
+/// whether the reaction was added.
+pub async fn toggle_reaction(
+    tx: &mut Transaction<'_, Postgres>,
+    target_type: ReactionTargetType,
+    target_id: Uuid,
+    user_id: Uuid,
+    emoji: &str,
+) -> Result<bool, AppError> {
+    let (element_id, comment_id) = target_columns(target_type, target_id);
+    let removed = crate::log_query_execute!(
+        "reactions.remove_reaction",
+        sqlx::query(
+            r#"
+            DELETE FROM collab.reaction
+            WHERE element_id IS NOT DISTINCT FROM $1
+            AND comment_id IS NOT DISTINCT FROM $2
+            AND user_id = $3
+            AND emoji = $4
+            "#,
+        )
+        .bind(element_id)
+        .bind(comment_id)
+        .bind(user_id)
+        .bind(emoji)
+        .execute(&mut **tx)
+    )?;
+    if removed.rows_affected() > 0 {
+        return Ok(false);
+    }
+
+    crate::log_query_execute!(
+        "reactions.add_reaction",
+        sqlx::query(
+            r#"
+            INSERT INTO collab.reaction (element_id, comment_id, user_id, emoji)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(element_id)
+        .bind(comment_id)
+        .bind(user_id)
+        .bind(emoji)
+        .execute(&mut **tx)
+    )?;
+
+    Ok(true)
+}
+
+/// Aggregated reactions on the board's live elements and comments, optionally
+/// limited to one target. `reacted` marks emojis `user_id` reacted with.
+pub async fn list_reaction_counts(
+    pool: &PgPool,
+    board_id: Uuid,
+    user_id: Uuid,
+    target: Option<(ReactionTargetType, Uuid)>,
+) -> Result<Vec<ReactionCountRow>, AppError> {
+    let target_type = target.map(|(target_type, _)| target_type == ReactionTargetType::Element);
+    let target_id = target.map(|(_, target_id)| target_id);
+    let rows = crate::log_query_fetch_all!(
+        "reactions.list_reaction_counts",
+        sqlx::query_as::<_, ReactionCountRow>(
+            r#"
+            SELECT
+                r.element_id,
+                r.comment_id,
+                r.emoji,
+                COUNT(*) AS count,
+                bool_or(r.user_id = $2) AS reacted
+            FROM collab.reaction r
+            LEFT JOIN board.element e ON e.id = r.element_id
+            LEFT JOIN collab.comment c ON c.id = r.comment_id
+            WHERE (
+                (e.board_id = $1 AND e.deleted_at IS NULL)
+                OR (c.board_id = $1 AND c.deleted_at IS NULL)
+            )
+            AND ($3::boolean IS NULL OR (r.element_id IS NOT NULL) = $3)
+            AND ($4::uuid IS NULL OR COALESCE(r.element_id, r.comment_id) = $4)
+            GROUP BY r.element_id, r.comment_id, r.emoji
+            ORDER BY r.element_id, r.comment_id, MIN(r.created_at), r.emoji
+            "#,
+        )
+        .bind(board_id)
+        .bind(user_id)
+        .bind(target_type)
+        .bind(target_id)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
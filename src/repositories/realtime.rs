@@ -174,6 +174,48 @@ pub async fn create_snapshot_and_cleanup(
     Ok((insert_result.rows_affected(), delete_result.rows_affected()))
 }
 
+/// Makes `state_bin` the board's only snapshot and deletes the updates it
+/// covers, returning how many updates were deleted.
+pub async fn replace_snapshots(
+    pool: &PgPool,
+    board_id: Uuid,
+    snapshot_seq: i64,
+    state_bin: Vec<u8>,
+) -> Result<u64, AppError> {
+    let mut tx = pool.begin().await?;
+
+    crate::log_query_execute!(
+        "realtime.replace_snapshots_delete",
+        sqlx::query("DELETE FROM crdt.board_snapshot WHERE board_id = $1")
+            .bind(board_id)
+            .execute(&mut *tx)
+    )?;
+    crate::log_query_execute!(
+        "realtime.replace_snapshots_insert",
+        sqlx::query(
+            r#"
+                INSERT INTO crdt.board_snapshot (board_id, snapshot_seq, state_bin)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (board_id, snapshot_seq) DO UPDATE SET state_bin = EXCLUDED.state_bin
+            "#,
+        )
+        .bind(board_id)
+        .bind(snapshot_seq)
+        .bind(state_bin)
+        .execute(&mut *tx)
+    )?;
+    let deleted = crate::log_query_execute!(
+        "realtime.replace_snapshots_cleanup",
+        sqlx::query("DELETE FROM crdt.board_update WHERE board_id = $1 AND seq <= $2")
+            .bind(board_id)
+            .bind(snapshot_seq)
+            .execute(&mut *tx)
+    )?;
+
+    tx.commit().await?;
+    Ok(deleted.rows_affected())
+}
+
 pub async fn insert_snapshot(
     tx: &mut Transaction<'_, Postgres>,
     board_id: Uuid,
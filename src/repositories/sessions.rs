@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, prelude::FromRow};
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+/// Minimum gap between `last_seen_at` writes for one session.
+const LAST_SEEN_RESOLUTION_SECS: i64 = 60;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct UserSession {
+    pub id: Uuid,
+    pub device: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub async fn insert_session(
+    pool: &PgPool,
+    session_id: Uuid,
+    user_id: Uuid,
+    device: Option<&str>,
+    ip_address: Option<&str>,
+    expires_at: DateTime<Utc>,
+) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "sessions.insert_session",
+        sqlx::query(
+            r#"
+                INSERT INTO core.session (id, user_id, device, ip_address, expires_at)
+                VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .bind(device)
+        .bind(ip_address)
+        .bind(expires_at)
+        .execute(pool)
+    )?;
+    Ok(())
+}
+
+/// Returns whether the session is still valid, refreshing `last_seen_at` at
+/// most once per minute.
+pub async fn touch_active_session(
+    pool: &PgPool,
+    session_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, AppError> {
+    let active = crate::log_query_fetch_one!(
+        "sessions.touch_active_session",
+        sqlx::query_scalar::<_, bool>(
+            r#"
+                WITH active AS (
+                    SELECT id, last_seen_at
+                    FROM core.session
+                    WHERE id = $1
+                    AND user_id = $2
+                    AND revoked_at IS NULL
+                    AND expires_at > CURRENT_TIMESTAMP
+                ),
+                touched AS (
+                    UPDATE core.session
+                    SET last_seen_at = CURRENT_TIMESTAMP
+                    WHERE id IN (
+                        SELECT id FROM active
+                        WHERE last_seen_at < CURRENT_TIMESTAMP - make_interval(secs => $3)
+                    )
+                )
+                SELECT EXISTS (SELECT 1 FROM active)
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .bind(LAST_SEEN_RESOLUTION_SECS as f64)
+        .fetch_one(pool)
+    )?;
+    Ok(active)
+}
+
+pub async fn list_active_sessions(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<UserSession>, AppError> {
+    let sessions = crate::log_query_fetch_all!(
+        "sessions.list_active_sessions",
+        sqlx::query_as::<_, UserSession>(
+            r#"
+                SELECT id, device, ip_address, created_at, last_seen_at, expires_at
+                FROM core.session
+                WHERE user_id = $1
+                AND revoked_at IS NULL
+                AND expires_at > CURRENT_TIMESTAMP
+                ORDER BY last_seen_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+    )?;
+    Ok(sessions)
+}
+
+/// Revokes one of the user's sessions. Returns false when it does not exist
+/// or was already revoked.
+pub async fn revoke_session(
+    pool: &PgPool,
+    user_id: Uuid,
+    session_id: Uuid,
+) -> Result<bool, AppError> {
+    let result = crate::log_query_execute!(
+        "sessions.revoke_session",
+        sqlx::query(
+            r#"
+                UPDATE core.session
+                SET revoked_at = CURRENT_TIMESTAMP
+                WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .execute(pool)
+    )?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,70 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{error::AppError, models::users::SubscriptionTier};
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct TierLimitRow {
+    pub tier: SubscriptionTier,
+    pub max_members: i32,
+    pub max_boards: i32,
+    pub storage_limit_mb: i32,
+}
+
+/// Loads all configured subscription tier limits.
+pub async fn list_tier_limits(pool: &PgPool) -> Result<Vec<TierLimitRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "tier_limits.list_tier_limits",
+        sqlx::query_as::<_, TierLimitRow>(
+            r#"
+                SELECT tier, max_members, max_boards, storage_limit_mb
+                FROM core.tier_limit
+            "#,
+        )
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+/// Inserts or replaces the limits for a subscription tier.
+pub async fn upsert_tier_limit(
+    pool: &PgPool,
+    tier: SubscriptionTier,
+    max_members: i32,
+    max_boards: i32,
+    storage_limit_mb: i32,
+    updated_by: Uuid,
+) -> Result<TierLimitRow, AppError> {
+    let row = crate::log_query_fetch_one!(
+        "tier_limits.upsert_tier_limit",
+        sqlx::query_as::<_, TierLimitRow>(
+            r#"
+                INSERT INTO core.tier_limit (
+                    tier,
+                    max_members,
+                    max_boards,
+                    storage_limit_mb,
+                    updated_by,
+                    updated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, NOW())
+                ON CONFLICT (tier) DO UPDATE
+                SET max_members = EXCLUDED.max_members,
+                    max_boards = EXCLUDED.max_boards,
+                    storage_limit_mb = EXCLUDED.storage_limit_mb,
+                    updated_by = EXCLUDED.updated_by,
+                    updated_at = NOW()
+                RETURNING tier, max_members, max_boards, storage_limit_mb
+            "#,
+        )
+        .bind(tier)
+        .bind(max_members)
+        .bind(max_boards)
+        .bind(storage_limit_mb)
+        .bind(updated_by)
+        .fetch_one(pool)
+    )?;
+
+    Ok(row)
+}
@@ -118,6 +118,57 @@ pub async fn update_last_active(pool: &PgPool, user_id: Uuid) -> Result<(), AppE
     Ok(())
 }
 
+/// Increments the consecutive failed login counter and returns the new value.
+pub async fn record_failed_login(pool: &PgPool, user_id: Uuid) -> Result<i32, AppError> {
+    let count = crate::log_query_fetch_one!(
+        "users.record_failed_login",
+        sqlx::query_scalar::<_, i32>(
+            r#"
+                UPDATE core.user
+                SET failed_login_count = failed_login_count + 1
+                WHERE id = $1
+                RETURNING failed_login_count
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+    )?;
+    Ok(count)
+}
+
+pub async fn lock_user_until(
+    pool: &PgPool,
+    user_id: Uuid,
+    locked_until: DateTime<Utc>,
+) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "users.lock_user_until",
+        sqlx::query("UPDATE core.user SET locked_until = $2 WHERE id = $1")
+            .bind(user_id)
+            .bind(locked_until)
+            .execute(pool)
+    )?;
+    Ok(())
+}
+
+/// Clears the failed login counter and any lock. Returns false when the user
+/// does not exist.
+pub async fn reset_login_lockout(pool: &PgPool, user_id: Uuid) -> Result<bool, AppError> {
+    let result = crate::log_query_execute!(
+        "users.reset_login_lockout",
+        sqlx::query(
+            r#"
+                UPDATE core.user
+                SET failed_login_count = 0, locked_until = NULL
+                WHERE id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(pool)
+    )?;
+    Ok(result.rows_affected() > 0)
+}
+
 pub async fn mark_email_verified_tx(
     tx: &mut Transaction<'_, Postgres>,
     user_id: Uuid,
@@ -149,6 +200,79 @@ pub async fn get_user_by_id(pool: &PgPool, user_id: Uuid) -> Result<User, AppErr
     Ok(user)
 }
 
+/// Loads active users by id without a relationship check; callers must
+/// already have established the viewer may see them.
+pub async fn find_active_users_by_ids(
+    pool: &PgPool,
+    user_ids: &[Uuid],
+) -> Result<Vec<User>, AppError> {
+    let users = crate::log_query_fetch_all!(
+        "users.find_active_users_by_ids",
+        sqlx::query_as::<_, User>(
+            r#"
+                SELECT *
+                FROM core.user
+                WHERE id = ANY($1)
+                AND deleted_at IS NULL
+            "#,
+        )
+        .bind(user_ids)
+        .fetch_all(pool)
+    )?;
+
+    Ok(users)
+}
+
+/// Loads users by id, limited to the viewer and users sharing a board or
+/// organization with them.
+pub async fn find_users_by_ids(
+    pool: &PgPool,
+    viewer_id: Uuid,
+    user_ids: &[Uuid],
+) -> Result<Vec<User>, AppError> {
+    let users = crate::log_query_fetch_all!(
+        "users.find_users_by_ids",
+        sqlx::query_as::<_, User>(
+            r#"
+                SELECT u.*
+                FROM core.user u
+                WHERE u.id = ANY($2)
+                AND u.deleted_at IS NULL
+                AND (
+                    u.id = $1
+                    OR EXISTS (
+                        SELECT 1
+                        FROM board.board_member bm_target
+                        JOIN board.board_member bm_viewer
+                            ON bm_viewer.board_id = bm_target.board_id
+                            AND bm_viewer.user_id = $1
+                        JOIN board.board b
+                            ON b.id = bm_target.board_id
+                            AND b.deleted_at IS NULL
+                        WHERE bm_target.user_id = u.id
+                    )
+                    OR EXISTS (
+                        SELECT 1
+                        FROM core.organization_member om_target
+                        JOIN core.organization_member om_viewer
+                            ON om_viewer.organization_id = om_target.organization_id
+                            AND om_viewer.user_id = $1
+                            AND om_viewer.accepted_at IS NOT NULL
+                        WHERE om_target.user_id = u.id
+                        AND om_target.accepted_at IS NOT NULL
+                    )
+                )
+                ORDER BY u.display_name
+            "#,
+        )
+        .bind(viewer_id)
+        .bind(user_ids)
+        .fetch_all(pool)
+    )?;
+
+    Ok(users)
+}
+
 pub async fn update_user_profile(
     pool: &PgPool,
     user_id: Uuid,
@@ -0,0 +1,209 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub(crate) struct VotingSessionRow {
+    pub id: Uuid,
+    pub board_id: Uuid,
+    pub votes_per_user: i32,
+    pub started_by: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_by: Option<Uuid>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct VoteTallyRow {
+    pub element_id: Uuid,
+    pub votes: i64,
+    pub my_votes: i64,
+}
+
+pub async fn create_voting_session(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+    votes_per_user: i32,
+    started_by: Uuid,
+) -> Result<VotingSessionRow, AppError> {
+    let row = crate::log_query_fetch_one!(
+        "voting.create_voting_session",
+        sqlx::query_as::<_, VotingSessionRow>(
+            r#"
+            INSERT INTO board.voting_session (board_id, votes_per_user, started_by)
+            VALUES ($1, $2, $3)
+            RETURNING id, board_id, votes_per_user, started_by, started_at, ended_by, ended_at
+            "#,
+        )
+        .bind(board_id)
+        .bind(votes_per_user)
+        .bind(started_by)
+        .fetch_one(&mut **tx)
+    )
+    .map_err(map_active_session_violation)?;
+
+    Ok(row)
+}
+
+/// Locks the board's active session so vote limits are checked and applied
+/// atomically.
+pub async fn lock_active_session(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+) -> Result<Option<VotingSessionRow>, AppError> {
+    let row = crate::log_query_fetch_optional!(
+        "voting.lock_active_session",
+        sqlx::query_as::<_, VotingSessionRow>(
+            r#"
+            SELECT id, board_id, votes_per_user, started_by, started_at, ended_by, ended_at
+            FROM board.voting_session
+            WHERE board_id = $1
+            AND ended_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(board_id)
+        .fetch_optional(&mut **tx)
+    )?;
+
+    Ok(row)
+}
+
+/// The active session, or the most recently ended one.
+pub async fn find_latest_session(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<Option<VotingSessionRow>, AppError> {
+    let row = crate::log_query_fetch_optional!(
+        "voting.find_latest_session",
+        sqlx::query_as::<_, VotingSessionRow>(
+            r#"
+            SELECT id, board_id, votes_per_user, started_by, started_at, ended_by, ended_at
+            FROM board.voting_session
+            WHERE board_id = $1
+            ORDER BY (ended_at IS NULL) DESC, started_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(board_id)
+        .fetch_optional(pool)
+    )?;
+
+    Ok(row)
+}
+
+pub async fn end_voting_session(
+    tx: &mut Transaction<'_, Postgres>,
+    session_id: Uuid,
+    ended_by: Uuid,
+) -> Result<VotingSessionRow, AppError> {
+    let row = crate::log_query_fetch_one!(
+        "voting.end_voting_session",
+        sqlx::query_as::<_, VotingSessionRow>(
+            r#"
+            UPDATE board.voting_session
+            SET ended_by = $2,
+                ended_at = now()
+            WHERE id = $1
+            RETURNING id, board_id, votes_per_user, started_by, started_at, ended_by, ended_at
+            "#,
+        )
+        .bind(session_id)
+        .bind(ended_by)
+        .fetch_one(&mut **tx)
+    )?;
+
+    Ok(row)
+}
+
+/// Votes the user cast on live elements; votes on deleted elements are
+/// given back.
+pub async fn count_user_votes(
+    tx: &mut Transaction<'_, Postgres>,
+    session_id: Uuid,
+    user_id: Uuid,
+) -> Result<i64, AppError> {
+    let count = crate::log_query_fetch_one!(
+        "voting.count_user_votes",
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM board.vote v
+            JOIN board.element e ON e.id = v.element_id
+            WHERE v.session_id = $1
+            AND v.user_id = $2
+            AND e.deleted_at IS NULL
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .fetch_one(&mut **tx)
+    )?;
+
+    Ok(count)
+}
+
+pub async fn insert_vote(
+    tx: &mut Transaction<'_, Postgres>,
+    session_id: Uuid,
+    element_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    crate::log_query_execute!(
+        "voting.insert_vote",
+        sqlx::query(
+            r#"
+            INSERT INTO board.vote (session_id, element_id, user_id)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(session_id)
+        .bind(element_id)
+        .bind(user_id)
+        .execute(&mut **tx)
+    )?;
+
+    Ok(())
+}
+
+/// Votes per live element, most voted first, with how many of them
+/// `user_id` cast.
+pub async fn list_vote_tallies(
+    pool: &PgPool,
+    session_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<VoteTallyRow>, AppError> {
+    let rows = crate::log_query_fetch_all!(
+        "voting.list_vote_tallies",
+        sqlx::query_as::<_, VoteTallyRow>(
+            r#"
+            SELECT
+                v.element_id,
+                COUNT(*) AS votes,
+                COUNT(*) FILTER (WHERE v.user_id = $2) AS my_votes
+            FROM board.vote v
+            JOIN board.element e ON e.id = v.element_id
+            WHERE v.session_id = $1
+            AND e.deleted_at IS NULL
+            GROUP BY v.element_id
+            ORDER BY votes DESC, v.element_id
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .fetch_all(pool)
+    )?;
+
+    Ok(rows)
+}
+
+fn map_active_session_violation(err: sqlx::Error) -> AppError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23505") => {
+            AppError::Conflict("A voting session is already active on this board".to_string())
+        }
+        _ => err.into(),
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    sync::{OnceLock, RwLock},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use redis::aio::MultiplexedConnection;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::{
+    models::boards::{BoardPermissions, BoardRole},
+    realtime::protocol::BoardRoleUpdate,
+    services::redis_health,
+};
+
+const DEFAULT_ACCESS_CACHE_TTL_SECS: u64 = 30;
+const INVALIDATION_CHANNEL: &str = "board_access:invalidations";
+const LOCAL_INVALIDATION_CAPACITY: usize = 256;
+const LISTENER_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+static ACCESS_CACHE: OnceLock<AccessCache> = OnceLock::new();
+static LOCAL_INVALIDATIONS: OnceLock<broadcast::Sender<AccessInvalidation>> = OnceLock::new();
+
+/// Redis cache of resolved board access per `(board_id, user_id)`.
+///
+/// Entries record the board and user generation counters seen before the
+/// access was resolved; bumping either counter invalidates every entry for
+/// that board or user without scanning keys. Any Redis failure is treated as
+/// a miss so callers fall back to the database.
+struct AccessCache {
+    client: redis::Client,
+    ttl_secs: u64,
+    connection: RwLock<Option<MultiplexedConnection>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CachedAccess {
+    pub role: BoardRole,
+    pub permissions: BoardPermissions,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    board_generation: i64,
+    user_generation: i64,
+    #[serde(flatten)]
+    access: CachedAccess,
+}
+
+/// Generation counters observed on a miss, passed back to [`store`].
+#[derive(Debug, Clone, Copy)]
+pub struct Generations {
+    board: i64,
+    user: i64,
+}
+
+/// Access change published to every node so cached entries are dropped and
+/// open board sessions pick up the new role.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AccessInvalidation {
+    /// Board-wide change such as visibility, ownership, archive or delete.
+    Board { board_id: Uuid },
+    /// Change affecting every board of a user, e.g. an organization role.
+    User { user_id: Uuid },
+    /// A board member's resolved role changed or the member was removed.
+    Member {
+        board_id: Uuid,
+        update: BoardRoleUpdate,
+    },
+}
+
+pub enum CacheLookup {
+    Hit(CachedAccess),
+    Miss(Option<Generations>),
+}
+
+/// Enables the cache when Redis is configured. `BOARD_ACCESS_CACHE_TTL_SECS`
+/// (default 30) bounds how long an entry may be served.
+pub fn init(redis: Option<redis::Client>) {
+    let Some(client) = redis else {
+        return;
+    };
+    let ttl_secs = std::env::var("BOARD_ACCESS_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_ACCESS_CACHE_TTL_SECS);
+    let _ = ACCESS_CACHE.set(AccessCache {
+        client,
+        ttl_secs,
+        connection: RwLock::new(None),
+    });
+}
+
+pub async fn lookup(board_id: Uuid, user_id: Uuid) -> CacheLookup {
+    let Some(cache) = ACCESS_CACHE.get() else {
+        return CacheLookup::Miss(None);
+    };
+    let Some(mut conn) = cache.connection().await else {
+        return CacheLookup::Miss(None);
+    };
+    let keys = [
+        entry_key(board_id, user_id),
+        board_generation_key(board_id),
+        user_generation_key(user_id),
+    ];
+    let values: Vec<Option<String>> = match redis::cmd("MGET")
+        .arg(&keys[..])
+        .query_async(&mut conn)
+        .await
+    {
+        Ok(values) => values,
+        Err(error) => {
+            cache.reset_connection(&error);
+            return CacheLookup::Miss(None);
+        }
+    };
+    let generation = |index: usize| {
+        values
+            .get(index)
+            .and_then(Option::as_deref)
+            .and_then(|value| value.parse::<i64>().ok())
+            .unwrap_or(0)
+    };
+    let generations = Generations {
+        board: generation(1),
+        user: generation(2),
+    };
+    let entry = values
+        .first()
+        .and_then(Option::as_deref)
+        .and_then(|value| serde_json::from_str::<CacheEntry>(value).ok());
+    match entry {
+        Some(entry) if is_current(&entry, generations) => CacheLookup::Hit(entry.access),
+        _ => CacheLookup::Miss(Some(generations)),
+    }
+}
+
+/// Caches access resolved after a [`lookup`] miss returned `generations`.
+pub async fn store(board_id: Uuid, user_id: Uuid, generations: Generations, access: CachedAccess) {
+    let Some(cache) = ACCESS_CACHE.get() else {
+        return;
+    };
+    let Some(mut conn) = cache.connection().await else {
+        return;
+    };
+    let entry = CacheEntry {
+        board_generation: generations.board,
+        user_generation: generations.user,
+        access,
+    };
+    let Ok(payload) = serde_json::to_string(&entry) else {
+        return;
+    };
+    let result: redis::RedisResult<()> = redis::cmd("SET")
+        .arg(entry_key(board_id, user_id))
+        .arg(payload)
+        .arg("EX")
+        .arg(cache.ttl_secs)
+        .query_async(&mut conn)
+        .await;
+    if let Err(error) = result {
+        cache.reset_connection(&error);
+    }
+}
+
+/// Invalidates the affected cache entries and broadcasts `invalidation` to
+/// all nodes over Redis pub/sub, or only to this node without Redis.
+pub async fn publish(invalidation: AccessInvalidation) {
+    let generation_key = match &invalidation {
+        AccessInvalidation::Board { board_id } => board_generation_key(*board_id),
+        AccessInvalidation::User { user_id } => user_generation_key(*user_id),
+        AccessInvalidation::Member { update, .. } => user_generation_key(update.user_id),
+    };
+    bump_generation(generation_key).await;
+    if !publish_remote(&invalidation).await {
+        let _ = local_invalidations().send(invalidation);
+    }
+}
+
+/// Receives invalidations published by any node.
+pub fn subscribe() -> broadcast::Receiver<AccessInvalidation> {
+    local_invalidations().subscribe()
+}
+
+/// Relays invalidations from Redis pub/sub to [`subscribe`] receivers,
+/// resubscribing after connection failures. No-op without Redis.
+pub fn spawn_invalidation_listener() {
+    let Some(cache) = ACCESS_CACHE.get() else {
+        return;
+    };
+    let client = cache.client.clone();
+    tokio::spawn(async move {
+        loop {
+            if let Err(error) = relay_invalidations(&client).await {
+                tracing::warn!("Board access invalidation listener failed: {}", error);
+            }
+            tokio::time::sleep(LISTENER_RETRY_DELAY).await;
+        }
+    });
+}
+
+async fn relay_invalidations(client: &redis::Client) -> redis::RedisResult<()> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(INVALIDATION_CHANNEL).await?;
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        match serde_json::from_slice::<AccessInvalidation>(message.get_payload_bytes()) {
+            Ok(invalidation) => {
+                let _ = local_invalidations().send(invalidation);
+            }
+            Err(error) => tracing::warn!("Ignoring malformed access invalidation: {}", error),
+        }
+    }
+    Ok(())
+}
+
+async fn publish_remote(invalidation: &AccessInvalidation) -> bool {
+    let Some(cache) = ACCESS_CACHE.get() else {
+        return false;
+    };
+    let Some(mut conn) = cache.connection().await else {
+        return false;
+    };
+    let Ok(payload) = serde_json::to_string(invalidation) else {
+        return false;
+    };
+    let result: redis::RedisResult<()> = redis::cmd("PUBLISH")
+        .arg(INVALIDATION_CHANNEL)
+        .arg(payload)
+        .query_async(&mut conn)
+        .await;
+    match result {
+        Ok(()) => true,
+        Err(error) => {
+            cache.reset_connection(&error);
+            false
+        }
+    }
+}
+
+fn local_invalidations() -> &'static broadcast::Sender<AccessInvalidation> {
+    LOCAL_INVALIDATIONS.get_or_init(|| broadcast::channel(LOCAL_INVALIDATION_CAPACITY).0)
+}
+
+async fn bump_generation(key: String) {
+    let Some(cache) = ACCESS_CACHE.get() else {
+        return;
+    };
+    let Some(mut conn) = cache.connection().await else {
+        return;
+    };
+    // Counters outlive every entry stamped with them, so an expired counter
+    // can never match a stale entry again.
+    let result: redis::RedisResult<()> = redis::pipe()
+        .atomic()
+        .incr(&key, 1)
+        .ignore()
+        .expire(&key, (cache.ttl_secs * 2) as i64)
+        .ignore()
+        .query_async(&mut conn)
+        .await;
+    if let Err(error) = result {
+        tracing::warn!("Failed to invalidate board access cache {}: {}", key, error);
+        cache.reset_connection(&error);
+    }
+}
+
+impl AccessCache {
+    async fn connection(&self) -> Option<MultiplexedConnection> {
+        if !redis_health::is_available() {
+            return None;
+        }
+        if let Some(conn) = self.connection.read().ok().and_then(|guard| guard.clone()) {
+            return Some(conn);
+        }
+        match self.client.get_multiplexed_async_connection().await {
+            Ok(conn) => {
+                if let Ok(mut guard) = self.connection.write() {
+                    *guard = Some(conn.clone());
+                }
+                Some(conn)
+            }
+            Err(error) => {
+                redis_health::record_failure(&error);
+                tracing::warn!("Redis unavailable for board access cache: {}", error);
+                None
+            }
+        }
+    }
+
+    fn reset_connection(&self, error: &redis::RedisError) {
+        redis_health::record_failure(error);
+        tracing::warn!("Board access cache request failed, using DB: {}", error);
+        if let Ok(mut guard) = self.connection.write() {
+            *guard = None;
+        }
+    }
+}
+
+fn is_current(entry: &CacheEntry, generations: Generations) -> bool {
+    entry.board_generation == generations.board && entry.user_generation == generations.user
+}
+
+fn entry_key(board_id: Uuid, user_id: Uuid) -> String {
+    format!("board_access:{}:{}", board_id, user_id)
+}
+
+fn board_generation_key(board_id: Uuid) -> String {
+    format!("board_access_gen:board:{}", board_id)
+}
+
+fn user_generation_key(user_id: Uuid) -> String {
+    format!("board_access_gen:user:{}", user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::{AccessInvalidation, CacheEntry, CachedAccess, Generations, is_current};
+    use crate::{models::boards::BoardRole, realtime::protocol::BoardRoleUpdate};
+
+    #[test]
+    fn entries_are_stale_once_either_generation_moves() {
+        let entry = CacheEntry {
+            board_generation: 3,
+            user_generation: 1,
+            access: CachedAccess {
+                role: BoardRole::Editor,
+                permissions: BoardRole::Editor.permissions(),
+            },
+        };
+
+        assert!(is_current(&entry, Generations { board: 3, user: 1 }));
+        assert!(!is_current(&entry, Generations { board: 4, user: 1 }));
+        assert!(!is_current(&entry, Generations { board: 3, user: 2 }));
+    }
+
+    #[test]
+    fn invalidations_round_trip_with_kind_tag() {
+        let board_id = Uuid::nil();
+        let removed = AccessInvalidation::Member {
+            board_id,
+            update: BoardRoleUpdate {
+                user_id: board_id,
+                role: None,
+                permissions: None,
+            },
+        };
+
+        let encoded = serde_json::to_value(&removed).unwrap();
+
+        assert_eq!(encoded["kind"], json!("member"));
+        assert!(matches!(
+            serde_json::from_value::<AccessInvalidation>(encoded).unwrap(),
+            AccessInvalidation::Member { update, .. } if update.role.is_none()
+        ));
+        assert!(matches!(
+            serde_json::from_value::<AccessInvalidation>(
+                json!({ "kind": "board", "board_id": board_id })
+            ),
+            Ok(AccessInvalidation::Board { .. })
+        ));
+    }
+}
@@ -0,0 +1,273 @@
+use std::{collections::HashSet, sync::Arc};
+
+use serde_json::Value;
+
+use crate::error::AppError;
+
+/// Element property keys that carry user-visible text.
+const FILTERED_PROPERTY_KEYS: [(&str, ContentField); 2] = [
+    ("content", ContentField::ElementContent),
+    ("title", ContentField::ElementTitle),
+];
+
+/// Which text surface is being checked, so a filter can apply per-field rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentField {
+    ElementContent,
+    ElementTitle,
+    CommentBody,
+}
+
+impl ContentField {
+    fn label(self) -> &'static str {
+        match self {
+            ContentField::ElementContent => "Element content",
+            ContentField::ElementTitle => "Element title",
+            ContentField::CommentBody => "Comment",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterVerdict {
+    Pass,
+    /// Store the given text instead of the original.
+    Redact(String),
+    /// Refuse the write; the reason is returned to the client.
+    Reject(String),
+}
+
+/// Moderation hook called whenever element text or a comment body is set.
+///
+/// Implementations must be cheap and non-blocking: they run inline on the
+/// request and WebSocket paths.
+pub trait ContentFilter: Send + Sync {
+    fn check(&self, field: ContentField, text: &str) -> FilterVerdict;
+}
+
+pub type SharedContentFilter = Arc<dyn ContentFilter>;
+
+/// Default filter that accepts everything.
+pub struct NoopContentFilter;
+
+impl ContentFilter for NoopContentFilter {
+    fn check(&self, _field: ContentField, _text: &str) -> FilterVerdict {
+        FilterVerdict::Pass
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockedWordsMode {
+    Redact,
+    Reject,
+}
+
+/// Built-in filter matching whole words case-insensitively against a list.
+pub struct BlockedWordsFilter {
+    words: HashSet<String>,
+    mode: BlockedWordsMode,
+}
+
+impl BlockedWordsFilter {
+    fn new(words: &str, mode: BlockedWordsMode) -> Self {
+        Self {
+            words: words
+                .split(',')
+                .map(|word| word.trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect(),
+            mode,
+        }
+    }
+
+    /// Masks blocked words with `*`, or returns `None` when nothing matched.
+    fn redact(&self, text: &str) -> Option<String> {
+        let mut output = String::with_capacity(text.len());
+        let mut matched = false;
+        let mut rest = text;
+        while !rest.is_empty() {
+            let word_len = rest
+                .find(|ch: char| !ch.is_alphanumeric())
+                .unwrap_or(rest.len());
+            let (word, tail) = rest.split_at(word_len);
+            if !word.is_empty() && self.words.contains(&word.to_lowercase()) {
+                matched = true;
+                output.extend(std::iter::repeat_n('*', word.chars().count()));
+            } else {
+                output.push_str(word);
+            }
+            let gap_len = tail.find(char::is_alphanumeric).unwrap_or(tail.len());
+            let (gap, next) = tail.split_at(gap_len);
+            output.push_str(gap);
+            rest = next;
+        }
+        matched.then_some(output)
+    }
+}
+
+impl ContentFilter for BlockedWordsFilter {
+    fn check(&self, field: ContentField, text: &str) -> FilterVerdict {
+        match self.redact(text) {
+            None => FilterVerdict::Pass,
+            Some(redacted) => match self.mode {
+                BlockedWordsMode::Redact => FilterVerdict::Redact(redacted),
+                BlockedWordsMode::Reject => {
+                    FilterVerdict::Reject(format!("{} contains blocked words", field.label()))
+                }
+            },
+        }
+    }
+}
+
+/// Builds the filter configured by `CONTENT_FILTER_BLOCKED_WORDS` and
+/// `CONTENT_FILTER_MODE` (`redact` or `reject`), or the no-op filter.
+pub fn from_env() -> SharedContentFilter {
+    let words = std::env::var("CONTENT_FILTER_BLOCKED_WORDS").unwrap_or_default();
+    let mode = match std::env::var("CONTENT_FILTER_MODE")
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "reject" => BlockedWordsMode::Reject,
+        _ => BlockedWordsMode::Redact,
+    };
+    let filter = BlockedWordsFilter::new(&words, mode);
+    if filter.words.is_empty() {
+        return Arc::new(NoopContentFilter);
+    }
+    tracing::info!(
+        words = filter.words.len(),
+        ?mode,
+        "Content filter enabled with blocked word list"
+    );
+    Arc::new(filter)
+}
+
+/// Runs `filter` over `text`, returning the text to store.
+pub fn filter_text(
+    filter: &dyn ContentFilter,
+    field: ContentField,
+    text: String,
+) -> Result<String, AppError> {
+    match filter.check(field, &text) {
+        FilterVerdict::Pass => Ok(text),
+        FilterVerdict::Redact(redacted) => Ok(redacted),
+        FilterVerdict::Reject(reason) => Err(AppError::ValidationError(reason)),
+    }
+}
+
+/// Filters the text-bearing keys of an element `properties` object in place.
+pub fn filter_element_properties(
+    filter: &dyn ContentFilter,
+    properties: &mut Value,
+) -> Result<(), AppError> {
+    let Some(object) = properties.as_object_mut() else {
+        return Ok(());
+    };
+    for (key, field) in FILTERED_PROPERTY_KEYS {
+        if let Some(Value::String(text)) = object.get_mut(key) {
+            *text = filter_text(filter, field, std::mem::take(text))?;
+        }
+    }
+    Ok(())
+}
+
+/// Computes the property patch that brings already-applied element text in
+/// line with `filter`, or `None` when every field passes.
+///
+/// Realtime edits reach the document before they can be checked, so there is
+/// no request to reject: redacted text is rewritten and rejected text is
+/// cleared.
+pub fn moderate_element_properties(
+    filter: &dyn ContentFilter,
+    properties: &Value,
+) -> Option<Value> {
+    let object = properties.as_object()?;
+    let mut patch = serde_json::Map::new();
+    for (key, field) in FILTERED_PROPERTY_KEYS {
+        let Some(Value::String(text)) = object.get(key) else {
+            continue;
+        };
+        match filter.check(field, text) {
+            FilterVerdict::Pass => {}
+            FilterVerdict::Redact(redacted) => {
+                patch.insert(key.to_string(), Value::String(redacted));
+            }
+            FilterVerdict::Reject(_) => {
+                patch.insert(key.to_string(), Value::String(String::new()));
+            }
+        }
+    }
+    (!patch.is_empty()).then_some(Value::Object(patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        BlockedWordsFilter, BlockedWordsMode, ContentField, ContentFilter, FilterVerdict,
+        filter_element_properties, moderate_element_properties,
+    };
+
+    #[test]
+    fn blocked_words_redacts_whole_words_case_insensitively() {
+        let filter = BlockedWordsFilter::new("darn, heck", BlockedWordsMode::Redact);
+
+        assert_eq!(
+            filter.check(ContentField::CommentBody, "Darn it, what the heck!"),
+            FilterVerdict::Redact("**** it, what the ****!".to_string())
+        );
+        assert_eq!(
+            filter.check(ContentField::CommentBody, "darned hecklers"),
+            FilterVerdict::Pass
+        );
+    }
+
+    #[test]
+    fn reject_mode_fails_element_properties_with_validation_error() {
+        let filter = BlockedWordsFilter::new("darn", BlockedWordsMode::Reject);
+        let mut properties = json!({ "title": "Darn title", "content": "fine" });
+
+        let error = filter_element_properties(&filter, &mut properties).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Validation error: Element title contains blocked words"
+        );
+    }
+
+    #[test]
+    fn redact_mode_rewrites_element_properties_in_place() {
+        let filter = BlockedWordsFilter::new("darn", BlockedWordsMode::Redact);
+        let mut properties = json!({ "content": "oh darn", "shapeType": "darn" });
+
+        filter_element_properties(&filter, &mut properties).unwrap();
+
+        assert_eq!(
+            properties,
+            json!({ "content": "oh ****", "shapeType": "darn" })
+        );
+    }
+
+    #[test]
+    fn moderation_patches_only_failing_fields() {
+        let redact = BlockedWordsFilter::new("darn", BlockedWordsMode::Redact);
+        let reject = BlockedWordsFilter::new("darn", BlockedWordsMode::Reject);
+        let properties = json!({ "title": "Darn title", "content": "fine" });
+
+        assert_eq!(
+            moderate_element_properties(&redact, &properties),
+            Some(json!({ "title": "**** title" }))
+        );
+        assert_eq!(
+            moderate_element_properties(&reject, &properties),
+            Some(json!({ "title": "" }))
+        );
+        assert_eq!(
+            moderate_element_properties(&redact, &json!({ "content": "fine" })),
+            None
+        );
+    }
+}
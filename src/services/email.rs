@@ -1,11 +1,15 @@
 use lettre::{
     AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
-    message::{Mailbox, SinglePart, header::ContentType},
+    message::{Mailbox, MultiPart, SinglePart, header::ContentType},
     transport::smtp::authentication::Credentials,
 };
 use std::env;
 
-use crate::error::AppError;
+use crate::{
+    error::AppError,
+    models::{organizations::Organization, users::DigestFrequency},
+    services::email_templates::{self, BoardDigest, EmailLocale, InviteBranding, RenderedEmail},
+};
 
 #[derive(Clone)]
 pub struct EmailService {
@@ -50,6 +54,7 @@ impl EmailService {
     pub async fn send_verification_email(
         &self,
         recipient: &str,
+        locale: EmailLocale,
         token: &str,
     ) -> Result<(), AppError> {
         let verify_link = format!(
@@ -58,38 +63,20 @@ impl EmailService {
             urlencoding::encode(token)
         );
 
-        let body = format!(
-            "Welcome to Real-time Board!\n\nVerify your email by clicking the link below:\n{}\n\nIf you did not create an account, you can ignore this email.",
-            verify_link
-        );
-
-        let to_address = recipient
-            .parse()
-            .map_err(|_| AppError::BadRequest("Invalid recipient email".to_string()))?;
-        let message = Message::builder()
-            .from(self.from.clone())
-            .to(Mailbox::new(None, to_address))
-            .subject("Verify your email")
-            .singlepart(
-                SinglePart::builder()
-                    .header(ContentType::TEXT_PLAIN)
-                    .body(body),
-            )
-            .map_err(|e| AppError::ExternalService(format!("Email build failed: {}", e)))?;
-
-        self.mailer
-            .send(message)
-            .await
-            .map_err(|e| AppError::ExternalService(format!("Email send failed: {}", e)))?;
-        Ok(())
+        self.send_plain_text(
+            recipient,
+            email_templates::verification_email(locale, &verify_link),
+        )
+        .await
     }
 
-    /// Sends an organization invite email to a recipient.
+    /// Sends an organization invite email to a recipient, branded with the
+    /// organization's logo, sender name, accent color and support address.
     pub async fn send_organization_invite(
         &self,
         recipient: &str,
-        organization_name: &str,
-        organization_slug: &str,
+        locale: EmailLocale,
+        organization: &Organization,
         invite_token: Option<&str>,
     ) -> Result<(), AppError> {
         let base_url = self.frontend_url.trim_end_matches('/');
@@ -107,24 +94,94 @@ impl EmailService {
             ),
         };
 
-        let body = format!(
-            "You have been invited to join the \"{}\" workspace.\n\nWorkspace URL: {}\n\nSign in or create an account to accept the invitation:\n{}\n\nIf you did not expect this invite, you can ignore this email.",
-            organization_name, organization_slug, action_link
+        let branding = &organization.settings.branding;
+        let email = email_templates::organization_invite_email(
+            locale,
+            &InviteBranding {
+                organization_name: &organization.name,
+                organization_slug: &organization.slug,
+                logo_url: organization.logo_url.as_deref(),
+                accent_color: branding.accent_color.as_deref(),
+                support_email: branding.support_email.as_deref(),
+            },
+            &action_link,
         );
+        let from = match branding.email_from_name.as_deref() {
+            Some(name) => Mailbox::new(Some(name.to_string()), self.from.email.clone()),
+            None => self.from.clone(),
+        };
+        let reply_to = branding
+            .support_email
+            .as_deref()
+            .and_then(|email| email.parse().ok())
+            .map(|address| Mailbox::new(None, address));
+
+        self.send_message(recipient, email, from, reply_to).await
+    }
 
+    /// Reminds a pre-signup invitee about their pending invite without issuing
+    /// a new token, so the original invitation link stays valid.
+    pub async fn send_invite_reminder(
+        &self,
+        recipient: &str,
+        locale: EmailLocale,
+        organization: &Organization,
+    ) -> Result<(), AppError> {
+        self.send_plain_text(
+            recipient,
+            email_templates::invite_reminder_email(locale, &organization.name),
+        )
+        .await
+    }
+
+    /// Sends a board activity digest linking each board.
+    pub async fn send_activity_digest(
+        &self,
+        recipient: &str,
+        locale: EmailLocale,
+        frequency: DigestFrequency,
+        boards: &[BoardDigest],
+    ) -> Result<(), AppError> {
+        let board_base_url = format!("{}/board", self.frontend_url.trim_end_matches('/'));
+
+        self.send_plain_text(
+            recipient,
+            email_templates::activity_digest_email(locale, frequency, boards, &board_base_url),
+        )
+        .await
+    }
+
+    async fn send_plain_text(&self, recipient: &str, email: RenderedEmail) -> Result<(), AppError> {
+        self.send_message(recipient, email, self.from.clone(), None)
+            .await
+    }
+
+    async fn send_message(
+        &self,
+        recipient: &str,
+        email: RenderedEmail,
+        from: Mailbox,
+        reply_to: Option<Mailbox>,
+    ) -> Result<(), AppError> {
         let to_address = recipient
             .parse()
             .map_err(|_| AppError::BadRequest("Invalid recipient email".to_string()))?;
-        let message = Message::builder()
-            .from(self.from.clone())
+        let mut builder = Message::builder()
+            .from(from)
             .to(Mailbox::new(None, to_address))
-            .subject(format!("Invite to {}", organization_name))
-            .singlepart(
+            .subject(email.subject);
+        if let Some(reply_to) = reply_to {
+            builder = builder.reply_to(reply_to);
+        }
+        let message = match email.html {
+            Some(html) => builder.multipart(MultiPart::alternative_plain_html(email.body, html)),
+            None => builder.singlepart(
                 SinglePart::builder()
                     .header(ContentType::TEXT_PLAIN)
-                    .body(body),
-            )
-            .map_err(|e| AppError::ExternalService(format!("Email build failed: {}", e)))?;
+                    .body(email.body),
+            ),
+        }
+        .map_err(|e| AppError::ExternalService(format!("Email build failed: {}", e)))?;
 
         self.mailer
             .send(message)
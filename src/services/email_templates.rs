@@ -0,0 +1,368 @@
+use std::fmt::Write;
+
+use uuid::Uuid;
+
+use crate::models::users::DigestFrequency;
+
+/// Languages with translated email templates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmailLocale {
+    #[default]
+    En,
+    Vi,
+}
+
+impl EmailLocale {
+    /// Resolves a user `language` preference such as `vi` or `vi-VN`,
+    /// falling back to English when no template exists for it.
+    pub fn from_language(language: &str) -> Self {
+        let primary = language
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "vi" => EmailLocale::Vi,
+            _ => EmailLocale::En,
+        }
+    }
+}
+
+pub struct RenderedEmail {
+    pub subject: String,
+    pub body: String,
+    /// HTML alternative sent alongside the plain-text `body`.
+    pub html: Option<String>,
+}
+
+/// Organization details rendered into invite emails.
+pub struct InviteBranding<'a> {
+    pub organization_name: &'a str,
+    pub organization_slug: &'a str,
+    pub logo_url: Option<&'a str>,
+    pub accent_color: Option<&'a str>,
+    pub support_email: Option<&'a str>,
+}
+
+pub fn verification_email(locale: EmailLocale, verify_link: &str) -> RenderedEmail {
+    match locale {
+        EmailLocale::En => RenderedEmail {
+            subject: "Verify your email".to_string(),
+            body: format!(
+                "Welcome to Real-time Board!\n\nVerify your email by clicking the link below:\n{}\n\nIf you did not create an account, you can ignore this email.",
+                verify_link
+            ),
+            html: None,
+        },
+        EmailLocale::Vi => RenderedEmail {
+            subject: "Xác minh email của bạn".to_string(),
+            body: format!(
+                "Chào mừng bạn đến với Real-time Board!\n\nVui lòng xác minh email bằng cách nhấn vào liên kết bên dưới:\n{}\n\nNếu bạn không tạo tài khoản, hãy bỏ qua email này.",
+                verify_link
+            ),
+            html: None,
+        },
+    }
+}
+
+pub fn organization_invite_email(
+    locale: EmailLocale,
+    branding: &InviteBranding<'_>,
+    action_link: &str,
+) -> RenderedEmail {
+    let name = branding.organization_name;
+    let slug = branding.organization_slug;
+    let (subject, mut body, intro, cta, ignore, support_label) = match locale {
+        EmailLocale::En => (
+            format!("Invite to {}", name),
+            format!(
+                "You have been invited to join the \"{}\" workspace.\n\nWorkspace URL: {}\n\nSign in or create an account to accept the invitation:\n{}\n\nIf you did not expect this invite, you can ignore this email.",
+                name, slug, action_link
+            ),
+            format!("You have been invited to join the \"{}\" workspace.", name),
+            "Accept invitation",
+            "If you did not expect this invite, you can ignore this email.",
+            "Questions? Contact",
+        ),
+        EmailLocale::Vi => (
+            format!("Lời mời tham gia {}", name),
+            format!(
+                "Bạn được mời tham gia không gian làm việc \"{}\".\n\nĐịa chỉ không gian làm việc: {}\n\nĐăng nhập hoặc tạo tài khoản để chấp nhận lời mời:\n{}\n\nNếu bạn không mong đợi lời mời này, hãy bỏ qua email này.",
+                name, slug, action_link
+            ),
+            format!("Bạn được mời tham gia không gian làm việc \"{}\".", name),
+            "Chấp nhận lời mời",
+            "Nếu bạn không mong đợi lời mời này, hãy bỏ qua email này.",
+            "Có câu hỏi? Liên hệ",
+        ),
+    };
+    if let Some(support_email) = branding.support_email {
+        let _ = write!(body, "\n\n{} {}", support_label, support_email);
+    }
+
+    let accent = escape_html(branding.accent_color.unwrap_or(DEFAULT_ACCENT_COLOR));
+    let mut html = format!(
+        "<div style=\"font-family:Arial,sans-serif;max-width:560px;margin:0 auto;padding:24px;border-top:4px solid {}\">",
+        accent
+    );
+    if let Some(logo_url) = branding.logo_url {
+        let _ = write!(
+            html,
+            "<img src=\"{}\" alt=\"{}\" style=\"max-height:48px;margin-bottom:16px\">",
+            escape_html(logo_url),
+            escape_html(name)
+        );
+    }
+    let _ = write!(
+        html,
+        "<h1 style=\"font-size:20px;color:#111827\">{}</h1><p>{}</p><p><a href=\"{}\" style=\"display:inline-block;padding:10px 18px;border-radius:6px;background:{};color:#ffffff;text-decoration:none\">{}</a></p><p style=\"font-size:13px;color:#6b7280\">{}</p>",
+        escape_html(&subject),
+        escape_html(&intro),
+        escape_html(action_link),
+        accent,
+        cta,
+        ignore
+    );
+    if let Some(support_email) = branding.support_email {
+        let support_email = escape_html(support_email);
+        let _ = write!(
+            html,
+            "<p style=\"font-size:13px;color:#6b7280\">{} <a href=\"mailto:{}\">{}</a></p>",
+            support_label, support_email, support_email
+        );
+    }
+    html.push_str("</div>");
+
+    RenderedEmail {
+        subject,
+        body,
+        html: Some(html),
+    }
+}
+
+/// Reminder for a pre-signup invite. The invite token is only stored hashed,
+/// so the recipient is pointed back at the original invitation link.
+pub fn invite_reminder_email(locale: EmailLocale, organization_name: &str) -> RenderedEmail {
+    match locale {
+        EmailLocale::En => RenderedEmail {
+            subject: format!("Reminder: invite to {}", organization_name),
+            body: format!(
+                "You still have a pending invitation to join the \"{}\" workspace.\n\nOpen the link in your original invitation email to accept it before it expires.\n\nIf you did not expect this invite, you can ignore this email.",
+                organization_name
+            ),
+            html: None,
+        },
+        EmailLocale::Vi => RenderedEmail {
+            subject: format!("Nhắc nhở: lời mời tham gia {}", organization_name),
+            body: format!(
+                "Bạn vẫn còn một lời mời tham gia không gian làm việc \"{}\" đang chờ.\n\nHãy mở liên kết trong email mời ban đầu để chấp nhận trước khi lời mời hết hạn.\n\nNếu bạn không mong đợi lời mời này, hãy bỏ qua email này.",
+                organization_name
+            ),
+            html: None,
+        },
+    }
+}
+
+const DEFAULT_ACCENT_COLOR: &str = "#2563eb";
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// One board's section of an activity digest.
+pub struct BoardDigest {
+    pub board_id: Uuid,
+    pub board_name: String,
+    pub elements: i64,
+    pub comments: i64,
+    pub top_contributors: Vec<String>,
+}
+
+pub fn activity_digest_email(
+    locale: EmailLocale,
+    frequency: DigestFrequency,
+    boards: &[BoardDigest],
+    board_base_url: &str,
+) -> RenderedEmail {
+    let mut sections = String::new();
+    for board in boards {
+        let _ = match locale {
+            EmailLocale::En => write!(
+                sections,
+                "\n{}\n  {} new elements, {} new comments\n",
+                board.board_name, board.elements, board.comments
+            ),
+            EmailLocale::Vi => write!(
+                sections,
+                "\n{}\n  {} phần tử mới, {} bình luận mới\n",
+                board.board_name, board.elements, board.comments
+            ),
+        };
+        if !board.top_contributors.is_empty() {
+            let label = match locale {
+                EmailLocale::En => "Most active",
+                EmailLocale::Vi => "Tích cực nhất",
+            };
+            let _ = writeln!(
+                sections,
+                "  {}: {}",
+                label,
+                board.top_contributors.join(", ")
+            );
+        }
+        let _ = writeln!(sections, "  {}/{}", board_base_url, board.board_id);
+    }
+
+    match locale {
+        EmailLocale::En => {
+            let period = match frequency {
+                DigestFrequency::Weekly => "weekly",
+                _ => "daily",
+            };
+            RenderedEmail {
+                subject: format!("Your {} board activity digest", period),
+                body: format!(
+                    "Here is what happened on your boards:\n{}\nYou can change how often you receive this digest in your profile settings.",
+                    sections
+                ),
+                html: None,
+            }
+        }
+        EmailLocale::Vi => {
+            let period = match frequency {
+                DigestFrequency::Weekly => "hàng tuần",
+                _ => "hàng ngày",
+            };
+            RenderedEmail {
+                subject: format!("Tóm tắt hoạt động bảng {}", period),
+                body: format!(
+                    "Đây là những gì đã diễn ra trên các bảng của bạn:\n{}\nBạn có thể thay đổi tần suất nhận bản tóm tắt này trong phần cài đặt hồ sơ.",
+                    sections
+                ),
+                html: None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BoardDigest, EmailLocale, InviteBranding, activity_digest_email, invite_reminder_email,
+        organization_invite_email, verification_email,
+    };
+    use uuid::Uuid;
+
+    use crate::models::users::DigestFrequency;
+
+    #[test]
+    fn from_language_matches_primary_subtag_and_defaults_to_english() {
+        assert_eq!(EmailLocale::from_language("vi"), EmailLocale::Vi);
+        assert_eq!(EmailLocale::from_language(" VI-vn "), EmailLocale::Vi);
+        assert_eq!(EmailLocale::from_language("en_US"), EmailLocale::En);
+        assert_eq!(EmailLocale::from_language("fr"), EmailLocale::En);
+        assert_eq!(EmailLocale::from_language(""), EmailLocale::En);
+    }
+
+    #[test]
+    fn templates_embed_links_in_each_locale() {
+        for locale in [EmailLocale::En, EmailLocale::Vi] {
+            let verification = verification_email(locale, "https://app/verify");
+            assert!(verification.body.contains("https://app/verify"));
+
+            let invite = organization_invite_email(locale, &branding("Acme"), "https://app/join");
+            assert!(invite.subject.contains("Acme"));
+            assert!(invite.body.contains("https://app/join"));
+
+            let reminder = invite_reminder_email(locale, "Acme");
+            assert!(reminder.subject.contains("Acme"));
+            assert!(reminder.body.contains("\"Acme\""));
+        }
+        assert_eq!(
+            verification_email(EmailLocale::Vi, "x").subject,
+            "Xác minh email của bạn"
+        );
+    }
+
+    fn branding(organization_name: &str) -> InviteBranding<'_> {
+        InviteBranding {
+            organization_name,
+            organization_slug: "acme",
+            logo_url: None,
+            accent_color: None,
+            support_email: None,
+        }
+    }
+
+    #[test]
+    fn branded_invite_renders_logo_accent_and_escaped_name() {
+        let invite = organization_invite_email(
+            EmailLocale::En,
+            &InviteBranding {
+                logo_url: Some("https://cdn.acme.test/logo.png"),
+                accent_color: Some("#ff6600"),
+                support_email: Some("help@acme.test"),
+                ..branding("Acme <R&D>")
+            },
+            "https://app/join?invite=a&email=b",
+        );
+
+        let html = invite.html.unwrap();
+        assert!(
+            html.contains(
+                "<img src=\"https://cdn.acme.test/logo.png\" alt=\"Acme &lt;R&amp;D&gt;\""
+            )
+        );
+        assert!(html.contains("background:#ff6600"));
+        assert!(html.contains("href=\"https://app/join?invite=a&amp;email=b\""));
+        assert!(!html.contains("<R&D>"));
+        assert!(invite.body.ends_with("Questions? Contact help@acme.test"));
+
+        let plain = organization_invite_email(EmailLocale::En, &branding("Acme"), "x");
+        assert!(!plain.html.unwrap().contains("<img"));
+    }
+
+    #[test]
+    fn activity_digest_lists_each_board_with_counts_and_contributors() {
+        let board_id = Uuid::nil();
+        let boards = [BoardDigest {
+            board_id,
+            board_name: "Roadmap".to_string(),
+            elements: 4,
+            comments: 2,
+            top_contributors: vec!["Ann".to_string(), "Bo".to_string()],
+        }];
+
+        let email = activity_digest_email(
+            EmailLocale::En,
+            DigestFrequency::Weekly,
+            &boards,
+            "https://app/board",
+        );
+
+        assert_eq!(email.subject, "Your weekly board activity digest");
+        assert!(
+            email
+                .body
+                .contains("Roadmap\n  4 new elements, 2 new comments")
+        );
+        assert!(email.body.contains("Most active: Ann, Bo"));
+        assert!(
+            email
+                .body
+                .contains(&format!("https://app/board/{}", board_id))
+        );
+    }
+}
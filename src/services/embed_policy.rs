@@ -0,0 +1,143 @@
+use std::sync::OnceLock;
+
+use serde_json::{Value, json};
+
+use crate::{error::AppError, models::elements::ElementType};
+
+const EMBED_ALLOWED_DOMAINS_ENV: &str = "EMBED_ALLOWED_DOMAINS";
+const DEFAULT_EMBED_ALLOWED_DOMAINS: [&str; 9] = [
+    "youtube.com",
+    "youtube-nocookie.com",
+    "youtu.be",
+    "vimeo.com",
+    "loom.com",
+    "figma.com",
+    "docs.google.com",
+    "codepen.io",
+    "codesandbox.io",
+];
+
+static ALLOWED_DOMAINS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Rejects Embed elements whose `properties.url` is not an `https` URL on an
+/// allowed domain (or one of its subdomains). Other element types and embeds
+/// without a URL yet are accepted.
+///
+/// `EMBED_ALLOWED_DOMAINS` (comma-separated) replaces the default list; `*`
+/// allows any domain.
+pub fn validate_embed_properties(
+    element_type: ElementType,
+    properties: &Value,
+) -> Result<(), AppError> {
+    if element_type != ElementType::Embed {
+        return Ok(());
+    }
+    match properties.get("url") {
+        Some(Value::String(url)) if !url.trim().is_empty() => {
+            ensure_allowed_url(url, allowed_domains())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Property patch removing the URL of an Embed element that fails
+/// [`validate_embed_properties`], for embeds that reached the document
+/// through realtime updates and can no longer be rejected.
+pub fn embed_url_correction(element_type: ElementType, properties: &Value) -> Option<Value> {
+    validate_embed_properties(element_type, properties)
+        .is_err()
+        .then(|| json!({ "url": null }))
+}
+
+fn allowed_domains() -> &'static [String] {
+    ALLOWED_DOMAINS.get_or_init(|| match std::env::var(EMBED_ALLOWED_DOMAINS_ENV) {
+        Ok(value) => parse_domains(&value),
+        Err(_) => DEFAULT_EMBED_ALLOWED_DOMAINS
+            .iter()
+            .map(|domain| domain.to_string())
+            .collect(),
+    })
+}
+
+fn parse_domains(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|domain| domain.trim().trim_start_matches('.').to_ascii_lowercase())
+        .filter(|domain| !domain.is_empty())
+        .collect()
+}
+
+fn ensure_allowed_url(url: &str, allowed: &[String]) -> Result<(), AppError> {
+    if allowed.iter().any(|domain| domain == "*") {
+        return Ok(());
+    }
+    let Some(host) = https_host(url) else {
+        return Err(AppError::Forbidden(
+            "Embed URL must be an https URL".to_string(),
+        ));
+    };
+    let allowed = allowed.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    });
+    if allowed {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "Embeds from {} are not allowed",
+            host
+        )))
+    }
+}
+
+/// Lowercased host of an `https` URL, ignoring userinfo and port.
+fn https_host(url: &str) -> Option<String> {
+    let url = url.trim();
+    let rest = url
+        .get(..8)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("https://"))
+        .map(|_| &url[8..])?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority.rsplit('@').next().unwrap_or_default();
+    let host = host_port.split(':').next().unwrap_or_default();
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    (!host.is_empty()).then_some(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{embed_url_correction, ensure_allowed_url, parse_domains};
+    use crate::models::elements::ElementType;
+
+    #[test]
+    fn allows_listed_domains_and_subdomains_only() {
+        let allowed = parse_domains("youtube.com, .Figma.com");
+
+        assert!(ensure_allowed_url("https://www.youtube.com/embed/x", &allowed).is_ok());
+        assert!(ensure_allowed_url("HTTPS://figma.com:443/file/1", &allowed).is_ok());
+        assert!(ensure_allowed_url("https://evilyoutube.com/", &allowed).is_err());
+        assert!(ensure_allowed_url("https://youtube.com@evil.test/", &allowed).is_err());
+        assert!(ensure_allowed_url("http://youtube.com/", &allowed).is_err());
+        assert!(ensure_allowed_url("javascript:alert(1)", &allowed).is_err());
+        assert!(ensure_allowed_url("https://anything.test/", &parse_domains("*")).is_ok());
+    }
+
+    #[test]
+    fn embed_url_correction_clears_only_disallowed_embed_urls() {
+        let bad = json!({ "url": "http://evil.test/" });
+
+        assert_eq!(
+            embed_url_correction(ElementType::Embed, &bad),
+            Some(json!({ "url": null }))
+        );
+        assert_eq!(embed_url_correction(ElementType::Shape, &bad), None);
+        assert_eq!(
+            embed_url_correction(ElementType::Embed, &json!({ "title": "no url yet" })),
+            None
+        );
+    }
+}
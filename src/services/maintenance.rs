@@ -2,7 +2,20 @@ use std::time::Duration;
 
 use sqlx::PgPool;
 
-use crate::usecases::boards::BoardService;
+use crate::{
+    realtime::room::Rooms,
+    services::email::EmailService,
+    usecases::{
+        boards::BoardService, digests::DigestService, elements::ElementService,
+        organizations::OrganizationService,
+    },
+};
+
+const DEFAULT_INVITE_REMINDER_INTERVAL_SECS: u64 = 60 * 60;
+const DEFAULT_ACTIVITY_DIGEST_INTERVAL_SECS: u64 = 60 * 60;
+const DEFAULT_INVITE_REMINDER_AFTER_DAYS: i64 = 3;
+const DEFAULT_Z_INDEX_COMPACTION_INTERVAL_SECS: u64 = 60 * 60;
+const TIER_LIMIT_REFRESH_INTERVAL_SECS: u64 = 60;
 
 pub fn spawn_board_cleanup(pool: PgPool) {
     tokio::spawn(async move {
@@ -13,8 +26,14 @@ pub fn spawn_board_cleanup(pool: PgPool) {
             interval.tick().await;
             match BoardService::purge_deleted_boards(&pool).await {
                 Ok(purged) => {
-                    if purged > 0 {
-                        tracing::info!("Purged {} deleted boards", purged);
+                    if purged.boards > 0 {
+                        tracing::info!(
+                            elements = purged.elements,
+                            snapshots = purged.snapshots,
+                            updates = purged.updates,
+                            "Purged {} deleted boards",
+                            purged.boards
+                        );
                     }
                 }
                 Err(error) => {
@@ -24,3 +43,191 @@ pub fn spawn_board_cleanup(pool: PgPool) {
         }
     });
 }
+
+pub fn spawn_invite_cleanup(pool: PgPool) {
+    tokio::spawn(async move {
+        const CLEANUP_INTERVAL_SECS: u64 = 60 * 60;
+        let mut interval = tokio::time::interval(Duration::from_secs(CLEANUP_INTERVAL_SECS));
+
+        loop {
+            interval.tick().await;
+            match OrganizationService::purge_expired_invites(&pool).await {
+                Ok((email_invites, member_invites)) => {
+                    if email_invites > 0 || member_invites > 0 {
+                        tracing::info!(
+                            email_invites,
+                            member_invites,
+                            "Removed expired organization invites"
+                        );
+                    }
+                }
+                Err(error) => {
+                    tracing::error!("Failed to remove expired invites: {}", error);
+                }
+            }
+        }
+    });
+}
+
+pub fn spawn_element_history_pruning(pool: PgPool) {
+    tokio::spawn(async move {
+        const PRUNE_INTERVAL_SECS: u64 = 6 * 60 * 60;
+        let mut interval = tokio::time::interval(Duration::from_secs(PRUNE_INTERVAL_SECS));
+
+        loop {
+            interval.tick().await;
+            match ElementService::prune_element_history(&pool).await {
+                Ok(pruned) => {
+                    if pruned > 0 {
+                        tracing::info!("Pruned {} element history rows", pruned);
+                    }
+                }
+                Err(error) => {
+                    tracing::error!("Failed to prune element history: {}", error);
+                }
+            }
+        }
+    });
+}
+
+/// Reloads subscription tier limits periodically so limit changes made
+/// through another node reach this one's cache within the refresh interval.
+pub fn spawn_tier_limit_refresh(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(TIER_LIMIT_REFRESH_INTERVAL_SECS));
+        // Limits were loaded at startup.
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            if let Err(error) = OrganizationService::load_tier_limits(&pool).await {
+                tracing::warn!("Failed to refresh subscription tier limits: {}", error);
+            }
+        }
+    });
+}
+
+pub fn spawn_invite_reminders(pool: PgPool, email_service: Option<EmailService>) {
+    let Some(email_service) = email_service else {
+        tracing::info!("Invite reminders disabled: email service not configured");
+        return;
+    };
+    let interval_secs = std::env::var("INVITE_REMINDER_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_INVITE_REMINDER_INTERVAL_SECS);
+    let reminder_after = chrono::Duration::days(invite_reminder_after_days(
+        std::env::var("INVITE_REMINDER_AFTER_DAYS").ok().as_deref(),
+    ));
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            interval.tick().await;
+            match OrganizationService::send_invite_reminders(
+                &pool,
+                Some(&email_service),
+                reminder_after,
+            )
+            .await
+            {
+                Ok(sent) => {
+                    if sent > 0 {
+                        tracing::info!("Sent {} invite reminders", sent);
+                    }
+                }
+                Err(error) => {
+                    tracing::error!("Failed to send invite reminders: {}", error);
+                }
+            }
+        }
+    });
+}
+
+pub fn spawn_activity_digests(pool: PgPool, email_service: Option<EmailService>) {
+    let Some(email_service) = email_service else {
+        tracing::info!("Activity digests disabled: email service not configured");
+        return;
+    };
+    let interval_secs = std::env::var("ACTIVITY_DIGEST_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_ACTIVITY_DIGEST_INTERVAL_SECS);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            interval.tick().await;
+            match DigestService::send_activity_digests(&pool, &email_service).await {
+                Ok(sent) => {
+                    if sent > 0 {
+                        tracing::info!("Sent {} activity digests", sent);
+                    }
+                }
+                Err(error) => {
+                    tracing::error!("Failed to send activity digests: {}", error);
+                }
+            }
+        }
+    });
+}
+
+pub fn spawn_z_index_compaction(pool: PgPool, rooms: Rooms) {
+    let interval_secs = std::env::var("Z_INDEX_COMPACTION_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_Z_INDEX_COMPACTION_INTERVAL_SECS);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+        loop {
+            interval.tick().await;
+            match ElementService::compact_inflated_z_indices(&pool, &rooms).await {
+                Ok(compacted) => {
+                    if compacted > 0 {
+                        tracing::info!("Compacted z-indices on {} boards", compacted);
+                    }
+                }
+                Err(error) => {
+                    tracing::error!("Failed to compact element z-indices: {}", error);
+                }
+            }
+        }
+    });
+}
+
+fn invite_reminder_after_days(value: Option<&str>) -> i64 {
+    value
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_INVITE_REMINDER_AFTER_DAYS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_INVITE_REMINDER_AFTER_DAYS, invite_reminder_after_days};
+
+    #[test]
+    fn invite_reminder_delay_falls_back_on_invalid_values() {
+        assert_eq!(invite_reminder_after_days(Some("7")), 7);
+        assert_eq!(
+            invite_reminder_after_days(Some("0")),
+            DEFAULT_INVITE_REMINDER_AFTER_DAYS
+        );
+        assert_eq!(
+            invite_reminder_after_days(Some("soon")),
+            DEFAULT_INVITE_REMINDER_AFTER_DAYS
+        );
+        assert_eq!(
+            invite_reminder_after_days(None),
+            DEFAULT_INVITE_REMINDER_AFTER_DAYS
+        );
+    }
+}
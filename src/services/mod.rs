@@ -1,2 +1,7 @@
+pub(crate) mod access_cache;
+pub(crate) mod content_filter;
 pub(crate) mod email;
+pub(crate) mod email_templates;
+pub(crate) mod embed_policy;
 pub(crate) mod maintenance;
+pub(crate) mod redis_health;
@@ -0,0 +1,81 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+const DEFAULT_REDIS_PROBE_INTERVAL_SECS: u64 = 15;
+
+static REDIS_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// Circuit breaker shared by the Redis-backed features (presence, access
+/// cache). The first runtime failure opens it so callers go straight to their
+/// DB-only path instead of waiting on a dead connection; the probe started by
+/// [`spawn_probe`] closes it once Redis answers `PING` again.
+pub fn is_available() -> bool {
+    !REDIS_UNAVAILABLE.load(Ordering::Relaxed)
+}
+
+pub fn record_failure(error: &redis::RedisError) {
+    if !REDIS_UNAVAILABLE.swap(true, Ordering::Relaxed) {
+        tracing::warn!(
+            "Redis marked unavailable, falling back to DB-only mode: {}",
+            error
+        );
+    }
+}
+
+/// Re-probes Redis every `REDIS_PROBE_INTERVAL_SECS` (default 15) while the
+/// circuit is open. No-op without Redis.
+pub fn spawn_probe(redis: Option<redis::Client>) {
+    let Some(client) = redis else {
+        return;
+    };
+    let interval = std::env::var("REDIS_PROBE_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(DEFAULT_REDIS_PROBE_INTERVAL_SECS));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if is_available() {
+                continue;
+            }
+            match tokio::time::timeout(interval, ping(&client)).await {
+                Ok(Ok(())) => {
+                    REDIS_UNAVAILABLE.store(false, Ordering::Relaxed);
+                    tracing::info!("Redis reachable again, resuming Redis-backed features");
+                }
+                Ok(Err(error)) => tracing::debug!("Redis still unavailable: {}", error),
+                Err(_) => tracing::debug!("Redis probe timed out"),
+            }
+        }
+    });
+}
+
+async fn ping(client: &redis::Client) -> redis::RedisResult<()> {
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    redis::cmd("PING").query_async::<String>(&mut conn).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::{REDIS_UNAVAILABLE, is_available, record_failure};
+
+    #[test]
+    fn failures_open_the_circuit_until_reset() {
+        record_failure(&redis::RedisError::from((
+            redis::ErrorKind::IoError,
+            "connection refused",
+        )));
+        assert!(!is_available());
+
+        REDIS_UNAVAILABLE.store(false, Ordering::Relaxed);
+        assert!(is_available());
+    }
+}
@@ -1,8 +1,12 @@
-use std::time::Instant;
+use std::{sync::atomic::AtomicU64, time::Instant};
 
 use sqlx::postgres::PgQueryResult;
 use tracing::{Instrument, debug, info_span, warn};
 
+use super::sampling;
+
+static SUCCESS_LOG_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 pub async fn log_query<F, T, E, R>(query_name: &str, query: F, row_counter: R) -> Result<T, E>
 where
     F: std::future::Future<Output = Result<T, E>>,
@@ -15,6 +19,7 @@ where
     let duration_ms = start.elapsed().as_millis();
 
     span.in_scope(|| match &result {
+        Ok(_) if !sampling::should_sample("db_query", &SUCCESS_LOG_COUNTER) => {}
         Ok(value) => {
             if let Some(rows) = row_counter(value) {
                 debug!(latency_ms = %duration_ms, rows = %rows, "Query executed successfully");
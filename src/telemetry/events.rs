@@ -2,6 +2,21 @@ use serde::Serialize;
 use tracing::info;
 use uuid::Uuid;
 
+use crate::auth::api_tokens::is_api_token;
+
+const REDACTED: &str = "[REDACTED]";
+const SENSITIVE_PARAMS: &[&str] = &[
+    "token",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "api_key",
+    "password",
+    "secret",
+    "code",
+    "invite",
+];
+
 #[derive(Debug, Serialize)]
 #[serde(tag = "event_type")]
 pub enum BusinessEvent {
@@ -19,6 +34,15 @@ pub enum BusinessEvent {
     EmailVerified {
         user_id: Uuid,
     },
+    AccountLocked {
+        user_id: Uuid,
+        failed_attempts: i32,
+        locked_secs: i64,
+    },
+    AccountUnlocked {
+        user_id: Uuid,
+        unlocked_by: Uuid,
+    },
     BoardCreated {
         board_id: Uuid,
         user_id: Uuid,
@@ -58,6 +82,21 @@ pub enum BusinessEvent {
         removed_by: Uuid,
         removed_user: Uuid,
     },
+    OwnershipTransferred {
+        org_id: Uuid,
+        previous_owner_id: Uuid,
+        new_owner_id: Uuid,
+    },
+    ApiTokenCreated {
+        org_id: Uuid,
+        token_id: Uuid,
+        created_by: Uuid,
+    },
+    ApiTokenRevoked {
+        org_id: Uuid,
+        token_id: Uuid,
+        revoked_by: Uuid,
+    },
     #[serde(rename = "COLLAB_COMMENT_CREATE")]
     CommentCreated {
         comment_id: Uuid,
@@ -72,6 +111,16 @@ pub enum BusinessEvent {
         actor_id: Uuid,
         mentioned_user_ids: Vec<Uuid>,
     },
+    BoardJoined {
+        board_id: Uuid,
+        user_id: Uuid,
+        session_id: Uuid,
+    },
+    BoardLeft {
+        board_id: Uuid,
+        user_id: Uuid,
+        duration_secs: u64,
+    },
     CrdtSnapshotSaved {
         board_id: Uuid,
         snapshot_size: usize,
@@ -98,6 +147,133 @@ pub fn redact_email(email: &str) -> String {
     format!("{first_char}***@{domain}")
 }
 
+/// Scrubs free text submitted by clients: emails are masked with
+/// [`redact_email`], and bearer tokens, JWTs, API tokens and sensitive query
+/// parameters are replaced.
+pub fn redact_pii(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut word_start = None;
+    let mut after_bearer = false;
+    for (index, ch) in text.char_indices() {
+        let is_delimiter = ch.is_whitespace() || "\"'`<>()[]{},;".contains(ch);
+        match (is_delimiter, word_start) {
+            (false, None) => word_start = Some(index),
+            (true, Some(start)) => {
+                push_redacted_word(&mut redacted, &text[start..index], &mut after_bearer);
+                word_start = None;
+            }
+            _ => {}
+        }
+        if is_delimiter {
+            redacted.push(ch);
+        }
+    }
+    if let Some(start) = word_start {
+        push_redacted_word(&mut redacted, &text[start..], &mut after_bearer);
+    }
+    redacted
+}
+
+fn push_redacted_word(out: &mut String, word: &str, after_bearer: &mut bool) {
+    if std::mem::take(after_bearer) {
+        out.push_str(REDACTED);
+        return;
+    }
+    if word.eq_ignore_ascii_case("bearer") {
+        *after_bearer = true;
+        out.push_str(word);
+        return;
+    }
+    // Split URLs into query parameters so `?token=...` is caught as well.
+    let mut segment_start = 0;
+    for (index, ch) in word.char_indices() {
+        if matches!(ch, '?' | '&' | '#') {
+            push_redacted_segment(out, &word[segment_start..index]);
+            out.push(ch);
+            segment_start = index + 1;
+        }
+    }
+    push_redacted_segment(out, &word[segment_start..]);
+}
+
+fn push_redacted_segment(out: &mut String, segment: &str) {
+    match segment.split_once('=') {
+        Some((key, _)) if SENSITIVE_PARAMS.contains(&key.to_ascii_lowercase().as_str()) => {
+            out.push_str(key);
+            out.push('=');
+            out.push_str(REDACTED);
+        }
+        Some((key, value)) => {
+            out.push_str(key);
+            out.push('=');
+            push_redacted_value(out, value);
+        }
+        None => push_redacted_value(out, segment),
+    }
+}
+
+fn push_redacted_value(out: &mut String, value: &str) {
+    let core = value.trim_end_matches(['.', ':', '!']);
+    let trailing = &value[core.len()..];
+    if is_api_token(core) || is_jwt(core) {
+        out.push_str(REDACTED);
+    } else if is_email(core) {
+        out.push_str(&redact_email(core));
+    } else {
+        out.push_str(core);
+    }
+    out.push_str(trailing);
+}
+
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '-')
+}
+
+fn is_jwt(value: &str) -> bool {
+    let segments: Vec<&str> = value.split('.').collect();
+    segments.len() == 3
+        && value.starts_with("eyJ")
+        && segments.iter().all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        })
+}
+
+/// Whether join/leave events are logged for a WebSocket session, keeping
+/// `BOARD_SESSION_EVENTS_SAMPLE_RATE` (0.0-1.0, default 1.0) of sessions.
+/// Sampling keys on the session id so both events of a session agree.
+pub fn sample_session_events(session_id: Uuid) -> bool {
+    let rate = std::env::var("BOARD_SESSION_EVENTS_SAMPLE_RATE")
+        .ok()
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|value| value.is_finite())
+        .unwrap_or(1.0);
+    is_sampled(session_id, rate)
+}
+
+fn is_sampled(session_id: Uuid, rate: f64) -> bool {
+    if rate >= 1.0 {
+        return true;
+    }
+    if rate <= 0.0 {
+        return false;
+    }
+    // UUIDv7 ends in random bits, so the low bits spread sessions evenly.
+    let bucket = (session_id.as_u128() % 10_000) as f64;
+    bucket < rate * 10_000.0
+}
+
 impl BusinessEvent {
     pub fn log(&self) {
         let event_json = serde_json::to_string(self).unwrap_or_else(|_| format!("{:?}", self));
@@ -111,13 +287,39 @@ impl BusinessEvent {
 
 #[cfg(test)]
 mod tests {
-    use super::redact_email;
+    use uuid::Uuid;
+
+    use super::{is_sampled, redact_email, redact_pii};
 
     #[test]
     fn redacts_valid_email() {
         assert_eq!(redact_email("user@example.com"), "u***@example.com");
     }
 
+    #[test]
+    fn redacts_emails_and_tokens_in_free_text() {
+        assert_eq!(
+            redact_pii("Login failed for jane.doe@example.com."),
+            "Login failed for j***@example.com."
+        );
+        assert_eq!(
+            redact_pii("GET /boards?invite=abc123&email=bob@acme.io failed"),
+            "GET /boards?invite=[REDACTED]&email=b***@acme.io failed"
+        );
+        assert_eq!(
+            redact_pii("Authorization: Bearer abc.def.ghi (rtb_secret)"),
+            "Authorization: Bearer [REDACTED] ([REDACTED])"
+        );
+        assert_eq!(
+            redact_pii("jwt=eyJhbGciOi.eyJzdWIiOi.sig-123 expired"),
+            "jwt=[REDACTED] expired"
+        );
+        assert_eq!(
+            redact_pii("Cannot read property 'x' of undefined"),
+            "Cannot read property 'x' of undefined"
+        );
+    }
+
     #[test]
     fn redacts_missing_domain() {
         assert_eq!(redact_email("invalid"), "***");
@@ -127,4 +329,16 @@ mod tests {
     fn redacts_empty_value() {
         assert_eq!(redact_email(""), "***");
     }
+
+    #[test]
+    fn session_sampling_respects_bounds_and_rate() {
+        let session_id = Uuid::now_v7();
+        assert!(is_sampled(session_id, 1.0));
+        assert!(!is_sampled(session_id, 0.0));
+
+        let kept = (0..2_000)
+            .filter(|_| is_sampled(Uuid::now_v7(), 0.5))
+            .count();
+        assert!((700..1_300).contains(&kept));
+    }
 }
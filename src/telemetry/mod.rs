@@ -2,9 +2,10 @@ pub mod database;
 pub mod events;
 pub mod http;
 pub mod otel;
+pub mod sampling;
 pub mod subscriber;
 
-pub use events::{BusinessEvent, redact_email};
+pub use events::{BusinessEvent, redact_email, redact_pii, sample_session_events};
 pub use http::{
     REQUEST_ID_HEADER, TRACE_ID_HEADER, extract_header, extract_or_generate_header,
     request_logging_middleware,
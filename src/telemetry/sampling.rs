@@ -0,0 +1,78 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+const LOG_SAMPLE_RATES_ENV: &str = "LOG_SAMPLE_RATES";
+const LEGACY_WS_MESSAGE_SAMPLE_RATE_ENV: &str = "WS_MESSAGE_LOG_SAMPLE_RATE";
+const DEFAULT_WS_MESSAGE_SAMPLE_RATE: u64 = 100;
+
+static SAMPLE_RATES: OnceLock<HashMap<String, u64>> = OnceLock::new();
+
+/// Keeps one in `rate` log lines for `target`, where the rate comes from
+/// `LOG_SAMPLE_RATES` (e.g. `ws_message=100,db_query=10`), parsed once.
+/// Targets not listed log every line, except `ws_message` which defaults to
+/// `WS_MESSAGE_LOG_SAMPLE_RATE` or 100.
+pub fn sample_rate_for(target: &str) -> u64 {
+    SAMPLE_RATES
+        .get_or_init(load_sample_rates)
+        .get(target)
+        .copied()
+        .unwrap_or(1)
+}
+
+/// Advances `counter` and reports whether this line should be logged.
+pub fn should_sample(target: &str, counter: &AtomicU64) -> bool {
+    let rate = sample_rate_for(target);
+    rate <= 1 || counter.fetch_add(1, Ordering::Relaxed).is_multiple_of(rate)
+}
+
+fn load_sample_rates() -> HashMap<String, u64> {
+    let mut rates = HashMap::from([(
+        "ws_message".to_string(),
+        std::env::var(LEGACY_WS_MESSAGE_SAMPLE_RATE_ENV)
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_WS_MESSAGE_SAMPLE_RATE),
+    )]);
+    if let Ok(value) = std::env::var(LOG_SAMPLE_RATES_ENV) {
+        rates.extend(parse_sample_rates(&value));
+    }
+    rates
+}
+
+fn parse_sample_rates(value: &str) -> HashMap<String, u64> {
+    value
+        .split(',')
+        .filter_map(|entry| {
+            let (target, rate) = entry.split_once('=')?;
+            let target = target.trim();
+            let rate = rate.trim().parse::<u64>().ok().filter(|rate| *rate > 0);
+            match rate {
+                Some(rate) if !target.is_empty() => Some((target.to_string(), rate)),
+                _ => {
+                    tracing::warn!("Ignoring invalid {} entry: {}", LOG_SAMPLE_RATES_ENV, entry);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_sample_rates;
+
+    #[test]
+    fn parses_target_rates_and_skips_invalid_entries() {
+        let rates = parse_sample_rates(" ws_message=100, db_query = 10,bad,zero=0,=5");
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates["ws_message"], 100);
+        assert_eq!(rates["db_query"], 10);
+    }
+}
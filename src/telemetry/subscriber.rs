@@ -37,7 +37,7 @@ impl LogSettings {
 
     fn default_filter(&self) -> String {
         let level = self.level.as_str().to_lowercase();
-        format!("{level},tower_http=info")
+        format!("{level},tower_http=info,board_access=info")
     }
 }
 
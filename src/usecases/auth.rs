@@ -4,24 +4,63 @@ use crate::{
     auth::invite_tokens::hash_invite_token,
     auth::jwt::{JwtConfig, hash_password, verify_password_user},
     dto::auth::{
-        ChangePasswordRequest, DeleteAccountRequest, LoginRequest, LoginResponse, RegisterRequest,
-        UpdatePreferencesRequest, UpdateUserRequest, UserProfileResponse, UserResponse,
+        ChangePasswordRequest, DeleteAccountRequest, LoginRequest, LoginResponse,
+        PublicUserProfile, RegisterRequest, UpdatePreferencesRequest, UpdateUserRequest,
+        UserLookupRequest, UserLookupResponse, UserProfileResponse, UserResponse,
+        UserSessionResponse, UserSessionsResponse,
     },
-    error::AppError,
+    error::{AppError, FieldErrors},
     repositories::organizations as org_repo,
+    repositories::sessions as session_repo,
     repositories::users as user_repo,
-    services::email::EmailService,
+    services::{email::EmailService, email_templates::EmailLocale},
     telemetry::{BusinessEvent, redact_email},
+    usecases::{boards::env_usize, organizations::is_configured_platform_admin},
 };
 use std::sync::OnceLock;
 
 const INVALID_CREDENTIALS_MSG: &str = "Invalid email or password";
+const DEFAULT_LOGIN_LOCKOUT_THRESHOLD: usize = 5;
+const DEFAULT_LOGIN_LOCKOUT_BASE_SECS: usize = 60;
+const DEFAULT_LOGIN_LOCKOUT_MAX_SECS: usize = 3600;
+const MAX_USER_LOOKUP_IDS: usize = 100;
 static DUMMY_HASH: OnceLock<String> = OnceLock::new();
 
 fn invalid_credentials_error() -> AppError {
     AppError::InvalidCredentials(INVALID_CREDENTIALS_MSG.to_string())
 }
 
+/// Where a sign-in came from, recorded on the session it creates.
+#[derive(Debug, Clone, Default)]
+pub struct SessionClient {
+    pub device: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// Records a new session and issues a token carrying its id as `jti`.
+async fn issue_session_token(
+    pool: &sqlx::PgPool,
+    jwt_config: &JwtConfig,
+    user_id: Uuid,
+    email: &str,
+    client: &SessionClient,
+) -> Result<String, AppError> {
+    let session_id = Uuid::now_v7();
+    let expires_at = chrono::Utc::now() + chrono::Duration::hours(jwt_config.expiration_hours);
+    session_repo::insert_session(
+        pool,
+        session_id,
+        user_id,
+        client.device.as_deref(),
+        client.ip_address.as_deref(),
+        expires_at,
+    )
+    .await?;
+    jwt_config
+        .create_token(user_id, email.to_string(), session_id)
+        .map_err(|e| AppError::Internal(format!("Failed to create token: {}", e)))
+}
+
 pub struct UserServices;
 impl UserServices {
     pub async fn register_user(
@@ -29,20 +68,10 @@ impl UserServices {
         jwt_config: &JwtConfig,
         email_service: Option<&EmailService>,
         req: RegisterRequest,
+        client: &SessionClient,
     ) -> Result<LoginResponse, AppError> {
         let email = req.email.trim().to_string();
-        if !is_valid_email(&email) {
-            return Err(AppError::ValidationError(
-                "Email format is invalid".to_string(),
-            ));
-        }
-
-        if !is_strong_password(&req.password_hash) {
-            return Err(AppError::ValidationError(
-                "Password must be at least 8 characters and include 1 uppercase letter and 1 number"
-                    .to_string(),
-            ));
-        }
+        validate_registration(&email, &req.username, &req.password_hash)?;
 
         if user_repo::email_exists(pool, &email).await? {
             return Err(AppError::Conflict("Email already exists".to_string()));
@@ -125,9 +154,7 @@ impl UserServices {
             .log();
         }
 
-        let token = jwt_config
-            .create_token(user.id, user.email.clone())
-            .map_err(|e| AppError::Internal(format!("Failed to create token: {}", e)))?;
+        let token = issue_session_token(pool, jwt_config, user.id, &user.email, client).await?;
 
         if user.email_verified_at.is_none() {
             let verification_token = jwt_config
@@ -138,7 +165,11 @@ impl UserServices {
                 "Email service not configured".to_string(),
             ))?;
             email_service
-                .send_verification_email(&user.email, &verification_token)
+                .send_verification_email(
+                    &user.email,
+                    EmailLocale::from_language(&user.preferences.language),
+                    &verification_token,
+                )
                 .await?;
             user_repo::set_verification_sent_at(pool, user.id, chrono::Utc::now()).await?;
         }
@@ -152,6 +183,7 @@ impl UserServices {
         pool: &sqlx::PgPool,
         jwt_config: &JwtConfig,
         req: LoginRequest,
+        client: &SessionClient,
     ) -> Result<LoginResponse, AppError> {
         let user = match user_repo::find_user_by_email(pool, &req.email).await? {
             Some(user) => user,
@@ -175,6 +207,9 @@ impl UserServices {
                 return Err(invalid_credentials_error());
             }
         };
+        let locked = user
+            .locked_until
+            .is_some_and(|until| until > chrono::Utc::now());
         let hash = user
             .password_hash
             .as_deref()
@@ -183,12 +218,23 @@ impl UserServices {
         //verify password
         let verifypassword =
             verify_password_user(&req.password, hash).map_err(|_| invalid_credentials_error())?;
+        if locked {
+            BusinessEvent::LoginFailed {
+                email_redacted: redact_email(&req.email),
+                reason: "account_locked".to_string(),
+            }
+            .log();
+            // Same response as an unknown email, so a lock does not reveal
+            // that the account exists.
+            return Err(invalid_credentials_error());
+        }
         if !verifypassword {
             BusinessEvent::LoginFailed {
                 email_redacted: redact_email(&req.email),
                 reason: "invalid_password".to_string(),
             }
             .log();
+            register_failed_login(pool, user.id).await?;
             return Err(invalid_credentials_error());
         }
         if !user.is_active {
@@ -200,10 +246,11 @@ impl UserServices {
             return Err(invalid_credentials_error());
         }
 
+        if user.failed_login_count > 0 || user.locked_until.is_some() {
+            user_repo::reset_login_lockout(pool, user.id).await?;
+        }
         user_repo::update_last_active(pool, user.id).await?;
-        let token = jwt_config
-            .create_token(user.id, user.email.clone())
-            .map_err(|e| AppError::Internal(format!("Failed to create token: {}", e)))?;
+        let token = issue_session_token(pool, jwt_config, user.id, &user.email, client).await?;
 
         BusinessEvent::UserLoggedIn { user_id: user.id }.log();
         Ok(LoginResponse {
@@ -212,6 +259,63 @@ impl UserServices {
         })
     }
 
+    /// Lists the user's unexpired, unrevoked sessions, most recent first.
+    pub async fn list_sessions(
+        pool: &sqlx::PgPool,
+        user_id: Uuid,
+        current_session_id: Option<Uuid>,
+    ) -> Result<UserSessionsResponse, AppError> {
+        let sessions = session_repo::list_active_sessions(pool, user_id).await?;
+        Ok(UserSessionsResponse {
+            sessions: sessions
+                .into_iter()
+                .map(|session| UserSessionResponse {
+                    current: Some(session.id) == current_session_id,
+                    id: session.id,
+                    device: session.device,
+                    ip_address: session.ip_address,
+                    created_at: session.created_at,
+                    last_seen_at: session.last_seen_at,
+                    expires_at: session.expires_at,
+                })
+                .collect(),
+        })
+    }
+
+    /// Revokes one of the user's sessions; its token stops working at once.
+    pub async fn revoke_session(
+        pool: &sqlx::PgPool,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<(), AppError> {
+        if !session_repo::revoke_session(pool, user_id, session_id).await? {
+            return Err(AppError::NotFound("Session not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Clears a login lockout (platform admins only).
+    pub async fn unlock_account(
+        pool: &sqlx::PgPool,
+        requester_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        if !is_configured_platform_admin(requester_id) {
+            return Err(AppError::Forbidden(
+                "Only platform administrators can unlock accounts".to_string(),
+            ));
+        }
+        if !user_repo::reset_login_lockout(pool, user_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        BusinessEvent::AccountUnlocked {
+            user_id,
+            unlocked_by: requester_id,
+        }
+        .log();
+        Ok(())
+    }
+
     pub async fn get_user_by_id(
         pool: &sqlx::PgPool,
         user_id: Uuid,
@@ -220,6 +324,23 @@ impl UserServices {
         Ok(UserResponse::from(user))
     }
 
+    /// Resolves public profiles for users the caller shares a board or
+    /// organization with; unknown or unrelated ids are omitted.
+    pub async fn lookup_users(
+        pool: &sqlx::PgPool,
+        viewer_id: Uuid,
+        req: UserLookupRequest,
+    ) -> Result<UserLookupResponse, AppError> {
+        let user_ids = normalize_lookup_ids(req.user_ids)?;
+        if user_ids.is_empty() {
+            return Ok(UserLookupResponse { users: Vec::new() });
+        }
+        let users = user_repo::find_users_by_ids(pool, viewer_id, &user_ids).await?;
+        Ok(UserLookupResponse {
+            users: users.into_iter().map(PublicUserProfile::from).collect(),
+        })
+    }
+
     pub async fn get_user_profile(
         pool: &sqlx::PgPool,
         user_id: Uuid,
@@ -393,7 +514,11 @@ impl UserServices {
             "Email service not configured".to_string(),
         ))?;
         email_service
-            .send_verification_email(&user.email, &token)
+            .send_verification_email(
+                &user.email,
+                EmailLocale::from_language(&user.preferences.language),
+                &token,
+            )
             .await?;
         user_repo::set_verification_sent_at(pool, user.id, chrono::Utc::now()).await?;
 
@@ -433,6 +558,79 @@ impl UserServices {
     }
 }
 
+/// Counts a failed password attempt and locks the account once the
+/// configured threshold is reached.
+async fn register_failed_login(pool: &sqlx::PgPool, user_id: Uuid) -> Result<(), AppError> {
+    let failed_attempts = user_repo::record_failed_login(pool, user_id).await?;
+    let Some(locked_secs) = lockout_duration_secs(
+        failed_attempts,
+        env_usize("LOGIN_LOCKOUT_THRESHOLD", DEFAULT_LOGIN_LOCKOUT_THRESHOLD),
+        env_usize("LOGIN_LOCKOUT_BASE_SECS", DEFAULT_LOGIN_LOCKOUT_BASE_SECS),
+        env_usize("LOGIN_LOCKOUT_MAX_SECS", DEFAULT_LOGIN_LOCKOUT_MAX_SECS),
+    ) else {
+        return Ok(());
+    };
+    user_repo::lock_user_until(
+        pool,
+        user_id,
+        chrono::Utc::now() + chrono::Duration::seconds(locked_secs),
+    )
+    .await?;
+    BusinessEvent::AccountLocked {
+        user_id,
+        failed_attempts,
+        locked_secs,
+    }
+    .log();
+    Ok(())
+}
+
+/// Lock length for the given consecutive failure count: `base` at the
+/// threshold, doubling with each further failure up to `max`.
+fn lockout_duration_secs(
+    failed_attempts: i32,
+    threshold: usize,
+    base: usize,
+    max: usize,
+) -> Option<i64> {
+    let failed_attempts = usize::try_from(failed_attempts).ok()?;
+    if failed_attempts < threshold {
+        return None;
+    }
+    let doublings = (failed_attempts - threshold).min(32) as u32;
+    let secs = base.saturating_mul(1_usize << doublings).min(max);
+    i64::try_from(secs).ok()
+}
+
+/// Checks every registration field so the form can flag all of them at once.
+fn validate_registration(email: &str, username: &str, password: &str) -> Result<(), AppError> {
+    let mut errors = FieldErrors::default();
+    if !is_valid_email(email) {
+        errors.add("email", "Email format is invalid");
+    }
+    if !is_valid_username(username) {
+        errors.add(
+            "username",
+            "Username must be 3-50 letters, digits, or underscores",
+        );
+    }
+    if !is_strong_password(password) {
+        errors.add(
+            "password",
+            "Password must be at least 8 characters and include 1 uppercase letter and 1 number",
+        );
+    }
+    errors.into_result()
+}
+
+/// Mirrors the `user_username_format` check constraint.
+fn is_valid_username(username: &str) -> bool {
+    (3..=50).contains(&username.len())
+        && username
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
 fn is_valid_email(email: &str) -> bool {
     let trimmed = email.trim();
     if trimmed.is_empty() || trimmed.contains(' ') {
@@ -459,6 +657,18 @@ fn is_valid_email(email: &str) -> bool {
     domain.contains('.')
 }
 
+fn normalize_lookup_ids(user_ids: Vec<Uuid>) -> Result<Vec<Uuid>, AppError> {
+    let mut seen = std::collections::HashSet::with_capacity(user_ids.len());
+    let unique: Vec<Uuid> = user_ids.into_iter().filter(|id| seen.insert(*id)).collect();
+    if unique.len() > MAX_USER_LOOKUP_IDS {
+        return Err(AppError::BadRequest(format!(
+            "At most {} user ids can be looked up at once",
+            MAX_USER_LOOKUP_IDS
+        )));
+    }
+    Ok(unique)
+}
+
 fn is_strong_password(password: &str) -> bool {
     if password.len() < 8 {
         return false;
@@ -475,3 +685,49 @@ fn is_strong_password(password: &str) -> bool {
     }
     has_upper && has_digit
 }
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{
+        MAX_USER_LOOKUP_IDS, lockout_duration_secs, normalize_lookup_ids, validate_registration,
+    };
+    use crate::error::AppError;
+
+    #[test]
+    fn lockout_duration_doubles_past_threshold_up_to_cap() {
+        assert_eq!(lockout_duration_secs(4, 5, 60, 3600), None);
+        assert_eq!(lockout_duration_secs(5, 5, 60, 3600), Some(60));
+        assert_eq!(lockout_duration_secs(7, 5, 60, 3600), Some(240));
+        assert_eq!(lockout_duration_secs(12, 5, 60, 3600), Some(3600));
+        assert_eq!(lockout_duration_secs(i32::MAX, 5, 60, 3600), Some(3600));
+    }
+
+    #[test]
+    fn validate_registration_reports_each_invalid_field() {
+        assert!(validate_registration("ann@example.com", "ann_01", "Secret123").is_ok());
+
+        let Err(AppError::FieldValidation(errors)) =
+            validate_registration("not-an-email", "a!", "weak")
+        else {
+            panic!("expected field validation error");
+        };
+        let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["email", "username", "password"]);
+    }
+
+    #[test]
+    fn normalize_lookup_ids_dedupes_in_order() {
+        let first = Uuid::now_v7();
+        let second = Uuid::now_v7();
+        let ids = normalize_lookup_ids(vec![first, second, first]).expect("valid ids");
+        assert_eq!(ids, vec![first, second]);
+    }
+
+    #[test]
+    fn normalize_lookup_ids_rejects_oversized_batches() {
+        let ids = (0..=MAX_USER_LOOKUP_IDS).map(|_| Uuid::now_v7()).collect();
+        assert!(normalize_lookup_ids(ids).is_err());
+    }
+}
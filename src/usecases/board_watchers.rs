@@ -0,0 +1,141 @@
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    dto::boards::BoardWatchResponse, error::AppError, repositories::board_watchers as watcher_repo,
+    usecases::boards::BoardService,
+};
+
+const DEFAULT_WATCH_NOTIFY_DEBOUNCE_SECS: i64 = 900;
+
+/// Board change worth telling watchers about.
+#[derive(Debug, Clone, Copy)]
+pub enum BoardActivity {
+    ElementsAdded { count: usize },
+    CommentAdded { comment_id: Uuid },
+}
+
+impl BoardActivity {
+    fn kind(self) -> &'static str {
+        match self {
+            BoardActivity::ElementsAdded { .. } => "element_added",
+            BoardActivity::CommentAdded { .. } => "comment_added",
+        }
+    }
+
+    fn body(self) -> String {
+        match self {
+            BoardActivity::ElementsAdded { count: 1 } => "A new element was added".to_string(),
+            BoardActivity::ElementsAdded { count } => format!("{} new elements were added", count),
+            BoardActivity::CommentAdded { .. } => "A new comment was added".to_string(),
+        }
+    }
+}
+
+/// Subscriptions to board activity, independent of membership.
+pub struct BoardWatcherService;
+
+impl BoardWatcherService {
+    pub async fn watch(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<BoardWatchResponse, AppError> {
+        BoardService::ensure_can_view(pool, board_id, user_id).await?;
+        watcher_repo::insert_watcher(pool, board_id, user_id).await?;
+        Ok(BoardWatchResponse { is_watching: true })
+    }
+
+    pub async fn unwatch(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<BoardWatchResponse, AppError> {
+        watcher_repo::delete_watcher(pool, board_id, user_id).await?;
+        Ok(BoardWatchResponse { is_watching: false })
+    }
+
+    pub async fn get_watch_status(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<BoardWatchResponse, AppError> {
+        BoardService::ensure_can_view(pool, board_id, user_id).await?;
+        let is_watching = watcher_repo::is_watching(pool, board_id, user_id).await?;
+        Ok(BoardWatchResponse { is_watching })
+    }
+
+    /// Notifies watchers of `activity`, at most once per debounce window each.
+    ///
+    /// Failures are logged rather than returned so the triggering write is
+    /// never rolled back or reported as failed because of a notification.
+    pub async fn notify_activity(
+        pool: &PgPool,
+        board_id: Uuid,
+        actor_id: Option<Uuid>,
+        activity: BoardActivity,
+    ) {
+        if let Err(error) = notify_watchers(pool, board_id, actor_id, activity).await {
+            tracing::warn!(
+                board_id = %board_id,
+                activity = activity.kind(),
+                "Failed to notify board watchers: {}",
+                error
+            );
+        }
+    }
+}
+
+async fn notify_watchers(
+    pool: &PgPool,
+    board_id: Uuid,
+    actor_id: Option<Uuid>,
+    activity: BoardActivity,
+) -> Result<(), AppError> {
+    let due =
+        watcher_repo::claim_due_watchers(pool, board_id, actor_id, notify_debounce_secs()).await?;
+    let mut recipients = Vec::with_capacity(due.len());
+    for user_id in due {
+        // Watching does not outlive access; drop watches the user can no longer see.
+        let access = BoardService::resolve_access_for_boards(pool, user_id, &[board_id]).await?;
+        if access.contains_key(&board_id) {
+            recipients.push(user_id);
+        } else {
+            watcher_repo::delete_watcher(pool, board_id, user_id).await?;
+        }
+    }
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    let mut data = json!({
+        "board_id": board_id,
+        "activity": activity.kind(),
+    });
+    match activity {
+        BoardActivity::ElementsAdded { count } => data["count"] = json!(count),
+        BoardActivity::CommentAdded { comment_id } => data["comment_id"] = json!(comment_id),
+    }
+    watcher_repo::create_activity_notifications(
+        pool,
+        watcher_repo::CreateBoardActivityNotifications {
+            user_ids: recipients,
+            actor_id,
+            board_id,
+            title: "New activity on a board you watch".to_string(),
+            body: activity.body(),
+            data,
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+fn notify_debounce_secs() -> i64 {
+    std::env::var("WATCH_NOTIFY_DEBOUNCE_SECS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|value| *value >= 0)
+        .unwrap_or(DEFAULT_WATCH_NOTIFY_DEBOUNCE_SECS)
+}
@@ -1,42 +1,89 @@
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{Value, json};
 use sqlx::{PgPool, Postgres, Transaction};
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicBool, Ordering},
+};
 use uuid::Uuid;
+use yrs::{Doc, Transact, Update, updates::decoder::Decode};
 
 use crate::{
     dto::boards::{
-        BoardActionMessage, BoardFavoriteResponse, BoardMemberResponse, BoardMemberUser,
-        BoardMembersResponse, BoardResponse, CreateBoardRequest, InviteBoardMembersRequest,
-        InviteBoardMembersResponse, TransferBoardOwnershipRequest, UpdateBoardMemberRoleRequest,
-        UpdateBoardRequest,
+        AddBoardCoOwnerRequest, BoardAccessOverviewResponse, BoardAccessSummary,
+        BoardActionMessage, BoardCapabilities, BoardCardListResponse, BoardCardPagination,
+        BoardCardsQuery, BoardCrdtDumpResponse, BoardDetailResponse, BoardFavoriteResponse,
+        BoardImportFormat, BoardMemberResponse, BoardMemberUser, BoardMembersResponse,
+        BoardPermissionsResponse, BoardRenderQuery, BoardResponse, BoardStatsResponse,
+        CreateBoardRequest, DuplicateBoardSummary, ElementTypeCount, ImplicitBoardAccess,
+        ImportBoardBinaryQuery, ImportBoardQuery, ImportBoardResponse, InviteBoardMembersRequest,
+        InviteBoardMembersResponse, ProjectionRebuildResponse, TransferBoardOwnershipRequest,
+        UpdateBoardMemberRoleRequest, UpdateBoardRequest,
     },
-    error::AppError,
+    error::{AppError, FieldErrors},
     models::{
-        boards::{Board, BoardPermissionOverrides, BoardPermissions, BoardRole, CanvasSettings},
+        boards::{
+            Board, BoardFeature, BoardFeatures, BoardPermissionOverrides, BoardPermissions,
+            BoardRole, CanvasSettings,
+        },
         elements::BoardElement,
         organizations::OrgRole,
-        users::{SubscriptionTier, User},
+        users::{EmailCategory, SubscriptionTier, User},
+    },
+    realtime::{
+        element_crdt, projection,
+        protocol::BoardRoleUpdate,
+        render,
+        resume::{self, ResyncMetrics},
+        room::Rooms,
+        snapshot,
     },
-    realtime::snapshot,
-    repositories::boards as board_repo,
+    repositories::boards::{self as board_repo, PurgedBoardCounts},
     repositories::elements as element_repo,
     repositories::organizations as org_repo,
     repositories::realtime as realtime_repo,
     repositories::users as user_repo,
-    services::email::EmailService,
+    services::{
+        access_cache::{self, AccessInvalidation, CacheLookup, CachedAccess},
+        email::EmailService,
+    },
     telemetry::{BusinessEvent, redact_email},
+    usecases::import::excalidraw,
     usecases::invites::collect_invite_emails,
-    usecases::organizations::{max_boards_for_tier, send_invite_emails},
+    usecases::organizations::{
+        is_configured_platform_admin, max_boards_for_tier, send_invite_emails,
+    },
+    usecases::retry::with_retry,
 };
 pub struct BoardService;
 
 const TRASH_RETENTION_DAYS: i64 = 30;
-
-pub(crate) struct BoardMemberChange {
-    pub message: BoardActionMessage,
-    pub member_user_id: Uuid,
-    pub role: Option<BoardRole>,
-    pub permissions: Option<BoardPermissions>,
+const MAX_BOARD_ACCESS_BATCH: usize = 200;
+const MAX_DUPLICATE_BOARD_WARNINGS: i64 = 5;
+const DEFAULT_BOARD_CARDS_LIMIT: u32 = 24;
+const MAX_BOARD_CARDS_LIMIT: u32 = 100;
+const MAX_LAST_WRITE_WINS_FIELDS: usize = 32;
+const MAX_BOARD_TAGS: usize = 20;
+const MAX_BOARD_TAG_LENGTH: usize = 32;
+const DEFAULT_BOARD_PUBLIC_ENV: &str = "DEFAULT_BOARD_PUBLIC";
+const DEFAULT_ORG_BOARD_PUBLIC_ENV: &str = "DEFAULT_ORG_BOARD_PUBLIC";
+const PROJECTION_REBUILD_CONCURRENCY_ENV: &str = "PROJECTION_REBUILD_CONCURRENCY";
+const DEFAULT_PROJECTION_REBUILD_CONCURRENCY: usize = 4;
+const REQUIRE_VERIFIED_EMAIL_FOR_BOARDS_ENV: &str = "REQUIRE_VERIFIED_EMAIL_FOR_BOARDS";
+const DEFAULT_CANVAS_WIDTH_ENV: &str = "BOARD_DEFAULT_CANVAS_WIDTH";
+const DEFAULT_CANVAS_HEIGHT_ENV: &str = "BOARD_DEFAULT_CANVAS_HEIGHT";
+const DEFAULT_CANVAS_GRID_SIZE_ENV: &str = "BOARD_DEFAULT_GRID_SIZE";
+
+static PROJECTION_REBUILD_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Clears [`PROJECTION_REBUILD_RUNNING`] when dropped, so a failed or
+/// panicking rebuild does not block later ones.
+struct ProjectionRebuildGuard;
+
+impl Drop for ProjectionRebuildGuard {
+    fn drop(&mut self) {
+        PROJECTION_REBUILD_RUNNING.store(false, Ordering::Release);
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -45,7 +92,7 @@ struct BoardAccess {
     permissions: BoardPermissions,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum BoardPermission {
     View,
     Edit,
@@ -55,22 +102,81 @@ enum BoardPermission {
 }
 
 impl BoardService {
-    /// Lists boards accessible to the user, optionally filtered by organization.
+    /// Lists boards accessible to the user, optionally filtered by
+    /// organization, template flag and tag.
     pub async fn get_board(
         pool: &PgPool,
         user_id: Uuid,
         organization_id: Option<Uuid>,
         is_template: Option<bool>,
+        tag: Option<String>,
+    ) -> Result<Vec<BoardResponse>, AppError> {
+        let tag = tag.and_then(|tag| normalize_tag(&tag));
+        board_repo::list_boards_for_user(
+            pool,
+            user_id,
+            organization_id,
+            is_template,
+            tag.as_deref(),
+        )
+        .await
+    }
+
+    /// Lists an organization's template boards visible to the user.
+    pub async fn list_organization_templates(
+        pool: &PgPool,
+        user_id: Uuid,
+        organization_id: Uuid,
+        tag: Option<String>,
     ) -> Result<Vec<BoardResponse>, AppError> {
-        board_repo::list_boards_for_user(pool, user_id, organization_id, is_template).await
+        org_repo::get_member_role(pool, organization_id, user_id)
+            .await?
+            .ok_or(AppError::Forbidden(
+                "You are not a member of this organization".to_string(),
+            ))?;
+        Self::get_board(pool, user_id, Some(organization_id), Some(true), tag).await
+    }
+
+    /// Lists one page of dashboard cards (owner, favorite, member count and
+    /// last activity) for boards the user can see.
+    pub async fn list_board_cards(
+        pool: &PgPool,
+        user_id: Uuid,
+        query: BoardCardsQuery,
+    ) -> Result<BoardCardListResponse, AppError> {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_BOARD_CARDS_LIMIT)
+            .clamp(1, MAX_BOARD_CARDS_LIMIT);
+        let offset = query.offset.unwrap_or(0);
+
+        let mut data = board_repo::list_board_cards(
+            pool,
+            user_id,
+            query.organization_id,
+            limit as i64 + 1,
+            offset as i64,
+        )
+        .await?;
+        let has_more = data.len() > limit as usize;
+        data.truncate(limit as usize);
+        let next_offset = has_more.then(|| offset + limit);
+
+        Ok(BoardCardListResponse {
+            data,
+            pagination: BoardCardPagination {
+                next_offset,
+                has_more,
+            },
+        })
     }
 
-    /// Loads a board with full metadata, enforcing access rules.
+    /// Loads a board with full metadata and content stats, enforcing access rules.
     pub async fn get_board_detail(
         pool: &PgPool,
         board_id: Uuid,
         user_id: Uuid,
-    ) -> Result<Board, AppError> {
+    ) -> Result<BoardDetailResponse, AppError> {
         require_board_permission(pool, board_id, user_id, BoardPermission::View).await?;
         if let Err(error) = board_repo::touch_board_last_accessed(pool, board_id, user_id).await {
             tracing::warn!(
@@ -79,9 +185,190 @@ impl BoardService {
                 error
             );
         }
-        board_repo::find_board_by_id(pool, board_id)
+        let board = board_repo::find_board_by_id(pool, board_id)
             .await?
-            .ok_or(AppError::NotFound("Board not found".to_string()))
+            .ok_or(AppError::NotFound("Board not found".to_string()))?;
+        let stats = board_repo::get_board_content_stats(pool, board_id).await?;
+        Ok(BoardDetailResponse {
+            board,
+            element_count: stats.element_count,
+            snapshot_seq: stats.snapshot_seq,
+            snapshot_bytes: stats.snapshot_bytes,
+            last_snapshot_at: stats.last_snapshot_at,
+        })
+    }
+
+    /// Returns the board CRDT state as a yrs `update_v1` blob.
+    ///
+    /// Uses the live room doc when the board is loaded so unsaved edits are included.
+    pub async fn export_board_snapshot(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<u8>, AppError> {
+        require_board_permission(pool, board_id, user_id, BoardPermission::View).await?;
+        if let Some(room_entry) = rooms.get(&board_id) {
+            let room = room_entry.clone();
+            drop(room_entry);
+            return Ok(snapshot::encode_doc_state(&room.doc).await);
+        }
+        snapshot::build_state_update(pool, board_id).await
+    }
+
+    /// Returns element counts by type, comment and contributor totals, and
+    /// activity timestamps. Counts come from the element projection, so edits
+    /// in a live room appear once they are projected.
+    pub async fn board_stats(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<BoardStatsResponse, AppError> {
+        let board = load_board_for_access(pool, board_id).await?;
+        require_board_permission_with_board(pool, &board, user_id, BoardPermission::View).await?;
+
+        let elements_by_type: Vec<ElementTypeCount> =
+            board_repo::count_elements_by_type(pool, board_id)
+                .await?
+                .into_iter()
+                .map(|row| ElementTypeCount {
+                    element_type: row.element_type,
+                    count: row.count,
+                })
+                .collect();
+        let totals = board_repo::get_collaboration_totals(pool, board_id).await?;
+
+        Ok(BoardStatsResponse {
+            board_id,
+            total_elements: elements_by_type.iter().map(|entry| entry.count).sum(),
+            elements_by_type,
+            total_comments: totals.comment_count,
+            unique_contributors: totals.contributor_count,
+            created_at: board.created_at,
+            last_edited_at: board.last_edited_at,
+        })
+    }
+
+    /// Dumps the board's raw `elements` CRDT map for support (platform admins
+    /// only), preferring the live room doc over stored updates.
+    pub async fn dump_board_crdt(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        requester_id: Uuid,
+    ) -> Result<BoardCrdtDumpResponse, AppError> {
+        if !is_configured_platform_admin(requester_id) {
+            return Err(AppError::Forbidden(
+                "Only platform administrators can inspect board CRDT state".to_string(),
+            ));
+        }
+        load_board_including_deleted(pool, board_id).await?;
+
+        let live_room = rooms.get(&board_id).map(|entry| entry.clone());
+        let elements = match live_room.as_ref() {
+            Some(room) => {
+                let doc = room.doc.lock().await;
+                element_crdt::dump_elements(&doc)
+            }
+            None => {
+                let state = snapshot::build_state_update(pool, board_id).await?;
+                let doc = Doc::new();
+                if !state.is_empty() {
+                    let update = Update::decode_v1(&state).map_err(|error| {
+                        AppError::Internal(format!("Invalid stored board state: {}", error))
+                    })?;
+                    doc.transact_mut().apply_update(update).map_err(|error| {
+                        AppError::Internal(format!("Invalid stored board state: {}", error))
+                    })?;
+                }
+                element_crdt::dump_elements(&doc)
+            }
+        };
+
+        Ok(BoardCrdtDumpResponse {
+            board_id,
+            live_room: live_room.is_some(),
+            element_count: elements.as_object().map_or(0, |elements| elements.len()),
+            elements,
+        })
+    }
+
+    /// Returns this node's WebSocket initial-sync counters (platform admins
+    /// only), splitting full syncs from resumed ones.
+    pub fn resync_metrics(requester_id: Uuid) -> Result<ResyncMetrics, AppError> {
+        if !is_configured_platform_admin(requester_id) {
+            return Err(AppError::Forbidden(
+                "Only platform administrators can view realtime metrics".to_string(),
+            ));
+        }
+        Ok(resume::resync_metrics())
+    }
+
+    /// Starts a background rebuild of every board's element projection from
+    /// its CRDT state (platform admins only). Only one rebuild runs at a time.
+    pub async fn rebuild_projections(
+        pool: &PgPool,
+        rooms: &Rooms,
+        requester_id: Uuid,
+    ) -> Result<ProjectionRebuildResponse, AppError> {
+        if !is_configured_platform_admin(requester_id) {
+            return Err(AppError::Forbidden(
+                "Only platform administrators can rebuild projections".to_string(),
+            ));
+        }
+        if PROJECTION_REBUILD_RUNNING.swap(true, Ordering::AcqRel) {
+            return Err(AppError::Conflict(
+                "A projection rebuild is already running".to_string(),
+            ));
+        }
+        let guard = ProjectionRebuildGuard;
+        let board_ids = board_repo::list_all_board_ids(pool).await?;
+        let boards = board_ids.len();
+        let concurrency = projection_rebuild_concurrency(
+            std::env::var(PROJECTION_REBUILD_CONCURRENCY_ENV)
+                .ok()
+                .as_deref(),
+        );
+
+        let pool = pool.clone();
+        let rooms = rooms.clone();
+        tokio::spawn(async move {
+            let _guard = guard;
+            projection::rebuild_projections(&pool, &rooms, board_ids, concurrency).await;
+        });
+
+        Ok(ProjectionRebuildResponse {
+            boards,
+            concurrency,
+        })
+    }
+
+    /// Renders the board's live elements as an SVG document.
+    pub async fn render_board(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+        query: BoardRenderQuery,
+    ) -> Result<String, AppError> {
+        let format = query.format.as_deref().unwrap_or("svg");
+        if !format.eq_ignore_ascii_case("svg") {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported render format `{}`; only `svg` is available",
+                format
+            )));
+        }
+        require_board_permission(pool, board_id, user_id, BoardPermission::View).await?;
+        let elements = if let Some(room_entry) = rooms.get(&board_id) {
+            let room = room_entry.clone();
+            drop(room_entry);
+            let doc = room.doc.lock().await;
+            element_crdt::materialize_elements(&doc)
+        } else {
+            let state = snapshot::build_state_update(pool, board_id).await?;
+            decode_imported_elements(&state)?
+        };
+        Ok(render::render_svg(&elements))
     }
 
     pub async fn toggle_board_favorite(
@@ -107,6 +394,16 @@ impl BoardService {
         Ok(resolve_board_access(pool, board_id, user_id).await?.role)
     }
 
+    /// Resolves the caller's role and effective permissions together.
+    pub async fn get_access(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(BoardRole, BoardPermissions), AppError> {
+        let access = resolve_board_access(pool, board_id, user_id).await?;
+        Ok((access.role, access.permissions))
+    }
+
     pub async fn get_access_permissions(
         pool: &PgPool,
         board_id: Uuid,
@@ -117,6 +414,110 @@ impl BoardService {
             .permissions)
     }
 
+    /// Returns the caller's role, permissions and derived UI capabilities.
+    pub async fn get_permission_summary(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<BoardPermissionsResponse, AppError> {
+        let access = resolve_board_access(pool, board_id, user_id).await?;
+        Ok(BoardPermissionsResponse {
+            board_id,
+            role: access.role,
+            capabilities: BoardCapabilities::from_permissions(&access.permissions),
+            permissions: access.permissions,
+        })
+    }
+
+    /// Resolves access for many boards with batched membership lookups.
+    ///
+    /// Boards that are missing, archived, deleted, or inaccessible are omitted.
+    pub async fn resolve_access_for_boards(
+        pool: &PgPool,
+        user_id: Uuid,
+        board_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, BoardAccessSummary>, AppError> {
+        if board_ids.len() > MAX_BOARD_ACCESS_BATCH {
+            return Err(AppError::BadRequest(format!(
+                "At most {} boards can be checked at once",
+                MAX_BOARD_ACCESS_BATCH
+            )));
+        }
+        if board_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let boards = board_repo::find_active_boards_by_ids(pool, board_ids).await?;
+        let mut organization_ids: Vec<Uuid> = boards
+            .iter()
+            .filter_map(|board| board.organization_id)
+            .collect();
+        organization_ids.sort_unstable();
+        organization_ids.dedup();
+
+        let (board_members, org_members) = tokio::try_join!(
+            board_repo::list_board_member_access_for_user(pool, user_id, board_ids),
+            org_repo::list_member_records_for_user(pool, user_id, &organization_ids),
+        )?;
+        let board_members: HashMap<Uuid, board_repo::BoardMemberRecord> = board_members
+            .into_iter()
+            .map(|row| (row.board_id, row.member))
+            .collect();
+        let org_members: HashMap<Uuid, org_repo::OrganizationMemberRecord> = org_members
+            .into_iter()
+            .map(|row| (row.organization_id, row.member))
+            .collect();
+
+        let mut admin_policies: HashMap<Uuid, bool> = HashMap::new();
+        let mut resolved = HashMap::with_capacity(boards.len());
+        for board in &boards {
+            let org_member = board
+                .organization_id
+                .and_then(|organization_id| org_members.get(&organization_id));
+            let access = match decide_board_access(
+                board.organization_id.is_some(),
+                board.is_public,
+                board_members.get(&board.id),
+                org_member,
+            ) {
+                AccessDecision::Granted(access, branch) => {
+                    log_access_decision(board, user_id, branch, Some(access.role));
+                    access
+                }
+                AccessDecision::OrgAdmin => {
+                    let Some(organization_id) = board.organization_id else {
+                        continue;
+                    };
+                    let admins_manage_all_boards = match admin_policies.get(&organization_id) {
+                        Some(value) => *value,
+                        None => {
+                            let value =
+                                load_admins_manage_all_boards(pool, organization_id).await?;
+                            admin_policies.insert(organization_id, value);
+                            value
+                        }
+                    };
+                    let access = org_admin_board_access(admins_manage_all_boards);
+                    log_access_decision(board, user_id, "org_admin", Some(access.role));
+                    access
+                }
+                AccessDecision::Denied(denial) => {
+                    log_access_decision(board, user_id, denial.branch(), None);
+                    continue;
+                }
+            };
+            resolved.insert(
+                board.id,
+                BoardAccessSummary {
+                    role: access.role,
+                    permissions: access.permissions,
+                },
+            );
+        }
+
+        Ok(resolved)
+    }
+
     pub async fn ensure_can_view(
         pool: &PgPool,
         board_id: Uuid,
@@ -132,119 +533,133 @@ impl BoardService {
         user_id: Uuid,
     ) -> Result<(), AppError> {
         require_board_permission(pool, board_id, user_id, BoardPermission::Comment).await?;
-        Ok(())
+        Self::ensure_feature_enabled(pool, board_id, BoardFeature::Comments).await
+    }
+
+    /// Fails with `Forbidden` when `feature` is switched off for the board.
+    pub async fn ensure_feature_enabled(
+        pool: &PgPool,
+        board_id: Uuid,
+        feature: BoardFeature,
+    ) -> Result<(), AppError> {
+        let board = load_board_for_access(pool, board_id).await?;
+        ensure_feature(&board.features, feature)
     }
 
+    /// Creates a board. Unless `force` is set, an organization board whose
+    /// name matches boards the user can open is refused with a conflict that
+    /// lists them, so the client can offer to open one instead.
     pub async fn create_board(
         pool: &PgPool,
         req: CreateBoardRequest,
         user_id: Uuid,
     ) -> Result<Board, AppError> {
-        let CreateBoardRequest {
-            organization_id,
-            name,
-            description,
-            thumbnail_url,
-            is_public,
-            is_template,
-            template_board_id,
-            canvas_settings,
-        } = req;
-
-        let name = name.trim();
-        if name.is_empty() {
-            return Err(AppError::BadRequest("Board name is required".to_string()));
-        }
-
-        if let Some(organization_id) = organization_id {
-            let organization = org_repo::find_organization_by_id(pool, organization_id)
-                .await?
-                .ok_or(AppError::NotFound("Organization not found".to_string()))?;
-            let member_role = org_repo::get_member_role(pool, organization_id, user_id)
-                .await?
-                .ok_or(AppError::Forbidden(
-                    "You are not a member of this organization".to_string(),
-                ))?;
-            ensure_org_manager(member_role)?;
-
-            let board_count =
-                board_repo::count_boards_by_organization(pool, organization_id).await?;
-            ensure_board_capacity(board_count, organization.max_boards)?;
-        } else {
-            let user = user_repo::get_user_by_id(pool, user_id).await?;
-            let board_count = board_repo::count_personal_boards_by_owner(pool, user_id).await?;
-            let max_boards = max_boards_for_tier(resolve_active_tier(&user));
-            ensure_board_capacity(board_count, max_boards)?;
-        }
-
-        let mut template_elements: Vec<BoardElement> = Vec::new();
-        let mut base_canvas_settings = CanvasSettings::default();
-        if let Some(template_board_id) = template_board_id {
-            let template = board_repo::find_board_by_id(pool, template_board_id)
-                .await?
-                .ok_or(AppError::NotFound("Template board not found".to_string()))?;
-            if !template.is_template {
-                return Err(AppError::BadRequest("Template board not found".to_string()));
+        if let Some(organization_id) = req.organization_id
+            && !req.force
+        {
+            let duplicate_boards =
+                find_visible_duplicate_boards(pool, organization_id, user_id, req.name.trim())
+                    .await?;
+            if !duplicate_boards.is_empty() {
+                return Err(AppError::ConflictWithPayload(
+                    "A board with this name already exists".to_string(),
+                    json!({ "duplicate_boards": duplicate_boards }),
+                ));
             }
-            require_board_permission(pool, template_board_id, user_id, BoardPermission::View)
-                .await?;
-            template_elements =
-                element_repo::list_elements_by_board(pool, template_board_id).await?;
-            base_canvas_settings = template.canvas_settings;
         }
+        create_board_with_elements(pool, req, user_id, Vec::new()).await
+    }
 
-        let canvas_settings = match canvas_settings {
-            Some(input) => input.apply_to(base_canvas_settings),
-            None => base_canvas_settings,
+    /// Creates a board seeded from a raw yrs `update_v1` blob.
+    ///
+    /// Imported elements get fresh ids so the blob can be re-imported or
+    /// restored alongside the board it was exported from.
+    pub async fn import_board_binary(
+        pool: &PgPool,
+        query: ImportBoardBinaryQuery,
+        user_id: Uuid,
+        blob: &[u8],
+    ) -> Result<Board, AppError> {
+        let imported = decode_imported_elements(blob)?;
+        let req = CreateBoardRequest {
+            organization_id: query.organization_id,
+            name: query.name,
+            description: query.description,
+            thumbnail_url: None,
+            is_public: None,
+            is_template: None,
+            template_board_id: None,
+            canvas_settings: None,
+            force: false,
         };
-        validate_canvas_settings(&canvas_settings)?;
+        create_board_with_elements(pool, req, user_id, imported).await
+    }
 
-        let params = board_repo::CreateBoardParams {
-            organization_id,
-            name: name.to_string(),
-            description,
-            thumbnail_url,
-            is_public: is_public.unwrap_or(true),
-            is_template: is_template.unwrap_or(false),
-            canvas_settings,
+    /// Creates a board from another tool's export, skipping source elements
+    /// that have no equivalent instead of failing the import.
+    pub async fn import_board(
+        pool: &PgPool,
+        query: ImportBoardQuery,
+        user_id: Uuid,
+        data: &[u8],
+    ) -> Result<ImportBoardResponse, AppError> {
+        let converted = match query.format {
+            BoardImportFormat::Excalidraw => excalidraw::convert(data)?,
         };
-
-        let mut tx = pool.begin().await?;
-        let board = board_repo::create_board(&mut tx, params, user_id).await?;
-        board_repo::add_owner_member(&mut tx, board.id, user_id).await?;
-        if !template_elements.is_empty() {
-            let cloned =
-                clone_template_elements(&mut tx, board.id, user_id, template_elements).await?;
-            let state_bin = snapshot::build_state_update_from_elements(&cloned)?;
-            if !state_bin.is_empty() {
-                realtime_repo::insert_snapshot(&mut tx, board.id, 0, state_bin, Some(user_id))
-                    .await?;
-            }
-        }
-        tx.commit().await?;
-
-        BusinessEvent::BoardCreated {
-            board_id: board.id,
-            user_id,
-            organization_id,
-            is_template: board.is_template,
-        }
-        .log();
-
-        Ok(board)
+        let imported = converted.elements.len();
+        let req = CreateBoardRequest {
+            organization_id: query.organization_id,
+            name: query.name,
+            description: query.description,
+            thumbnail_url: None,
+            is_public: None,
+            is_template: None,
+            template_board_id: None,
+            canvas_settings: None,
+            force: false,
+        };
+        let board = create_board_with_elements(pool, req, user_id, converted.elements).await?;
+        Ok(ImportBoardResponse {
+            board,
+            imported,
+            skipped: converted.skipped,
+        })
     }
 
-    /// Updates board metadata (name, description, visibility).
+    /// Updates board metadata (name, description, visibility) and pushes the
+    /// new values to sessions connected to the board.
     pub async fn update_board(
         pool: &PgPool,
+        rooms: &Rooms,
         board_id: Uuid,
         user_id: Uuid,
         req: UpdateBoardRequest,
     ) -> Result<Board, AppError> {
-        require_board_permission(pool, board_id, user_id, BoardPermission::ManageBoard).await?;
+        let board = load_board_for_access(pool, board_id).await?;
+        require_board_permission_with_board(pool, &board, user_id, BoardPermission::ManageBoard)
+            .await?;
 
-        let name = normalize_optional_name(req.name)?;
-        let description = normalize_optional_description(req.description);
+        let limits = BoardTextLimits::from_env();
+        let name = normalize_optional_name(req.name, &limits)?;
+        let description = normalize_optional_description(req.description, &limits)?;
+        let tags = req.tags.map(normalize_tags).transpose()?;
+        let features = req
+            .features
+            .map(|input| input.apply_to(board.features))
+            .filter(|features| *features != board.features);
+        let mut is_public = req.is_public;
+        let element_delete_policy = req
+            .element_delete_policy
+            .filter(|policy| *policy != board.element_delete_policy);
+        if is_public == Some(true) {
+            ensure_feature(
+                &features.unwrap_or(board.features),
+                BoardFeature::PublicSharing,
+            )?;
+        }
+        if features.is_some_and(|features| !features.public_sharing) && board.is_public {
+            is_public = Some(false);
+        }
         let mut fields = Vec::new();
         if name.is_some() {
             fields.push("name".to_string());
@@ -252,16 +667,55 @@ impl BoardService {
         if description.is_some() {
             fields.push("description".to_string());
         }
-        if req.is_public.is_some() {
+        if is_public.is_some() {
             fields.push("is_public".to_string());
         }
+        if tags.is_some() {
+            fields.push("tags".to_string());
+        }
+        if features.is_some() {
+            fields.push("features".to_string());
+        }
+        if element_delete_policy.is_some() {
+            fields.push("element_delete_policy".to_string());
+        }
 
         let mut tx = pool.begin().await?;
-        let updated =
-            board_repo::update_board_metadata(&mut tx, board_id, name, description, req.is_public)
-                .await?;
+        let updated = board_repo::update_board_metadata(
+            &mut tx,
+            board_id,
+            board_repo::UpdateBoardMetadataParams {
+                name,
+                description,
+                is_public,
+                tags,
+                features,
+                element_delete_policy,
+            },
+        )
+        .await?;
         tx.commit().await?;
+        if is_public.is_some() {
+            access_cache::publish(AccessInvalidation::Board { board_id }).await;
+        }
         if !fields.is_empty() {
+            if let Some(room) = rooms.get(&board_id).map(|entry| entry.clone()) {
+                room.broadcast_event(
+                    "board:metadata",
+                    json!({
+                        "board_id": board_id,
+                        "name": updated.name,
+                        "description": updated.description,
+                        "is_public": updated.is_public,
+                        "tags": updated.tags,
+                        "features": updated.features,
+                        "element_delete_policy": updated.element_delete_policy,
+                        "updated_by": user_id,
+                        "fields": fields,
+                        "timestamp": Utc::now().timestamp_millis(),
+                    }),
+                );
+            }
             BusinessEvent::BoardUpdated {
                 board_id,
                 user_id,
@@ -292,6 +746,7 @@ impl BoardService {
         let mut tx = pool.begin().await?;
         board_repo::set_board_archived(&mut tx, board_id, Some(Utc::now())).await?;
         tx.commit().await?;
+        access_cache::publish(AccessInvalidation::Board { board_id }).await;
 
         Ok(BoardActionMessage {
             message: "Board archived".to_string(),
@@ -323,43 +778,47 @@ impl BoardService {
         })
     }
 
-    /// Transfers board ownership to another member.
+    /// Makes a board member an owner. Other owners are demoted to admin
+    /// unless `keep_current_owner` is set, in which case they stay co-owners.
     pub async fn transfer_board_ownership(
         pool: &PgPool,
         board_id: Uuid,
         requester_id: Uuid,
         req: TransferBoardOwnershipRequest,
     ) -> Result<BoardActionMessage, AppError> {
-        let board = load_board_for_access(pool, board_id).await?;
-        ensure_board_active(&board)?;
-        require_board_owner_with_board(pool, &board, requester_id).await?;
-
-        let member = board_repo::get_board_member_by_user_id(pool, board_id, req.new_owner_id)
-            .await?
-            .ok_or(AppError::NotFound(
-                "Target user is not a board member".to_string(),
-            ))?;
-        if member.role == BoardRole::Owner {
-            return Ok(BoardActionMessage {
-                message: "User is already an owner".to_string(),
-            });
-        }
-
-        let mut tx = pool.begin().await?;
-        board_repo::set_actor_id(&mut tx, requester_id).await?;
-        board_repo::demote_other_board_owners(&mut tx, board_id, req.new_owner_id).await?;
-        board_repo::update_board_member_role(
-            &mut tx,
-            board_id,
-            member.user_id,
-            BoardRole::Owner,
-            None,
-        )
+        let new_owner_id = req.new_owner_id;
+        let demote_others = !req.keep_current_owner;
+        let promoted = with_retry("boards.transfer_ownership", move || async move {
+            promote_board_owner(pool, board_id, requester_id, new_owner_id, demote_others).await
+        })
         .await?;
-        tx.commit().await?;
+        let message = match (promoted, req.keep_current_owner) {
+            (false, _) => "User is already an owner",
+            (true, true) => "Board co-owner added",
+            (true, false) => "Board ownership transferred",
+        };
+        Ok(BoardActionMessage {
+            message: message.to_string(),
+        })
+    }
 
+    /// Adds an existing board member as an additional owner, keeping every
+    /// current owner.
+    pub async fn add_co_owner(
+        pool: &PgPool,
+        board_id: Uuid,
+        requester_id: Uuid,
+        req: AddBoardCoOwnerRequest,
+    ) -> Result<BoardActionMessage, AppError> {
+        let promoted =
+            promote_board_owner(pool, board_id, requester_id, req.user_id, false).await?;
+        let message = if promoted {
+            "Board co-owner added"
+        } else {
+            "User is already an owner"
+        };
         Ok(BoardActionMessage {
-            message: "Board ownership transferred".to_string(),
+            message: message.to_string(),
         })
     }
 
@@ -381,6 +840,7 @@ impl BoardService {
         let mut tx = pool.begin().await?;
         board_repo::mark_board_deleted(&mut tx, board_id).await?;
         tx.commit().await?;
+        access_cache::publish(AccessInvalidation::Board { board_id }).await;
         BusinessEvent::BoardDeleted {
             board_id,
             user_id: requester_id,
@@ -411,8 +871,9 @@ impl BoardService {
         })
     }
 
-    /// Purges boards that have been deleted beyond the retention window.
-    pub async fn purge_deleted_boards(pool: &PgPool) -> Result<u64, AppError> {
+    /// Purges boards that have been deleted beyond the retention window,
+    /// together with their elements and CRDT history, in one transaction.
+    pub async fn purge_deleted_boards(pool: &PgPool) -> Result<PurgedBoardCounts, AppError> {
         let mut tx = pool.begin().await?;
         let purged = board_repo::purge_deleted_boards(&mut tx, TRASH_RETENTION_DAYS).await?;
         tx.commit().await?;
@@ -432,33 +893,74 @@ impl BoardService {
         let rows = board_repo::list_board_members(pool, board_id).await?;
         let data = rows
             .into_iter()
-            .map(|row| {
-                let effective_permissions = resolve_member_permissions(
-                    row.role,
-                    row.custom_permissions.as_ref(),
-                    is_org_board,
-                    row.org_role,
-                );
-                BoardMemberResponse {
-                    id: row.member_id,
-                    user: BoardMemberUser {
-                        id: row.user_id,
-                        username: row.username.unwrap_or_default(),
-                        display_name: row.display_name,
-                        avatar_url: row.avatar_url,
-                    },
-                    role: row.role,
-                    custom_permissions: row.custom_permissions,
-                    effective_permissions,
-                    created_at: row.created_at,
-                    updated_at: row.updated_at,
-                }
-            })
+            .map(|row| member_row_to_response(row, is_org_board))
             .collect();
 
         Ok(BoardMembersResponse { data })
     }
 
+    /// Lists every access path to a board: explicit members, org owners/admins
+    /// with implicit access, and public visibility.
+    pub async fn access_summary(
+        pool: &PgPool,
+        board_id: Uuid,
+        requester_id: Uuid,
+    ) -> Result<BoardAccessOverviewResponse, AppError> {
+        let board = load_board_for_access(pool, board_id).await?;
+        ensure_board_active(&board)?;
+        require_board_permission_with_board(
+            pool,
+            &board,
+            requester_id,
+            BoardPermission::ManageMembers,
+        )
+        .await?;
+
+        let is_org_board = board.organization_id.is_some();
+        let members: Vec<BoardMemberResponse> = board_repo::list_board_members(pool, board_id)
+            .await?
+            .into_iter()
+            .map(|row| member_row_to_response(row, is_org_board))
+            .collect();
+
+        let (org_admin_role, org_admins) = match board.organization_id {
+            Some(organization_id) => {
+                let admin_role = org_admin_board_access(
+                    load_admins_manage_all_boards(pool, organization_id).await?,
+                )
+                .role;
+                let explicit: HashSet<Uuid> = members.iter().map(|member| member.user.id).collect();
+                let org_admins = org_repo::list_accepted_managers(pool, organization_id)
+                    .await?
+                    .into_iter()
+                    .filter(|row| !explicit.contains(&row.user_id))
+                    .map(|row| ImplicitBoardAccess {
+                        user: BoardMemberUser {
+                            id: row.user_id,
+                            username: row.username.unwrap_or_default(),
+                            display_name: row.display_name,
+                            avatar_url: row.avatar_url,
+                        },
+                        org_role: row.role,
+                        role: admin_role,
+                    })
+                    .collect();
+                (Some(admin_role), org_admins)
+            }
+            None => (None, Vec::new()),
+        };
+
+        Ok(BoardAccessOverviewResponse {
+            board_id,
+            members,
+            org_admin_access: org_admin_role.is_some(),
+            org_admin_role,
+            org_admins,
+            is_public: board.is_public,
+            public_role: board.is_public.then_some(BoardRole::Viewer),
+        })
+    }
+
     /// Invites board members by email (existing users only).
     pub async fn invite_board_members(
         pool: &PgPool,
@@ -478,6 +980,18 @@ impl BoardService {
         let role = normalize_board_role(role)?;
         let emails = collect_invite_emails(email, emails)?;
         let users = load_invite_users(pool, &emails).await?;
+        let mut existing_member_ids = HashSet::new();
+        for user in &users {
+            if board_repo::get_board_member_by_user_id(pool, board_id, user.id)
+                .await?
+                .is_some()
+            {
+                existing_member_ids.insert(user.id);
+            }
+        }
+        let (users, already_members) = split_existing_members(users, &existing_member_ids);
+        let already_member: Vec<String> =
+            already_members.into_iter().map(|user| user.email).collect();
         let organization_id = board_repo::load_board_organization_id(pool, board_id).await?;
         if let Some(org_id) = organization_id {
             for user in &users {
@@ -509,7 +1023,7 @@ impl BoardService {
                 org_invite_users.push(user.clone());
             }
         }
-        for user in users {
+        for user in &users {
             board_repo::add_board_member(&mut tx, board_id, user.id, role, inviter_id).await?;
             pending_events.push(BusinessEvent::BoardShared {
                 board_id,
@@ -519,16 +1033,26 @@ impl BoardService {
             });
         }
         tx.commit().await?;
+        for user in &users {
+            access_cache::publish(AccessInvalidation::User { user_id: user.id }).await;
+        }
         for event in pending_events {
             event.log();
         }
 
         if let Some(org) = organization {
-            send_invite_emails(email_service, &org, &org_invite_users).await?;
+            send_invite_emails(
+                email_service,
+                &org,
+                &org_invite_users,
+                EmailCategory::Invite,
+            )
+            .await?;
         }
 
         Ok(InviteBoardMembersResponse {
             invited: invited_emails,
+            already_member,
         })
     }
 
@@ -539,11 +1063,16 @@ impl BoardService {
         requester_id: Uuid,
         member_id: Uuid,
         req: UpdateBoardMemberRoleRequest,
-    ) -> Result<BoardMemberChange, AppError> {
+    ) -> Result<BoardActionMessage, AppError> {
         let requester_access =
             require_board_permission(pool, board_id, requester_id, BoardPermission::ManageMembers)
                 .await?;
 
+        let custom_permissions = req
+            .custom_permissions
+            .map(|overrides| normalize_permission_overrides(req.role, overrides))
+            .transpose()?;
+
         let member = board_repo::get_board_member_by_id(pool, board_id, member_id)
             .await?
             .ok_or(AppError::NotFound("Board member not found".to_string()))?;
@@ -553,7 +1082,7 @@ impl BoardService {
             let member_record =
                 org_repo::get_member_by_user_id(pool, org_id, member.user_id).await?;
             org_role = member_record.map(|record| record.role);
-            ensure_guest_role_permissions(org_role, req.role, req.custom_permissions.as_ref())?;
+            ensure_guest_role_permissions(org_role, req.role, custom_permissions.as_ref())?;
         }
 
         if member.role == BoardRole::Owner && requester_access.role != BoardRole::Owner {
@@ -567,35 +1096,58 @@ impl BoardService {
                 "Only owners can assign owner role".to_string(),
             ));
         }
+        if member.role == BoardRole::Owner && req.role != BoardRole::Owner {
+            let owners = board_repo::count_board_owners(pool, board_id).await?;
+            ensure_owner_remains(member.role, Some(req.role), owners)?;
+        }
 
-        let mut tx = pool.begin().await?;
-        board_repo::set_actor_id(&mut tx, requester_id).await?;
-        board_repo::update_board_member_role(
-            &mut tx,
-            board_id,
-            member_id,
+        let final_permissions = resolve_member_permissions(
             req.role,
-            req.custom_permissions.clone(),
-        )
-        .await?;
-        tx.commit().await?;
-
-        let final_permissions = resolve_member_permissions(
-            req.role,
-            req.custom_permissions
+            custom_permissions
                 .as_ref()
                 .or(member.custom_permissions.as_ref()),
             organization_id.is_some(),
             org_role,
         );
+        ensure_member_manager_remains(
+            pool,
+            board_id,
+            member_id,
+            Some(final_permissions),
+            organization_id.is_some(),
+        )
+        .await?;
+
+        let overrides = custom_permissions.as_ref();
+        let role = req.role;
+        with_retry("boards.update_member_role", move || async move {
+            let mut tx = pool.begin().await?;
+            board_repo::set_actor_id(&mut tx, requester_id).await?;
+            board_repo::update_board_member_role(
+                &mut tx,
+                board_id,
+                member_id,
+                role,
+                overrides.cloned(),
+            )
+            .await?;
+            tx.commit().await?;
+            Ok(())
+        })
+        .await?;
 
-        Ok(BoardMemberChange {
-            message: BoardActionMessage {
-                message: "Board member role updated".to_string(),
+        access_cache::publish(AccessInvalidation::Member {
+            board_id,
+            update: BoardRoleUpdate {
+                user_id: member.user_id,
+                role: Some(req.role),
+                permissions: Some(final_permissions),
             },
-            member_user_id: member.user_id,
-            role: Some(req.role),
-            permissions: Some(final_permissions),
+        })
+        .await;
+
+        Ok(BoardActionMessage {
+            message: "Board member role updated".to_string(),
         })
     }
 
@@ -605,7 +1157,7 @@ impl BoardService {
         board_id: Uuid,
         requester_id: Uuid,
         member_id: Uuid,
-    ) -> Result<BoardMemberChange, AppError> {
+    ) -> Result<BoardActionMessage, AppError> {
         let requester_access =
             require_board_permission(pool, board_id, requester_id, BoardPermission::ManageMembers)
                 .await?;
@@ -621,27 +1173,385 @@ impl BoardService {
                 ));
             }
             let owners = board_repo::count_board_owners(pool, board_id).await?;
-            if owners <= 1 {
-                return Err(AppError::BadRequest(
-                    "Cannot remove the last owner".to_string(),
-                ));
-            }
+            ensure_owner_remains(member.role, None, owners)?;
         }
+        let is_org_board = board_repo::load_board_organization_id(pool, board_id)
+            .await?
+            .is_some();
+        ensure_member_manager_remains(pool, board_id, member_id, None, is_org_board).await?;
 
         let mut tx = pool.begin().await?;
         board_repo::set_actor_id(&mut tx, requester_id).await?;
         board_repo::remove_board_member(&mut tx, board_id, member_id).await?;
         tx.commit().await?;
-
-        Ok(BoardMemberChange {
-            message: BoardActionMessage {
-                message: "Board member removed".to_string(),
+        access_cache::publish(AccessInvalidation::Member {
+            board_id,
+            update: BoardRoleUpdate {
+                user_id: member.user_id,
+                role: None,
+                permissions: None,
             },
-            member_user_id: member.user_id,
-            role: None,
-            permissions: None,
         })
+        .await;
+
+        Ok(BoardActionMessage {
+            message: "Board member removed".to_string(),
+        })
+    }
+}
+
+/// Splits invitees into users to add and users already on the board, so a
+/// batch is not rejected because one invitee is already a member.
+fn split_existing_members(
+    users: Vec<User>,
+    existing_member_ids: &HashSet<Uuid>,
+) -> (Vec<User>, Vec<User>) {
+    users
+        .into_iter()
+        .partition(|user| !existing_member_ids.contains(&user.id))
+}
+
+/// Promotes `target_user_id` to owner, demoting the other owners to admin
+/// when `demote_others` is set. Returns `false` if they already were an owner.
+async fn promote_board_owner(
+    pool: &PgPool,
+    board_id: Uuid,
+    requester_id: Uuid,
+    target_user_id: Uuid,
+    demote_others: bool,
+) -> Result<bool, AppError> {
+    let board = load_board_for_access(pool, board_id).await?;
+    ensure_board_active(&board)?;
+    require_board_owner_with_board(pool, &board, requester_id).await?;
+
+    let member = board_repo::get_board_member_by_user_id(pool, board_id, target_user_id)
+        .await?
+        .ok_or(AppError::NotFound(
+            "Target user is not a board member".to_string(),
+        ))?;
+    if member.role == BoardRole::Owner {
+        return Ok(false);
+    }
+    if let Some(org_id) = board.organization_id {
+        let org_role = org_repo::get_member_by_user_id(pool, org_id, target_user_id)
+            .await?
+            .map(|record| record.role);
+        ensure_guest_role_permissions(org_role, BoardRole::Owner, None)?;
+    }
+
+    let mut tx = pool.begin().await?;
+    board_repo::set_actor_id(&mut tx, requester_id).await?;
+    if demote_others {
+        board_repo::demote_other_board_owners(&mut tx, board_id, target_user_id).await?;
+    }
+    board_repo::update_board_member_role(&mut tx, board_id, member.user_id, BoardRole::Owner, None)
+        .await?;
+    tx.commit().await?;
+    access_cache::publish(AccessInvalidation::Board { board_id }).await;
+
+    Ok(true)
+}
+
+/// Rejects changes that would leave the board without an owner: removing
+/// (`new_role` of `None`) or demoting the only remaining owner.
+fn ensure_owner_remains(
+    current_role: BoardRole,
+    new_role: Option<BoardRole>,
+    owner_count: i64,
+) -> Result<(), AppError> {
+    if current_role != BoardRole::Owner || new_role == Some(BoardRole::Owner) || owner_count > 1 {
+        return Ok(());
+    }
+    let message = if new_role.is_some() {
+        "Cannot demote the last owner"
+    } else {
+        "Cannot remove the last owner"
+    };
+    Err(AppError::BadRequest(message.to_string()))
+}
+
+/// Rejects member updates or removals (`new_permissions` of `None`) that
+/// would leave no explicit member able to manage members.
+async fn ensure_member_manager_remains(
+    pool: &PgPool,
+    board_id: Uuid,
+    member_id: Uuid,
+    new_permissions: Option<BoardPermissions>,
+    is_org_board: bool,
+) -> Result<(), AppError> {
+    let members = board_repo::list_board_members(pool, board_id).await?;
+    let mut current_permissions = None;
+    let mut other_managers = 0;
+    for row in members {
+        let permissions = resolve_member_permissions(
+            row.role,
+            row.custom_permissions.as_ref(),
+            is_org_board,
+            row.org_role,
+        );
+        if row.member_id == member_id {
+            current_permissions = Some(permissions);
+        } else if permissions.can_manage_members {
+            other_managers += 1;
+        }
+    }
+    let Some(current_permissions) = current_permissions else {
+        return Ok(());
+    };
+    ensure_manager_remains(current_permissions, new_permissions, other_managers)
+}
+
+/// Rejects changes that take `can_manage_members` away from the only member
+/// who still has it.
+fn ensure_manager_remains(
+    current_permissions: BoardPermissions,
+    new_permissions: Option<BoardPermissions>,
+    other_managers: usize,
+) -> Result<(), AppError> {
+    let keeps_managing = new_permissions.is_some_and(|permissions| permissions.can_manage_members);
+    if !current_permissions.can_manage_members || keeps_managing || other_managers > 0 {
+        return Ok(());
+    }
+    let message = if new_permissions.is_some() {
+        "Cannot demote the last member who can manage members"
+    } else {
+        "Cannot remove the last member who can manage members"
+    };
+    Err(AppError::BadRequest(message.to_string()))
+}
+
+async fn create_board_with_elements(
+    pool: &PgPool,
+    req: CreateBoardRequest,
+    user_id: Uuid,
+    imported_elements: Vec<element_crdt::ElementMaterialized>,
+) -> Result<Board, AppError> {
+    let CreateBoardRequest {
+        organization_id,
+        name,
+        description,
+        thumbnail_url,
+        is_public,
+        is_template,
+        template_board_id,
+        canvas_settings,
+        force: _,
+    } = req;
+
+    let limits = BoardTextLimits::from_env();
+    let name = name.trim();
+    let mut errors = FieldErrors::default();
+    if name.is_empty() {
+        errors.add("name", "Board name is required");
+    } else {
+        errors.check("name", ensure_board_name_length(name, &limits))?;
+    }
+    let description = errors
+        .check(
+            "description",
+            normalize_optional_description(description, &limits),
+        )?
+        .flatten();
+    errors.into_result()?;
+
+    let user = user_repo::get_user_by_id(pool, user_id).await?;
+    ensure_email_verified_for_boards(
+        user.email_verified_at.is_some(),
+        env_flag(REQUIRE_VERIFIED_EMAIL_FOR_BOARDS_ENV, false),
+    )?;
+
+    let mut default_public = env_flag(DEFAULT_BOARD_PUBLIC_ENV, true);
+    let mut default_member_role = None;
+    if let Some(organization_id) = organization_id {
+        let organization = org_repo::find_organization_by_id(pool, organization_id)
+            .await?
+            .ok_or(AppError::NotFound("Organization not found".to_string()))?;
+        let member_role = org_repo::get_member_role(pool, organization_id, user_id)
+            .await?
+            .ok_or(AppError::Forbidden(
+                "You are not a member of this organization".to_string(),
+            ))?;
+        ensure_org_manager(member_role)?;
+
+        let board_count = board_repo::count_boards_by_organization(pool, organization_id).await?;
+        ensure_board_capacity(board_count, organization.max_boards)?;
+        default_public = resolve_default_visibility(
+            organization.settings.default_board_public,
+            env_flag(DEFAULT_ORG_BOARD_PUBLIC_ENV, false),
+        );
+        default_member_role = organization.settings.default_member_board_role;
+    } else {
+        let board_count = board_repo::count_personal_boards_by_owner(pool, user_id).await?;
+        let max_boards = max_boards_for_tier(resolve_active_tier(&user));
+        ensure_board_capacity(board_count, max_boards)?;
+    }
+
+    let mut template_elements: Vec<BoardElement> = Vec::new();
+    let mut base_canvas_settings = default_canvas_settings();
+    if let Some(template_board_id) = template_board_id {
+        let template = board_repo::find_board_by_id(pool, template_board_id)
+            .await?
+            .ok_or(AppError::NotFound("Template board not found".to_string()))?;
+        if !template.is_template {
+            return Err(AppError::BadRequest("Template board not found".to_string()));
+        }
+        require_board_permission(pool, template_board_id, user_id, BoardPermission::View).await?;
+        template_elements = element_repo::list_elements_by_board(pool, template_board_id).await?;
+        base_canvas_settings = template.canvas_settings;
+    }
+
+    let canvas_settings = match canvas_settings {
+        Some(input) => input.apply_to(base_canvas_settings),
+        None => base_canvas_settings,
+    };
+    validate_canvas_settings(&canvas_settings)?;
+
+    let params = board_repo::CreateBoardParams {
+        organization_id,
+        name: name.to_string(),
+        description,
+        thumbnail_url,
+        is_public: is_public.unwrap_or(default_public),
+        is_template: is_template.unwrap_or(false),
+        canvas_settings,
+    };
+
+    let mut tx = pool.begin().await?;
+    let board = board_repo::create_board(&mut tx, params, user_id).await?;
+    board_repo::add_owner_member(&mut tx, board.id, user_id).await?;
+    if let (Some(organization_id), Some(role)) = (organization_id, default_member_role) {
+        board_repo::add_org_members_to_board(&mut tx, board.id, organization_id, user_id, role)
+            .await?;
+    }
+    let mut seeded = clone_template_elements(&mut tx, board.id, user_id, template_elements).await?;
+    seeded.extend(insert_imported_elements(&mut tx, board.id, user_id, imported_elements).await?);
+    seed_board_snapshot(&mut tx, board.id, user_id, &seeded).await?;
+    tx.commit().await?;
+
+    BusinessEvent::BoardCreated {
+        board_id: board.id,
+        user_id,
+        organization_id,
+        is_template: board.is_template,
+    }
+    .log();
+
+    Ok(board)
+}
+
+async fn find_visible_duplicate_boards(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+    name: &str,
+) -> Result<Vec<DuplicateBoardSummary>, AppError> {
+    if name.is_empty() {
+        return Ok(Vec::new());
+    }
+    let boards = board_repo::find_active_org_boards_by_name(
+        pool,
+        organization_id,
+        name,
+        MAX_DUPLICATE_BOARD_WARNINGS,
+    )
+    .await?;
+    if boards.is_empty() {
+        return Ok(Vec::new());
+    }
+    let board_ids: Vec<Uuid> = boards.iter().map(|board| board.id).collect();
+    let visible = BoardService::resolve_access_for_boards(pool, user_id, &board_ids).await?;
+    Ok(boards
+        .into_iter()
+        .filter(|board| visible.contains_key(&board.id))
+        .map(|board| DuplicateBoardSummary {
+            id: board.id,
+            name: board.name,
+            updated_at: board.updated_at,
+        })
+        .collect())
+}
+
+/// Canvas settings for boards created without a template, with width,
+/// height and grid size overridable per deployment.
+fn default_canvas_settings() -> CanvasSettings {
+    canvas_settings_with_overrides(|key| std::env::var(key).ok())
+}
+
+fn canvas_settings_with_overrides(read: impl Fn(&str) -> Option<String>) -> CanvasSettings {
+    let mut settings = CanvasSettings::default();
+    let positive = |key: &str| {
+        let value = read(key)?;
+        let parsed = value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|parsed| parsed.is_finite() && *parsed > 0.0);
+        if parsed.is_none() {
+            tracing::warn!("Ignoring invalid {}: {}", key, value);
+        }
+        parsed
+    };
+    if let Some(width) = positive(DEFAULT_CANVAS_WIDTH_ENV) {
+        settings.width = width;
+    }
+    if let Some(height) = positive(DEFAULT_CANVAS_HEIGHT_ENV) {
+        settings.height = height;
+    }
+    if let Some(grid_size) = positive(DEFAULT_CANVAS_GRID_SIZE_ENV)
+        .filter(|grid_size| grid_size.fract() == 0.0 && *grid_size <= f64::from(i32::MAX))
+    {
+        settings.grid_size = grid_size as i32;
     }
+    settings
+}
+
+/// Reads a boolean env flag, falling back when it is unset or unrecognised.
+pub(crate) fn env_flag(key: &str, fallback: bool) -> bool {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| parse_bool_flag(&value))
+        .unwrap_or(fallback)
+}
+
+fn parse_bool_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn ensure_email_verified_for_boards(email_verified: bool, required: bool) -> Result<(), AppError> {
+    if required && !email_verified {
+        return Err(AppError::Forbidden(
+            "Verify your email to create boards".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Org setting wins over the environment default when present.
+fn resolve_default_visibility(org_default: Option<bool>, env_default: bool) -> bool {
+    org_default.unwrap_or(env_default)
+}
+
+/// Stores the seeded elements as the board's seq-0 snapshot.
+///
+/// Takes the board's element advisory lock first so the seed cannot interleave
+/// with a projection or update-log write from a client that joins the moment
+/// the board becomes visible.
+async fn seed_board_snapshot(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+    user_id: Uuid,
+    seeded: &[BoardElement],
+) -> Result<(), AppError> {
+    let state_bin = snapshot::build_state_update_from_elements(seeded)?;
+    if state_bin.is_empty() {
+        return Ok(());
+    }
+    element_repo::lock_board_elements(tx, board_id).await?;
+    realtime_repo::insert_snapshot(tx, board_id, 0, state_bin, Some(user_id)).await
 }
 
 async fn clone_template_elements(
@@ -650,6 +1560,9 @@ async fn clone_template_elements(
     user_id: Uuid,
     template_elements: Vec<BoardElement>,
 ) -> Result<Vec<BoardElement>, AppError> {
+    if template_elements.is_empty() {
+        return Ok(Vec::new());
+    }
     element_repo::lock_board_elements(tx, board_id).await?;
     let mut id_map = HashMap::with_capacity(template_elements.len());
     for element in &template_elements {
@@ -691,6 +1604,136 @@ async fn clone_template_elements(
     Ok(cloned_elements)
 }
 
+fn decode_imported_elements(
+    blob: &[u8],
+) -> Result<Vec<element_crdt::ElementMaterialized>, AppError> {
+    if blob.is_empty() {
+        return Err(AppError::BadRequest("Snapshot body is empty".to_string()));
+    }
+    let update = Update::decode_v1(blob)
+        .map_err(|error| AppError::BadRequest(format!("Invalid board snapshot: {}", error)))?;
+    let doc = Doc::new();
+    doc.transact_mut()
+        .apply_update(update)
+        .map_err(|error| AppError::BadRequest(format!("Invalid board snapshot: {}", error)))?;
+    Ok(element_crdt::materialize_elements(&doc))
+}
+
+/// Re-keys imported elements for `board_id`, dropping deleted ones and
+/// remapping parent and connector binding references to the new ids. Layer
+/// references are cleared since the source board's layers are not imported.
+fn remap_imported_elements(
+    board_id: Uuid,
+    user_id: Uuid,
+    imported: Vec<element_crdt::ElementMaterialized>,
+    now: DateTime<Utc>,
+) -> Vec<BoardElement> {
+    let live: Vec<element_crdt::ElementMaterialized> = imported
+        .into_iter()
+        .filter(|element| element.deleted_at.is_none())
+        .collect();
+    let id_map: HashMap<Uuid, Uuid> = live
+        .iter()
+        .map(|element| (element.id, Uuid::now_v7()))
+        .collect();
+
+    live.into_iter()
+        .filter_map(|element| {
+            let id = *id_map.get(&element.id)?;
+            Some(BoardElement {
+                id,
+                board_id,
+                layer_id: None,
+                parent_id: element
+                    .parent_id
+                    .and_then(|parent| id_map.get(&parent).copied()),
+                created_by: user_id,
+                element_type: element.element_type,
+                position_x: element.position_x,
+                position_y: element.position_y,
+                width: element.width,
+                height: element.height,
+                rotation: element.rotation,
+                z_index: element.z_index,
+                style: element.style,
+                properties: remap_connector_bindings(element.properties, &id_map),
+                version: 1,
+                metadata: element.metadata,
+                created_at: now,
+                updated_at: now,
+                deleted_at: None,
+            })
+        })
+        .collect()
+}
+
+/// Points connector bindings at re-keyed element ids, dropping bindings to
+/// elements that were not carried over.
+fn remap_connector_bindings(mut properties: Value, id_map: &HashMap<Uuid, Uuid>) -> Value {
+    let Some(bindings) = properties
+        .get_mut("bindings")
+        .and_then(Value::as_object_mut)
+    else {
+        return properties;
+    };
+    bindings.retain(|_, binding| {
+        let target = binding
+            .get("elementId")
+            .and_then(Value::as_str)
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .and_then(|id| id_map.get(&id).copied());
+        match target {
+            Some(target) => {
+                binding["elementId"] = json!(target.to_string());
+                true
+            }
+            None => false,
+        }
+    });
+    properties
+}
+
+async fn insert_imported_elements(
+    tx: &mut Transaction<'_, Postgres>,
+    board_id: Uuid,
+    user_id: Uuid,
+    imported: Vec<element_crdt::ElementMaterialized>,
+) -> Result<Vec<BoardElement>, AppError> {
+    if imported.is_empty() {
+        return Ok(Vec::new());
+    }
+    element_repo::lock_board_elements(tx, board_id).await?;
+    let elements = remap_imported_elements(board_id, user_id, imported, Utc::now());
+    for element in &elements {
+        element_repo::upsert_projected_element(
+            tx,
+            element_repo::ProjectedElementParams {
+                id: element.id,
+                board_id,
+                layer_id: element.layer_id,
+                parent_id: element.parent_id,
+                created_by: element.created_by,
+                element_type: element.element_type,
+                position_x: element.position_x,
+                position_y: element.position_y,
+                width: element.width,
+                height: element.height,
+                rotation: element.rotation,
+                z_index: element.z_index,
+                style: element.style.clone(),
+                properties: element.properties.clone(),
+                metadata: element.metadata.clone(),
+                version: element.version,
+                created_at: element.created_at,
+                updated_at: element.updated_at,
+                deleted_at: None,
+            },
+        )
+        .await?;
+    }
+    Ok(elements)
+}
+
 fn validate_canvas_settings(settings: &CanvasSettings) -> Result<(), AppError> {
     if settings.width <= 0.0 || settings.height <= 0.0 {
         return Err(AppError::BadRequest(
@@ -702,9 +1745,38 @@ fn validate_canvas_settings(settings: &CanvasSettings) -> Result<(), AppError> {
             "Grid size must be positive".to_string(),
         ));
     }
+    if settings.last_write_wins_fields.len() > MAX_LAST_WRITE_WINS_FIELDS {
+        return Err(AppError::BadRequest(format!(
+            "At most {} last-write-wins fields are allowed",
+            MAX_LAST_WRITE_WINS_FIELDS
+        )));
+    }
+    if let Some(field) = settings
+        .last_write_wins_fields
+        .iter()
+        .find(|field| !is_last_write_wins_field(field))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported last-write-wins field: {}",
+            field
+        )));
+    }
     Ok(())
 }
 
+/// Accepts numeric element fields, whole objects (`style`), or one key of an
+/// object (`properties.votes`).
+fn is_last_write_wins_field(field: &str) -> bool {
+    const SCALAR_FIELDS: [&str; 5] = ["position_x", "position_y", "width", "height", "rotation"];
+    const OBJECT_FIELDS: [&str; 3] = ["style", "properties", "metadata"];
+    if SCALAR_FIELDS.contains(&field) || OBJECT_FIELDS.contains(&field) {
+        return true;
+    }
+    field
+        .split_once('.')
+        .is_some_and(|(object, key)| OBJECT_FIELDS.contains(&object) && !key.is_empty())
+}
+
 async fn require_board_permission(
     pool: &PgPool,
     board_id: Uuid,
@@ -746,9 +1818,26 @@ async fn resolve_board_access(
     board_id: Uuid,
     user_id: Uuid,
 ) -> Result<BoardAccess, AppError> {
+    let generations = match access_cache::lookup(board_id, user_id).await {
+        CacheLookup::Hit(cached) => {
+            return Ok(BoardAccess {
+                role: cached.role,
+                permissions: cached.permissions,
+            });
+        }
+        CacheLookup::Miss(generations) => generations,
+    };
     let board = load_board_for_access(pool, board_id).await?;
     ensure_board_active(&board)?;
-    resolve_board_access_with_board(pool, &board, user_id).await
+    let access = resolve_board_access_with_board(pool, &board, user_id).await?;
+    if let Some(generations) = generations {
+        let cached = CachedAccess {
+            role: access.role,
+            permissions: access.permissions,
+        };
+        access_cache::store(board_id, user_id, generations, cached).await;
+    }
+    Ok(access)
 }
 
 async fn resolve_board_access_with_board(
@@ -770,58 +1859,197 @@ async fn resolve_board_access_with_board(
         ),
     };
 
+    match decide_board_access(
+        board.organization_id.is_some(),
+        board.is_public,
+        board_member.as_ref(),
+        org_member.as_ref(),
+    ) {
+        AccessDecision::Granted(access, branch) => {
+            log_access_decision(board, user_id, branch, Some(access.role));
+            Ok(access)
+        }
+        AccessDecision::OrgAdmin => {
+            let admins_manage_all_boards = match board.organization_id {
+                Some(organization_id) => {
+                    load_admins_manage_all_boards(pool, organization_id).await?
+                }
+                None => false,
+            };
+            let access = org_admin_board_access(admins_manage_all_boards);
+            log_access_decision(board, user_id, "org_admin", Some(access.role));
+            Ok(access)
+        }
+        AccessDecision::Denied(denial) => {
+            log_access_decision(board, user_id, denial.branch(), None);
+            Err(denial.into_error())
+        }
+    }
+}
+
+/// Access outcome before any org policy lookup.
+enum AccessDecision {
+    Granted(BoardAccess, &'static str),
+    /// Org owner/admin without board membership; depends on org policy.
+    OrgAdmin,
+    Denied(AccessDenial),
+}
+
+/// Why board access was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AccessDenial {
+    /// Board member of an org board whose workspace invite is not accepted.
+    PendingOrgInvite,
+    NoAccess,
+}
+
+impl AccessDenial {
+    fn branch(self) -> &'static str {
+        match self {
+            AccessDenial::PendingOrgInvite => "pending_org_invite",
+            AccessDenial::NoAccess => "no_access",
+        }
+    }
+
+    fn into_error(self) -> AppError {
+        match self {
+            AccessDenial::PendingOrgInvite => AppError::Forbidden(
+                "You must accept the workspace invitation before accessing this board".to_string(),
+            ),
+            AccessDenial::NoAccess => {
+                AppError::Forbidden("You are not a member of this board".to_string())
+            }
+        }
+    }
+}
+
+fn decide_board_access(
+    is_org_board: bool,
+    is_public: bool,
+    board_member: Option<&board_repo::BoardMemberRecord>,
+    org_member: Option<&org_repo::OrganizationMemberRecord>,
+) -> AccessDecision {
     if let Some(member) = board_member {
-        if board.organization_id.is_some() {
-            match org_member {
+        if is_org_board {
+            return match org_member {
                 Some(record) if record.accepted_at.is_some() => {
                     let permissions = resolve_board_permissions_for_org_member(
                         member.role,
                         member.custom_permissions.as_ref(),
                         record.role,
                     );
-                    return Ok(BoardAccess {
-                        role: member.role,
-                        permissions,
-                    });
-                }
-                _ => {
-                    return Err(AppError::Forbidden(
-                        "You must accept the workspace invitation before accessing this board"
-                            .to_string(),
-                    ));
+                    AccessDecision::Granted(
+                        BoardAccess {
+                            role: member.role,
+                            permissions,
+                        },
+                        "org_board_member",
+                    )
                 }
-            }
+                _ => AccessDecision::Denied(AccessDenial::PendingOrgInvite),
+            };
         }
 
         let permissions = member
             .role
             .permissions()
             .apply_overrides(member.custom_permissions.as_ref());
-        return Ok(BoardAccess {
-            role: member.role,
-            permissions,
-        });
+        return AccessDecision::Granted(
+            BoardAccess {
+                role: member.role,
+                permissions,
+            },
+            "board_member",
+        );
     }
 
     if let Some(record) = org_member {
         if record.accepted_at.is_some() && matches!(record.role, OrgRole::Owner | OrgRole::Admin) {
-            return Ok(BoardAccess {
+            return AccessDecision::OrgAdmin;
+        }
+    }
+
+    if is_public {
+        return AccessDecision::Granted(
+            BoardAccess {
                 role: BoardRole::Viewer,
                 permissions: BoardPermissions::viewer_only(),
-            });
-        }
+            },
+            "public",
+        );
     }
 
-    if board.is_public {
-        return Ok(BoardAccess {
-            role: BoardRole::Viewer,
-            permissions: BoardPermissions::viewer_only(),
-        });
+    AccessDecision::Denied(AccessDenial::NoAccess)
+}
+
+fn member_row_to_response(
+    row: board_repo::BoardMemberRow,
+    is_org_board: bool,
+) -> BoardMemberResponse {
+    let effective_permissions = resolve_member_permissions(
+        row.role,
+        row.custom_permissions.as_ref(),
+        is_org_board,
+        row.org_role,
+    );
+    BoardMemberResponse {
+        id: row.member_id,
+        user: BoardMemberUser {
+            id: row.user_id,
+            username: row.username.unwrap_or_default(),
+            display_name: row.display_name,
+            avatar_url: row.avatar_url,
+        },
+        role: row.role,
+        custom_permissions: row.custom_permissions,
+        effective_permissions,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }
+}
+
+async fn load_admins_manage_all_boards(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<bool, AppError> {
+    Ok(org_repo::find_organization_by_id(pool, organization_id)
+        .await?
+        .is_some_and(|organization| organization.settings.admins_manage_all_boards))
+}
+
+/// Access for org owners/admins who are not explicit board members.
+fn org_admin_board_access(admins_manage_all_boards: bool) -> BoardAccess {
+    let role = if admins_manage_all_boards {
+        BoardRole::Admin
+    } else {
+        BoardRole::Viewer
+    };
+    BoardAccess {
+        role,
+        permissions: role.permissions(),
     }
+}
 
-    Err(AppError::Forbidden(
-        "You are not a member of this board".to_string(),
-    ))
+/// Records which branch of board access resolution applied.
+///
+/// Off by default; enable with `RUST_LOG=board_access=debug`.
+fn log_access_decision(
+    board: &Board,
+    user_id: Uuid,
+    branch: &'static str,
+    role: Option<BoardRole>,
+) {
+    tracing::debug!(
+        target: "board_access",
+        board_id = %board.id,
+        user_id = %user_id,
+        organization_id = ?board.organization_id,
+        is_public = board.is_public,
+        branch,
+        granted = role.is_some(),
+        role = ?role,
+        "Board access resolved"
+    );
 }
 
 async fn load_board_for_access(pool: &PgPool, board_id: Uuid) -> Result<Board, AppError> {
@@ -895,15 +2123,7 @@ fn ensure_permission(
     permissions: &BoardPermissions,
     permission: BoardPermission,
 ) -> Result<(), AppError> {
-    let allowed = match permission {
-        BoardPermission::View => permissions.can_view,
-        BoardPermission::Edit => permissions.can_edit,
-        BoardPermission::Comment => permissions.can_comment,
-        BoardPermission::ManageMembers => permissions.can_manage_members,
-        BoardPermission::ManageBoard => permissions.can_manage_board,
-    };
-
-    if allowed {
+    if has_permission(permissions, permission) {
         return Ok(());
     }
 
@@ -918,6 +2138,140 @@ fn ensure_permission(
     Err(AppError::Forbidden(message.to_string()))
 }
 
+/// Pairs of `(permission, required permission)` every effective permission
+/// set must satisfy.
+const PERMISSION_IMPLICATIONS: [(BoardPermission, BoardPermission); 4] = [
+    (BoardPermission::ManageBoard, BoardPermission::Edit),
+    (BoardPermission::Edit, BoardPermission::View),
+    (BoardPermission::Comment, BoardPermission::View),
+    (BoardPermission::ManageMembers, BoardPermission::View),
+];
+
+const ALL_BOARD_PERMISSIONS: [BoardPermission; 5] = [
+    BoardPermission::View,
+    BoardPermission::Edit,
+    BoardPermission::Comment,
+    BoardPermission::ManageMembers,
+    BoardPermission::ManageBoard,
+];
+
+/// Makes custom permission overrides consistent with the role they apply to.
+///
+/// Explicitly granting a permission grants what it requires (manage board
+/// implies edit implies view), and explicitly revoking one revokes what
+/// depends on it. Overrides that both grant a permission and revoke
+/// something it requires are rejected.
+fn normalize_permission_overrides(
+    role: BoardRole,
+    mut overrides: BoardPermissionOverrides,
+) -> Result<BoardPermissionOverrides, AppError> {
+    let base = role.permissions();
+
+    for permission in ALL_BOARD_PERMISSIONS {
+        if explicit_override(&overrides, permission) != Some(true) {
+            continue;
+        }
+        for required in required_permissions(permission) {
+            if explicit_override(&overrides, required) == Some(false) {
+                return Err(AppError::ValidationError(format!(
+                    "{} requires {}",
+                    permission_field_name(permission),
+                    permission_field_name(required)
+                )));
+            }
+        }
+    }
+
+    let explicit = overrides.clone();
+    for permission in ALL_BOARD_PERMISSIONS {
+        match explicit_override(&explicit, permission) {
+            Some(true) => {
+                for required in required_permissions(permission) {
+                    let slot = override_slot(&mut overrides, required);
+                    if slot.is_none() && !has_permission(&base, required) {
+                        *slot = Some(true);
+                    }
+                }
+            }
+            Some(false) => {
+                for dependent in ALL_BOARD_PERMISSIONS {
+                    if !required_permissions(dependent).contains(&permission) {
+                        continue;
+                    }
+                    let slot = override_slot(&mut overrides, dependent);
+                    if slot.is_none() && has_permission(&base, dependent) {
+                        *slot = Some(false);
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    Ok(overrides)
+}
+
+/// Every permission transitively required by `permission`.
+fn required_permissions(permission: BoardPermission) -> Vec<BoardPermission> {
+    let mut required = Vec::new();
+    let mut pending = vec![permission];
+    while let Some(current) = pending.pop() {
+        for (granted, needs) in PERMISSION_IMPLICATIONS {
+            if granted == current && !required.contains(&needs) {
+                required.push(needs);
+                pending.push(needs);
+            }
+        }
+    }
+    required
+}
+
+fn override_slot(
+    overrides: &mut BoardPermissionOverrides,
+    permission: BoardPermission,
+) -> &mut Option<bool> {
+    match permission {
+        BoardPermission::View => &mut overrides.can_view,
+        BoardPermission::Edit => &mut overrides.can_edit,
+        BoardPermission::Comment => &mut overrides.can_comment,
+        BoardPermission::ManageMembers => &mut overrides.can_manage_members,
+        BoardPermission::ManageBoard => &mut overrides.can_manage_board,
+    }
+}
+
+fn explicit_override(
+    overrides: &BoardPermissionOverrides,
+    permission: BoardPermission,
+) -> Option<bool> {
+    match permission {
+        BoardPermission::View => overrides.can_view,
+        BoardPermission::Edit => overrides.can_edit,
+        BoardPermission::Comment => overrides.can_comment,
+        BoardPermission::ManageMembers => overrides.can_manage_members,
+        BoardPermission::ManageBoard => overrides.can_manage_board,
+    }
+}
+
+fn has_permission(permissions: &BoardPermissions, permission: BoardPermission) -> bool {
+    match permission {
+        BoardPermission::View => permissions.can_view,
+        BoardPermission::Edit => permissions.can_edit,
+        BoardPermission::Comment => permissions.can_comment,
+        BoardPermission::ManageMembers => permissions.can_manage_members,
+        BoardPermission::ManageBoard => permissions.can_manage_board,
+    }
+}
+
+fn permission_field_name(permission: BoardPermission) -> &'static str {
+    match permission {
+        BoardPermission::View => "canView",
+        BoardPermission::Edit => "canEdit",
+        BoardPermission::Comment => "canComment",
+        BoardPermission::ManageMembers => "canManageMembers",
+        BoardPermission::ManageBoard => "canManageBoard",
+    }
+}
+
 fn ensure_guest_role_permissions(
     org_role: Option<OrgRole>,
     role: BoardRole,
@@ -970,6 +2324,13 @@ fn ensure_board_active(board: &Board) -> Result<(), AppError> {
     Ok(())
 }
 
+fn projection_rebuild_concurrency(value: Option<&str>) -> usize {
+    value
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|concurrency| *concurrency > 0)
+        .unwrap_or(DEFAULT_PROJECTION_REBUILD_CONCURRENCY)
+}
+
 fn ensure_board_restorable(board: &Board) -> Result<(), AppError> {
     let deleted_at = board
         .deleted_at
@@ -983,7 +2344,54 @@ fn ensure_board_restorable(board: &Board) -> Result<(), AppError> {
     Ok(())
 }
 
-fn normalize_optional_name(name: Option<String>) -> Result<Option<String>, AppError> {
+const DEFAULT_BOARD_NAME_MAX_LENGTH: usize = 200;
+// Matches the VARCHAR(255) column on board.board.name.
+const BOARD_NAME_HARD_MAX_LENGTH: usize = 255;
+const DEFAULT_BOARD_DESCRIPTION_MAX_LENGTH: usize = 2_000;
+
+#[derive(Debug, Clone, Copy)]
+struct BoardTextLimits {
+    name_max: usize,
+    description_max: usize,
+}
+
+impl BoardTextLimits {
+    fn from_env() -> Self {
+        Self {
+            name_max: env_usize("BOARD_NAME_MAX_LENGTH", DEFAULT_BOARD_NAME_MAX_LENGTH)
+                .min(BOARD_NAME_HARD_MAX_LENGTH),
+            description_max: env_usize(
+                "BOARD_DESCRIPTION_MAX_LENGTH",
+                DEFAULT_BOARD_DESCRIPTION_MAX_LENGTH,
+            ),
+        }
+    }
+}
+
+/// Reads a positive integer env setting, falling back when it is unset or invalid.
+pub(crate) fn env_usize(key: &str, fallback: usize) -> usize {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(fallback)
+}
+
+fn ensure_board_name_length(name: &str, limits: &BoardTextLimits) -> Result<(), AppError> {
+    let length = name.chars().count();
+    if length > limits.name_max {
+        return Err(AppError::ValidationError(format!(
+            "Board name must be at most {} characters (got {})",
+            limits.name_max, length
+        )));
+    }
+    Ok(())
+}
+
+fn normalize_optional_name(
+    name: Option<String>,
+    limits: &BoardTextLimits,
+) -> Result<Option<String>, AppError> {
     let Some(value) = name else {
         return Ok(None);
     };
@@ -993,18 +2401,69 @@ fn normalize_optional_name(name: Option<String>) -> Result<Option<String>, AppEr
             "Board name cannot be empty".to_string(),
         ));
     }
+    ensure_board_name_length(trimmed, limits)?;
     Ok(Some(trimmed.to_string()))
 }
 
-fn normalize_optional_description(description: Option<String>) -> Option<String> {
-    description.and_then(|value| {
-        let trimmed = value.trim().to_string();
-        if trimmed.is_empty() {
-            None
-        } else {
-            Some(trimmed)
+fn normalize_optional_description(
+    description: Option<String>,
+    limits: &BoardTextLimits,
+) -> Result<Option<String>, AppError> {
+    let Some(value) = description else {
+        return Ok(None);
+    };
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let length = trimmed.chars().count();
+    if length > limits.description_max {
+        return Err(AppError::ValidationError(format!(
+            "Board description must be at most {} characters (got {})",
+            limits.description_max, length
+        )));
+    }
+    Ok(Some(trimmed.to_string()))
+}
+
+fn ensure_feature(features: &BoardFeatures, feature: BoardFeature) -> Result<(), AppError> {
+    if features.is_enabled(feature) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "The {} feature is disabled for this board",
+            feature.label()
+        )))
+    }
+}
+
+fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Trims, lowercases and dedupes tags, keeping their first-seen order.
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().filter_map(|tag| normalize_tag(tag)) {
+        let length = tag.chars().count();
+        if length > MAX_BOARD_TAG_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Board tags must be at most {} characters (got {})",
+                MAX_BOARD_TAG_LENGTH, length
+            )));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
         }
-    })
+    }
+    if normalized.len() > MAX_BOARD_TAGS {
+        return Err(AppError::ValidationError(format!(
+            "A board can have at most {} tags",
+            MAX_BOARD_TAGS
+        )));
+    }
+    Ok(normalized)
 }
 
 fn ensure_member_capacity(current: i64, additional: i64, limit: i32) -> Result<(), AppError> {
@@ -1067,7 +2526,55 @@ async fn prepare_org_invites(
 
 #[cfg(test)]
 mod tests {
-    use super::is_limit_exceeded;
+    use std::sync::atomic::Ordering;
+
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::{
+        AccessDecision, AccessDenial, BoardTextLimits, PROJECTION_REBUILD_RUNNING,
+        ProjectionRebuildGuard, canvas_settings_with_overrides, decide_board_access,
+        ensure_email_verified_for_boards, ensure_manager_remains, ensure_owner_remains,
+        is_limit_exceeded, normalize_board_role, normalize_optional_description,
+        normalize_optional_name, normalize_permission_overrides, normalize_tags,
+        org_admin_board_access, parse_bool_flag, projection_rebuild_concurrency,
+        remap_connector_bindings, remap_imported_elements, resolve_default_visibility,
+    };
+    use crate::error::AppError;
+    use crate::{
+        models::{
+            boards::{BoardPermissionOverrides, BoardPermissions, BoardRole, CanvasSettings},
+            elements::ElementType,
+            organizations::OrgRole,
+        },
+        realtime::element_crdt::ElementMaterialized,
+        repositories::{boards as board_repo, organizations as org_repo},
+    };
+
+    fn imported(id: Uuid, parent_id: Option<Uuid>, deleted: bool) -> ElementMaterialized {
+        ElementMaterialized {
+            id,
+            board_id: Uuid::now_v7(),
+            layer_id: None,
+            parent_id,
+            created_by: Some(Uuid::now_v7()),
+            element_type: ElementType::Shape,
+            position_x: 10.0,
+            position_y: 20.0,
+            width: 100.0,
+            height: 50.0,
+            rotation: 0.0,
+            z_index: 1,
+            style: json!({}),
+            properties: json!({}),
+            metadata: json!({}),
+            created_at: None,
+            updated_at: None,
+            deleted_at: deleted.then(Utc::now),
+            version: Some(7),
+        }
+    }
 
     #[test]
     fn limit_exceeded_when_over_capacity() {
@@ -1079,10 +2586,389 @@ mod tests {
     fn limit_exceeded_skips_when_unlimited() {
         assert!(!is_limit_exceeded(20, 1, 0));
     }
+
+    #[test]
+    fn default_visibility_prefers_org_setting() {
+        assert!(!resolve_default_visibility(Some(false), true));
+        assert!(resolve_default_visibility(Some(true), false));
+        assert!(!resolve_default_visibility(None, false));
+    }
+
+    #[test]
+    fn decide_board_access_covers_each_branch() {
+        let user_id = Uuid::now_v7();
+        let editor = board_repo::BoardMemberRecord {
+            user_id,
+            role: BoardRole::Editor,
+            custom_permissions: None,
+        };
+        let accepted_admin = org_repo::OrganizationMemberRecord {
+            user_id,
+            role: OrgRole::Admin,
+            accepted_at: Some(Utc::now()),
+        };
+        let pending_member = org_repo::OrganizationMemberRecord {
+            user_id,
+            role: OrgRole::Member,
+            accepted_at: None,
+        };
+
+        assert!(matches!(
+            decide_board_access(false, false, Some(&editor), None),
+            AccessDecision::Granted(access, "board_member") if access.role == BoardRole::Editor
+        ));
+        assert!(matches!(
+            decide_board_access(true, false, Some(&editor), Some(&accepted_admin)),
+            AccessDecision::Granted(_, "org_board_member")
+        ));
+        assert!(matches!(
+            decide_board_access(true, true, Some(&editor), Some(&pending_member)),
+            AccessDecision::Denied(AccessDenial::PendingOrgInvite)
+        ));
+        assert!(matches!(
+            decide_board_access(true, false, None, Some(&accepted_admin)),
+            AccessDecision::OrgAdmin
+        ));
+        assert!(matches!(
+            decide_board_access(false, true, None, None),
+            AccessDecision::Granted(access, "public") if access.role == BoardRole::Viewer
+        ));
+        assert!(matches!(
+            decide_board_access(false, false, None, None),
+            AccessDecision::Denied(AccessDenial::NoAccess)
+        ));
+    }
+
+    #[test]
+    fn org_admin_access_is_viewer_unless_policy_enabled() {
+        let default_access = org_admin_board_access(false);
+        assert_eq!(default_access.role, BoardRole::Viewer);
+        assert_eq!(default_access.permissions, BoardPermissions::viewer_only());
+
+        let managed = org_admin_board_access(true);
+        assert_eq!(managed.role, BoardRole::Admin);
+        assert!(managed.permissions.can_edit);
+        assert!(managed.permissions.can_manage_board);
+    }
+
+    #[test]
+    fn unverified_email_blocks_board_creation_when_required() {
+        assert!(ensure_email_verified_for_boards(false, true).is_err());
+        assert!(ensure_email_verified_for_boards(false, false).is_ok());
+        assert!(ensure_email_verified_for_boards(true, true).is_ok());
+    }
+
+    #[test]
+    fn name_and_description_limits_count_characters() {
+        let limits = BoardTextLimits {
+            name_max: 5,
+            description_max: 3,
+        };
+
+        assert_eq!(
+            normalize_optional_name(Some("  héllo ".to_string()), &limits).unwrap(),
+            Some("héllo".to_string())
+        );
+        assert!(matches!(
+            normalize_optional_name(Some("toolong".to_string()), &limits),
+            Err(AppError::ValidationError(message)) if message.contains("at most 5")
+        ));
+        assert_eq!(
+            normalize_optional_description(Some("   ".to_string()), &limits).unwrap(),
+            None
+        );
+        assert!(matches!(
+            normalize_optional_description(Some("abcd".to_string()), &limits),
+            Err(AppError::ValidationError(message)) if message.contains("at most 3")
+        ));
+    }
+
+    #[test]
+    fn canvas_defaults_apply_valid_overrides_only() {
+        let settings = canvas_settings_with_overrides(|key| match key {
+            "BOARD_DEFAULT_CANVAS_WIDTH" => Some("20000".to_string()),
+            "BOARD_DEFAULT_CANVAS_HEIGHT" => Some("-5".to_string()),
+            "BOARD_DEFAULT_GRID_SIZE" => Some("40".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(settings.width, 20000.0);
+        assert_eq!(settings.height, 10000.0);
+        assert_eq!(settings.grid_size, 40);
+        assert_eq!(
+            canvas_settings_with_overrides(|_| None).width,
+            CanvasSettings::default().width
+        );
+    }
+
+    #[test]
+    fn last_owner_cannot_be_removed_or_demoted() {
+        assert!(matches!(
+            ensure_owner_remains(BoardRole::Owner, None, 1),
+            Err(AppError::BadRequest(message)) if message == "Cannot remove the last owner"
+        ));
+        assert!(matches!(
+            ensure_owner_remains(BoardRole::Owner, Some(BoardRole::Admin), 1),
+            Err(AppError::BadRequest(message)) if message == "Cannot demote the last owner"
+        ));
+        assert!(ensure_owner_remains(BoardRole::Owner, None, 2).is_ok());
+        assert!(ensure_owner_remains(BoardRole::Owner, Some(BoardRole::Editor), 2).is_ok());
+        assert!(ensure_owner_remains(BoardRole::Owner, Some(BoardRole::Owner), 1).is_ok());
+        assert!(ensure_owner_remains(BoardRole::Editor, None, 0).is_ok());
+    }
+
+    #[test]
+    fn projection_rebuild_concurrency_defaults_when_invalid() {
+        assert_eq!(projection_rebuild_concurrency(Some(" 8 ")), 8);
+        assert_eq!(projection_rebuild_concurrency(Some("0")), 4);
+        assert_eq!(projection_rebuild_concurrency(Some("many")), 4);
+        assert_eq!(projection_rebuild_concurrency(None), 4);
+    }
+
+    #[test]
+    fn projection_rebuild_flag_clears_when_the_guard_unwinds() {
+        PROJECTION_REBUILD_RUNNING.store(true, Ordering::Release);
+        let result = std::panic::catch_unwind(|| {
+            let _guard = ProjectionRebuildGuard;
+            panic!("rebuild failed");
+        });
+
+        assert!(result.is_err());
+        assert!(!PROJECTION_REBUILD_RUNNING.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn last_member_manager_cannot_be_removed_or_demoted() {
+        let admin = BoardRole::Admin.permissions();
+        let editor = BoardRole::Editor.permissions();
+
+        assert!(matches!(
+            ensure_manager_remains(admin, None, 0),
+            Err(AppError::BadRequest(message)) if message.starts_with("Cannot remove")
+        ));
+        assert!(matches!(
+            ensure_manager_remains(admin, Some(editor), 0),
+            Err(AppError::BadRequest(message)) if message.starts_with("Cannot demote")
+        ));
+        assert!(ensure_manager_remains(admin, Some(editor), 1).is_ok());
+        assert!(ensure_manager_remains(admin, Some(admin), 0).is_ok());
+        assert!(ensure_manager_remains(editor, None, 0).is_ok());
+    }
+
+    #[test]
+    fn tags_are_trimmed_lowercased_and_deduped() {
+        let tags = vec![
+            " Retro ".to_string(),
+            "retro".to_string(),
+            "".to_string(),
+            "Sprint Planning".to_string(),
+        ];
+
+        assert_eq!(
+            normalize_tags(tags).unwrap(),
+            vec!["retro".to_string(), "sprint planning".to_string()]
+        );
+        assert!(matches!(
+            normalize_tags(vec!["x".repeat(33)]),
+            Err(AppError::ValidationError(message)) if message.contains("at most 32")
+        ));
+        assert!(normalize_tags((0..21).map(|i| i.to_string()).collect()).is_err());
+    }
+
+    fn overrides(
+        can_view: Option<bool>,
+        can_edit: Option<bool>,
+        can_comment: Option<bool>,
+        can_manage_members: Option<bool>,
+        can_manage_board: Option<bool>,
+    ) -> BoardPermissionOverrides {
+        BoardPermissionOverrides {
+            can_view,
+            can_edit,
+            can_comment,
+            can_manage_members,
+            can_manage_board,
+        }
+    }
+
+    #[test]
+    fn normalized_overrides_keep_effective_permissions_consistent() {
+        let roles = [
+            BoardRole::Owner,
+            BoardRole::Admin,
+            BoardRole::Editor,
+            BoardRole::Commenter,
+            BoardRole::Viewer,
+        ];
+        let values = [None, Some(true), Some(false)];
+        for role in roles {
+            for view in values {
+                for edit in values {
+                    for comment in values {
+                        for members in values {
+                            for manage in values {
+                                let input = overrides(view, edit, comment, members, manage);
+                                let Ok(normalized) =
+                                    normalize_permission_overrides(role, input.clone())
+                                else {
+                                    continue;
+                                };
+                                let effective =
+                                    role.permissions().apply_overrides(Some(&normalized));
+                                assert!(
+                                    !effective.can_manage_board || effective.can_edit,
+                                    "{role:?} {input:?}"
+                                );
+                                assert!(
+                                    !(effective.can_edit
+                                        || effective.can_comment
+                                        || effective.can_manage_members)
+                                        || effective.can_view,
+                                    "{role:?} {input:?}"
+                                );
+                                for (explicit, actual) in [
+                                    (input.can_view, effective.can_view),
+                                    (input.can_edit, effective.can_edit),
+                                    (input.can_comment, effective.can_comment),
+                                    (input.can_manage_members, effective.can_manage_members),
+                                    (input.can_manage_board, effective.can_manage_board),
+                                ] {
+                                    if let Some(explicit) = explicit {
+                                        assert_eq!(explicit, actual, "{role:?} {input:?}");
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn normalize_overrides_rejects_contradictions() {
+        let result = normalize_permission_overrides(
+            BoardRole::Editor,
+            overrides(Some(false), None, None, None, Some(true)),
+        );
+        assert!(matches!(
+            result,
+            Err(AppError::ValidationError(message)) if message == "canManageBoard requires canView"
+        ));
+        assert!(
+            normalize_permission_overrides(
+                BoardRole::Viewer,
+                overrides(None, Some(false), None, None, Some(true)),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn normalize_overrides_propagates_grants_and_revocations() {
+        let granted = normalize_permission_overrides(
+            BoardRole::Viewer,
+            overrides(None, None, None, None, Some(true)),
+        )
+        .unwrap();
+        assert_eq!(granted.can_edit, Some(true));
+        assert_eq!(granted.can_view, None);
+
+        let revoked = normalize_permission_overrides(
+            BoardRole::Owner,
+            overrides(Some(false), None, None, None, None),
+        )
+        .unwrap();
+        assert_eq!(
+            revoked,
+            overrides(
+                Some(false),
+                Some(false),
+                Some(false),
+                Some(false),
+                Some(false)
+            )
+        );
+    }
+
+    #[test]
+    fn parse_bool_flag_accepts_common_spellings() {
+        assert_eq!(parse_bool_flag(" TRUE "), Some(true));
+        assert_eq!(parse_bool_flag("off"), Some(false));
+        assert_eq!(parse_bool_flag("maybe"), None);
+    }
+
+    #[test]
+    fn remap_imported_elements_rekeys_and_drops_deleted() {
+        let board_id = Uuid::now_v7();
+        let user_id = Uuid::now_v7();
+        let frame_id = Uuid::now_v7();
+        let child_id = Uuid::now_v7();
+        let mut elements = vec![
+            imported(frame_id, None, false),
+            imported(child_id, Some(frame_id), false),
+            imported(Uuid::now_v7(), None, true),
+        ];
+        elements[0].layer_id = Some(Uuid::now_v7());
+
+        let remapped = remap_imported_elements(board_id, user_id, elements, Utc::now());
+
+        assert_eq!(remapped.len(), 2);
+        let frame = &remapped[0];
+        let child = &remapped[1];
+        assert_ne!(frame.id, frame_id);
+        assert_eq!(child.parent_id, Some(frame.id));
+        assert!(remapped.iter().all(|element| element.board_id == board_id
+            && element.layer_id.is_none()
+            && element.created_by == user_id
+            && element.version == 1));
+    }
+
+    #[test]
+    fn remap_connector_bindings_rekeys_and_drops_missing_targets() {
+        let kept = Uuid::now_v7();
+        let new_id = Uuid::now_v7();
+        let id_map = [(kept, new_id)].into_iter().collect();
+        let properties = json!({
+            "start": { "x": 0, "y": 0 },
+            "bindings": {
+                "start": { "elementId": kept.to_string(), "side": "auto" },
+                "end": { "elementId": Uuid::now_v7().to_string(), "side": "left" }
+            }
+        });
+
+        let remapped = remap_connector_bindings(properties, &id_map);
+
+        assert_eq!(
+            remapped["bindings"],
+            json!({ "start": { "elementId": new_id.to_string(), "side": "auto" } })
+        );
+        assert_eq!(remapped["start"], json!({ "x": 0, "y": 0 }));
+    }
+
+    #[test]
+    fn invite_role_defaults_to_viewer_and_rejects_owner() {
+        assert_eq!(normalize_board_role(None).unwrap(), BoardRole::Viewer);
+        assert_eq!(
+            normalize_board_role(Some(BoardRole::Admin)).unwrap(),
+            BoardRole::Admin
+        );
+        assert!(matches!(
+            normalize_board_role(Some(BoardRole::Owner)),
+            Err(AppError::ValidationError(_))
+        ));
+    }
 }
 
+/// Resolves the role for invited board members; owners are only added via
+/// ownership transfer or co-owner promotion.
 fn normalize_board_role(role: Option<BoardRole>) -> Result<BoardRole, AppError> {
     let role = role.unwrap_or(BoardRole::Viewer);
+    if role == BoardRole::Owner {
+        return Err(AppError::ValidationError(
+            "Owner role cannot be assigned via invite".to_string(),
+        ));
+    }
     Ok(role)
 }
 
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use chrono::Utc;
+use serde_json::json;
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -9,12 +11,17 @@ use crate::{
         CreateCommentRequest, ListCommentsQuery,
     },
     error::AppError,
+    realtime::room::Rooms,
     repositories::{
         comments as comment_repo, comments::CommentCursor, comments::CreateCommentParams,
         elements as element_repo, notifications as notification_repo,
     },
+    services::content_filter::{ContentField, ContentFilter, filter_text},
     telemetry::BusinessEvent,
-    usecases::boards::BoardService,
+    usecases::{
+        board_watchers::{BoardActivity, BoardWatcherService},
+        boards::BoardService,
+    },
 };
 
 pub struct CommentService;
@@ -24,10 +31,12 @@ const MAX_COMMENT_LENGTH: usize = 5000;
 const MAX_COMMENT_MENTIONS: usize = 20;
 const DEFAULT_COMMENT_PAGE_SIZE: u32 = 50;
 const MAX_COMMENT_PAGE_SIZE: u32 = 200;
+const MAX_PINNED_PER_THREAD: i64 = 3;
 
 impl CommentService {
     pub async fn create_comment(
         pool: &PgPool,
+        content_filter: &dyn ContentFilter,
         board_id: Uuid,
         user_id: Uuid,
         req: CreateCommentRequest,
@@ -35,6 +44,14 @@ impl CommentService {
         BoardService::ensure_can_comment(pool, board_id, user_id).await?;
 
         let content = normalize_comment_content(&req.content)?;
+        let filtered = filter_text(content_filter, ContentField::CommentBody, content.clone())?;
+        // Rendered HTML would still carry the original words, so drop it on redaction.
+        let content_html = if filtered == content {
+            req.content_html
+        } else {
+            None
+        };
+        let content = filtered;
         let mentions = normalize_mentions(req.mentions)?;
         let mentions = comment_repo::filter_mentions(pool, board_id, &mentions).await?;
         let notify_mentions = mentions
@@ -63,7 +80,7 @@ impl CommentService {
                 position_x,
                 position_y,
                 content,
-                content_html: req.content_html,
+                content_html,
                 mentions,
             },
         )
@@ -108,6 +125,13 @@ impl CommentService {
             }
             .log();
         }
+        BoardWatcherService::notify_activity(
+            pool,
+            board_id,
+            Some(user_id),
+            BoardActivity::CommentAdded { comment_id: row.id },
+        )
+        .await;
 
         Ok(map_comment_response(row))
     }
@@ -133,10 +157,111 @@ impl CommentService {
             query_limit,
         )
         .await?;
-        let (data, pagination) = build_comment_page(rows, limit);
+        let (mut data, pagination) = build_comment_page(rows, limit);
+        // Pinned comments are kept out of the paginated rows and lead the
+        // first page instead.
+        if query.cursor.is_none() {
+            let pinned = comment_repo::list_pinned_comments(
+                pool,
+                board_id,
+                query.element_id,
+                query.parent_id,
+                query.status,
+            )
+            .await?;
+            data.splice(0..0, pinned.into_iter().map(map_comment_response));
+        }
 
         Ok(CommentListResponse { data, pagination })
     }
+
+    pub async fn pin_comment(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        comment_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<CommentResponse, AppError> {
+        Self::set_pinned(pool, rooms, board_id, comment_id, user_id, true).await
+    }
+
+    pub async fn unpin_comment(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        comment_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<CommentResponse, AppError> {
+        Self::set_pinned(pool, rooms, board_id, comment_id, user_id, false).await
+    }
+
+    /// Pins or unpins a comment and broadcasts `comment:pinned` to the room.
+    /// A thread (comments sharing an element and parent) holds at most
+    /// [`MAX_PINNED_PER_THREAD`] pins.
+    async fn set_pinned(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        comment_id: Uuid,
+        user_id: Uuid,
+        pinned: bool,
+    ) -> Result<CommentResponse, AppError> {
+        BoardService::ensure_can_comment(pool, board_id, user_id).await?;
+
+        let mut tx = pool.begin().await?;
+        comment_repo::lock_comment_pins(&mut tx, board_id).await?;
+        let thread = comment_repo::find_comment_thread(&mut tx, board_id, comment_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
+        if pinned == thread.is_pinned {
+            let message = if pinned {
+                "Comment is already pinned"
+            } else {
+                "Comment is not pinned"
+            };
+            return Err(AppError::Conflict(message.to_string()));
+        }
+        if pinned {
+            let pinned_count = comment_repo::count_pinned_in_thread(
+                &mut tx,
+                board_id,
+                thread.element_id,
+                thread.parent_id,
+            )
+            .await?;
+            ensure_pin_capacity(pinned_count)?;
+        }
+        let row = comment_repo::set_comment_pinned(&mut tx, comment_id, pinned.then_some(user_id))
+            .await?;
+        tx.commit().await?;
+
+        if let Some(room) = rooms.get(&board_id).map(|entry| entry.clone()) {
+            room.broadcast_event(
+                "comment:pinned",
+                json!({
+                    "board_id": board_id,
+                    "comment_id": row.id,
+                    "element_id": row.element_id,
+                    "parent_id": row.parent_id,
+                    "pinned": row.is_pinned,
+                    "pinned_at": row.pinned_at,
+                    "updated_by": user_id,
+                    "timestamp": Utc::now().timestamp_millis(),
+                }),
+            );
+        }
+
+        Ok(map_comment_response(row))
+    }
+}
+
+fn ensure_pin_capacity(pinned_count: i64) -> Result<(), AppError> {
+    if pinned_count >= MAX_PINNED_PER_THREAD {
+        return Err(AppError::Conflict(format!(
+            "A thread can have at most {MAX_PINNED_PER_THREAD} pinned comments"
+        )));
+    }
+    Ok(())
 }
 
 fn normalize_comment_content(content: &str) -> Result<String, AppError> {
@@ -309,6 +434,9 @@ fn map_comment_response(row: comment_repo::CommentRow) -> CommentResponse {
         is_edited: row.is_edited,
         edited_at: row.edited_at,
         reply_count: row.reply_count,
+        is_pinned: row.is_pinned,
+        pinned_by: row.pinned_by,
+        pinned_at: row.pinned_at,
         created_at: row.created_at,
         updated_at: row.updated_at,
     }
@@ -393,6 +521,15 @@ mod tests {
         assert_eq!(parsed.id, id);
     }
 
+    #[test]
+    fn pin_limit_applies_per_thread() {
+        assert!(ensure_pin_capacity(MAX_PINNED_PER_THREAD - 1).is_ok());
+        assert!(matches!(
+            ensure_pin_capacity(MAX_PINNED_PER_THREAD),
+            Err(AppError::Conflict(_))
+        ));
+    }
+
     #[test]
     fn rejects_limit_zero() {
         let result = normalize_comment_limit(Some(0));
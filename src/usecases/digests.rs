@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::users::EmailCategory,
+    repositories::digests::{self as digest_repo, BoardActivityRow, DigestRecipientRow},
+    services::{
+        email::EmailService,
+        email_templates::{BoardDigest, EmailLocale},
+    },
+};
+
+const DIGEST_BATCH_SIZE: i64 = 200;
+const DIGEST_TOP_CONTRIBUTORS: usize = 3;
+const DIGEST_MAX_BOARDS: usize = 20;
+
+/// Periodic board activity summaries for users who opted in.
+pub struct DigestService;
+
+impl DigestService {
+    /// Sends digests to users whose daily or weekly period has elapsed.
+    /// Users with no activity to report are stamped without an email.
+    ///
+    /// A failure for one user is logged and leaves them due for the next
+    /// run; the rest of the batch is still processed.
+    pub async fn send_activity_digests(
+        pool: &PgPool,
+        email_service: &EmailService,
+    ) -> Result<usize, AppError> {
+        let now = chrono::Utc::now();
+        let mut sent = 0;
+
+        let recipients = digest_repo::list_users_due_for_digest(pool, DIGEST_BATCH_SIZE).await?;
+        for recipient in recipients {
+            match send_digest(pool, email_service, &recipient, now).await {
+                Ok(true) => sent += 1,
+                Ok(false) => {}
+                Err(error) => {
+                    tracing::error!(
+                        user_id = %recipient.id,
+                        error = %error,
+                        "Failed to send activity digest"
+                    );
+                }
+            }
+        }
+
+        Ok(sent)
+    }
+}
+
+/// Claims and sends one user's digest, returning whether an email went out.
+///
+/// The claim keeps concurrent workers from mailing the same user; it is
+/// released if the digest cannot be built or sent.
+async fn send_digest(
+    pool: &PgPool,
+    email_service: &EmailService,
+    recipient: &DigestRecipientRow,
+    now: DateTime<Utc>,
+) -> Result<bool, AppError> {
+    let notifications = &recipient.preferences.notifications;
+    let Some(period) = notifications.digest_frequency.period() else {
+        return Ok(false);
+    };
+    if !notifications.allows_email(EmailCategory::ActivityDigest) {
+        return Ok(false);
+    }
+    if !digest_repo::claim_digest(pool, recipient.id, recipient.digest_sent_at).await? {
+        return Ok(false);
+    }
+
+    let since = recipient.digest_sent_at.unwrap_or(now - period);
+    let result = async {
+        let rows = digest_repo::list_board_activity_since(pool, recipient.id, since).await?;
+        let boards = summarize_board_activity(rows);
+        if boards.is_empty() {
+            return Ok(false);
+        }
+        email_service
+            .send_activity_digest(
+                &recipient.email,
+                EmailLocale::from_language(&recipient.preferences.language),
+                notifications.digest_frequency,
+                &boards,
+            )
+            .await?;
+        Ok(true)
+    }
+    .await;
+    if result.is_err() {
+        digest_repo::release_digest_claim(pool, recipient.id, recipient.digest_sent_at).await?;
+    }
+    result
+}
+
+/// Folds per-actor rows into one digest per board, busiest boards first.
+fn summarize_board_activity(rows: Vec<BoardActivityRow>) -> Vec<BoardDigest> {
+    let mut by_board: HashMap<Uuid, (String, Vec<BoardActivityRow>)> = HashMap::new();
+    for row in rows {
+        by_board
+            .entry(row.board_id)
+            .or_insert_with(|| (row.board_name.clone(), Vec::new()))
+            .1
+            .push(row);
+    }
+
+    let mut digests: Vec<BoardDigest> = by_board
+        .into_iter()
+        .map(|(board_id, (board_name, mut actors))| {
+            actors.sort_by(|a, b| {
+                (b.elements + b.comments)
+                    .cmp(&(a.elements + a.comments))
+                    .then_with(|| a.actor_name.cmp(&b.actor_name))
+            });
+            BoardDigest {
+                board_id,
+                board_name,
+                elements: actors.iter().map(|actor| actor.elements).sum(),
+                comments: actors.iter().map(|actor| actor.comments).sum(),
+                top_contributors: actors
+                    .iter()
+                    .filter_map(|actor| actor.actor_name.clone())
+                    .take(DIGEST_TOP_CONTRIBUTORS)
+                    .collect(),
+            }
+        })
+        .collect();
+    digests.sort_by(|a, b| {
+        (b.elements + b.comments)
+            .cmp(&(a.elements + a.comments))
+            .then_with(|| a.board_name.cmp(&b.board_name))
+    });
+    digests.truncate(DIGEST_MAX_BOARDS);
+    digests
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::summarize_board_activity;
+    use crate::repositories::digests::BoardActivityRow;
+
+    fn row(
+        board_id: Uuid,
+        board: &str,
+        actor: &str,
+        elements: i64,
+        comments: i64,
+    ) -> BoardActivityRow {
+        BoardActivityRow {
+            board_id,
+            board_name: board.to_string(),
+            actor_name: Some(actor.to_string()),
+            elements,
+            comments,
+        }
+    }
+
+    #[test]
+    fn summarizes_boards_by_activity_with_top_contributors() {
+        let roadmap = Uuid::now_v7();
+        let notes = Uuid::now_v7();
+        let rows = vec![
+            row(notes, "Notes", "Cy", 1, 0),
+            row(roadmap, "Roadmap", "Ann", 1, 1),
+            row(roadmap, "Roadmap", "Bo", 5, 0),
+            row(roadmap, "Roadmap", "Di", 0, 1),
+            row(roadmap, "Roadmap", "Ed", 0, 1),
+        ];
+
+        let digests = summarize_board_activity(rows);
+
+        assert_eq!(digests.len(), 2);
+        assert_eq!(digests[0].board_id, roadmap);
+        assert_eq!((digests[0].elements, digests[0].comments), (6, 3));
+        assert_eq!(digests[0].top_contributors, vec!["Bo", "Ann", "Di"]);
+        assert_eq!(digests[1].board_name, "Notes");
+    }
+}
@@ -0,0 +1,165 @@
+//! In-place element type conversion rules.
+//!
+//! Only types that render the same kind of content convert into each other:
+//! text-bearing types (text, sticky note) and media types (image, video,
+//! embed). Type-specific property keys are carried over or renamed, keys the
+//! target type does not use are dropped, and style keys still at the source
+//! type's defaults take the target type's defaults.
+
+use serde_json::{Map, Value};
+
+use crate::{error::AppError, models::elements::ElementType, usecases::element_defaults};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConversionFamily {
+    Text,
+    Media,
+}
+
+/// Converted `(style, properties)` for an element of type `from` becoming `to`.
+pub fn convert(
+    from: ElementType,
+    to: ElementType,
+    style: &Value,
+    properties: &Value,
+) -> Result<(Value, Value), AppError> {
+    match (family(from), family(to)) {
+        (Some(source), Some(target)) if source == target => {}
+        _ => {
+            return Err(AppError::ValidationError(format!(
+                "Cannot convert {:?} elements to {:?}",
+                from, to
+            )));
+        }
+    }
+    Ok((
+        convert_style(from, to, style),
+        convert_properties(from, to, properties),
+    ))
+}
+
+fn family(element_type: ElementType) -> Option<ConversionFamily> {
+    match element_type {
+        ElementType::Text | ElementType::StickyNote => Some(ConversionFamily::Text),
+        ElementType::Image | ElementType::Video | ElementType::Embed => {
+            Some(ConversionFamily::Media)
+        }
+        _ => None,
+    }
+}
+
+/// Property keys owned by a convertible type.
+fn type_keys(element_type: ElementType) -> &'static [&'static str] {
+    match element_type {
+        ElementType::Text | ElementType::StickyNote => &["content"],
+        ElementType::Image => &["src", "alt"],
+        ElementType::Video => &["src", "provider"],
+        ElementType::Embed => &["url"],
+        _ => &[],
+    }
+}
+
+fn target_key(key: &'static str, to: ElementType) -> &'static str {
+    match (key, to) {
+        ("src", ElementType::Embed) => "url",
+        ("url", ElementType::Image | ElementType::Video) => "src",
+        _ => key,
+    }
+}
+
+fn convert_properties(from: ElementType, to: ElementType, properties: &Value) -> Value {
+    let mut next = properties.as_object().cloned().unwrap_or_default();
+    for key in type_keys(from) {
+        let Some(value) = next.remove(*key) else {
+            continue;
+        };
+        let key = target_key(key, to);
+        if type_keys(to).contains(&key) {
+            next.entry(key).or_insert(value);
+        }
+    }
+    element_defaults::properties_with_defaults(to, Some(Value::Object(next)))
+}
+
+fn convert_style(from: ElementType, to: ElementType, style: &Value) -> Value {
+    let Some(style) = style.as_object() else {
+        return style.clone();
+    };
+    let source_defaults = element_defaults::style_with_defaults(from, None);
+    let target_defaults = element_defaults::style_with_defaults(to, None);
+    let mut next = Map::new();
+    for (key, value) in style {
+        if source_defaults.get(key) != Some(value) {
+            next.insert(key.clone(), value.clone());
+        } else if let Some(default) = target_defaults.get(key) {
+            next.insert(key.clone(), default.clone());
+        }
+    }
+    element_defaults::style_with_defaults(to, Some(Value::Object(next)))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::convert;
+    use crate::models::elements::ElementType;
+
+    #[test]
+    fn sticky_note_to_text_keeps_content_and_custom_style() {
+        let (style, properties) = convert(
+            ElementType::StickyNote,
+            ElementType::Text,
+            &json!({ "fill": "#FDE68A", "cornerRadius": 12, "fontSize": 28 }),
+            &json!({ "content": "Ship it", "tag": "todo" }),
+        )
+        .unwrap();
+
+        assert_eq!(style, json!({ "fill": "#ffffff", "fontSize": 28 }));
+        assert_eq!(properties, json!({ "content": "Ship it", "tag": "todo" }));
+    }
+
+    #[test]
+    fn media_conversions_rename_source_keys() {
+        let (_, properties) = convert(
+            ElementType::Image,
+            ElementType::Embed,
+            &json!({}),
+            &json!({ "src": "https://x/a.png", "alt": "A" }),
+        )
+        .unwrap();
+
+        assert_eq!(properties, json!({ "url": "https://x/a.png" }));
+    }
+
+    #[test]
+    fn rejects_conversions_across_families() {
+        assert!(
+            convert(
+                ElementType::Shape,
+                ElementType::Text,
+                &json!({}),
+                &json!({})
+            )
+            .is_err()
+        );
+        assert!(
+            convert(
+                ElementType::Text,
+                ElementType::Image,
+                &json!({}),
+                &json!({})
+            )
+            .is_err()
+        );
+        assert!(
+            convert(
+                ElementType::Frame,
+                ElementType::Document,
+                &json!({}),
+                &json!({})
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Server-side style and property defaults per element type.
+//!
+//! Values mirror the web client's creation defaults so elements created by
+//! any client look the same. Explicitly provided keys always win.
+
+use serde_json::{Value, json};
+
+use crate::models::elements::ElementType;
+
+/// Style for `element_type` with any keys missing from `provided` filled in.
+pub fn style_with_defaults(element_type: ElementType, provided: Option<Value>) -> Value {
+    merge_defaults(default_style(element_type), provided)
+}
+
+/// Properties for `element_type` with any keys missing from `provided` filled in.
+pub fn properties_with_defaults(element_type: ElementType, provided: Option<Value>) -> Value {
+    merge_defaults(default_properties(element_type), provided)
+}
+
+fn default_style(element_type: ElementType) -> Value {
+    match element_type {
+        ElementType::Shape => json!({
+            "stroke": "#ffffff",
+            "strokeWidth": 2,
+            "fill": "transparent",
+        }),
+        ElementType::Text => json!({ "fontSize": 20, "fill": "#ffffff" }),
+        ElementType::StickyNote => json!({
+            "fill": "#FDE68A",
+            "stroke": "#F59E0B",
+            "strokeWidth": 1,
+            "cornerRadius": 12,
+            "fontSize": 16,
+            "textColor": "#1F2937",
+        }),
+        ElementType::Frame => json!({
+            "fill": "transparent",
+            "stroke": "#FBBF24",
+            "strokeWidth": 1.5,
+        }),
+        ElementType::Connector => json!({ "stroke": "#E5E7EB", "strokeWidth": 2 }),
+        ElementType::Drawing => json!({ "stroke": "#EAB308", "strokeWidth": 3 }),
+        ElementType::Image
+        | ElementType::Video
+        | ElementType::Embed
+        | ElementType::Document
+        | ElementType::Component => json!({
+            "fill": "#0f172a",
+            "stroke": "#334155",
+            "strokeWidth": 1,
+            "cornerRadius": 12,
+        }),
+    }
+}
+
+fn default_properties(element_type: ElementType) -> Value {
+    match element_type {
+        ElementType::Shape => json!({ "shapeType": "rectangle" }),
+        ElementType::Text | ElementType::StickyNote => json!({ "content": "" }),
+        ElementType::Drawing => json!({ "points": [] }),
+        _ => json!({}),
+    }
+}
+
+/// Adds default keys absent from `provided`. A non-object value is an explicit
+/// client choice and is kept unchanged.
+fn merge_defaults(defaults: Value, provided: Option<Value>) -> Value {
+    match (defaults, provided) {
+        (defaults, None) => defaults,
+        (Value::Object(defaults), Some(Value::Object(mut provided))) => {
+            for (key, value) in defaults {
+                provided.entry(key).or_insert(value);
+            }
+            Value::Object(provided)
+        }
+        (_, Some(provided)) => provided,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{properties_with_defaults, style_with_defaults};
+    use crate::models::elements::ElementType;
+
+    #[test]
+    fn fills_missing_keys_without_overriding_explicit_ones() {
+        let style = style_with_defaults(
+            ElementType::StickyNote,
+            Some(json!({ "fill": "#BFDBFE", "fontSize": null })),
+        );
+
+        assert_eq!(style["fill"], "#BFDBFE");
+        assert!(style["fontSize"].is_null());
+        assert_eq!(style["textColor"], "#1F2937");
+        assert_eq!(
+            properties_with_defaults(ElementType::Shape, None),
+            json!({ "shapeType": "rectangle" })
+        );
+        assert_eq!(
+            properties_with_defaults(ElementType::Image, Some(json!({ "src": "a.png" }))),
+            json!({ "src": "a.png" })
+        );
+    }
+}
@@ -1,44 +1,357 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
 use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
+    dto::auth::PublicUserProfile,
     dto::elements::{
-        BoardElementResponse, CreateBoardElementRequest, DeleteBoardElementResponse,
-        RestoreBoardElementResponse, UpdateBoardElementRequest,
+        ApplyStyleResponse, BoardElementListResponse, BoardElementResponse,
+        BulkBoardElementsResponse, BulkElementResult, BulkElementStatus, BulkElementTarget,
+        CompactZIndexResponse, CreateBoardElementRequest, DeleteBoardElementResponse,
+        DeletedBoardElementResponse, ElementDiffQuery, ElementDiffResponse, ElementFieldChange,
+        ElementHistoryEntry, ElementHistoryQuery, ElementHistoryResponse, ElementListPagination,
+        ListBoardElementsQuery, PasteBoardElementsRequest, PasteBoardElementsResponse,
+        RecentlyDeletedElementsResponse, RestoreBoardElementResponse, UpdateBoardElementRequest,
     },
     error::AppError,
+    models::{
+        boards::{BoardFeature, CanvasSettings, ElementDeletePolicy},
+        elements::{BoardElement, ElementType},
+    },
     realtime::{
-        element_crdt::{ElementMaterialized, ElementSnapshot},
-        elements as realtime_elements,
+        element_crdt::{ElementMaterialized, ElementSnapshot, ElementTypeChange, UpdateContext},
+        elements::{self as realtime_elements, BatchDeleted},
         room::Rooms,
     },
-    usecases::boards::BoardService,
+    repositories::{
+        boards as board_repo,
+        elements::{self as element_repo, ElementBounds, ElementHistoryRow},
+        users as user_repo,
+    },
+    services::{
+        content_filter::{ContentFilter, filter_element_properties},
+        embed_policy::validate_embed_properties,
+    },
+    usecases::{boards::BoardService, element_conversion, element_defaults},
 };
 
 const MAX_ROTATION: f64 = 360.0;
+const DEFAULT_ELEMENT_LIST_LIMIT: u32 = 500;
+const MAX_ELEMENT_LIST_LIMIT: u32 = 2_000;
+const DEFAULT_ELEMENT_HISTORY_LIMIT: u32 = 50;
+const MAX_ELEMENT_HISTORY_LIMIT: u32 = 200;
+const ELEMENT_HISTORY_RETAINED_VERSIONS: i64 = 500;
+const MAX_PASTE_ELEMENTS: usize = 500;
+const MAX_BULK_ELEMENTS: usize = 500;
+const DEFAULT_RECENTLY_DELETED_MINUTES: u32 = 30;
+const MAX_RECENTLY_DELETED_MINUTES: u32 = 24 * 60;
+const DEFAULT_Z_INDEX_COMPACTION_THRESHOLD: i32 = 100_000;
+const Z_INDEX_COMPACTION_THRESHOLD_ENV: &str = "ELEMENT_Z_INDEX_COMPACTION_THRESHOLD";
+/// Boards compacted per maintenance run.
+const Z_INDEX_COMPACTION_BATCH: i64 = 50;
+/// Keys whose nested fields are diffed individually.
+const NESTED_DIFF_FIELDS: [&str; 3] = ["style", "properties", "metadata"];
+/// Bookkeeping columns that change on every write and carry no user intent.
+const IGNORED_DIFF_FIELDS: [&str; 2] = ["version", "updated_at"];
 
 pub struct ElementService;
 
 impl ElementService {
+    /// Lists live elements page by page, optionally restricted to a viewport.
+    pub async fn list_elements(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+        query: ListBoardElementsQuery,
+    ) -> Result<BoardElementListResponse, AppError> {
+        BoardService::ensure_can_view(pool, board_id, user_id).await?;
+        let bounds = parse_bounds(&query)?;
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_ELEMENT_LIST_LIMIT)
+            .clamp(1, MAX_ELEMENT_LIST_LIMIT);
+        let offset = query.offset.unwrap_or(0);
+
+        let mut rows = match bounds {
+            Some(bounds) => {
+                element_repo::list_elements_in_bounds(
+                    pool,
+                    board_id,
+                    bounds,
+                    limit as i64 + 1,
+                    offset as i64,
+                )
+                .await?
+            }
+            None => {
+                element_repo::list_elements_in_region(
+                    pool,
+                    board_id,
+                    None,
+                    limit as i64 + 1,
+                    offset as i64,
+                )
+                .await?
+            }
+        };
+        let has_more = rows.len() > limit as usize;
+        rows.truncate(limit as usize);
+        let next_offset = has_more.then(|| offset + limit);
+
+        let mut data: Vec<BoardElementResponse> =
+            rows.into_iter().map(element_to_response).collect();
+        if query.include_creator {
+            attach_creators(pool, &mut data).await?;
+        }
+
+        Ok(BoardElementListResponse {
+            data,
+            pagination: ElementListPagination {
+                next_offset,
+                has_more,
+            },
+        })
+    }
+
+    /// Lists recorded versions of an element, newest first.
+    pub async fn list_element_history(
+        pool: &PgPool,
+        board_id: Uuid,
+        element_id: Uuid,
+        user_id: Uuid,
+        query: ElementHistoryQuery,
+    ) -> Result<ElementHistoryResponse, AppError> {
+        BoardService::ensure_can_view(pool, board_id, user_id).await?;
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_ELEMENT_HISTORY_LIMIT)
+            .clamp(1, MAX_ELEMENT_HISTORY_LIMIT);
+        let offset = query.offset.unwrap_or(0);
+
+        let mut rows = element_repo::list_element_history(
+            pool,
+            board_id,
+            element_id,
+            limit as i64 + 1,
+            offset as i64,
+        )
+        .await?;
+        let has_more = rows.len() > limit as usize;
+        rows.truncate(limit as usize);
+        let next_offset = has_more.then(|| offset + limit);
+
+        Ok(ElementHistoryResponse {
+            data: rows.into_iter().map(history_to_entry).collect(),
+            pagination: ElementListPagination {
+                next_offset,
+                has_more,
+            },
+        })
+    }
+
+    /// Trims each element's history to its newest retained versions; returns
+    /// the rows removed.
+    pub async fn prune_element_history(pool: &PgPool) -> Result<u64, AppError> {
+        element_repo::prune_element_history(pool, ELEMENT_HISTORY_RETAINED_VERSIONS).await
+    }
+
+    /// Compares two recorded versions of an element field by field.
+    pub async fn diff_element_versions(
+        pool: &PgPool,
+        board_id: Uuid,
+        element_id: Uuid,
+        user_id: Uuid,
+        query: ElementDiffQuery,
+    ) -> Result<ElementDiffResponse, AppError> {
+        BoardService::ensure_can_view(pool, board_id, user_id).await?;
+        validate_expected_version(query.from)?;
+        validate_expected_version(query.to)?;
+
+        let from = load_history_version(pool, board_id, element_id, query.from).await?;
+        let to = load_history_version(pool, board_id, element_id, query.to).await?;
+
+        Ok(ElementDiffResponse {
+            element_id,
+            from_version: from.version,
+            to_version: to.version,
+            changes: diff_element_data(&from.element_data, &to.element_data),
+        })
+    }
+
+    /// Puts a single element back to the state recorded at `target_version`.
+    ///
+    /// The revert is applied as a regular CRDT update, so it gets a new version
+    /// and reaches connected clients like any other edit.
+    pub async fn revert_element(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        element_id: Uuid,
+        user_id: Uuid,
+        target_version: i32,
+    ) -> Result<BoardElementResponse, AppError> {
+        ensure_can_edit(pool, board_id, user_id).await?;
+        validate_expected_version(target_version)?;
+
+        let target = load_history_version(pool, board_id, element_id, target_version).await?;
+        let target: BoardElement =
+            serde_json::from_value(target.element_data).map_err(|error| {
+                AppError::Internal(format!("Invalid element history snapshot: {}", error))
+            })?;
+        if target.deleted_at.is_some() {
+            return Err(AppError::BadRequest(
+                "Cannot revert to a version where the element was deleted".to_string(),
+            ));
+        }
+
+        let current =
+            realtime_elements::load_element_materialized(rooms, pool, board_id, element_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Element not found".to_string()))?;
+        if current.deleted_at.is_some() {
+            return Err(AppError::Conflict(
+                "Restore the element before reverting it".to_string(),
+            ));
+        }
+        let current_version = require_field(current.version, "version")?;
+        if current_version == target_version {
+            return materialized_to_response(current);
+        }
+
+        let req = UpdateBoardElementRequest {
+            expected_version: current_version,
+            client_updated_at: None,
+            position_x: Some(target.position_x),
+            position_y: Some(target.position_y),
+            width: Some(target.width),
+            height: Some(target.height),
+            rotation: Some(target.rotation),
+            style: Some(revert_patch(&current.style, &target.style)),
+            properties: Some(revert_patch(&current.properties, &target.properties)),
+            metadata: Some(revert_patch(&current.metadata, &target.metadata)),
+        };
+        let applied = realtime_elements::apply_element_update(
+            rooms,
+            pool,
+            user_id,
+            board_id,
+            element_id,
+            &req,
+            UpdateContext {
+                updated_at: Utc::now(),
+                last_write_wins_fields: &[],
+            },
+        )
+        .await?
+        .ok_or_else(|| AppError::NotFound("Element not found".to_string()))?;
+
+        materialized_to_response(applied.element)
+    }
+
+    /// Converts an element to `element_type` in place, e.g. a sticky note to
+    /// text. Properties and default styles are migrated as a single CRDT
+    /// update; converting to the current type is a no-op.
+    pub async fn change_element_type(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        element_id: Uuid,
+        user_id: Uuid,
+        element_type: ElementType,
+    ) -> Result<BoardElementResponse, AppError> {
+        ensure_can_edit(pool, board_id, user_id).await?;
+
+        let current =
+            realtime_elements::load_element_materialized(rooms, pool, board_id, element_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Element not found".to_string()))?;
+        if current.deleted_at.is_some() {
+            return Err(AppError::Conflict(
+                "Restore the element before converting it".to_string(),
+            ));
+        }
+        if current.element_type == element_type {
+            return materialized_to_response(current);
+        }
+
+        let (style, properties) = element_conversion::convert(
+            current.element_type,
+            element_type,
+            &current.style,
+            &current.properties,
+        )?;
+        ensure_embeds_enabled(pool, board_id, element_type).await?;
+        validate_embed_properties(element_type, &properties)?;
+        let change = ElementTypeChange {
+            element_type,
+            style_patch: revert_patch(&current.style, &style),
+            properties_patch: revert_patch(&current.properties, &properties),
+        };
+        let applied = realtime_elements::apply_element_type_change(
+            rooms,
+            pool,
+            user_id,
+            board_id,
+            element_id,
+            &change,
+            Utc::now(),
+        )
+        .await?
+        .ok_or_else(|| AppError::NotFound("Element not found".to_string()))?;
+
+        materialized_to_response(applied.element)
+    }
+
     pub async fn create_element(
         pool: &PgPool,
         rooms: &Rooms,
+        content_filter: &dyn ContentFilter,
         board_id: Uuid,
         user_id: Uuid,
         req: CreateBoardElementRequest,
     ) -> Result<BoardElementResponse, AppError> {
         ensure_can_edit(pool, board_id, user_id).await?;
+        Self::create_element_authorized(pool, rooms, content_filter, board_id, user_id, req).await
+    }
+
+    /// Creates an element for a caller whose edit permission was already
+    /// verified, e.g. a WebSocket session checked against `edit_permissions`.
+    pub async fn create_element_authorized(
+        pool: &PgPool,
+        rooms: &Rooms,
+        content_filter: &dyn ContentFilter,
+        board_id: Uuid,
+        user_id: Uuid,
+        req: CreateBoardElementRequest,
+    ) -> Result<BoardElementResponse, AppError> {
         validate_rotation(req.rotation)?;
         validate_position(req.position_x, req.position_y)?;
+        if let Some(id) = req.id {
+            validate_client_element_id(id)?;
+            ensure_element_id_available(rooms, pool, board_id, id).await?;
+        }
 
-        let (position_x, width) = normalize_dimension(req.position_x, req.width);
-        let (position_y, height) = normalize_dimension(req.position_y, req.height);
+        let (mut position_x, width) = normalize_dimension(req.position_x, req.width);
+        let (mut position_y, height) = normalize_dimension(req.position_y, req.height);
         validate_dimensions(width, height)?;
+        if let Some(grid_size) = enforced_grid_size(&board_canvas_settings(pool, board_id).await?) {
+            position_x = snap_to_grid(position_x, grid_size);
+            position_y = snap_to_grid(position_y, grid_size);
+        }
 
         let z_index = realtime_elements::next_z_index(rooms, pool, board_id, req.layer_id).await?;
-        let style = req.style.unwrap_or_else(default_style);
-        let properties = req.properties.unwrap_or_else(default_properties);
+        let style = element_defaults::style_with_defaults(req.element_type, req.style);
+        let mut properties =
+            element_defaults::properties_with_defaults(req.element_type, req.properties);
+        filter_element_properties(content_filter, &mut properties)?;
+        ensure_embeds_enabled(pool, board_id, req.element_type).await?;
+        validate_embed_properties(req.element_type, &properties)?;
+        if req.element_type == ElementType::Connector && !req.force {
+            ensure_connector_targets_exist(rooms, pool, board_id, &properties).await?;
+        }
         let metadata = req.metadata.unwrap_or_else(default_metadata);
         let now = Utc::now();
 
@@ -69,13 +382,80 @@ impl ElementService {
         materialized_to_response(applied.element)
     }
 
+    /// Copies elements from another board onto `board_id` with fresh ids.
+    ///
+    /// Parent links inside the selection are remapped; links to elements left
+    /// behind are dropped, as are source layers, which belong to the source
+    /// board. Pasted elements keep their positions and relative
+    /// stacking order, placed above everything already on the target.
+    pub async fn paste_elements(
+        pool: &PgPool,
+        rooms: &Rooms,
+        content_filter: &dyn ContentFilter,
+        board_id: Uuid,
+        user_id: Uuid,
+        req: PasteBoardElementsRequest,
+    ) -> Result<PasteBoardElementsResponse, AppError> {
+        ensure_can_edit(pool, board_id, user_id).await?;
+        BoardService::ensure_can_view(pool, req.source_board_id, user_id).await?;
+
+        let selected: HashSet<Uuid> = req.element_ids.into_iter().collect();
+        if selected.is_empty() {
+            return Err(AppError::ValidationError(
+                "element_ids must not be empty".to_string(),
+            ));
+        }
+        if selected.len() > MAX_PASTE_ELEMENTS {
+            return Err(AppError::ValidationError(format!(
+                "Cannot paste more than {} elements at once",
+                MAX_PASTE_ELEMENTS
+            )));
+        }
+
+        let source: Vec<ElementMaterialized> =
+            realtime_elements::load_elements_materialized(rooms, pool, req.source_board_id)
+                .await?
+                .into_iter()
+                .filter(|element| element.deleted_at.is_none() && selected.contains(&element.id))
+                .collect();
+        if source.len() != selected.len() {
+            return Err(AppError::NotFound(
+                "One or more source elements were not found".to_string(),
+            ));
+        }
+
+        let base_z_index = realtime_elements::next_z_index(rooms, pool, board_id, None).await?;
+        let (mut snapshots, id_map) =
+            remap_pasted_elements(source, board_id, user_id, base_z_index, Utc::now());
+        for snapshot in &mut snapshots {
+            filter_element_properties(content_filter, &mut snapshot.properties)?;
+            validate_embed_properties(snapshot.element_type, &snapshot.properties)?;
+        }
+        if snapshots
+            .iter()
+            .any(|snapshot| snapshot.element_type == ElementType::Embed)
+        {
+            ensure_embeds_enabled(pool, board_id, ElementType::Embed).await?;
+        }
+
+        let applied =
+            realtime_elements::apply_element_snapshots(rooms, pool, user_id, board_id, &snapshots)
+                .await?;
+        let data = applied
+            .into_iter()
+            .map(materialized_to_response)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PasteBoardElementsResponse { data, id_map })
+    }
+
     pub async fn update_element(
         pool: &PgPool,
         rooms: &Rooms,
+        content_filter: &dyn ContentFilter,
         board_id: Uuid,
         element_id: Uuid,
         user_id: Uuid,
-        req: UpdateBoardElementRequest,
+        mut req: UpdateBoardElementRequest,
     ) -> Result<BoardElementResponse, AppError> {
         ensure_can_edit(pool, board_id, user_id).await?;
         validate_expected_version(req.expected_version)?;
@@ -84,10 +464,40 @@ impl ElementService {
         validate_optional_coordinate(req.position_y, "position_y")?;
         validate_optional_dimension(req.width, "width")?;
         validate_optional_dimension(req.height, "height")?;
+        if let Some(properties) = req.properties.as_mut() {
+            filter_element_properties(content_filter, properties)?;
+        }
+        if let Some(properties) = req.properties.as_ref()
+            && properties.get("url").is_some()
+            && let Some(current) =
+                realtime_elements::load_element_materialized(rooms, pool, board_id, element_id)
+                    .await?
+        {
+            ensure_embeds_enabled(pool, board_id, current.element_type).await?;
+            validate_embed_properties(current.element_type, properties)?;
+        }
+        let settings = if req.position_x.is_some()
+            || req.position_y.is_some()
+            || req.client_updated_at.is_some()
+        {
+            Some(board_canvas_settings(pool, board_id).await?)
+        } else {
+            None
+        };
+        if let Some(grid_size) = settings.as_ref().and_then(enforced_grid_size) {
+            req.position_x = req.position_x.map(|value| snap_to_grid(value, grid_size));
+            req.position_y = req.position_y.map(|value| snap_to_grid(value, grid_size));
+        }
+        let last_write_wins_fields = settings
+            .map(|settings| settings.last_write_wins_fields)
+            .unwrap_or_default();
 
-        let updated_at = Utc::now();
+        let context = UpdateContext {
+            updated_at: Utc::now(),
+            last_write_wins_fields: &last_write_wins_fields,
+        };
         let applied = realtime_elements::apply_element_update(
-            rooms, pool, user_id, board_id, element_id, &req, updated_at,
+            rooms, pool, user_id, board_id, element_id, &req, context,
         )
         .await?;
 
@@ -109,6 +519,21 @@ impl ElementService {
         ensure_can_edit(pool, board_id, user_id).await?;
         validate_expected_version(expected_version)?;
 
+        let board = board_repo::find_board_by_id(pool, board_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Board not found".to_string()))?;
+        if board.element_delete_policy == ElementDeletePolicy::Hard {
+            return hard_delete_element(
+                pool,
+                rooms,
+                board_id,
+                element_id,
+                user_id,
+                expected_version,
+            )
+            .await;
+        }
+
         let now = Utc::now();
         let result = realtime_elements::apply_element_deleted(
             rooms,
@@ -132,9 +557,203 @@ impl ElementService {
             deleted_at,
             updated_at,
             already_deleted: if result.was_deleted { Some(true) } else { None },
+            hard_deleted: None,
         })
     }
 
+    /// Deletes several elements in one doc update, following the board's
+    /// element delete policy, and reports each id as deleted, already deleted,
+    /// version conflict or not found.
+    pub async fn bulk_delete(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+        targets: Vec<BulkElementTarget>,
+    ) -> Result<BulkBoardElementsResponse, AppError> {
+        ensure_can_edit(pool, board_id, user_id).await?;
+        let targets = validate_bulk_targets(&targets)?;
+
+        let board = board_repo::find_board_by_id(pool, board_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Board not found".to_string()))?;
+        let now = Utc::now();
+        let results = if board.element_delete_policy == ElementDeletePolicy::Hard {
+            remove_elements(pool, rooms, board_id, user_id, &targets, now).await?
+        } else {
+            realtime_elements::apply_elements_deleted(
+                rooms,
+                pool,
+                user_id,
+                board_id,
+                &targets,
+                Some(now),
+                now,
+            )
+            .await?
+        };
+
+        let data = targets
+            .into_iter()
+            .zip(results)
+            .map(|((id, _), result)| bulk_element_result(id, result, false))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BulkBoardElementsResponse { data })
+    }
+
+    /// Copies the source element's `style` onto the targets (format painter),
+    /// leaving their other fields untouched.
+    pub async fn apply_style(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+        source_id: Uuid,
+        target_ids: Vec<Uuid>,
+    ) -> Result<ApplyStyleResponse, AppError> {
+        ensure_can_edit(pool, board_id, user_id).await?;
+        let target_ids = normalize_style_targets(source_id, target_ids)?;
+
+        let source = realtime_elements::load_element_materialized(rooms, pool, board_id, source_id)
+            .await?
+            .filter(|element| element.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Source element not found".to_string()))?;
+        if source.style.as_object().is_none_or(Map::is_empty) {
+            return Err(AppError::ValidationError(
+                "Source element has no style to apply".to_string(),
+            ));
+        }
+
+        let results = realtime_elements::apply_style_to_elements(
+            rooms,
+            pool,
+            user_id,
+            board_id,
+            &target_ids,
+            &source.style,
+        )
+        .await?;
+        let mut data = Vec::new();
+        let mut not_found = Vec::new();
+        for (id, element) in target_ids.into_iter().zip(results) {
+            match element {
+                Some(element) => data.push(materialized_to_response(element)?),
+                None => not_found.push(id),
+            }
+        }
+        Ok(ApplyStyleResponse { data, not_found })
+    }
+
+    /// Rewrites the board's z-indices to a dense sequence per layer, keeping
+    /// the stacking order.
+    pub async fn compact_z_indices(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<CompactZIndexResponse, AppError> {
+        ensure_can_edit(pool, board_id, user_id).await?;
+        let compacted =
+            realtime_elements::apply_z_index_compaction(rooms, pool, Some(user_id), board_id)
+                .await?;
+        Ok(CompactZIndexResponse {
+            board_id,
+            compacted,
+        })
+    }
+
+    /// Compacts boards whose highest z-index exceeds
+    /// `ELEMENT_Z_INDEX_COMPACTION_THRESHOLD`; returns the boards compacted.
+    pub async fn compact_inflated_z_indices(
+        pool: &PgPool,
+        rooms: &Rooms,
+    ) -> Result<usize, AppError> {
+        let threshold = z_index_compaction_threshold(
+            std::env::var(Z_INDEX_COMPACTION_THRESHOLD_ENV)
+                .ok()
+                .as_deref(),
+        );
+        let board_ids =
+            element_repo::list_boards_with_z_index_above(pool, threshold, Z_INDEX_COMPACTION_BATCH)
+                .await?;
+        let mut compacted = 0;
+        for board_id in board_ids {
+            match realtime_elements::apply_z_index_compaction(rooms, pool, None, board_id).await {
+                Ok(_) => compacted += 1,
+                Err(error) => {
+                    tracing::warn!(
+                        "Failed to compact z-indices on board {}: {}",
+                        board_id,
+                        error
+                    );
+                }
+            }
+        }
+        Ok(compacted)
+    }
+
+    /// Lists elements soft-deleted within the last `within_minutes`, newest
+    /// first, so a batch of accidental deletes can be found and restored.
+    pub async fn list_recently_deleted(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+        within_minutes: Option<u32>,
+    ) -> Result<RecentlyDeletedElementsResponse, AppError> {
+        ensure_can_edit(pool, board_id, user_id).await?;
+        let within = recently_deleted_window(within_minutes)?;
+
+        let elements = element_repo::list_recently_deleted_elements(
+            pool,
+            board_id,
+            Utc::now() - within,
+            MAX_BULK_ELEMENTS as i64,
+        )
+        .await?;
+        let data = elements
+            .into_iter()
+            .filter_map(|element| {
+                let deleted_at = element.deleted_at?;
+                Some(DeletedBoardElementResponse {
+                    element: element_to_response(element),
+                    deleted_at,
+                })
+            })
+            .collect();
+        Ok(RecentlyDeletedElementsResponse { data })
+    }
+
+    /// Restores several soft-deleted elements in one doc update, reporting
+    /// each id as restored, not deleted, version conflict or not found.
+    pub async fn bulk_restore(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+        targets: Vec<BulkElementTarget>,
+    ) -> Result<BulkBoardElementsResponse, AppError> {
+        ensure_can_edit(pool, board_id, user_id).await?;
+        let targets = validate_bulk_targets(&targets)?;
+
+        let results = realtime_elements::apply_elements_deleted(
+            rooms,
+            pool,
+            user_id,
+            board_id,
+            &targets,
+            None,
+            Utc::now(),
+        )
+        .await?;
+
+        let data = targets
+            .into_iter()
+            .zip(results)
+            .map(|((id, _), result)| bulk_element_result(id, result, true))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BulkBoardElementsResponse { data })
+    }
+
     pub async fn restore_element(
         pool: &PgPool,
         rooms: &Rooms,
@@ -212,9 +831,218 @@ fn materialized_to_response(
         metadata: element.metadata,
         created_at,
         updated_at,
+        creator: None,
     })
 }
 
+/// Builds target-board snapshots for pasted elements, returning them in
+/// stacking order together with the source-to-new id map.
+fn remap_pasted_elements(
+    mut source: Vec<ElementMaterialized>,
+    board_id: Uuid,
+    user_id: Uuid,
+    base_z_index: i32,
+    now: DateTime<Utc>,
+) -> (Vec<ElementSnapshot>, HashMap<Uuid, Uuid>) {
+    source.sort_by_key(|element| element.z_index);
+    let id_map: HashMap<Uuid, Uuid> = source
+        .iter()
+        .map(|element| (element.id, Uuid::now_v7()))
+        .collect();
+
+    let snapshots = source
+        .into_iter()
+        .zip(base_z_index..)
+        .map(|(element, z_index)| ElementSnapshot {
+            id: id_map[&element.id],
+            board_id,
+            layer_id: None,
+            parent_id: element
+                .parent_id
+                .and_then(|parent| id_map.get(&parent).copied()),
+            created_by: user_id,
+            element_type: element.element_type,
+            position_x: element.position_x,
+            position_y: element.position_y,
+            width: element.width,
+            height: element.height,
+            rotation: element.rotation,
+            z_index,
+            style: element.style,
+            properties: element.properties,
+            metadata: element.metadata,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            version: 1,
+        })
+        .collect();
+    (snapshots, id_map)
+}
+
+fn element_to_response(element: BoardElement) -> BoardElementResponse {
+    BoardElementResponse {
+        id: element.id,
+        board_id: element.board_id,
+        layer_id: element.layer_id,
+        parent_id: element.parent_id,
+        created_by: element.created_by,
+        element_type: element.element_type,
+        position_x: element.position_x,
+        position_y: element.position_y,
+        width: element.width,
+        height: element.height,
+        rotation: element.rotation,
+        z_index: element.z_index,
+        style: element.style,
+        properties: element.properties,
+        version: element.version,
+        metadata: element.metadata,
+        created_at: element.created_at,
+        updated_at: element.updated_at,
+        creator: None,
+    }
+}
+
+/// Fills `creator` with one batched profile lookup for the page.
+async fn attach_creators(
+    pool: &PgPool,
+    elements: &mut [BoardElementResponse],
+) -> Result<(), AppError> {
+    let creator_ids: Vec<Uuid> = elements
+        .iter()
+        .map(|element| element.created_by)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    if creator_ids.is_empty() {
+        return Ok(());
+    }
+    let profiles: HashMap<Uuid, PublicUserProfile> =
+        user_repo::find_active_users_by_ids(pool, &creator_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, PublicUserProfile::from(user)))
+            .collect();
+    for element in elements {
+        element.creator = profiles.get(&element.created_by).cloned();
+    }
+    Ok(())
+}
+
+fn history_to_entry(row: ElementHistoryRow) -> ElementHistoryEntry {
+    ElementHistoryEntry {
+        version: row.version,
+        operation: row.operation,
+        changed_by: row.changed_by,
+        changed_fields: row.changed_fields,
+        element: row.element_data,
+        created_at: row.created_at,
+    }
+}
+
+async fn load_history_version(
+    pool: &PgPool,
+    board_id: Uuid,
+    element_id: Uuid,
+    version: i32,
+) -> Result<ElementHistoryRow, AppError> {
+    element_repo::find_element_history_version(pool, board_id, element_id, version)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Element version {} not found", version)))
+}
+
+/// Builds an object patch that turns `current` into `target`.
+///
+/// CRDT updates merge object fields, so keys that only exist in `current` are
+/// sent as `null` to remove them.
+fn revert_patch(current: &Value, target: &Value) -> Value {
+    let mut patch = target.as_object().cloned().unwrap_or_default();
+    if let Some(current) = current.as_object() {
+        for key in current.keys() {
+            if !patch.contains_key(key) {
+                patch.insert(key.clone(), Value::Null);
+            }
+        }
+    }
+    Value::Object(patch)
+}
+
+/// Lists the fields that differ between two element snapshots, sorted by path.
+fn diff_element_data(from: &Value, to: &Value) -> Vec<ElementFieldChange> {
+    let empty = Map::new();
+    let from = from.as_object().unwrap_or(&empty);
+    let to = to.as_object().unwrap_or(&empty);
+    let mut changes = Vec::new();
+
+    let keys: BTreeSet<&String> = from.keys().chain(to.keys()).collect();
+    for key in keys {
+        if IGNORED_DIFF_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+        let before = from.get(key).unwrap_or(&Value::Null);
+        let after = to.get(key).unwrap_or(&Value::Null);
+        if before == after {
+            continue;
+        }
+        match (before, after) {
+            (Value::Object(before), Value::Object(after))
+                if NESTED_DIFF_FIELDS.contains(&key.as_str()) =>
+            {
+                let nested: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+                for nested_key in nested {
+                    let nested_before = before.get(nested_key).unwrap_or(&Value::Null);
+                    let nested_after = after.get(nested_key).unwrap_or(&Value::Null);
+                    if nested_before != nested_after {
+                        changes.push(ElementFieldChange {
+                            field: format!("{}.{}", key, nested_key),
+                            from: nested_before.clone(),
+                            to: nested_after.clone(),
+                        });
+                    }
+                }
+            }
+            _ => changes.push(ElementFieldChange {
+                field: key.clone(),
+                from: before.clone(),
+                to: after.clone(),
+            }),
+        }
+    }
+    changes
+}
+
+fn parse_bounds(query: &ListBoardElementsQuery) -> Result<Option<ElementBounds>, AppError> {
+    let (min_x, min_y, max_x, max_y) = match (query.min_x, query.min_y, query.max_x, query.max_y) {
+        (None, None, None, None) => return Ok(None),
+        (Some(min_x), Some(min_y), Some(max_x), Some(max_y)) => (min_x, min_y, max_x, max_y),
+        _ => {
+            return Err(AppError::ValidationError(
+                "Viewport bounds require min_x, min_y, max_x and max_y".to_string(),
+            ));
+        }
+    };
+    if ![min_x, min_y, max_x, max_y]
+        .iter()
+        .all(|value| value.is_finite())
+    {
+        return Err(AppError::ValidationError(
+            "Viewport bounds must be finite numbers".to_string(),
+        ));
+    }
+    if min_x > max_x || min_y > max_y {
+        return Err(AppError::ValidationError(
+            "Viewport minimum must not exceed maximum".to_string(),
+        ));
+    }
+    Ok(Some(ElementBounds {
+        min_x,
+        min_y,
+        max_x,
+        max_y,
+    }))
+}
+
 fn extract_delete_fields(
     element: &ElementMaterialized,
 ) -> Result<(i32, DateTime<Utc>, DateTime<Utc>), AppError> {
@@ -226,6 +1054,84 @@ fn extract_delete_fields(
     Ok((version, deleted_at, updated_at))
 }
 
+/// Validates a bulk delete or restore request and returns its element ids and
+/// expected versions in request order, keeping the first entry for each id.
+fn validate_bulk_targets(targets: &[BulkElementTarget]) -> Result<Vec<(Uuid, i32)>, AppError> {
+    if targets.is_empty() {
+        return Err(AppError::ValidationError(
+            "elements must not be empty".to_string(),
+        ));
+    }
+    if targets.len() > MAX_BULK_ELEMENTS {
+        return Err(AppError::ValidationError(format!(
+            "Cannot change more than {} elements at once",
+            MAX_BULK_ELEMENTS
+        )));
+    }
+    let mut seen = HashSet::with_capacity(targets.len());
+    let mut deduped = Vec::with_capacity(targets.len());
+    for target in targets {
+        validate_expected_version(target.expected_version)?;
+        if seen.insert(target.id) {
+            deduped.push((target.id, target.expected_version));
+        }
+    }
+    Ok(deduped)
+}
+
+/// Validates apply-style targets, dropping duplicates and the source itself.
+fn normalize_style_targets(source_id: Uuid, target_ids: Vec<Uuid>) -> Result<Vec<Uuid>, AppError> {
+    if target_ids.len() > MAX_BULK_ELEMENTS {
+        return Err(AppError::ValidationError(format!(
+            "Cannot change more than {} elements at once",
+            MAX_BULK_ELEMENTS
+        )));
+    }
+    let mut seen = HashSet::from([source_id]);
+    let target_ids: Vec<Uuid> = target_ids
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect();
+    if target_ids.is_empty() {
+        return Err(AppError::ValidationError(
+            "target_ids must include an element other than the source".to_string(),
+        ));
+    }
+    Ok(target_ids)
+}
+
+/// Maps a batch outcome to its per-id status; `restore` selects whether the
+/// batch deleted or restored elements.
+fn bulk_element_result(
+    id: Uuid,
+    result: Option<BatchDeleted>,
+    restore: bool,
+) -> Result<BulkElementResult, AppError> {
+    let Some(result) = result else {
+        return Ok(BulkElementResult {
+            id,
+            status: BulkElementStatus::NotFound,
+            version: None,
+            deleted_at: None,
+            updated_at: None,
+        });
+    };
+    let status = match (restore, result.was_deleted) {
+        _ if result.version_conflict => BulkElementStatus::VersionConflict,
+        (false, false) => BulkElementStatus::Deleted,
+        (false, true) => BulkElementStatus::AlreadyDeleted,
+        (true, true) => BulkElementStatus::Restored,
+        (true, false) => BulkElementStatus::NotDeleted,
+    };
+    Ok(BulkElementResult {
+        id,
+        status,
+        version: Some(require_field(result.element.version, "version")?),
+        deleted_at: result.element.deleted_at,
+        updated_at: Some(require_field(result.element.updated_at, "updated_at")?),
+    })
+}
+
 fn require_field<T>(value: Option<T>, label: &str) -> Result<T, AppError> {
     value.ok_or_else(|| AppError::Internal(format!("Missing element {}", label)))
 }
@@ -240,6 +1146,24 @@ async fn ensure_can_edit(pool: &PgPool, board_id: Uuid, user_id: Uuid) -> Result
     Ok(())
 }
 
+fn z_index_compaction_threshold(value: Option<&str>) -> i32 {
+    value
+        .and_then(|value| value.trim().parse::<i32>().ok())
+        .filter(|threshold| *threshold > 0)
+        .unwrap_or(DEFAULT_Z_INDEX_COMPACTION_THRESHOLD)
+}
+
+fn recently_deleted_window(within_minutes: Option<u32>) -> Result<chrono::Duration, AppError> {
+    let minutes = within_minutes.unwrap_or(DEFAULT_RECENTLY_DELETED_MINUTES);
+    if minutes == 0 || minutes > MAX_RECENTLY_DELETED_MINUTES {
+        return Err(AppError::ValidationError(format!(
+            "within_minutes must be between 1 and {}",
+            MAX_RECENTLY_DELETED_MINUTES
+        )));
+    }
+    Ok(chrono::Duration::minutes(i64::from(minutes)))
+}
+
 fn validate_dimensions(width: f64, height: f64) -> Result<(), AppError> {
     if !width.is_finite() || !height.is_finite() {
         return Err(AppError::ValidationError(
@@ -263,6 +1187,81 @@ fn validate_expected_version(version: i32) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Element ids a connector is bound to via `properties.bindings.{start,end}`.
+fn connector_binding_ids(properties: &Value) -> Result<Vec<Uuid>, AppError> {
+    let Some(bindings) = properties.get("bindings").filter(|value| !value.is_null()) else {
+        return Ok(Vec::new());
+    };
+    let mut ids = Vec::new();
+    for end in ["start", "end"] {
+        let Some(element_id) = bindings
+            .get(end)
+            .and_then(|binding| binding.get("elementId"))
+            .filter(|value| !value.is_null())
+        else {
+            continue;
+        };
+        let id = element_id
+            .as_str()
+            .and_then(|value| Uuid::parse_str(value).ok())
+            .ok_or_else(|| {
+                AppError::ValidationError(format!(
+                    "Connector {} binding must reference an element id",
+                    end
+                ))
+            })?;
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+async fn ensure_connector_targets_exist(
+    rooms: &Rooms,
+    pool: &PgPool,
+    board_id: Uuid,
+    properties: &Value,
+) -> Result<(), AppError> {
+    for element_id in connector_binding_ids(properties)? {
+        let target =
+            realtime_elements::load_element_materialized(rooms, pool, board_id, element_id).await?;
+        if target.is_none_or(|target| target.deleted_at.is_some()) {
+            return Err(AppError::ValidationError(format!(
+                "Connector references missing element {}",
+                element_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Client-generated ids must be UUIDv7 so they sort like server-issued ones.
+fn validate_client_element_id(id: Uuid) -> Result<(), AppError> {
+    if id.get_version_num() != 7 {
+        return Err(AppError::ValidationError(
+            "Element id must be a UUIDv7".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn ensure_element_id_available(
+    rooms: &Rooms,
+    pool: &PgPool,
+    board_id: Uuid,
+    id: Uuid,
+) -> Result<(), AppError> {
+    let in_board = realtime_elements::load_element_materialized(rooms, pool, board_id, id)
+        .await?
+        .is_some();
+    if in_board || element_repo::element_id_exists(pool, id).await? {
+        return Err(AppError::Conflict(format!(
+            "Element id {} is already in use",
+            id
+        )));
+    }
+    Ok(())
+}
+
 fn validate_rotation(rotation: Option<f64>) -> Result<(), AppError> {
     let Some(value) = rotation else {
         return Ok(());
@@ -329,19 +1328,101 @@ fn normalize_dimension(origin: f64, size: f64) -> (f64, f64) {
     }
 }
 
-fn default_style() -> serde_json::Value {
-    serde_json::json!({
-        "fill": "#ffffff",
-        "stroke": "#000000",
-        "strokeWidth": 1,
-        "opacity": 1,
-        "cornerRadius": 0,
-        "shadow": null
+/// Removes an element from the doc and deletes its row and history, for
+/// boards with the `hard` delete policy.
+async fn hard_delete_element(
+    pool: &PgPool,
+    rooms: &Rooms,
+    board_id: Uuid,
+    element_id: Uuid,
+    user_id: Uuid,
+    expected_version: i32,
+) -> Result<DeleteBoardElementResponse, AppError> {
+    let now = Utc::now();
+    let removed = remove_elements(
+        pool,
+        rooms,
+        board_id,
+        user_id,
+        &[(element_id, expected_version)],
+        now,
+    )
+    .await?
+    .pop()
+    .flatten()
+    .ok_or_else(|| AppError::NotFound("Element not found".to_string()))?;
+
+    let version = require_field(removed.element.version, "version")?;
+    if removed.version_conflict {
+        return Err(AppError::Conflict(format!(
+            "Element is at version {}, expected {}",
+            version, expected_version
+        )));
+    }
+    Ok(DeleteBoardElementResponse {
+        id: removed.element.id,
+        version,
+        deleted_at: now,
+        updated_at: now,
+        already_deleted: removed.was_deleted.then_some(true),
+        hard_deleted: Some(true),
     })
 }
 
-fn default_properties() -> serde_json::Value {
-    serde_json::json!({})
+/// Removes elements from the board doc and deletes their rows and history,
+/// skipping elements whose version differs from the expected one.
+async fn remove_elements(
+    pool: &PgPool,
+    rooms: &Rooms,
+    board_id: Uuid,
+    user_id: Uuid,
+    targets: &[(Uuid, i32)],
+    removed_at: DateTime<Utc>,
+) -> Result<Vec<Option<BatchDeleted>>, AppError> {
+    let results = realtime_elements::apply_elements_removed(
+        rooms, pool, user_id, board_id, targets, removed_at,
+    )
+    .await?;
+    let removed_ids: Vec<Uuid> = results
+        .iter()
+        .flatten()
+        .filter(|result| !result.version_conflict)
+        .map(|result| result.element.id)
+        .collect();
+    if !removed_ids.is_empty() {
+        let mut tx = pool.begin().await?;
+        element_repo::hard_delete_elements(&mut tx, board_id, &removed_ids).await?;
+        tx.commit().await?;
+    }
+    Ok(results)
+}
+
+async fn board_canvas_settings(pool: &PgPool, board_id: Uuid) -> Result<CanvasSettings, AppError> {
+    let board = board_repo::find_board_by_id(pool, board_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Board not found".to_string()))?;
+    Ok(board.canvas_settings)
+}
+
+async fn ensure_embeds_enabled(
+    pool: &PgPool,
+    board_id: Uuid,
+    element_type: ElementType,
+) -> Result<(), AppError> {
+    if element_type == ElementType::Embed {
+        BoardService::ensure_feature_enabled(pool, board_id, BoardFeature::Embeds).await?;
+    }
+    Ok(())
+}
+
+/// Grid size to snap element positions to, when the board enforces it.
+fn enforced_grid_size(settings: &CanvasSettings) -> Option<f64> {
+    (settings.enforce_snap_to_grid && settings.grid_size > 0)
+        .then_some(f64::from(settings.grid_size))
+}
+
+fn snap_to_grid(value: f64, grid_size: f64) -> f64 {
+    (value / grid_size).round() * grid_size
 }
 
 fn default_metadata() -> serde_json::Value {
@@ -350,7 +1431,23 @@ fn default_metadata() -> serde_json::Value {
 
 #[cfg(test)]
 mod tests {
-    use super::{validate_dimensions, validate_position, validate_rotation};
+    use serde_json::json;
+
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{
+        connector_binding_ids, diff_element_data, normalize_style_targets, parse_bounds,
+        recently_deleted_window, remap_pasted_elements, revert_patch, snap_to_grid,
+        validate_bulk_targets, validate_client_element_id, validate_dimensions, validate_position,
+        validate_rotation,
+    };
+    use crate::{
+        dto::elements::{BulkElementTarget, ListBoardElementsQuery},
+        models::elements::ElementType,
+        realtime::element_crdt::ElementMaterialized,
+        repositories::elements::ElementBounds,
+    };
 
     #[test]
     fn validate_dimensions_rejects_non_positive() {
@@ -367,6 +1464,29 @@ mod tests {
         assert!(validate_rotation(None).is_ok());
     }
 
+    #[test]
+    fn connector_binding_ids_reads_bound_ends() {
+        let start = Uuid::now_v7();
+        let properties = json!({
+            "start": { "x": 0, "y": 0 },
+            "bindings": { "start": { "elementId": start.to_string(), "side": "auto" } },
+        });
+
+        assert_eq!(connector_binding_ids(&properties).unwrap(), vec![start]);
+        assert!(connector_binding_ids(&json!({})).unwrap().is_empty());
+        assert!(
+            connector_binding_ids(&json!({ "bindings": { "end": { "elementId": "nope" } } }))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn client_element_ids_must_be_uuid_v7() {
+        assert!(validate_client_element_id(Uuid::now_v7()).is_ok());
+        assert!(validate_client_element_id(Uuid::new_v4()).is_err());
+        assert!(validate_client_element_id(Uuid::nil()).is_err());
+    }
+
     #[test]
     fn validate_position_rejects_non_finite() {
         assert!(validate_position(f64::NAN, 0.0).is_err());
@@ -374,10 +1494,211 @@ mod tests {
         assert!(validate_position(1.0, 2.0).is_ok());
     }
 
+    #[test]
+    fn parse_bounds_requires_complete_ordered_viewport() {
+        assert_eq!(
+            parse_bounds(&ListBoardElementsQuery::default()).unwrap(),
+            None
+        );
+
+        let partial = ListBoardElementsQuery {
+            min_x: Some(0.0),
+            ..Default::default()
+        };
+        assert!(parse_bounds(&partial).is_err());
+
+        let inverted = ListBoardElementsQuery {
+            min_x: Some(10.0),
+            min_y: Some(0.0),
+            max_x: Some(5.0),
+            max_y: Some(10.0),
+            ..Default::default()
+        };
+        assert!(parse_bounds(&inverted).is_err());
+
+        let viewport = ListBoardElementsQuery {
+            min_x: Some(-100.0),
+            min_y: Some(-50.0),
+            max_x: Some(100.0),
+            max_y: Some(50.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_bounds(&viewport).unwrap(),
+            Some(ElementBounds {
+                min_x: -100.0,
+                min_y: -50.0,
+                max_x: 100.0,
+                max_y: 50.0,
+            })
+        );
+    }
+
     #[test]
     fn normalize_dimension_flips_negative_sizes() {
         let (position, size) = super::normalize_dimension(10.0, -5.0);
         assert_eq!(position, 5.0);
         assert_eq!(size, 5.0);
     }
+
+    #[test]
+    fn snap_to_grid_rounds_to_nearest_multiple() {
+        assert_eq!(snap_to_grid(31.0, 20.0), 40.0);
+        assert_eq!(snap_to_grid(29.9, 20.0), 20.0);
+        assert_eq!(snap_to_grid(-11.0, 20.0), -20.0);
+        assert_eq!(snap_to_grid(0.0, 20.0), 0.0);
+    }
+
+    #[test]
+    fn diff_element_data_reports_nested_and_top_level_changes() {
+        let from = json!({
+            "position_x": 10.0,
+            "version": 3,
+            "updated_at": "2026-01-01T00:00:00Z",
+            "style": { "fill": "#fff", "stroke": "#000" },
+            "properties": { "content": "hi" },
+            "deleted_at": null
+        });
+        let to = json!({
+            "position_x": 25.0,
+            "version": 4,
+            "updated_at": "2026-01-02T00:00:00Z",
+            "style": { "fill": "#f00", "stroke": "#000", "opacity": 0.5 },
+            "properties": { "content": "hi" },
+            "deleted_at": "2026-01-02T00:00:00Z"
+        });
+
+        let changes = diff_element_data(&from, &to);
+        let fields: Vec<&str> = changes.iter().map(|change| change.field.as_str()).collect();
+
+        assert_eq!(
+            fields,
+            vec!["deleted_at", "position_x", "style.fill", "style.opacity"]
+        );
+        assert_eq!(changes[2].from, json!("#fff"));
+        assert_eq!(changes[3].from, json!(null));
+        assert_eq!(changes[3].to, json!(0.5));
+    }
+
+    #[test]
+    fn diff_element_data_is_empty_for_identical_versions() {
+        let data = json!({ "width": 5.0, "style": { "fill": "#fff" } });
+        assert!(diff_element_data(&data, &data).is_empty());
+    }
+
+    #[test]
+    fn revert_patch_clears_keys_added_after_target() {
+        let current = json!({ "fill": "#f00", "opacity": 0.5 });
+        let target = json!({ "fill": "#fff" });
+
+        assert_eq!(
+            revert_patch(&current, &target),
+            json!({ "fill": "#fff", "opacity": null })
+        );
+    }
+
+    fn source_element(z_index: i32, parent_id: Option<Uuid>) -> ElementMaterialized {
+        ElementMaterialized {
+            id: Uuid::now_v7(),
+            board_id: Uuid::now_v7(),
+            layer_id: Some(Uuid::now_v7()),
+            parent_id,
+            created_by: Some(Uuid::now_v7()),
+            element_type: ElementType::Shape,
+            position_x: 10.0,
+            position_y: 20.0,
+            width: 100.0,
+            height: 50.0,
+            rotation: 0.0,
+            z_index,
+            style: json!({}),
+            properties: json!({}),
+            metadata: json!({}),
+            created_at: Some(Utc::now()),
+            updated_at: None,
+            deleted_at: None,
+            version: Some(7),
+        }
+    }
+
+    #[test]
+    fn remap_pasted_elements_assigns_new_ids_and_remaps_parents() {
+        let frame = source_element(3, None);
+        let child = source_element(9, Some(frame.id));
+        let orphan = source_element(1, Some(Uuid::now_v7()));
+        let target_board = Uuid::now_v7();
+        let user_id = Uuid::now_v7();
+
+        let (snapshots, id_map) = remap_pasted_elements(
+            vec![child.clone(), frame.clone(), orphan.clone()],
+            target_board,
+            user_id,
+            40,
+            Utc::now(),
+        );
+
+        let ids: Vec<Uuid> = snapshots.iter().map(|snapshot| snapshot.id).collect();
+        assert_eq!(
+            ids,
+            vec![id_map[&orphan.id], id_map[&frame.id], id_map[&child.id]]
+        );
+        assert!(!id_map.contains_key(&target_board));
+        assert!(ids.iter().all(|id| !id_map.contains_key(id)));
+        assert_eq!(
+            snapshots.iter().map(|s| s.z_index).collect::<Vec<_>>(),
+            vec![40, 41, 42]
+        );
+        assert_eq!(snapshots[0].parent_id, None);
+        assert_eq!(snapshots[2].parent_id, Some(id_map[&frame.id]));
+        assert!(snapshots.iter().all(|snapshot| {
+            snapshot.board_id == target_board
+                && snapshot.layer_id.is_none()
+                && snapshot.created_by == user_id
+                && snapshot.version == 1
+        }));
+    }
+
+    #[test]
+    fn bulk_delete_targets_are_deduplicated_in_order() {
+        let first = Uuid::now_v7();
+        let second = Uuid::now_v7();
+        let target = |id, expected_version| BulkElementTarget {
+            id,
+            expected_version,
+        };
+
+        let ids = validate_bulk_targets(&[target(second, 2), target(first, 1), target(second, 3)])
+            .unwrap();
+
+        assert_eq!(ids, vec![(second, 2), (first, 1)]);
+        assert!(validate_bulk_targets(&[]).is_err());
+        assert!(validate_bulk_targets(&[target(first, 0)]).is_err());
+    }
+
+    #[test]
+    fn recently_deleted_window_defaults_and_caps_at_one_day() {
+        assert_eq!(
+            recently_deleted_window(None).unwrap(),
+            chrono::Duration::minutes(30)
+        );
+        assert_eq!(
+            recently_deleted_window(Some(1440)).unwrap(),
+            chrono::Duration::days(1)
+        );
+        assert!(recently_deleted_window(Some(0)).is_err());
+        assert!(recently_deleted_window(Some(1441)).is_err());
+    }
+
+    #[test]
+    fn style_targets_drop_source_and_duplicates() {
+        let source = Uuid::now_v7();
+        let target = Uuid::now_v7();
+
+        assert_eq!(
+            normalize_style_targets(source, vec![target, source, target]).unwrap(),
+            vec![target]
+        );
+        assert!(normalize_style_targets(source, vec![source]).is_err());
+        assert!(normalize_style_targets(source, Vec::new()).is_err());
+    }
 }
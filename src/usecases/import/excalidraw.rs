@@ -0,0 +1,308 @@
+//! Maps Excalidraw scene JSON (`.excalidraw` files) to board elements.
+//!
+//! Rectangles, ellipses, text and arrows are converted; every other shape is
+//! reported as skipped so the rest of the scene still imports. Element ids
+//! are freshly generated and arrow bindings are rewritten to them.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    dto::boards::SkippedImportElement, error::AppError, models::elements::ElementType,
+    realtime::element_crdt::ElementMaterialized,
+};
+
+/// Smallest width/height given to imported elements, e.g. straight arrows.
+const MIN_DIMENSION: f64 = 1.0;
+
+pub struct ExcalidrawImport {
+    pub elements: Vec<ElementMaterialized>,
+    pub skipped: Vec<SkippedImportElement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExcalidrawScene {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    elements: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExcalidrawElement {
+    #[serde(default)]
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    x: f64,
+    y: f64,
+    #[serde(default)]
+    width: f64,
+    #[serde(default)]
+    height: f64,
+    #[serde(default)]
+    angle: f64,
+    stroke_color: Option<String>,
+    background_color: Option<String>,
+    stroke_width: Option<f64>,
+    #[serde(default)]
+    is_deleted: bool,
+    text: Option<String>,
+    font_size: Option<f64>,
+    #[serde(default)]
+    points: Vec<[f64; 2]>,
+    start_binding: Option<ExcalidrawBinding>,
+    end_binding: Option<ExcalidrawBinding>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExcalidrawBinding {
+    element_id: String,
+}
+
+/// Converts an Excalidraw scene. Fails only when the file itself is not an
+/// Excalidraw scene; individual elements that cannot be mapped are skipped.
+pub fn convert(data: &[u8]) -> Result<ExcalidrawImport, AppError> {
+    let scene: ExcalidrawScene = serde_json::from_slice(data)
+        .map_err(|error| AppError::BadRequest(format!("Invalid Excalidraw file: {}", error)))?;
+    if scene
+        .kind
+        .as_deref()
+        .is_some_and(|kind| kind != "excalidraw")
+    {
+        return Err(AppError::BadRequest(
+            "Invalid Excalidraw file: unexpected scene type".to_string(),
+        ));
+    }
+
+    let mut elements = Vec::new();
+    let mut skipped = Vec::new();
+    let mut id_map: HashMap<String, Uuid> = HashMap::new();
+    let mut arrow_bindings = Vec::new();
+
+    for raw in scene.elements {
+        let raw_id = raw.get("id").and_then(Value::as_str).unwrap_or_default();
+        let raw_type = raw.get("type").and_then(Value::as_str).unwrap_or_default();
+        let element = match serde_json::from_value::<ExcalidrawElement>(raw.clone()) {
+            Ok(element) => element,
+            Err(_) => {
+                skipped.push(skip(raw_id, raw_type, "Malformed element"));
+                continue;
+            }
+        };
+        if element.is_deleted {
+            continue;
+        }
+        let z_index = elements.len() as i32;
+        let Some(converted) = convert_element(&element, z_index) else {
+            skipped.push(skip(&element.id, &element.kind, "Unsupported element type"));
+            continue;
+        };
+        if !has_valid_geometry(&converted) {
+            skipped.push(skip(&element.id, &element.kind, "Invalid geometry"));
+            continue;
+        }
+        if element.kind == "arrow" {
+            arrow_bindings.push((
+                elements.len(),
+                element.start_binding.map(|binding| binding.element_id),
+                element.end_binding.map(|binding| binding.element_id),
+            ));
+        }
+        id_map.insert(element.id, converted.id);
+        elements.push(converted);
+    }
+
+    for (index, start, end) in arrow_bindings {
+        let mut bindings = serde_json::Map::new();
+        for (side, target) in [("start", start), ("end", end)] {
+            if let Some(target_id) = target.and_then(|target| id_map.get(&target)) {
+                bindings.insert(
+                    side.to_string(),
+                    json!({ "elementId": target_id.to_string(), "side": "auto" }),
+                );
+            }
+        }
+        if !bindings.is_empty() {
+            elements[index].properties["bindings"] = Value::Object(bindings);
+        }
+    }
+
+    Ok(ExcalidrawImport { elements, skipped })
+}
+
+fn convert_element(element: &ExcalidrawElement, z_index: i32) -> Option<ElementMaterialized> {
+    let stroke = element.stroke_color.as_deref().unwrap_or("#1e1e1e");
+    let (element_type, position_x, position_y, width, height, style, properties) =
+        match element.kind.as_str() {
+            "rectangle" | "ellipse" => (
+                ElementType::Shape,
+                element.x,
+                element.y,
+                element.width,
+                element.height,
+                json!({
+                    "stroke": stroke,
+                    "strokeWidth": element.stroke_width.unwrap_or(2.0),
+                    "fill": element.background_color.as_deref().unwrap_or("transparent"),
+                }),
+                json!({
+                    "shapeType": if element.kind == "ellipse" { "circle" } else { "rectangle" },
+                }),
+            ),
+            "text" => (
+                ElementType::Text,
+                element.x,
+                element.y,
+                element.width.max(MIN_DIMENSION),
+                element.height.max(MIN_DIMENSION),
+                json!({ "fontSize": element.font_size.unwrap_or(20.0), "fill": stroke }),
+                json!({ "content": element.text.clone().unwrap_or_default() }),
+            ),
+            "arrow" => {
+                let first = element.points.first()?;
+                let last = element.points.last()?;
+                let start = (element.x + first[0], element.y + first[1]);
+                let end = (element.x + last[0], element.y + last[1]);
+                (
+                    ElementType::Connector,
+                    start.0.min(end.0),
+                    start.1.min(end.1),
+                    (end.0 - start.0).abs().max(MIN_DIMENSION),
+                    (end.1 - start.1).abs().max(MIN_DIMENSION),
+                    json!({
+                        "stroke": stroke,
+                        "strokeWidth": element.stroke_width.unwrap_or(2.0),
+                    }),
+                    json!({
+                        "start": { "x": start.0, "y": start.1 },
+                        "end": { "x": end.0, "y": end.1 },
+                    }),
+                )
+            }
+            _ => return None,
+        };
+
+    Some(ElementMaterialized {
+        id: Uuid::now_v7(),
+        board_id: Uuid::nil(),
+        layer_id: None,
+        parent_id: None,
+        created_by: None,
+        element_type,
+        position_x,
+        position_y,
+        width,
+        height,
+        rotation: element.angle.to_degrees().rem_euclid(360.0),
+        z_index,
+        style,
+        properties,
+        metadata: json!({ "importedFrom": "excalidraw" }),
+        created_at: None,
+        updated_at: None,
+        deleted_at: None,
+        version: None,
+    })
+}
+
+fn has_valid_geometry(element: &ElementMaterialized) -> bool {
+    [
+        element.position_x,
+        element.position_y,
+        element.width,
+        element.height,
+        element.rotation,
+    ]
+    .iter()
+    .all(|value| value.is_finite())
+        && element.width > 0.0
+        && element.height > 0.0
+}
+
+fn skip(id: &str, element_type: &str, reason: &str) -> SkippedImportElement {
+    SkippedImportElement {
+        id: id.to_string(),
+        element_type: element_type.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::convert;
+    use crate::models::elements::ElementType;
+
+    #[test]
+    fn converts_supported_shapes_and_reports_the_rest() {
+        let scene = json!({
+            "type": "excalidraw",
+            "elements": [
+                { "id": "r", "type": "rectangle", "x": 10, "y": 20, "width": 100, "height": 50,
+                  "angle": std::f64::consts::PI, "backgroundColor": "#ffc9c9" },
+                { "id": "e", "type": "ellipse", "x": 200, "y": 20, "width": 40, "height": 40 },
+                { "id": "t", "type": "text", "x": 0, "y": 0, "width": 30, "height": 20,
+                  "text": "Hello", "fontSize": 28 },
+                { "id": "a", "type": "arrow", "x": 110, "y": 45, "width": 90, "height": 0,
+                  "points": [[0, 0], [90, 0]],
+                  "startBinding": { "elementId": "r" }, "endBinding": { "elementId": "gone" } },
+                { "id": "d", "type": "diamond", "x": 0, "y": 0, "width": 10, "height": 10 },
+                { "id": "x", "type": "rectangle", "x": 0, "y": 0, "width": 10, "height": 10,
+                  "isDeleted": true },
+                { "id": "m", "type": "rectangle" }
+            ]
+        });
+
+        let import = convert(scene.to_string().as_bytes()).unwrap();
+
+        let types: Vec<ElementType> = import.elements.iter().map(|e| e.element_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                ElementType::Shape,
+                ElementType::Shape,
+                ElementType::Text,
+                ElementType::Connector
+            ]
+        );
+        let rectangle = &import.elements[0];
+        assert_eq!(rectangle.rotation, 180.0);
+        assert_eq!(rectangle.style["fill"], "#ffc9c9");
+        assert_eq!(import.elements[1].properties["shapeType"], "circle");
+        assert_eq!(import.elements[2].properties["content"], "Hello");
+
+        let arrow = &import.elements[3];
+        assert_eq!(arrow.height, 1.0);
+        assert_eq!(arrow.properties["end"], json!({ "x": 200.0, "y": 45.0 }));
+        assert_eq!(
+            arrow.properties["bindings"],
+            json!({ "start": { "elementId": rectangle.id.to_string(), "side": "auto" } })
+        );
+
+        let skipped: Vec<(&str, &str)> = import
+            .skipped
+            .iter()
+            .map(|skipped| (skipped.id.as_str(), skipped.reason.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                ("d", "Unsupported element type"),
+                ("m", "Malformed element")
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_excalidraw_scenes() {
+        assert!(convert(b"not json").is_err());
+        assert!(convert(br#"{ "type": "tldraw", "elements": [] }"#).is_err());
+    }
+}
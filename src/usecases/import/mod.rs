@@ -0,0 +1,3 @@
+//! Converters from other whiteboard tools' export formats into board elements.
+
+pub(crate) mod excalidraw;
@@ -1,7 +1,15 @@
 pub(crate) mod auth;
+pub(crate) mod board_watchers;
 pub(crate) mod boards;
 pub(crate) mod comments;
+pub(crate) mod digests;
+pub(crate) mod element_conversion;
+pub(crate) mod element_defaults;
 pub(crate) mod elements;
+pub(crate) mod import;
 pub(crate) mod invites;
 pub(crate) mod organizations;
 pub(crate) mod presence;
+pub(crate) mod reactions;
+pub(crate) mod retry;
+pub(crate) mod voting;
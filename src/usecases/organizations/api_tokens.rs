@@ -0,0 +1,172 @@
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::api_tokens::{display_prefix, generate_api_token, hash_api_token},
+    dto::organizations::{
+        ApiTokenResponse, ApiTokensResponse, CreateApiTokenRequest, CreatedApiTokenResponse,
+        OrganizationActionMessage,
+    },
+    error::AppError,
+    models::organizations::{ApiTokenScope, OrgRole},
+    repositories::{
+        api_tokens::{self as api_token_repo, ApiTokenRow, NewApiToken},
+        boards as board_repo,
+    },
+    telemetry::BusinessEvent,
+};
+
+use super::{OrganizationService, helpers::require_member_role};
+
+const MAX_API_TOKEN_NAME_LENGTH: usize = 100;
+const MAX_API_TOKEN_EXPIRY_DAYS: u32 = 365;
+
+impl OrganizationService {
+    /// Creates an API token acting as the requesting owner/admin, limited to
+    /// `scopes` and optionally one board. The secret is only returned here.
+    pub async fn create_api_token(
+        pool: &PgPool,
+        organization_id: Uuid,
+        user_id: Uuid,
+        req: CreateApiTokenRequest,
+    ) -> Result<CreatedApiTokenResponse, AppError> {
+        ensure_token_manager(require_member_role(pool, organization_id, user_id).await?)?;
+
+        let name = req.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_API_TOKEN_NAME_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Token name must be 1-{} characters",
+                MAX_API_TOKEN_NAME_LENGTH
+            )));
+        }
+        let scopes = normalize_scopes(req.scopes)?;
+        let expires_at = match req.expires_in_days {
+            Some(days) if days == 0 || days > MAX_API_TOKEN_EXPIRY_DAYS => {
+                return Err(AppError::ValidationError(format!(
+                    "expires_in_days must be between 1 and {}",
+                    MAX_API_TOKEN_EXPIRY_DAYS
+                )));
+            }
+            Some(days) => Some(Utc::now() + Duration::days(i64::from(days))),
+            None => None,
+        };
+        if let Some(board_id) = req.board_id {
+            let board_organization_id =
+                match board_repo::load_board_organization_id(pool, board_id).await {
+                    Ok(organization_id) => organization_id,
+                    Err(AppError::NotFound(_)) => None,
+                    Err(error) => return Err(error),
+                };
+            if board_organization_id != Some(organization_id) {
+                return Err(AppError::ValidationError(
+                    "board_id must be an active board of this organization".to_string(),
+                ));
+            }
+        }
+
+        let token = generate_api_token();
+        let row = api_token_repo::insert_api_token(
+            pool,
+            NewApiToken {
+                organization_id,
+                board_id: req.board_id,
+                created_by: user_id,
+                name,
+                token_hash: &hash_api_token(&token),
+                token_prefix: &display_prefix(&token),
+                scopes: scopes
+                    .iter()
+                    .map(|scope| scope.as_str().to_string())
+                    .collect(),
+                expires_at,
+            },
+        )
+        .await?;
+        BusinessEvent::ApiTokenCreated {
+            org_id: organization_id,
+            token_id: row.id,
+            created_by: user_id,
+        }
+        .log();
+
+        Ok(CreatedApiTokenResponse {
+            api_token: api_token_response(row),
+            token,
+        })
+    }
+
+    /// Lists the organization's active API tokens (owners/admins only).
+    pub async fn list_api_tokens(
+        pool: &PgPool,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<ApiTokensResponse, AppError> {
+        ensure_token_manager(require_member_role(pool, organization_id, user_id).await?)?;
+        let rows = api_token_repo::list_active_api_tokens(pool, organization_id).await?;
+        Ok(ApiTokensResponse {
+            data: rows.into_iter().map(api_token_response).collect(),
+        })
+    }
+
+    /// Revokes an API token (owners/admins only).
+    pub async fn revoke_api_token(
+        pool: &PgPool,
+        organization_id: Uuid,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<OrganizationActionMessage, AppError> {
+        ensure_token_manager(require_member_role(pool, organization_id, user_id).await?)?;
+        if !api_token_repo::revoke_api_token(pool, organization_id, token_id).await? {
+            return Err(AppError::NotFound("API token not found".to_string()));
+        }
+        BusinessEvent::ApiTokenRevoked {
+            org_id: organization_id,
+            token_id,
+            revoked_by: user_id,
+        }
+        .log();
+
+        Ok(OrganizationActionMessage {
+            message: "API token revoked".to_string(),
+        })
+    }
+}
+
+fn ensure_token_manager(role: OrgRole) -> Result<(), AppError> {
+    match role {
+        OrgRole::Owner | OrgRole::Admin => Ok(()),
+        _ => Err(AppError::Forbidden(
+            "Only organization owners and admins can manage API tokens".to_string(),
+        )),
+    }
+}
+
+fn normalize_scopes(mut scopes: Vec<ApiTokenScope>) -> Result<Vec<ApiTokenScope>, AppError> {
+    scopes.sort_by_key(|scope| scope.as_str());
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(AppError::ValidationError(
+            "At least one scope is required".to_string(),
+        ));
+    }
+    Ok(scopes)
+}
+
+fn api_token_response(row: ApiTokenRow) -> ApiTokenResponse {
+    ApiTokenResponse {
+        id: row.id,
+        name: row.name,
+        token_prefix: row.token_prefix,
+        scopes: row
+            .scopes
+            .iter()
+            .filter_map(|scope| ApiTokenScope::parse(scope))
+            .collect(),
+        board_id: row.board_id,
+        created_by: row.created_by,
+        created_at: row.created_at,
+        last_used_at: row.last_used_at,
+        expires_at: row.expires_at,
+    }
+}
@@ -0,0 +1,103 @@
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    dto::organizations::{
+        OrganizationBoardDefaultsResponse, UpdateOrganizationBoardDefaultsRequest,
+    },
+    error::AppError,
+    models::{boards::BoardRole, organizations::OrgRole},
+    repositories::organizations as org_repo,
+};
+
+use super::{OrganizationService, helpers::require_member_role};
+
+impl OrganizationService {
+    /// Sets the role org members receive on new org boards and whether those
+    /// boards start public; owners and admins only.
+    pub async fn update_board_defaults(
+        pool: &PgPool,
+        organization_id: Uuid,
+        requester_id: Uuid,
+        req: UpdateOrganizationBoardDefaultsRequest,
+    ) -> Result<OrganizationBoardDefaultsResponse, AppError> {
+        let role = require_member_role(pool, organization_id, requester_id).await?;
+        if !matches!(role, OrgRole::Owner | OrgRole::Admin) {
+            return Err(AppError::Forbidden(
+                "Only owners and admins can update board defaults".to_string(),
+            ));
+        }
+
+        let default_member_board_role =
+            validate_default_member_board_role(req.default_member_board_role)?;
+        let defaults = board_defaults_settings(default_member_board_role, req.default_board_public);
+        let organization =
+            org_repo::update_board_defaults(pool, organization_id, &defaults).await?;
+
+        Ok(OrganizationBoardDefaultsResponse::from(organization))
+    }
+}
+
+fn validate_default_member_board_role(
+    role: Option<BoardRole>,
+) -> Result<Option<BoardRole>, AppError> {
+    if role == Some(BoardRole::Owner) {
+        return Err(AppError::ValidationError(
+            "Owner cannot be the default member board role".to_string(),
+        ));
+    }
+    Ok(role)
+}
+
+/// The `settings` keys [`OrganizationSettings`] reads the board defaults from.
+///
+/// [`OrganizationSettings`]: crate::models::organizations::OrganizationSettings
+fn board_defaults_settings(
+    default_member_board_role: Option<BoardRole>,
+    default_board_public: Option<bool>,
+) -> serde_json::Value {
+    json!({
+        "defaultMemberBoardRole": default_member_board_role,
+        "defaultBoardPublic": default_board_public,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{board_defaults_settings, validate_default_member_board_role};
+    use crate::models::{boards::BoardRole, organizations::OrganizationSettings};
+
+    #[test]
+    fn default_member_board_role_rejects_owner() {
+        assert!(validate_default_member_board_role(Some(BoardRole::Owner)).is_err());
+        assert_eq!(
+            validate_default_member_board_role(Some(BoardRole::Viewer)).unwrap(),
+            Some(BoardRole::Viewer)
+        );
+        assert_eq!(validate_default_member_board_role(None).unwrap(), None);
+    }
+
+    #[test]
+    fn board_defaults_settings_round_trip_through_organization_settings() {
+        let mut settings = json!({
+            "allowPublicBoards": true,
+            "defaultBoardPermission": "view",
+            "ssoEnabled": false,
+            "domainRestriction": null,
+            "defaultBoardPublic": false,
+        });
+        let defaults = board_defaults_settings(Some(BoardRole::Editor), Some(true));
+        for (key, value) in defaults.as_object().unwrap() {
+            settings[key] = value.clone();
+        }
+
+        let settings: OrganizationSettings = serde_json::from_value(settings).unwrap();
+
+        assert_eq!(settings.default_member_board_role, Some(BoardRole::Editor));
+        assert_eq!(settings.default_board_public, Some(true));
+        assert!(settings.allow_public_boards);
+    }
+}
@@ -0,0 +1,150 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    dto::organizations::{OrganizationBrandingResponse, UpdateOrganizationBrandingRequest},
+    error::{AppError, FieldErrors},
+    models::organizations::{OrgRole, OrganizationBranding},
+    repositories::organizations as org_repo,
+};
+
+use super::{OrganizationService, helpers::require_member_role};
+
+const MAX_EMAIL_FROM_NAME_LENGTH: usize = 64;
+const MAX_LOGO_URL_LENGTH: usize = 2_048;
+
+impl OrganizationService {
+    /// Replaces the logo and email branding; owners and admins only.
+    pub async fn update_branding(
+        pool: &PgPool,
+        organization_id: Uuid,
+        requester_id: Uuid,
+        req: UpdateOrganizationBrandingRequest,
+    ) -> Result<OrganizationBrandingResponse, AppError> {
+        let role = require_member_role(pool, organization_id, requester_id).await?;
+        if !matches!(role, OrgRole::Owner | OrgRole::Admin) {
+            return Err(AppError::Forbidden(
+                "Only owners and admins can update branding".to_string(),
+            ));
+        }
+
+        let (logo_url, branding) = normalize_branding(req)?;
+        let organization = org_repo::update_organization_branding(
+            pool,
+            organization_id,
+            logo_url.as_deref(),
+            &branding,
+        )
+        .await?;
+
+        Ok(OrganizationBrandingResponse::from(organization))
+    }
+}
+
+fn normalize_branding(
+    req: UpdateOrganizationBrandingRequest,
+) -> Result<(Option<String>, OrganizationBranding), AppError> {
+    let mut errors = FieldErrors::default();
+    let logo_url = non_empty(req.logo_url);
+    if let Some(url) = logo_url.as_deref()
+        && (!url.starts_with("https://") || url.len() > MAX_LOGO_URL_LENGTH)
+    {
+        errors.add("logo_url", "Logo URL must be an https URL");
+    }
+    let email_from_name = non_empty(req.email_from_name);
+    if let Some(name) = email_from_name.as_deref()
+        && (name.chars().count() > MAX_EMAIL_FROM_NAME_LENGTH || name.chars().any(char::is_control))
+    {
+        errors.add(
+            "email_from_name",
+            format!(
+                "Sender name must be at most {} characters on one line",
+                MAX_EMAIL_FROM_NAME_LENGTH
+            ),
+        );
+    }
+    let accent_color = non_empty(req.accent_color);
+    if accent_color
+        .as_deref()
+        .is_some_and(|color| !is_hex_color(color))
+    {
+        errors.add(
+            "accent_color",
+            "Accent color must be a hex color like #1a73e8",
+        );
+    }
+    let support_email = non_empty(req.support_email);
+    if support_email
+        .as_deref()
+        .is_some_and(|email| email.parse::<lettre::Address>().is_err())
+    {
+        errors.add(
+            "support_email",
+            "Support email must be a valid email address",
+        );
+    }
+    errors.into_result()?;
+
+    Ok((
+        logo_url,
+        OrganizationBranding {
+            email_from_name,
+            accent_color: accent_color.map(|color| color.to_ascii_lowercase()),
+            support_email,
+        },
+    ))
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6) && hex.chars().all(|ch| ch.is_ascii_hexdigit())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_branding;
+    use crate::dto::organizations::UpdateOrganizationBrandingRequest;
+
+    fn request(accent_color: &str, support_email: &str) -> UpdateOrganizationBrandingRequest {
+        UpdateOrganizationBrandingRequest {
+            logo_url: Some(" https://cdn.example.com/logo.png ".to_string()),
+            email_from_name: Some("Acme Team".to_string()),
+            accent_color: Some(accent_color.to_string()),
+            support_email: Some(support_email.to_string()),
+        }
+    }
+
+    #[test]
+    fn normalize_branding_trims_and_validates_fields() {
+        let (logo_url, branding) =
+            normalize_branding(request("#1A73E8", "help@acme.test")).unwrap();
+        assert_eq!(
+            logo_url.as_deref(),
+            Some("https://cdn.example.com/logo.png")
+        );
+        assert_eq!(branding.accent_color.as_deref(), Some("#1a73e8"));
+
+        assert!(normalize_branding(request("blue", "help@acme.test")).is_err());
+        assert!(normalize_branding(request("#fff", "not-an-email")).is_err());
+
+        let mut header_injection = request("#fff", "help@acme.test");
+        header_injection.email_from_name = Some("Acme\r\nBcc: x@y.z".to_string());
+        assert!(normalize_branding(header_injection).is_err());
+
+        let (logo_url, branding) = normalize_branding(UpdateOrganizationBrandingRequest {
+            logo_url: Some("  ".to_string()),
+            email_from_name: None,
+            accent_color: None,
+            support_email: None,
+        })
+        .unwrap();
+        assert!(logo_url.is_none() && branding.email_from_name.is_none());
+    }
+}
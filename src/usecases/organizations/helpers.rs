@@ -7,6 +7,35 @@ use crate::{
     repositories::{organizations as org_repo, users as user_repo},
 };
 
+/// Slugs that would shadow app routes or pose as system areas.
+const DEFAULT_RESERVED_SLUGS: [&str; 24] = [
+    "about",
+    "admin",
+    "api",
+    "app",
+    "assets",
+    "auth",
+    "billing",
+    "blog",
+    "boards",
+    "dashboard",
+    "docs",
+    "help",
+    "invite",
+    "invites",
+    "login",
+    "logout",
+    "organizations",
+    "register",
+    "settings",
+    "signup",
+    "static",
+    "status",
+    "support",
+    "www",
+];
+const SLUG_SUGGESTION_SUFFIXES: [&str; 6] = ["-team", "-hq", "-studio", "-1", "-2", "-3"];
+
 pub(super) fn build_slug(provided: Option<&str>, name: &str) -> Result<String, AppError> {
     let slug = if let Some(value) = provided {
         let trimmed = value.trim();
@@ -24,10 +53,50 @@ pub(super) fn build_slug(provided: Option<&str>, name: &str) -> Result<String, A
             "Organization slug must be 3-100 lowercase characters, digits, or hyphens".to_string(),
         ));
     }
+    ensure_slug_not_reserved(&slug)?;
 
     Ok(slug)
 }
 
+/// Rejects reserved slugs with a `Conflict` that suggests an alternative.
+pub(super) fn ensure_slug_not_reserved(slug: &str) -> Result<(), AppError> {
+    if !is_reserved_slug(slug) {
+        return Ok(());
+    }
+    let message = match slug_candidates(slug).next() {
+        Some(suggestion) => format!(
+            "Organization slug \"{}\" is reserved, try \"{}\"",
+            slug, suggestion
+        ),
+        None => format!("Organization slug \"{}\" is reserved", slug),
+    };
+    Err(AppError::Conflict(message))
+}
+
+/// Checks `slug` against `RESERVED_ORGANIZATION_SLUGS` (comma-separated),
+/// which replaces the built-in list when set.
+pub(super) fn is_reserved_slug(slug: &str) -> bool {
+    let configured = std::env::var("RESERVED_ORGANIZATION_SLUGS").ok();
+    slug_in_reserved_list(slug, configured.as_deref())
+}
+
+fn slug_in_reserved_list(slug: &str, configured: Option<&str>) -> bool {
+    match configured {
+        Some(list) => list
+            .split(',')
+            .any(|entry| entry.trim().eq_ignore_ascii_case(slug)),
+        None => DEFAULT_RESERVED_SLUGS.contains(&slug),
+    }
+}
+
+/// Suffixed variants of `base` that are well-formed and not reserved.
+fn slug_candidates(base: &str) -> impl Iterator<Item = String> + '_ {
+    SLUG_SUGGESTION_SUFFIXES
+        .iter()
+        .map(move |suffix| format!("{}{}", base, suffix))
+        .filter(|candidate| is_valid_slug(candidate) && !is_reserved_slug(candidate))
+}
+
 pub(super) fn normalize_slug(value: &str) -> String {
     let mut slug = String::new();
     let mut last_hyphen = false;
@@ -98,6 +167,29 @@ pub(super) fn ensure_owner(role: OrgRole) -> Result<(), AppError> {
     ))
 }
 
+/// Allows platform operators listed in `PLATFORM_ADMIN_USER_IDS`.
+pub(super) fn ensure_platform_admin(user_id: Uuid) -> Result<(), AppError> {
+    if is_configured_platform_admin(user_id) {
+        return Ok(());
+    }
+
+    Err(AppError::Forbidden(
+        "Only platform administrators can manage tier limits".to_string(),
+    ))
+}
+
+pub(crate) fn is_configured_platform_admin(user_id: Uuid) -> bool {
+    let allowlist = std::env::var("PLATFORM_ADMIN_USER_IDS").unwrap_or_default();
+    is_platform_admin(user_id, &allowlist)
+}
+
+fn is_platform_admin(user_id: Uuid, allowlist: &str) -> bool {
+    allowlist
+        .split(',')
+        .filter_map(|value| Uuid::parse_str(value.trim()).ok())
+        .any(|admin_id| admin_id == user_id)
+}
+
 pub(super) async fn resolve_fallback_owner_id(
     pool: &PgPool,
     organization_id: Uuid,
@@ -155,16 +247,11 @@ pub(super) async fn split_invite_targets(
 }
 
 pub(super) async fn suggest_slugs(pool: &PgPool, base: &str) -> Result<Vec<String>, AppError> {
-    let suffixes = ["-team", "-hq", "-studio", "-1", "-2", "-3"];
     let mut suggestions = Vec::new();
-    for suffix in suffixes {
+    for candidate in slug_candidates(base) {
         if suggestions.len() >= 3 {
             break;
         }
-        let candidate = format!("{}{}", base, suffix);
-        if !is_valid_slug(&candidate) {
-            continue;
-        }
         if !org_repo::organization_slug_exists(pool, &candidate).await? {
             suggestions.push(candidate);
         }
@@ -172,9 +259,44 @@ pub(super) async fn suggest_slugs(pool: &PgPool, base: &str) -> Result<Vec<Strin
     Ok(suggestions)
 }
 
+/// Builds a `%term%` ILIKE pattern with wildcard characters escaped.
+pub(super) fn build_search_pattern(term: &str) -> String {
+    let mut pattern = String::with_capacity(term.len() + 2);
+    pattern.push('%');
+    for ch in term.chars() {
+        if matches!(ch, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+    }
+    pattern.push('%');
+    pattern
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{build_slug, is_limit_exceeded, is_valid_slug, normalize_slug};
+    use uuid::Uuid;
+
+    use super::{
+        build_search_pattern, build_slug, is_limit_exceeded, is_platform_admin, is_valid_slug,
+        normalize_slug, slug_in_reserved_list,
+    };
+    use crate::error::AppError;
+
+    #[test]
+    fn platform_admin_allowlist_matches_listed_ids() {
+        let admin = Uuid::now_v7();
+        let allowlist = format!("not-a-uuid, {admin}");
+        assert!(is_platform_admin(admin, &allowlist));
+        assert!(!is_platform_admin(Uuid::now_v7(), &allowlist));
+        assert!(!is_platform_admin(admin, ""));
+    }
+
+    #[test]
+    fn search_pattern_escapes_wildcards() {
+        assert_eq!(build_search_pattern("ann"), "%ann%");
+        assert_eq!(build_search_pattern("50%_off"), "%50\\%\\_off%");
+    }
 
     #[test]
     fn generate_slug_normalizes_name() {
@@ -202,6 +324,26 @@ mod tests {
         assert_eq!(slug, "acme-inc");
     }
 
+    #[test]
+    fn build_slug_rejects_reserved_slug_with_suggestion() {
+        let error = build_slug(None, "Admin").unwrap_err();
+        let AppError::Conflict(message) = error else {
+            panic!("expected conflict, got {error:?}");
+        };
+        assert_eq!(
+            message,
+            "Organization slug \"admin\" is reserved, try \"admin-team\""
+        );
+    }
+
+    #[test]
+    fn configured_reserved_list_replaces_defaults() {
+        assert!(slug_in_reserved_list("www", None));
+        assert!(!slug_in_reserved_list("acme", None));
+        assert!(slug_in_reserved_list("acme", Some("foo, ACME")));
+        assert!(!slug_in_reserved_list("www", Some("foo,acme")));
+    }
+
     #[test]
     fn limit_exceeded_when_over_capacity() {
         assert!(is_limit_exceeded(5, 1, 5));
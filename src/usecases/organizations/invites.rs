@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -10,9 +12,12 @@ use crate::{
         OrganizationInvitationResponse, OrganizationInvitationsResponse,
     },
     error::AppError,
-    models::users::User,
+    models::{
+        organizations::Organization,
+        users::{EmailCategory, User},
+    },
     repositories::{boards as board_repo, organizations as org_repo, users as user_repo},
-    services::email::EmailService,
+    services::{email::EmailService, email_templates::EmailLocale},
     telemetry::{BusinessEvent, redact_email},
     usecases::invites::collect_invite_emails,
 };
@@ -25,6 +30,11 @@ use super::{
     },
 };
 
+const INVITE_REMINDER_BATCH_SIZE: i64 = 200;
+const INVITE_REMINDER_MAX_ATTEMPTS: i32 = 5;
+const STALE_INVITE_BATCH_SIZE: i64 = 500;
+const MEMBER_INVITE_EXPIRY_DAYS: i64 = 30;
+
 impl OrganizationService {
     /// Lists pending invitations for the current user.
     pub async fn list_invitations(
@@ -167,7 +177,7 @@ impl OrganizationService {
             .log();
         }
 
-        send_invite_emails(email_service, &organization, &users).await?;
+        send_invite_emails(email_service, &organization, &users, EmailCategory::Invite).await?;
         send_pre_signup_invites(email_service, &organization, &pending_invites).await?;
 
         Ok(InviteMembersResponse {
@@ -393,26 +403,219 @@ impl OrganizationService {
         org_repo::resend_invite(&mut tx, organization_id, member_id).await?;
         tx.commit().await?;
 
-        send_invite_emails(email_service, &organization, &[invited_user]).await?;
+        send_invite_emails(
+            email_service,
+            &organization,
+            &[invited_user],
+            EmailCategory::Invite,
+        )
+        .await?;
 
         Ok(OrganizationActionMessage {
             message: "Invitation resent".to_string(),
         })
     }
+
+    /// Sends a single reminder for invites older than `reminder_after` and
+    /// returns how many were delivered.
+    ///
+    /// Each batch is claimed before sending, so concurrent workers never
+    /// remind the same invite twice. A row that fails is logged, released and
+    /// retried on a later run, up to `INVITE_REMINDER_MAX_ATTEMPTS` times.
+    /// Invitees who opted out of reminders stay claimed without an email.
+    /// Email invites keep their existing token.
+    pub async fn send_invite_reminders(
+        pool: &PgPool,
+        email_service: Option<&EmailService>,
+        reminder_after: chrono::Duration,
+    ) -> Result<usize, AppError> {
+        let Some(service) = email_service else {
+            return Ok(0);
+        };
+        let cutoff = chrono::Utc::now() - reminder_after;
+        let mut organizations = HashMap::new();
+        let mut sent = 0;
+
+        let members = org_repo::claim_members_due_for_reminder(
+            pool,
+            cutoff,
+            INVITE_REMINDER_MAX_ATTEMPTS,
+            INVITE_REMINDER_BATCH_SIZE,
+        )
+        .await?;
+        for row in members {
+            match remind_member(pool, service, &mut organizations, &row).await {
+                Ok(delivered) => sent += usize::from(delivered),
+                Err(err) => {
+                    tracing::error!(
+                        org_id = %row.organization_id,
+                        member_id = %row.member_id,
+                        error = %err,
+                        "Failed to send invite reminder email"
+                    );
+                    if let Err(err) =
+                        org_repo::release_member_reminder_claim(pool, row.member_id).await
+                    {
+                        tracing::warn!(
+                            member_id = %row.member_id,
+                            error = %err,
+                            "Failed to release invite reminder claim"
+                        );
+                    }
+                }
+            }
+        }
+
+        let invites = org_repo::claim_email_invites_due_for_reminder(
+            pool,
+            cutoff,
+            INVITE_REMINDER_MAX_ATTEMPTS,
+            INVITE_REMINDER_BATCH_SIZE,
+        )
+        .await?;
+        for row in invites {
+            match remind_email_invite(pool, service, &mut organizations, &row).await {
+                Ok(delivered) => sent += usize::from(delivered),
+                Err(err) => {
+                    tracing::error!(
+                        org_id = %row.organization_id,
+                        invitee_email_redacted = %redact_email(&row.email),
+                        error = %err,
+                        "Failed to send pre-signup invite reminder email"
+                    );
+                    if let Err(err) =
+                        org_repo::release_email_invite_reminder_claim(pool, row.id).await
+                    {
+                        tracing::warn!(
+                            invite_id = %row.id,
+                            error = %err,
+                            "Failed to release invite reminder claim"
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(sent)
+    }
+
+    /// Removes expired email invites and pending member invites older than
+    /// `MEMBER_INVITE_EXPIRY_DAYS` so they stop counting toward seats.
+    pub async fn purge_expired_invites(pool: &PgPool) -> Result<(u64, u64), AppError> {
+        let email_invites = org_repo::delete_expired_email_invites(pool).await?;
+
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(MEMBER_INVITE_EXPIRY_DAYS);
+        let stale =
+            org_repo::list_stale_member_invites(pool, cutoff, STALE_INVITE_BATCH_SIZE).await?;
+        let mut member_invites = 0;
+        for row in stale {
+            let mut tx = pool.begin().await?;
+            board_repo::remove_board_memberships_by_organization(
+                &mut tx,
+                row.organization_id,
+                row.user_id,
+            )
+            .await?;
+            org_repo::remove_member(&mut tx, row.organization_id, row.member_id).await?;
+            tx.commit().await?;
+            member_invites += 1;
+        }
+
+        Ok((email_invites, member_invites))
+    }
+}
+
+/// Reminds one claimed member invite; returns whether an email went out.
+/// A deleted organization leaves nothing to remind about.
+async fn remind_member(
+    pool: &PgPool,
+    service: &EmailService,
+    organizations: &mut HashMap<Uuid, Option<Organization>>,
+    row: &org_repo::PendingMemberInviteRow,
+) -> Result<bool, AppError> {
+    let Some(organization) =
+        load_cached_organization(pool, organizations, row.organization_id).await?
+    else {
+        return Ok(false);
+    };
+    let user = user_repo::get_user_by_id(pool, row.user_id).await?;
+    if !user
+        .preferences
+        .notifications
+        .allows_email(EmailCategory::InviteReminder)
+    {
+        return Ok(false);
+    }
+    service
+        .send_organization_invite(
+            &user.email,
+            EmailLocale::from_language(&user.preferences.language),
+            &organization,
+            None,
+        )
+        .await?;
+    Ok(true)
+}
+
+/// Reminds one claimed pre-signup invite; returns whether an email went out.
+async fn remind_email_invite(
+    pool: &PgPool,
+    service: &EmailService,
+    organizations: &mut HashMap<Uuid, Option<Organization>>,
+    row: &org_repo::PendingEmailInviteReminderRow,
+) -> Result<bool, AppError> {
+    let Some(organization) =
+        load_cached_organization(pool, organizations, row.organization_id).await?
+    else {
+        return Ok(false);
+    };
+    // Recipients have no account yet, so there is no language preference to honour.
+    service
+        .send_invite_reminder(&row.email, EmailLocale::default(), &organization)
+        .await?;
+    Ok(true)
+}
+
+async fn load_cached_organization(
+    pool: &PgPool,
+    cache: &mut HashMap<Uuid, Option<Organization>>,
+    organization_id: Uuid,
+) -> Result<Option<Organization>, AppError> {
+    if let Some(organization) = cache.get(&organization_id) {
+        return Ok(organization.clone());
+    }
+    let organization = org_repo::find_organization_by_id(pool, organization_id).await?;
+    cache.insert(organization_id, organization.clone());
+    Ok(organization)
 }
 
 pub(crate) async fn send_invite_emails(
     email_service: Option<&EmailService>,
     organization: &crate::models::organizations::Organization,
     users: &[User],
+    category: EmailCategory,
 ) -> Result<(), AppError> {
     let Some(service) = email_service else {
         return Ok(());
     };
 
     for user in users {
+        if !user.preferences.notifications.allows_email(category) {
+            tracing::debug!(
+                org_id = %organization.id,
+                user_id = %user.id,
+                ?category,
+                "Skipping invite email: recipient opted out"
+            );
+            continue;
+        }
         if let Err(err) = service
-            .send_organization_invite(&user.email, &organization.name, &organization.slug, None)
+            .send_organization_invite(
+                &user.email,
+                EmailLocale::from_language(&user.preferences.language),
+                organization,
+                None,
+            )
             .await
         {
             tracing::error!(
@@ -436,9 +639,10 @@ async fn send_pre_signup_invites(
         return Ok(());
     };
 
+    // Recipients have no account yet, so there is no language preference to honour.
     for (email, token) in invites {
         if let Err(err) = service
-            .send_organization_invite(email, &organization.name, &organization.slug, Some(token))
+            .send_organization_invite(email, EmailLocale::default(), organization, Some(token))
             .await
         {
             tracing::error!(
@@ -3,48 +3,144 @@ use uuid::Uuid;
 
 use crate::{
     dto::organizations::{
-        OrganizationActionMessage, OrganizationMemberResponse, OrganizationMemberUser,
-        OrganizationMembersResponse, UpdateMemberRoleRequest,
+        ListMembersQuery, MemberListPagination, MemberSearchPagination, MemberSearchQuery,
+        MemberStatusFilter, OrganizationActionMessage, OrganizationMemberResponse,
+        OrganizationMemberSearchResponse, OrganizationMemberUser, OrganizationMembersResponse,
+        TransferOrganizationOwnershipRequest, UpdateMemberRoleRequest,
     },
     error::AppError,
     models::organizations::OrgRole,
     repositories::{boards as board_repo, organizations as org_repo},
+    services::access_cache::{self, AccessInvalidation},
     telemetry::BusinessEvent,
 };
 
 use super::{
     OrganizationService,
-    helpers::{ensure_manager, require_member_role, resolve_fallback_owner_id},
+    helpers::{
+        build_search_pattern, ensure_manager, require_member_role, resolve_fallback_owner_id,
+    },
 };
 
+const DEFAULT_MEMBER_LIST_LIMIT: u32 = 50;
+const MAX_MEMBER_LIST_LIMIT: u32 = 200;
+const DEFAULT_MEMBER_SEARCH_LIMIT: u32 = 20;
+const MAX_MEMBER_SEARCH_LIMIT: u32 = 100;
+const MEMBER_EXPORT_HEADER: [&str; 4] = ["email", "display_name", "role", "joined_at"];
+
 impl OrganizationService {
-    /// Lists organization members.
+    /// Exports the member roster as CSV (owners/admins only). Pending
+    /// members have an empty `joined_at`.
+    pub async fn export_members_csv(
+        pool: &PgPool,
+        organization_id: Uuid,
+        requester_id: Uuid,
+    ) -> Result<String, AppError> {
+        let role = require_member_role(pool, organization_id, requester_id).await?;
+        ensure_manager(role)?;
+        let rows = org_repo::list_members_for_export(pool, organization_id).await?;
+
+        let mut csv = String::new();
+        push_csv_record(&mut csv, &MEMBER_EXPORT_HEADER);
+        for row in rows {
+            let joined_at = row
+                .accepted_at
+                .map(|value| value.to_rfc3339())
+                .unwrap_or_default();
+            push_csv_record(
+                &mut csv,
+                &[
+                    row.email.as_str(),
+                    row.display_name.as_str(),
+                    org_role_label(row.role),
+                    joined_at.as_str(),
+                ],
+            );
+        }
+        Ok(csv)
+    }
+
+    /// Lists organization members oldest first, one keyset page at a time,
+    /// optionally filtered by role and invitation status.
     pub async fn list_members(
         pool: &PgPool,
         organization_id: Uuid,
         user_id: Uuid,
+        query: ListMembersQuery,
     ) -> Result<OrganizationMembersResponse, AppError> {
         require_member_role(pool, organization_id, user_id).await?;
-        let rows = org_repo::list_members(pool, organization_id).await?;
-        let data = rows
-            .into_iter()
-            .map(|row| OrganizationMemberResponse {
-                id: row.member_id,
-                user: OrganizationMemberUser {
-                    id: row.user_id,
-                    username: row.username.unwrap_or_default(),
-                    display_name: row.display_name,
-                    avatar_url: row.avatar_url,
-                },
-                role: row.role,
-                invited_at: row.invited_at,
-                accepted_at: row.accepted_at,
-                created_at: row.created_at,
-                updated_at: row.updated_at,
-            })
-            .collect();
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_MEMBER_LIST_LIMIT)
+            .clamp(1, MAX_MEMBER_LIST_LIMIT);
+        let cursor = parse_member_cursor(query.cursor.as_deref())?;
+
+        let filter = org_repo::OrganizationMemberFilter {
+            role: query.role,
+            accepted: query
+                .status
+                .map(|status| status == MemberStatusFilter::Accepted),
+        };
 
-        Ok(OrganizationMembersResponse { data })
+        let mut rows =
+            org_repo::list_members(pool, organization_id, filter, cursor, limit as i64 + 1).await?;
+        let total = org_repo::count_listed_members(pool, organization_id, filter).await?;
+        let has_more = rows.len() > limit as usize;
+        rows.truncate(limit as usize);
+        let next_cursor = rows
+            .last()
+            .filter(|_| has_more)
+            .map(|row| encode_member_cursor(row.created_at, row.member_id));
+
+        Ok(OrganizationMembersResponse {
+            data: rows.into_iter().map(member_response).collect(),
+            pagination: MemberListPagination {
+                next_cursor,
+                has_more,
+                total,
+            },
+        })
+    }
+
+    /// Searches organization members by username, display name, or email.
+    pub async fn search_members(
+        pool: &PgPool,
+        organization_id: Uuid,
+        user_id: Uuid,
+        query: MemberSearchQuery,
+    ) -> Result<OrganizationMemberSearchResponse, AppError> {
+        require_member_role(pool, organization_id, user_id).await?;
+        let term = query.q.trim();
+        if term.is_empty() {
+            return Err(AppError::ValidationError(
+                "Search query is required".to_string(),
+            ));
+        }
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_MEMBER_SEARCH_LIMIT)
+            .clamp(1, MAX_MEMBER_SEARCH_LIMIT);
+        let offset = query.offset.unwrap_or(0);
+
+        let mut rows = org_repo::search_members(
+            pool,
+            organization_id,
+            &build_search_pattern(term),
+            limit as i64 + 1,
+            offset as i64,
+        )
+        .await?;
+        let has_more = rows.len() > limit as usize;
+        rows.truncate(limit as usize);
+        let next_offset = has_more.then(|| offset + limit);
+
+        Ok(OrganizationMemberSearchResponse {
+            data: rows.into_iter().map(member_response).collect(),
+            pagination: MemberSearchPagination {
+                next_offset,
+                has_more,
+            },
+        })
     }
 
     /// Updates a member role or transfers ownership.
@@ -76,27 +172,85 @@ impl OrganizationService {
             ));
         }
 
-        let mut tx = pool.begin().await?;
         if req.role == OrgRole::Owner {
             if requester_role != OrgRole::Owner {
                 return Err(AppError::Forbidden(
                     "Only owners can transfer ownership".to_string(),
                 ));
             }
-            org_repo::demote_other_owners(&mut tx, organization_id, member_id, OrgRole::Admin)
-                .await?;
-            org_repo::update_member_role(&mut tx, organization_id, member_id, OrgRole::Owner)
-                .await?;
+            apply_ownership_transfer(
+                pool,
+                organization_id,
+                requester_id,
+                member_id,
+                member.user_id,
+            )
+            .await?;
         } else {
+            let mut tx = pool.begin().await?;
             org_repo::update_member_role(&mut tx, organization_id, member_id, req.role).await?;
+            tx.commit().await?;
+            access_cache::publish(AccessInvalidation::User {
+                user_id: member.user_id,
+            })
+            .await;
         }
-        tx.commit().await?;
 
         Ok(OrganizationActionMessage {
             message: "Member role updated".to_string(),
         })
     }
 
+    /// Transfers ownership to another accepted member and demotes every
+    /// current owner, including the requester, to admin.
+    pub async fn transfer_ownership(
+        pool: &PgPool,
+        organization_id: Uuid,
+        requester_id: Uuid,
+        req: TransferOrganizationOwnershipRequest,
+    ) -> Result<OrganizationActionMessage, AppError> {
+        let requester_role = require_member_role(pool, organization_id, requester_id).await?;
+        if requester_role != OrgRole::Owner {
+            return Err(AppError::Forbidden(
+                "Only owners can transfer ownership".to_string(),
+            ));
+        }
+
+        let organization = org_repo::find_organization_by_id(pool, organization_id)
+            .await?
+            .ok_or(AppError::NotFound("Organization not found".to_string()))?;
+        ensure_transfer_confirmation(&req.confirmation, &organization.slug)?;
+
+        let member = org_repo::get_member_by_id(pool, organization_id, req.new_owner_member_id)
+            .await?
+            .ok_or(AppError::NotFound(
+                "Organization member not found".to_string(),
+            ))?;
+        if member.user_id == requester_id {
+            return Err(AppError::BadRequest(
+                "You already own this organization".to_string(),
+            ));
+        }
+        if member.accepted_at.is_none() {
+            return Err(AppError::BadRequest(
+                "Ownership can only be transferred to an active member".to_string(),
+            ));
+        }
+
+        apply_ownership_transfer(
+            pool,
+            organization_id,
+            requester_id,
+            req.new_owner_member_id,
+            member.user_id,
+        )
+        .await?;
+
+        Ok(OrganizationActionMessage {
+            message: "Ownership transferred".to_string(),
+        })
+    }
+
     /// Removes a member from an organization.
     pub async fn remove_member(
         pool: &PgPool,
@@ -128,6 +282,7 @@ impl OrganizationService {
         }
 
         let mut tx = pool.begin().await?;
+        let mut fallback_owner = None;
         let boards_to_transfer = board_repo::list_boards_requiring_owner_transfer(
             &mut tx,
             organization_id,
@@ -146,6 +301,7 @@ impl OrganizationService {
             for board_id in boards_to_transfer {
                 board_repo::ensure_board_owner(&mut tx, board_id, fallback_owner_id).await?;
             }
+            fallback_owner = Some(fallback_owner_id);
         }
         board_repo::remove_board_memberships_by_organization(
             &mut tx,
@@ -155,6 +311,16 @@ impl OrganizationService {
         .await?;
         org_repo::remove_member(&mut tx, organization_id, member_id).await?;
         tx.commit().await?;
+        access_cache::publish(AccessInvalidation::User {
+            user_id: member.user_id,
+        })
+        .await;
+        if let Some(fallback_owner_id) = fallback_owner {
+            access_cache::publish(AccessInvalidation::User {
+                user_id: fallback_owner_id,
+            })
+            .await;
+        }
         BusinessEvent::MemberRemoved {
             org_id: organization_id,
             removed_by: requester_id,
@@ -167,3 +333,174 @@ impl OrganizationService {
         })
     }
 }
+
+fn org_role_label(role: OrgRole) -> &'static str {
+    match role {
+        OrgRole::Owner => "owner",
+        OrgRole::Admin => "admin",
+        OrgRole::Member => "member",
+        OrgRole::Guest => "guest",
+    }
+}
+
+/// Appends one RFC 4180 record. Values that spreadsheet apps would evaluate
+/// as formulas are prefixed with `'`.
+fn push_csv_record(csv: &mut String, fields: &[&str]) {
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            csv.push(',');
+        }
+        let guarded;
+        let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+            guarded = format!("'{}", field);
+            guarded.as_str()
+        } else {
+            field
+        };
+        if field.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push_str("\r\n");
+}
+
+fn parse_member_cursor(
+    cursor: Option<&str>,
+) -> Result<Option<org_repo::OrganizationMemberCursor>, AppError> {
+    let Some(cursor) = cursor else {
+        return Ok(None);
+    };
+    let invalid = || AppError::ValidationError("Invalid member cursor".to_string());
+    let (created_at, id) = cursor.split_once('|').ok_or_else(invalid)?;
+    let created_at = chrono::DateTime::parse_from_rfc3339(created_at)
+        .map_err(|_| invalid())?
+        .with_timezone(&chrono::Utc);
+    let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+    Ok(Some(org_repo::OrganizationMemberCursor { created_at, id }))
+}
+
+/// Encodes the cursor with a `Z` offset so it survives unescaped in a query
+/// string, at the microsecond precision Postgres stores.
+fn encode_member_cursor(created_at: chrono::DateTime<chrono::Utc>, id: Uuid) -> String {
+    format!(
+        "{}|{}",
+        created_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+        id
+    )
+}
+
+fn member_response(row: org_repo::OrganizationMemberRow) -> OrganizationMemberResponse {
+    OrganizationMemberResponse {
+        id: row.member_id,
+        user: OrganizationMemberUser {
+            id: row.user_id,
+            username: row.username.unwrap_or_default(),
+            display_name: row.display_name,
+            avatar_url: row.avatar_url,
+        },
+        role: row.role,
+        invited_at: row.invited_at,
+        accepted_at: row.accepted_at,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }
+}
+
+async fn apply_ownership_transfer(
+    pool: &PgPool,
+    organization_id: Uuid,
+    previous_owner_id: Uuid,
+    new_owner_member_id: Uuid,
+    new_owner_id: Uuid,
+) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    let demoted_owner_ids = org_repo::demote_other_owners(
+        &mut tx,
+        organization_id,
+        new_owner_member_id,
+        OrgRole::Admin,
+    )
+    .await?;
+    org_repo::update_member_role(
+        &mut tx,
+        organization_id,
+        new_owner_member_id,
+        OrgRole::Owner,
+    )
+    .await?;
+    tx.commit().await?;
+
+    for user_id in std::iter::once(new_owner_id).chain(demoted_owner_ids) {
+        access_cache::publish(AccessInvalidation::User { user_id }).await;
+    }
+
+    BusinessEvent::OwnershipTransferred {
+        org_id: organization_id,
+        previous_owner_id,
+        new_owner_id,
+    }
+    .log();
+    Ok(())
+}
+
+fn ensure_transfer_confirmation(confirmation: &str, slug: &str) -> Result<(), AppError> {
+    if confirmation.trim() != slug {
+        return Err(AppError::BadRequest(
+            "Confirmation must match the organization slug".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{
+        encode_member_cursor, ensure_transfer_confirmation, parse_member_cursor, push_csv_record,
+    };
+
+    #[test]
+    fn csv_records_quote_special_characters_and_guard_formulas() {
+        let mut csv = String::new();
+        push_csv_record(&mut csv, &["ann@example.com", "Ann \"A\", Jr", "owner", ""]);
+        push_csv_record(
+            &mut csv,
+            &["bo@example.com", "=HYPERLINK(\"x\")", "member", ""],
+        );
+
+        assert_eq!(
+            csv,
+            "ann@example.com,\"Ann \"\"A\"\", Jr\",owner,\r\n\
+             bo@example.com,\"'=HYPERLINK(\"\"x\"\")\",member,\r\n"
+        );
+    }
+
+    #[test]
+    fn transfer_confirmation_requires_exact_slug() {
+        assert!(ensure_transfer_confirmation("acme-inc", "acme-inc").is_ok());
+        assert!(ensure_transfer_confirmation(" acme-inc ", "acme-inc").is_ok());
+        assert!(ensure_transfer_confirmation("Acme Inc", "acme-inc").is_err());
+        assert!(ensure_transfer_confirmation("", "acme-inc").is_err());
+    }
+
+    #[test]
+    fn member_cursor_round_trips_and_rejects_garbage() {
+        let id = Uuid::new_v4();
+        let created_at =
+            chrono::DateTime::from_timestamp_micros(chrono::Utc::now().timestamp_micros()).unwrap();
+        let encoded = encode_member_cursor(created_at, id);
+        assert!(!encoded.contains('+'));
+        let cursor = parse_member_cursor(Some(&encoded)).unwrap().unwrap();
+
+        assert_eq!(cursor.id, id);
+        assert_eq!(cursor.created_at, created_at);
+        assert!(parse_member_cursor(None).unwrap().is_none());
+        assert!(parse_member_cursor(Some("not-a-cursor")).is_err());
+        assert!(parse_member_cursor(Some("2026-01-01T00:00:00Z|nope")).is_err());
+    }
+}
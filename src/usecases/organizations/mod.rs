@@ -4,14 +4,17 @@ use uuid::Uuid;
 use crate::{
     dto::organizations::{
         CreateOrganizationRequest, OrganizationListResponse, OrganizationResponse,
-        OrganizationSummaryResponse, SlugAvailabilityResponse,
+        OrganizationSummaryResponse, SlugAvailabilityResponse, UpdateOrganizationSlugRequest,
     },
-    error::AppError,
+    error::{AppError, FieldErrors},
     models::users::SubscriptionTier,
     repositories::organizations as org_repo,
     telemetry::BusinessEvent,
 };
 
+mod api_tokens;
+mod board_defaults;
+mod branding;
 mod helpers;
 mod invites;
 mod members;
@@ -21,6 +24,7 @@ mod usage;
 /// Business logic for organization management.
 pub struct OrganizationService;
 
+pub(crate) use helpers::is_configured_platform_admin;
 pub(crate) use invites::send_invite_emails;
 pub(crate) use subscription::max_boards_for_tier;
 
@@ -32,18 +36,16 @@ impl OrganizationService {
         req: CreateOrganizationRequest,
     ) -> Result<OrganizationResponse, AppError> {
         let name = req.name.trim();
+        let mut errors = FieldErrors::default();
         if name.is_empty() {
-            return Err(AppError::ValidationError(
-                "Organization name is required".to_string(),
-            ));
+            errors.add("name", "Organization name is required");
+        } else if name.chars().count() > 100 {
+            errors.add("name", "Organization name must be 1-100 characters");
         }
-        if name.chars().count() > 100 {
-            return Err(AppError::ValidationError(
-                "Organization name must be 1-100 characters".to_string(),
-            ));
-        }
-
-        let slug = helpers::build_slug(req.slug.as_deref(), name)?;
+        let slug = errors.check("slug", helpers::build_slug(req.slug.as_deref(), name))?;
+        errors.into_result()?;
+        let slug =
+            slug.ok_or_else(|| AppError::ValidationError("Invalid organization slug".to_string()))?;
         if org_repo::organization_slug_exists(pool, &slug).await? {
             return Err(AppError::Conflict(
                 "Organization slug already exists".to_string(),
@@ -95,7 +97,8 @@ impl OrganizationService {
             ));
         }
 
-        let available = !org_repo::organization_slug_exists(pool, &normalized).await?;
+        let available = !helpers::is_reserved_slug(&normalized)
+            && !org_repo::organization_slug_exists(pool, &normalized).await?;
         let suggestions = if available {
             Vec::new()
         } else {
@@ -110,6 +113,52 @@ impl OrganizationService {
         })
     }
 
+    /// Changes an organization's slug; owners only.
+    ///
+    /// The previous slug is kept as an alias so it cannot be claimed by
+    /// another organization.
+    pub async fn update_slug(
+        pool: &PgPool,
+        organization_id: Uuid,
+        requester_id: Uuid,
+        req: UpdateOrganizationSlugRequest,
+    ) -> Result<OrganizationResponse, AppError> {
+        let requester_role =
+            helpers::require_member_role(pool, organization_id, requester_id).await?;
+        helpers::ensure_owner(requester_role)?;
+
+        let slug = req.slug.trim();
+        if !helpers::is_valid_slug(slug) {
+            return Err(AppError::ValidationError(
+                "Organization slug must be 3-100 lowercase characters, digits, or hyphens"
+                    .to_string(),
+            ));
+        }
+        helpers::ensure_slug_not_reserved(slug)?;
+
+        let organization = org_repo::find_organization_by_id(pool, organization_id)
+            .await?
+            .ok_or(AppError::NotFound("Organization not found".to_string()))?;
+        if organization.slug == slug {
+            return Ok(OrganizationResponse::from(organization));
+        }
+
+        let mut tx = pool.begin().await?;
+        if let Some(holder) = org_repo::find_slug_holder(&mut tx, slug).await?
+            && holder != organization_id
+        {
+            return Err(AppError::Conflict(
+                "Organization slug already exists".to_string(),
+            ));
+        }
+        let updated =
+            org_repo::update_organization_slug(&mut tx, organization_id, &organization.slug, slug)
+                .await?;
+        tx.commit().await?;
+
+        Ok(OrganizationResponse::from(updated))
+    }
+
     /// Lists organizations for the current user.
     pub async fn list_organizations(
         pool: &PgPool,
@@ -1,20 +1,46 @@
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
-    dto::organizations::{OrganizationResponse, UpdateOrganizationSubscriptionRequest},
+    dto::organizations::{
+        OrganizationResponse, SubscriptionChangePreview, SubscriptionLimitViolation,
+        TierLimitResponse, TierLimitsResponse, UpdateOrganizationSubscriptionRequest,
+        UpdateTierLimitRequest,
+    },
     error::AppError,
     models::users::SubscriptionTier,
-    repositories::organizations as org_repo,
+    repositories::{organizations as org_repo, tier_limits as tier_limit_repo},
 };
 
 use super::{
     OrganizationService,
-    helpers::{ensure_owner, require_member_role},
+    helpers::{ensure_owner, ensure_platform_admin, require_member_role},
     usage::{OrganizationUsageSnapshot, is_usage_over_limit, load_usage_snapshot},
 };
 
-#[derive(Debug, Clone, Copy)]
+const ALL_TIERS: [SubscriptionTier; 4] = [
+    SubscriptionTier::Free,
+    SubscriptionTier::Starter,
+    SubscriptionTier::Professional,
+    SubscriptionTier::Enterprise,
+];
+
+/// Tier limits loaded from `core.tier_limit`; tiers missing here use the
+/// built-in defaults. Reloaded every minute by
+/// `maintenance::spawn_tier_limit_refresh` so edits made on other nodes expire.
+static TIER_LIMIT_CACHE: OnceLock<RwLock<HashMap<SubscriptionTier, OrganizationLimits>>> =
+    OnceLock::new();
+
+fn tier_limit_cache() -> &'static RwLock<HashMap<SubscriptionTier, OrganizationLimits>> {
+    TIER_LIMIT_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub(super) struct OrganizationLimits {
     pub(super) max_members: i32,
     pub(super) max_boards: i32,
@@ -22,6 +48,40 @@ pub(super) struct OrganizationLimits {
 }
 
 impl OrganizationService {
+    /// Validates a subscription tier change without applying it.
+    pub async fn preview_subscription_tier(
+        pool: &PgPool,
+        organization_id: Uuid,
+        requester_id: Uuid,
+        req: UpdateOrganizationSubscriptionRequest,
+    ) -> Result<SubscriptionChangePreview, AppError> {
+        let requester_role = require_member_role(pool, organization_id, requester_id).await?;
+        ensure_owner(requester_role)?;
+
+        let organization = org_repo::find_organization_by_id(pool, organization_id)
+            .await?
+            .ok_or(AppError::NotFound("Organization not found".to_string()))?;
+
+        let limits = organization_limits_for_tier(req.subscription_tier);
+        let usage =
+            load_usage_snapshot(pool, organization_id, organization.storage_used_mb).await?;
+        let current = OrganizationLimits {
+            max_members: organization.max_members,
+            max_boards: organization.max_boards,
+            storage_limit_mb: organization.storage_limit_mb,
+        };
+        let violations = find_limit_violations(&usage, current, limits);
+
+        Ok(SubscriptionChangePreview {
+            subscription_tier: req.subscription_tier,
+            allowed: violations.is_empty(),
+            max_members: limits.max_members,
+            max_boards: limits.max_boards,
+            storage_limit_mb: limits.storage_limit_mb,
+            violations,
+        })
+    }
+
     /// Updates the subscription tier and recalculates limits.
     pub async fn update_subscription_tier(
         pool: &PgPool,
@@ -39,7 +99,13 @@ impl OrganizationService {
         let limits = organization_limits_for_tier(req.subscription_tier);
         let usage =
             load_usage_snapshot(pool, organization_id, organization.storage_used_mb).await?;
-        ensure_usage_within_limits(&usage, limits)?;
+        let current = OrganizationLimits {
+            max_members: organization.max_members,
+            max_boards: organization.max_boards,
+            storage_limit_mb: organization.storage_limit_mb,
+        };
+        let violations = find_limit_violations(&usage, current, limits);
+        ensure_no_violations(&violations)?;
 
         let mut tx = pool.begin().await?;
         let updated = org_repo::update_organization_subscription(
@@ -55,9 +121,114 @@ impl OrganizationService {
 
         Ok(OrganizationResponse::from(updated))
     }
+
+    /// Loads tier limits from the database into the in-process cache.
+    pub async fn load_tier_limits(pool: &PgPool) -> Result<usize, AppError> {
+        let rows = tier_limit_repo::list_tier_limits(pool).await?;
+        let limits: HashMap<SubscriptionTier, OrganizationLimits> = rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.tier,
+                    OrganizationLimits {
+                        max_members: row.max_members,
+                        max_boards: row.max_boards,
+                        storage_limit_mb: row.storage_limit_mb,
+                    },
+                )
+            })
+            .collect();
+        let loaded = limits.len();
+        if let Ok(mut cache) = tier_limit_cache().write() {
+            *cache = limits;
+        }
+        Ok(loaded)
+    }
+
+    /// Lists effective limits for every subscription tier.
+    pub async fn list_tier_limits(requester_id: Uuid) -> Result<TierLimitsResponse, AppError> {
+        ensure_platform_admin(requester_id)?;
+        let data = ALL_TIERS
+            .into_iter()
+            .map(|tier| tier_limit_response(tier, organization_limits_for_tier(tier)))
+            .collect();
+
+        Ok(TierLimitsResponse { data })
+    }
+
+    /// Updates a tier's limits and refreshes this node's cache; other nodes
+    /// pick the change up on their next periodic reload.
+    ///
+    /// Existing organizations keep their stored limits until their tier changes.
+    pub async fn update_tier_limit(
+        pool: &PgPool,
+        requester_id: Uuid,
+        req: UpdateTierLimitRequest,
+    ) -> Result<TierLimitResponse, AppError> {
+        ensure_platform_admin(requester_id)?;
+        if req.max_members < 0 || req.max_boards < 0 || req.storage_limit_mb < 0 {
+            return Err(AppError::ValidationError(
+                "Tier limits must be zero (unlimited) or positive".to_string(),
+            ));
+        }
+
+        let row = tier_limit_repo::upsert_tier_limit(
+            pool,
+            req.tier,
+            req.max_members,
+            req.max_boards,
+            req.storage_limit_mb,
+            requester_id,
+        )
+        .await?;
+        let limits = OrganizationLimits {
+            max_members: row.max_members,
+            max_boards: row.max_boards,
+            storage_limit_mb: row.storage_limit_mb,
+        };
+        if let Ok(mut cache) = tier_limit_cache().write() {
+            cache.insert(row.tier, limits);
+        }
+        tracing::info!(
+            tier = ?row.tier,
+            max_members = limits.max_members,
+            max_boards = limits.max_boards,
+            storage_limit_mb = limits.storage_limit_mb,
+            updated_by = %requester_id,
+            "Subscription tier limits updated"
+        );
+
+        Ok(tier_limit_response(row.tier, limits))
+    }
+}
+
+fn tier_limit_response(tier: SubscriptionTier, limits: OrganizationLimits) -> TierLimitResponse {
+    TierLimitResponse {
+        tier,
+        max_members: limits.max_members,
+        max_boards: limits.max_boards,
+        storage_limit_mb: limits.storage_limit_mb,
+    }
 }
 
 pub(super) fn organization_limits_for_tier(tier: SubscriptionTier) -> OrganizationLimits {
+    match tier_limit_cache().read() {
+        Ok(cache) => resolve_tier_limits(&cache, tier),
+        Err(_) => default_limits_for_tier(tier),
+    }
+}
+
+fn resolve_tier_limits(
+    cache: &HashMap<SubscriptionTier, OrganizationLimits>,
+    tier: SubscriptionTier,
+) -> OrganizationLimits {
+    cache
+        .get(&tier)
+        .copied()
+        .unwrap_or_else(|| default_limits_for_tier(tier))
+}
+
+fn default_limits_for_tier(tier: SubscriptionTier) -> OrganizationLimits {
     match tier {
         SubscriptionTier::Free => OrganizationLimits {
             max_members: 3,
@@ -86,33 +257,121 @@ pub(crate) fn max_boards_for_tier(tier: SubscriptionTier) -> i32 {
     organization_limits_for_tier(tier).max_boards
 }
 
-fn ensure_usage_within_limits(
+fn find_limit_violations(
     usage: &OrganizationUsageSnapshot,
+    current: OrganizationLimits,
     limits: OrganizationLimits,
-) -> Result<(), AppError> {
-    if is_usage_over_limit(usage.members_used, limits.max_members) {
-        return Err(AppError::BadRequest(
-            "Subscription tier not allowed: member usage exceeds limits".to_string(),
-        ));
-    }
-    if is_usage_over_limit(usage.boards_used, limits.max_boards) {
-        return Err(AppError::BadRequest(
-            "Subscription tier not allowed: board usage exceeds limits".to_string(),
-        ));
-    }
-    if is_usage_over_limit(i64::from(usage.storage_used_mb), limits.storage_limit_mb) {
-        return Err(AppError::BadRequest(
-            "Subscription tier not allowed: storage usage exceeds limits".to_string(),
-        ));
+) -> Vec<SubscriptionLimitViolation> {
+    let checks = [
+        (
+            "members",
+            usage.members_used,
+            current.max_members,
+            limits.max_members,
+        ),
+        (
+            "boards",
+            usage.boards_used,
+            current.max_boards,
+            limits.max_boards,
+        ),
+        (
+            "storage",
+            i64::from(usage.storage_used_mb),
+            current.storage_limit_mb,
+            limits.storage_limit_mb,
+        ),
+    ];
+
+    checks
+        .into_iter()
+        .filter(|(_, used, _, new_limit)| is_usage_over_limit(*used, *new_limit))
+        .map(
+            |(resource, used, current_limit, new_limit)| SubscriptionLimitViolation {
+                resource: resource.to_string(),
+                usage: used,
+                current_limit,
+                new_limit,
+            },
+        )
+        .collect()
+}
+
+fn ensure_no_violations(violations: &[SubscriptionLimitViolation]) -> Result<(), AppError> {
+    if violations.is_empty() {
+        return Ok(());
     }
 
-    Ok(())
+    let summary = violations
+        .iter()
+        .map(|violation| {
+            format!(
+                "{} usage {} exceeds new limit {}",
+                violation.resource, violation.usage, violation.new_limit
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(AppError::BadRequestWithPayload(
+        format!("Subscription tier not allowed: {}", summary),
+        serde_json::json!({ "violations": violations }),
+    ))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::organization_limits_for_tier;
-    use crate::models::users::SubscriptionTier;
+    use std::collections::HashMap;
+
+    use super::{
+        OrganizationLimits, OrganizationUsageSnapshot, default_limits_for_tier,
+        ensure_no_violations, find_limit_violations, organization_limits_for_tier,
+        resolve_tier_limits,
+    };
+    use crate::{error::AppError, models::users::SubscriptionTier};
+
+    #[test]
+    fn limit_violations_report_each_exceeded_resource() {
+        let usage = OrganizationUsageSnapshot {
+            members_used: 12,
+            boards_used: 4,
+            storage_used_mb: 2048,
+        };
+        let current = default_limits_for_tier(SubscriptionTier::Professional);
+        let target = default_limits_for_tier(SubscriptionTier::Starter);
+
+        let violations = find_limit_violations(&usage, current, target);
+
+        let resources: Vec<&str> = violations.iter().map(|v| v.resource.as_str()).collect();
+        assert_eq!(resources, vec!["members", "storage"]);
+        assert_eq!(violations[0].usage, 12);
+        assert_eq!(violations[0].current_limit, 50);
+        assert_eq!(violations[0].new_limit, 10);
+        match ensure_no_violations(&violations) {
+            Err(AppError::BadRequestWithPayload(message, payload)) => {
+                assert!(message.contains("members usage 12 exceeds new limit 10"));
+                assert_eq!(payload["violations"].as_array().map(Vec::len), Some(2));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cached_tier_limits_override_defaults() {
+        let custom = OrganizationLimits {
+            max_members: 500,
+            max_boards: 0,
+            storage_limit_mb: 512_000,
+        };
+        let cache = HashMap::from([(SubscriptionTier::Enterprise, custom)]);
+        assert_eq!(
+            resolve_tier_limits(&cache, SubscriptionTier::Enterprise),
+            custom
+        );
+        assert_eq!(
+            resolve_tier_limits(&cache, SubscriptionTier::Free),
+            default_limits_for_tier(SubscriptionTier::Free)
+        );
+    }
 
     #[test]
     fn tier_limits_follow_design_doc() {
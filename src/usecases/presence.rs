@@ -1,14 +1,18 @@
-use redis::AsyncCommands;
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use redis::{AsyncCommands, aio::MultiplexedConnection};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
     error::AppError,
     models::presence::{PresenceStatus, PresenceUser},
-    repositories::presence as presence_repo,
+    repositories::{presence as presence_repo, users as user_repo},
+    services::redis_health,
 };
 
-const PRESENCE_CACHE_TTL_SECS: usize = 60;
+const PRESENCE_SESSION_TTL_SECS: u64 = 90;
 const PRESENCE_STALE_AFTER_SECS: i64 = 300;
 
 pub struct PresenceService;
@@ -19,31 +23,23 @@ impl PresenceService {
         redis: Option<&redis::Client>,
         board_id: Uuid,
     ) -> Result<Vec<PresenceUser>, AppError> {
-        if let Some(redis) = redis {
-            if let Ok(mut conn) = redis.get_multiplexed_async_connection().await {
-                let key = cache_key(board_id);
-                let cached: Result<Option<String>, _> = conn.get(&key).await;
-                if let Ok(Some(payload)) = cached {
-                    if let Ok(users) = serde_json::from_str::<Vec<PresenceUser>>(&payload) {
-                        return Ok(users);
-                    }
+        if let Some(mut conn) = redis_connection(redis).await {
+            match load_live_sessions(&mut conn, board_id).await {
+                Ok(sessions) => {
+                    return Ok(latest_session_per_user(sessions.live.into_values()));
                 }
-            }
-        }
-
-        let users = presence_repo::list_active_presence(pool, board_id).await?;
-        if let Some(redis) = redis {
-            if let Ok(mut conn) = redis.get_multiplexed_async_connection().await {
-                let key = cache_key(board_id);
-                if let Ok(payload) = serde_json::to_string(&users) {
-                    let _: Result<(), _> = conn
-                        .set_ex(key, payload, PRESENCE_CACHE_TTL_SECS.try_into().unwrap())
-                        .await;
+                Err(error) => {
+                    redis_health::record_failure(&error);
+                    tracing::warn!(
+                        "Failed to read presence for board {} from Redis: {}",
+                        board_id,
+                        error
+                    );
                 }
             }
         }
 
-        Ok(users)
+        presence_repo::list_active_presence(pool, board_id).await
     }
 
     pub async fn join(
@@ -64,7 +60,28 @@ impl PresenceService {
             },
         )
         .await?;
-        invalidate_cache(redis, board_id).await;
+
+        if let Some(mut conn) = redis_connection(redis).await {
+            let user = user_repo::get_user_by_id(pool, user_id).await?;
+            let now = Utc::now();
+            let entry = PresenceUser {
+                user_id,
+                display_name: user.display_name,
+                avatar_url: user.avatar_url,
+                status: PresenceStatus::Online,
+                connected_at: now,
+                last_heartbeat_at: now,
+            };
+            if let Err(error) = store_session(&mut conn, board_id, session_id, &entry).await {
+                redis_health::record_failure(&error);
+                tracing::warn!(
+                    "Failed to store presence session {} for board {} in Redis: {}",
+                    session_id,
+                    board_id,
+                    error
+                );
+            }
+        }
         Ok(())
     }
 
@@ -76,16 +93,74 @@ impl PresenceService {
         status: PresenceStatus,
     ) -> Result<(), AppError> {
         presence_repo::update_presence_status(pool, board_id, session_id, status).await?;
-        invalidate_cache(redis, board_id).await;
+
+        if let Some(mut conn) = redis_connection(redis).await {
+            let key = session_key(board_id, session_id);
+            let cached: Result<Option<String>, _> = conn.get(&key).await;
+            if let Ok(Some(payload)) = cached
+                && let Ok(mut entry) = serde_json::from_str::<PresenceUser>(&payload)
+            {
+                entry.status = status;
+                entry.last_heartbeat_at = Utc::now();
+                if let Err(error) = store_session(&mut conn, board_id, session_id, &entry).await {
+                    redis_health::record_failure(&error);
+                    tracing::warn!(
+                        "Failed to update presence session {} in Redis: {}",
+                        session_id,
+                        error
+                    );
+                }
+            }
+        }
         Ok(())
     }
 
-    pub async fn heartbeat(
+    /// Refreshes liveness for buffered heartbeats.
+    ///
+    /// With Redis the session TTLs are extended and the DB is left to
+    /// `reconcile`; without Redis the heartbeats are written to the DB.
+    /// Sessions whose Redis key is missing (they joined, or their key expired,
+    /// while Redis was down) are written to the DB and re-stored in Redis.
+    pub async fn flush_heartbeats(
         pool: &PgPool,
+        redis: Option<&redis::Client>,
         board_id: Uuid,
-        session_id: Uuid,
-    ) -> Result<(), AppError> {
-        presence_repo::update_heartbeat(pool, board_id, session_id).await
+        session_ids: &[Uuid],
+    ) -> Result<u64, AppError> {
+        if session_ids.is_empty() {
+            return Ok(0);
+        }
+
+        if let Some(mut conn) = redis_connection(redis).await {
+            let mut pipe = redis::pipe();
+            for session_id in session_ids {
+                pipe.expire(
+                    session_key(board_id, *session_id),
+                    PRESENCE_SESSION_TTL_SECS as i64,
+                );
+            }
+            pipe.expire(sessions_key(board_id), PRESENCE_SESSION_TTL_SECS as i64)
+                .ignore();
+            match pipe.query_async::<Vec<bool>>(&mut conn).await {
+                Ok(refreshed) => {
+                    let missing = missing_sessions(session_ids, &refreshed);
+                    if !missing.is_empty() {
+                        restore_sessions(pool, &mut conn, board_id, &missing).await?;
+                    }
+                    return Ok(session_ids.len() as u64);
+                }
+                Err(error) => {
+                    redis_health::record_failure(&error);
+                    tracing::warn!(
+                        "Failed to refresh presence TTLs for board {} in Redis: {}",
+                        board_id,
+                        error
+                    );
+                }
+            }
+        }
+
+        presence_repo::update_heartbeats(pool, board_id, session_ids).await
     }
 
     pub async fn disconnect(
@@ -95,47 +170,376 @@ impl PresenceService {
         session_id: Uuid,
     ) -> Result<(), AppError> {
         presence_repo::mark_disconnected(pool, board_id, session_id).await?;
-        invalidate_cache(redis, board_id).await;
+
+        if let Some(mut conn) = redis_connection(redis).await
+            && let Err(error) = remove_sessions(&mut conn, board_id, &[session_id]).await
+        {
+            redis_health::record_failure(&error);
+            tracing::warn!(
+                "Failed to remove presence session {} from Redis: {}",
+                session_id,
+                error
+            );
+        }
         Ok(())
     }
 
+    /// Expires sessions that stopped sending heartbeats and returns the users
+    /// that no longer have any live session on the board.
     pub async fn cleanup_stale_sessions(
         pool: &PgPool,
         redis: Option<&redis::Client>,
         board_id: Uuid,
     ) -> Result<Vec<Uuid>, AppError> {
-        let users =
-            presence_repo::cleanup_stale_presence(pool, board_id, PRESENCE_STALE_AFTER_SECS)
-                .await?;
-        if !users.is_empty() {
-            invalidate_cache(redis, board_id).await;
+        if let Some(mut conn) = redis_connection(redis).await {
+            match load_live_sessions(&mut conn, board_id).await {
+                Ok(sessions) => {
+                    if sessions.expired.is_empty() {
+                        return Ok(Vec::new());
+                    }
+                    let expired_ids: Vec<Uuid> = sessions.expired.keys().copied().collect();
+                    presence_repo::mark_sessions_disconnected(pool, board_id, &expired_ids).await?;
+                    if let Err(error) = remove_sessions(&mut conn, board_id, &expired_ids).await {
+                        redis_health::record_failure(&error);
+                        tracing::warn!(
+                            "Failed to prune expired presence for board {} in Redis: {}",
+                            board_id,
+                            error
+                        );
+                    }
+                    return Ok(users_without_live_session(
+                        sessions.expired.values().copied(),
+                        sessions.live.values(),
+                    ));
+                }
+                Err(error) => {
+                    redis_health::record_failure(&error);
+                    tracing::warn!(
+                        "Failed to expire presence for board {} in Redis: {}",
+                        board_id,
+                        error
+                    );
+                }
+            }
         }
-        Ok(users)
+
+        presence_repo::cleanup_stale_presence(pool, board_id, PRESENCE_STALE_AFTER_SECS).await
     }
 
-    pub async fn count_active_users(pool: &PgPool, board_id: Uuid) -> Result<i64, AppError> {
+    /// Syncs Redis presence into the DB so the durable record converges.
+    ///
+    /// No-op when Redis is not configured, since the DB is already the source
+    /// of truth in that mode.
+    pub async fn reconcile(
+        pool: &PgPool,
+        redis: Option<&redis::Client>,
+        board_id: Uuid,
+    ) -> Result<(), AppError> {
+        let Some(mut conn) = redis_connection(redis).await else {
+            return Ok(());
+        };
+        let sessions = match load_live_sessions(&mut conn, board_id).await {
+            Ok(sessions) => sessions,
+            Err(error) => {
+                redis_health::record_failure(&error);
+                tracing::warn!(
+                    "Failed to reconcile presence for board {} from Redis: {}",
+                    board_id,
+                    error
+                );
+                return Ok(());
+            }
+        };
+        let live_ids: Vec<Uuid> = sessions.live.keys().copied().collect();
+        if !live_ids.is_empty() {
+            presence_repo::update_heartbeats(pool, board_id, &live_ids).await?;
+        }
+        presence_repo::cleanup_stale_presence(pool, board_id, PRESENCE_STALE_AFTER_SECS).await?;
+        Ok(())
+    }
+
+    pub async fn count_active_users(
+        pool: &PgPool,
+        redis: Option<&redis::Client>,
+        board_id: Uuid,
+    ) -> Result<i64, AppError> {
+        if let Some(mut conn) = redis_connection(redis).await {
+            match load_live_sessions(&mut conn, board_id).await {
+                Ok(sessions) => {
+                    let users: HashSet<Uuid> =
+                        sessions.live.values().map(|entry| entry.user_id).collect();
+                    return Ok(users.len() as i64);
+                }
+                Err(error) => {
+                    redis_health::record_failure(&error);
+                    tracing::warn!(
+                        "Failed to count presence for board {} in Redis: {}",
+                        board_id,
+                        error
+                    );
+                }
+            }
+        }
+
         presence_repo::count_active_users(pool, board_id).await
     }
 
     pub async fn has_active_session(
         pool: &PgPool,
+        redis: Option<&redis::Client>,
         board_id: Uuid,
         user_id: Uuid,
     ) -> Result<bool, AppError> {
+        if let Some(mut conn) = redis_connection(redis).await {
+            match load_live_sessions(&mut conn, board_id).await {
+                Ok(sessions) => {
+                    return Ok(sessions.live.values().any(|entry| entry.user_id == user_id));
+                }
+                Err(error) => {
+                    redis_health::record_failure(&error);
+                    tracing::warn!(
+                        "Failed to check presence for board {} in Redis: {}",
+                        board_id,
+                        error
+                    );
+                }
+            }
+        }
+
         presence_repo::has_active_presence(pool, board_id, user_id).await
     }
 }
 
-fn cache_key(board_id: Uuid) -> String {
-    format!("presence:{}", board_id)
+struct RedisPresenceSessions {
+    live: HashMap<Uuid, PresenceUser>,
+    expired: HashMap<Uuid, Uuid>,
+}
+
+fn sessions_key(board_id: Uuid) -> String {
+    format!("presence:{}:sessions", board_id)
+}
+
+fn session_key(board_id: Uuid, session_id: Uuid) -> String {
+    format!("presence:{}:session:{}", board_id, session_id)
 }
 
-async fn invalidate_cache(redis: Option<&redis::Client>, board_id: Uuid) {
-    let Some(redis) = redis else {
-        return;
+/// Connection for presence, or `None` (DB-only) when Redis is not configured
+/// or the circuit breaker considers it down.
+async fn redis_connection(redis: Option<&redis::Client>) -> Option<MultiplexedConnection> {
+    let redis = redis.filter(|_| redis_health::is_available())?;
+    match redis.get_multiplexed_async_connection().await {
+        Ok(conn) => Some(conn),
+        Err(error) => {
+            redis_health::record_failure(&error);
+            tracing::warn!("Redis unavailable for presence, using DB: {}", error);
+            None
+        }
+    }
+}
+
+async fn store_session(
+    conn: &mut MultiplexedConnection,
+    board_id: Uuid,
+    session_id: Uuid,
+    entry: &PresenceUser,
+) -> redis::RedisResult<()> {
+    let payload = serde_json::to_string(entry).map_err(|error| {
+        redis::RedisError::from((
+            redis::ErrorKind::TypeError,
+            "presence serialization failed",
+            error.to_string(),
+        ))
+    })?;
+    redis::pipe()
+        .atomic()
+        .set_ex(
+            session_key(board_id, session_id),
+            payload,
+            PRESENCE_SESSION_TTL_SECS,
+        )
+        .ignore()
+        .hset(
+            sessions_key(board_id),
+            session_id.to_string(),
+            entry.user_id.to_string(),
+        )
+        .ignore()
+        .expire(sessions_key(board_id), PRESENCE_SESSION_TTL_SECS as i64)
+        .ignore()
+        .query_async(conn)
+        .await
+}
+
+/// Sessions whose `EXPIRE` found no key to refresh.
+fn missing_sessions(session_ids: &[Uuid], refreshed: &[bool]) -> Vec<Uuid> {
+    session_ids
+        .iter()
+        .zip(refreshed)
+        .filter(|(_, refreshed)| !**refreshed)
+        .map(|(session_id, _)| *session_id)
+        .collect()
+}
+
+/// Writes the sessions' heartbeats to the DB and re-stores them in Redis from
+/// their DB rows.
+async fn restore_sessions(
+    pool: &PgPool,
+    conn: &mut MultiplexedConnection,
+    board_id: Uuid,
+    session_ids: &[Uuid],
+) -> Result<(), AppError> {
+    presence_repo::update_heartbeats(pool, board_id, session_ids).await?;
+    for row in presence_repo::list_session_presence(pool, board_id, session_ids).await? {
+        if let Err(error) = store_session(conn, board_id, row.session_id, &row.user).await {
+            redis_health::record_failure(&error);
+            tracing::warn!(
+                "Failed to restore presence session {} for board {} in Redis: {}",
+                row.session_id,
+                board_id,
+                error
+            );
+            break;
+        }
+    }
+    Ok(())
+}
+
+async fn remove_sessions(
+    conn: &mut MultiplexedConnection,
+    board_id: Uuid,
+    session_ids: &[Uuid],
+) -> redis::RedisResult<()> {
+    let mut pipe = redis::pipe();
+    for session_id in session_ids {
+        pipe.del(session_key(board_id, *session_id))
+            .ignore()
+            .hdel(sessions_key(board_id), session_id.to_string())
+            .ignore();
+    }
+    pipe.query_async(conn).await
+}
+
+/// Loads every session registered for the board, splitting out the ones whose
+/// TTL key expired. Read-only: expired sessions stay in the index until
+/// `cleanup_stale_sessions` marks them disconnected and prunes them.
+async fn load_live_sessions(
+    conn: &mut MultiplexedConnection,
+    board_id: Uuid,
+) -> redis::RedisResult<RedisPresenceSessions> {
+    let index: HashMap<String, String> = conn.hgetall(sessions_key(board_id)).await?;
+    let mut sessions = RedisPresenceSessions {
+        live: HashMap::new(),
+        expired: HashMap::new(),
     };
-    if let Ok(mut conn) = redis.get_multiplexed_async_connection().await {
-        let key = cache_key(board_id);
-        let _: Result<(), _> = conn.del(key).await;
+    let entries: Vec<(Uuid, Uuid)> = index
+        .iter()
+        .filter_map(|(session_id, user_id)| {
+            Some((
+                Uuid::parse_str(session_id).ok()?,
+                Uuid::parse_str(user_id).ok()?,
+            ))
+        })
+        .collect();
+    if entries.is_empty() {
+        return Ok(sessions);
+    }
+
+    let keys: Vec<String> = entries
+        .iter()
+        .map(|(session_id, _)| session_key(board_id, *session_id))
+        .collect();
+    let payloads: Vec<Option<String>> = conn.mget(keys).await?;
+    for ((session_id, user_id), payload) in entries.into_iter().zip(payloads) {
+        match payload.and_then(|value| serde_json::from_str::<PresenceUser>(&value).ok()) {
+            Some(entry) => {
+                sessions.live.insert(session_id, entry);
+            }
+            None => {
+                sessions.expired.insert(session_id, user_id);
+            }
+        }
+    }
+
+    Ok(sessions)
+}
+
+fn latest_session_per_user(sessions: impl IntoIterator<Item = PresenceUser>) -> Vec<PresenceUser> {
+    let mut latest: HashMap<Uuid, PresenceUser> = HashMap::new();
+    for entry in sessions {
+        match latest.get(&entry.user_id) {
+            Some(existing) if existing.connected_at >= entry.connected_at => {}
+            _ => {
+                latest.insert(entry.user_id, entry);
+            }
+        }
+    }
+    let mut users: Vec<PresenceUser> = latest.into_values().collect();
+    users.sort_by_key(|user| user.user_id);
+    users
+}
+
+fn users_without_live_session<'a>(
+    expired_users: impl IntoIterator<Item = Uuid>,
+    live: impl IntoIterator<Item = &'a PresenceUser>,
+) -> Vec<Uuid> {
+    let live_users: HashSet<Uuid> = live.into_iter().map(|entry| entry.user_id).collect();
+    let mut users: Vec<Uuid> = expired_users
+        .into_iter()
+        .filter(|user_id| !live_users.contains(user_id))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    users.sort();
+    users
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{latest_session_per_user, missing_sessions, users_without_live_session};
+    use crate::models::presence::{PresenceStatus, PresenceUser};
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    fn presence(user_id: Uuid, status: PresenceStatus, age_secs: i64) -> PresenceUser {
+        let connected_at = Utc::now() - Duration::seconds(age_secs);
+        PresenceUser {
+            user_id,
+            display_name: "User".to_string(),
+            avatar_url: None,
+            status,
+            connected_at,
+            last_heartbeat_at: connected_at,
+        }
+    }
+
+    #[test]
+    fn keeps_most_recent_session_per_user() {
+        let user_id = Uuid::now_v7();
+        let users = latest_session_per_user(vec![
+            presence(user_id, PresenceStatus::Away, 60),
+            presence(user_id, PresenceStatus::Online, 5),
+        ]);
+
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].status, PresenceStatus::Online);
+    }
+
+    #[test]
+    fn reports_only_users_without_remaining_sessions() {
+        let still_here = Uuid::now_v7();
+        let gone = Uuid::now_v7();
+        let live = [presence(still_here, PresenceStatus::Online, 1)];
+
+        let users = users_without_live_session(vec![still_here, gone, gone], live.iter());
+
+        assert_eq!(users, vec![gone]);
+    }
+
+    #[test]
+    fn sessions_without_a_refreshed_key_are_missing() {
+        let live = Uuid::now_v7();
+        let lost = Uuid::now_v7();
+
+        assert_eq!(missing_sessions(&[live, lost], &[true, false]), vec![lost]);
     }
 }
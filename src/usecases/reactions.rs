@@ -0,0 +1,217 @@
+use chrono::Utc;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    dto::reactions::{
+        ListReactionsQuery, ReactionCountResponse, ReactionListResponse, ReactionSummaryResponse,
+        ToggleReactionRequest, ToggleReactionResponse,
+    },
+    error::AppError,
+    models::comments::ReactionTargetType,
+    realtime::room::Rooms,
+    repositories::reactions::{self as reaction_repo, ReactionCountRow},
+    usecases::boards::BoardService,
+};
+
+pub struct ReactionService;
+
+const MAX_EMOJI_CHARS: usize = 32;
+
+impl ReactionService {
+    /// Adds the user's emoji reaction to an element or comment, or removes it
+    /// when already present, and broadcasts `reaction:update` to the room.
+    pub async fn toggle_reaction(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+        req: ToggleReactionRequest,
+    ) -> Result<ToggleReactionResponse, AppError> {
+        BoardService::ensure_can_comment(pool, board_id, user_id).await?;
+
+        let emoji = normalize_emoji(&req.emoji)?;
+        if !reaction_repo::target_exists(pool, board_id, req.target_type, req.target_id).await? {
+            return Err(AppError::NotFound("Reaction target not found".to_string()));
+        }
+
+        let mut tx = pool.begin().await?;
+        let added = reaction_repo::toggle_reaction(
+            &mut tx,
+            req.target_type,
+            req.target_id,
+            user_id,
+            &emoji,
+        )
+        .await?;
+        tx.commit().await?;
+
+        let rows = reaction_repo::list_reaction_counts(
+            pool,
+            board_id,
+            user_id,
+            Some((req.target_type, req.target_id)),
+        )
+        .await?;
+        let reactions: Vec<ReactionCountResponse> = rows.into_iter().map(map_count).collect();
+
+        if let Some(room) = rooms.get(&board_id).map(|entry| entry.clone()) {
+            // `reacted` is per-viewer, so only emoji counts are broadcast.
+            let counts: Vec<_> = reactions
+                .iter()
+                .map(|reaction| json!({ "emoji": reaction.emoji, "count": reaction.count }))
+                .collect();
+            room.broadcast_event(
+                "reaction:update",
+                json!({
+                    "board_id": board_id,
+                    "target_type": req.target_type,
+                    "target_id": req.target_id,
+                    "emoji": emoji,
+                    "user_id": user_id,
+                    "added": added,
+                    "reactions": counts,
+                    "timestamp": Utc::now().timestamp_millis(),
+                }),
+            );
+        }
+
+        Ok(ToggleReactionResponse {
+            added,
+            summary: ReactionSummaryResponse {
+                target_type: req.target_type,
+                target_id: req.target_id,
+                reactions,
+            },
+        })
+    }
+
+    /// Lists aggregated reactions on the board, optionally for one target.
+    pub async fn list_reactions(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+        query: ListReactionsQuery,
+    ) -> Result<ReactionListResponse, AppError> {
+        BoardService::ensure_can_view(pool, board_id, user_id).await?;
+
+        let target = match (query.target_type, query.target_id) {
+            (Some(target_type), Some(target_id)) => Some((target_type, target_id)),
+            (None, None) => None,
+            _ => {
+                return Err(AppError::ValidationError(
+                    "target_type and target_id must be provided together".to_string(),
+                ));
+            }
+        };
+        let rows = reaction_repo::list_reaction_counts(pool, board_id, user_id, target).await?;
+
+        Ok(ReactionListResponse {
+            data: group_reaction_counts(rows),
+        })
+    }
+}
+
+fn normalize_emoji(emoji: &str) -> Result<String, AppError> {
+    let trimmed = emoji.trim();
+    let len = trimmed.chars().count();
+    if len == 0 || len > MAX_EMOJI_CHARS {
+        return Err(AppError::ValidationError(format!(
+            "Emoji must be between 1 and {MAX_EMOJI_CHARS} characters"
+        )));
+    }
+    if trimmed
+        .chars()
+        .any(|ch| ch.is_whitespace() || ch.is_control())
+    {
+        return Err(AppError::ValidationError(
+            "Emoji must not contain whitespace".to_string(),
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn map_count(row: ReactionCountRow) -> ReactionCountResponse {
+    ReactionCountResponse {
+        emoji: row.emoji,
+        count: row.count,
+        reacted: row.reacted,
+    }
+}
+
+/// Groups per-emoji rows, already ordered by target, into one summary per
+/// element or comment.
+fn group_reaction_counts(rows: Vec<ReactionCountRow>) -> Vec<ReactionSummaryResponse> {
+    let mut summaries: Vec<ReactionSummaryResponse> = Vec::new();
+    for row in rows {
+        let (target_type, target_id) = match (row.element_id, row.comment_id) {
+            (Some(element_id), _) => (ReactionTargetType::Element, element_id),
+            (None, Some(comment_id)) => (ReactionTargetType::Comment, comment_id),
+            (None, None) => continue,
+        };
+        match summaries.last_mut() {
+            Some(summary)
+                if summary.target_type == target_type && summary.target_id == target_id =>
+            {
+                summary.reactions.push(map_count(row));
+            }
+            _ => summaries.push(ReactionSummaryResponse {
+                target_type,
+                target_id,
+                reactions: vec![map_count(row)],
+            }),
+        }
+    }
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(element_id: Option<Uuid>, comment_id: Option<Uuid>, emoji: &str) -> ReactionCountRow {
+        ReactionCountRow {
+            element_id,
+            comment_id,
+            emoji: emoji.to_string(),
+            count: 1,
+            reacted: false,
+        }
+    }
+
+    #[test]
+    fn normalizes_emoji() {
+        assert_eq!(normalize_emoji(" 👍 ").unwrap(), "👍");
+        assert!(matches!(
+            normalize_emoji("   "),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            normalize_emoji("thumbs up"),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            normalize_emoji(&"🎉".repeat(MAX_EMOJI_CHARS + 1)),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn groups_counts_by_target() {
+        let element_id = Uuid::now_v7();
+        let comment_id = Uuid::now_v7();
+
+        let summaries = group_reaction_counts(vec![
+            row(Some(element_id), None, "👍"),
+            row(Some(element_id), None, "🎉"),
+            row(None, Some(comment_id), "👍"),
+        ]);
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].target_type, ReactionTargetType::Element);
+        assert_eq!(summaries[0].reactions.len(), 2);
+        assert_eq!(summaries[1].target_type, ReactionTargetType::Comment);
+        assert_eq!(summaries[1].target_id, comment_id);
+    }
+}
@@ -0,0 +1,89 @@
+use std::{future::Future, time::Duration};
+
+use crate::error::AppError;
+
+const MAX_RETRIES_ENV: &str = "DB_TRANSACTION_MAX_RETRIES";
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF_MS: u64 = 25;
+const MAX_BACKOFF_MS: u64 = 1_000;
+
+/// SQLSTATEs for `serialization_failure` and `deadlock_detected`.
+const RETRYABLE_SQLSTATES: [&str; 2] = ["40001", "40P01"];
+
+/// Runs a transactional block, re-running it with exponential backoff when
+/// Postgres aborts it with a serialization failure or deadlock.
+///
+/// The block must open and commit its own transaction so each attempt starts
+/// clean. `DB_TRANSACTION_MAX_RETRIES` (default 3) bounds the retries.
+pub(crate) async fn with_retry<T, F, Fut>(
+    operation: &'static str,
+    mut block: F,
+) -> Result<T, AppError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    let max_retries = max_retries();
+    let mut attempt = 0;
+    loop {
+        match block().await {
+            Err(error) if is_retryable(&error) && attempt < max_retries => {
+                attempt += 1;
+                tracing::warn!(
+                    operation,
+                    attempt,
+                    "Transaction aborted by a serialization failure or deadlock; retrying"
+                );
+                tokio::time::sleep(backoff(attempt)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_retryable(error: &AppError) -> bool {
+    let AppError::Database(sqlx::Error::Database(db_error)) = error else {
+        return false;
+    };
+    db_error
+        .code()
+        .is_some_and(|code| RETRYABLE_SQLSTATES.contains(&code.as_ref()))
+}
+
+fn backoff(attempt: u32) -> Duration {
+    let delay = BASE_BACKOFF_MS.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+    Duration::from_millis(delay.min(MAX_BACKOFF_MS))
+}
+
+fn max_retries() -> u32 {
+    parse_max_retries(std::env::var(MAX_RETRIES_ENV).ok().as_deref())
+}
+
+fn parse_max_retries(value: Option<&str>) -> u32 {
+    value
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .unwrap_or(DEFAULT_MAX_RETRIES)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{backoff, parse_max_retries};
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff(1), Duration::from_millis(25));
+        assert_eq!(backoff(2), Duration::from_millis(50));
+        assert_eq!(backoff(3), Duration::from_millis(100));
+        assert_eq!(backoff(20), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn max_retries_falls_back_to_default() {
+        assert_eq!(parse_max_retries(Some(" 5 ")), 5);
+        assert_eq!(parse_max_retries(Some("0")), 0);
+        assert_eq!(parse_max_retries(Some("-1")), 3);
+        assert_eq!(parse_max_retries(None), 3);
+    }
+}
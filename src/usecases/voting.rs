@@ -0,0 +1,270 @@
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    dto::voting::{CastVoteRequest, StartVotingRequest, VoteTallyResponse, VotingSessionResponse},
+    error::AppError,
+    realtime::room::Rooms,
+    repositories::{
+        elements as element_repo,
+        voting::{self as voting_repo, VoteTallyRow, VotingSessionRow},
+    },
+    usecases::boards::BoardService,
+};
+
+pub struct VotingService;
+
+const MAX_VOTES_PER_USER: u32 = 50;
+
+impl VotingService {
+    /// Starts a dot-voting session; only board managers may start one and a
+    /// board has at most one active session.
+    pub async fn start_voting(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+        req: StartVotingRequest,
+    ) -> Result<VotingSessionResponse, AppError> {
+        ensure_can_manage_voting(pool, board_id, user_id).await?;
+        let votes_per_user = validate_votes_per_user(req.votes_per_user)?;
+
+        let mut tx = pool.begin().await?;
+        let session =
+            voting_repo::create_voting_session(&mut tx, board_id, votes_per_user, user_id).await?;
+        tx.commit().await?;
+
+        let response = build_session_response(session, Vec::new());
+        broadcast_voting(rooms, "voting:started", &response);
+        Ok(response)
+    }
+
+    /// Casts one vote on an element. Users may stack votes on the same
+    /// element up to the session's per-user budget.
+    pub async fn cast_vote(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+        req: CastVoteRequest,
+    ) -> Result<VotingSessionResponse, AppError> {
+        let permissions = BoardService::get_access_permissions(pool, board_id, user_id).await?;
+        if !permissions.can_edit && !permissions.can_comment {
+            return Err(AppError::Forbidden(
+                "You do not have permission to vote on this board".to_string(),
+            ));
+        }
+        if element_repo::find_element_by_id(pool, board_id, req.element_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::NotFound("Element not found".to_string()));
+        }
+
+        let mut tx = pool.begin().await?;
+        let session = voting_repo::lock_active_session(&mut tx, board_id)
+            .await?
+            .ok_or_else(no_active_session)?;
+        let cast = voting_repo::count_user_votes(&mut tx, session.id, user_id).await?;
+        ensure_vote_available(cast, session.votes_per_user)?;
+        voting_repo::insert_vote(&mut tx, session.id, req.element_id, user_id).await?;
+        tx.commit().await?;
+
+        let tallies = voting_repo::list_vote_tallies(pool, session.id, user_id).await?;
+        let response = build_session_response(session, tallies);
+        broadcast_voting(rooms, "voting:update", &response);
+        Ok(response)
+    }
+
+    /// Ends the active session and returns its final results.
+    pub async fn end_voting(
+        pool: &PgPool,
+        rooms: &Rooms,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<VotingSessionResponse, AppError> {
+        ensure_can_manage_voting(pool, board_id, user_id).await?;
+
+        let mut tx = pool.begin().await?;
+        let session = voting_repo::lock_active_session(&mut tx, board_id)
+            .await?
+            .ok_or_else(no_active_session)?;
+        let session = voting_repo::end_voting_session(&mut tx, session.id, user_id).await?;
+        tx.commit().await?;
+
+        let tallies = voting_repo::list_vote_tallies(pool, session.id, user_id).await?;
+        let response = build_session_response(session, tallies);
+        broadcast_voting(rooms, "voting:ended", &response);
+        Ok(response)
+    }
+
+    /// Returns the active session, or the results of the last one.
+    pub async fn get_voting(
+        pool: &PgPool,
+        board_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<VotingSessionResponse, AppError> {
+        BoardService::ensure_can_view(pool, board_id, user_id).await?;
+
+        let session = voting_repo::find_latest_session(pool, board_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("No voting session on this board".to_string()))?;
+        let tallies = voting_repo::list_vote_tallies(pool, session.id, user_id).await?;
+        Ok(build_session_response(session, tallies))
+    }
+}
+
+async fn ensure_can_manage_voting(
+    pool: &PgPool,
+    board_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    let permissions = BoardService::get_access_permissions(pool, board_id, user_id).await?;
+    if !permissions.can_manage_board {
+        return Err(AppError::Forbidden(
+            "You do not have permission to manage voting on this board".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn no_active_session() -> AppError {
+    AppError::Conflict("No voting session is active on this board".to_string())
+}
+
+fn validate_votes_per_user(votes_per_user: u32) -> Result<i32, AppError> {
+    if votes_per_user == 0 || votes_per_user > MAX_VOTES_PER_USER {
+        return Err(AppError::ValidationError(format!(
+            "votes_per_user must be between 1 and {MAX_VOTES_PER_USER}"
+        )));
+    }
+    Ok(votes_per_user as i32)
+}
+
+fn ensure_vote_available(cast: i64, votes_per_user: i32) -> Result<(), AppError> {
+    if cast >= i64::from(votes_per_user) {
+        return Err(AppError::Conflict(format!(
+            "You have used all {votes_per_user} votes in this session"
+        )));
+    }
+    Ok(())
+}
+
+/// `my_votes` and `votes_remaining` come from the tally rows, which count
+/// the votes of the user the tallies were loaded for.
+fn build_session_response(
+    session: VotingSessionRow,
+    tallies: Vec<VoteTallyRow>,
+) -> VotingSessionResponse {
+    let my_votes: i64 = tallies.iter().map(|tally| tally.my_votes).sum();
+    let total_votes = tallies.iter().map(|tally| tally.votes).sum();
+    let is_active = session.ended_at.is_none();
+    let votes_remaining = if is_active {
+        (i64::from(session.votes_per_user) - my_votes).max(0)
+    } else {
+        0
+    };
+    VotingSessionResponse {
+        id: session.id,
+        board_id: session.board_id,
+        votes_per_user: session.votes_per_user,
+        started_by: session.started_by,
+        started_at: session.started_at,
+        ended_by: session.ended_by,
+        ended_at: session.ended_at,
+        is_active,
+        total_votes,
+        tallies: tallies
+            .into_iter()
+            .map(|tally| VoteTallyResponse {
+                element_id: tally.element_id,
+                votes: tally.votes,
+            })
+            .collect(),
+        my_votes,
+        votes_remaining,
+    }
+}
+
+/// Broadcasts session state and tallies; per-user vote counts are left out
+/// because they only describe the acting user.
+fn broadcast_voting(rooms: &Rooms, event_type: &str, session: &VotingSessionResponse) {
+    let Some(room) = rooms.get(&session.board_id).map(|entry| entry.clone()) else {
+        return;
+    };
+    room.broadcast_event(
+        event_type,
+        json!({
+            "board_id": session.board_id,
+            "session_id": session.id,
+            "votes_per_user": session.votes_per_user,
+            "is_active": session.is_active,
+            "ended_at": session.ended_at,
+            "total_votes": session.total_votes,
+            "tallies": session.tallies,
+        }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    #[test]
+    fn validates_votes_per_user_range() {
+        assert!(matches!(
+            validate_votes_per_user(0),
+            Err(AppError::ValidationError(_))
+        ));
+        assert_eq!(validate_votes_per_user(5).unwrap(), 5);
+        assert!(validate_votes_per_user(MAX_VOTES_PER_USER + 1).is_err());
+    }
+
+    #[test]
+    fn enforces_per_user_vote_budget() {
+        assert!(ensure_vote_available(2, 3).is_ok());
+        assert!(matches!(
+            ensure_vote_available(3, 3),
+            Err(AppError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn session_response_totals_tallies_and_remaining_votes() {
+        let session = VotingSessionRow {
+            id: Uuid::now_v7(),
+            board_id: Uuid::now_v7(),
+            votes_per_user: 5,
+            started_by: Uuid::now_v7(),
+            started_at: Utc::now(),
+            ended_by: None,
+            ended_at: None,
+        };
+        let tallies = vec![
+            VoteTallyRow {
+                element_id: Uuid::now_v7(),
+                votes: 4,
+                my_votes: 2,
+            },
+            VoteTallyRow {
+                element_id: Uuid::now_v7(),
+                votes: 1,
+                my_votes: 1,
+            },
+        ];
+
+        let response = build_session_response(session.clone(), tallies);
+        assert_eq!(response.total_votes, 5);
+        assert_eq!(response.my_votes, 3);
+        assert_eq!(response.votes_remaining, 2);
+
+        let ended = VotingSessionRow {
+            ended_at: Some(Utc::now()),
+            ..session
+        };
+        assert_eq!(build_session_response(ended, Vec::new()).votes_remaining, 0);
+    }
+}